@@ -0,0 +1,4 @@
+ALTER TABLE bookings DROP COLUMN IF EXISTS rate_plan_id;
+
+DROP TABLE IF EXISTS rate_overrides;
+DROP TABLE IF EXISTS rate_plans;
@@ -0,0 +1,53 @@
+-- Rate plans: nightly pricing rules attached to a room type or a single room
+CREATE TABLE rate_plans (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    room_type room_type,
+    room_id UUID REFERENCES rooms(id) ON DELETE CASCADE,
+    base_rate DECIMAL(12, 2) NOT NULL,
+    weekend_rate DECIMAL(12, 2),
+    -- Optional seasonal window (inclusive on both ends)
+    start_date DATE,
+    end_date DATE,
+    min_stay_nights INTEGER NOT NULL DEFAULT 1,
+    priority INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    -- A plan targets exactly one of room type or room
+    CONSTRAINT chk_rate_plan_target CHECK ((room_type IS NULL) <> (room_id IS NULL)),
+    CONSTRAINT chk_rate_plan_rates_non_negative CHECK (
+        base_rate >= 0 AND (weekend_rate IS NULL OR weekend_rate >= 0)
+    ),
+    CONSTRAINT chk_rate_plan_season CHECK (
+        start_date IS NULL OR end_date IS NULL OR end_date >= start_date
+    ),
+    CONSTRAINT chk_rate_plan_min_stay CHECK (min_stay_nights >= 1)
+);
+
+CREATE INDEX idx_rate_plans_room_type ON rate_plans(room_type);
+CREATE INDEX idx_rate_plans_room_id ON rate_plans(room_id);
+
+SELECT diesel_manage_updated_at('rate_plans');
+
+-- Per-date overrides of a rate plan (holidays, events, closeouts)
+CREATE TABLE rate_overrides (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rate_plan_id UUID NOT NULL REFERENCES rate_plans(id) ON DELETE CASCADE,
+    stay_date DATE NOT NULL,
+    price DECIMAL(12, 2) NOT NULL,
+    min_stay_nights INTEGER,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_rate_override_plan_date UNIQUE (rate_plan_id, stay_date),
+    CONSTRAINT chk_rate_override_price_non_negative CHECK (price >= 0),
+    CONSTRAINT chk_rate_override_min_stay CHECK (min_stay_nights IS NULL OR min_stay_nights >= 1)
+);
+
+SELECT diesel_manage_updated_at('rate_overrides');
+
+-- Remember which plan priced the arrival night of a booking
+ALTER TABLE bookings
+  ADD COLUMN rate_plan_id UUID REFERENCES rate_plans(id) ON DELETE SET NULL;
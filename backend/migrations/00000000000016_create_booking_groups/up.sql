@@ -0,0 +1,18 @@
+-- Booking groups: one reservation (tour group, wedding) owning several room bookings
+CREATE TABLE booking_groups (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    reference VARCHAR(20) NOT NULL UNIQUE,
+    name VARCHAR(100) NOT NULL,
+    contact_name VARCHAR(100) NOT NULL,
+    notes TEXT,
+    created_by_user_id UUID REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+SELECT diesel_manage_updated_at('booking_groups');
+
+ALTER TABLE bookings
+  ADD COLUMN group_id UUID REFERENCES booking_groups(id) ON DELETE SET NULL;
+
+CREATE INDEX idx_bookings_group_id ON bookings(group_id);
@@ -0,0 +1,12 @@
+-- Enforce "one active booking per room per night" in the database so that
+-- concurrent inserts cannot both pass the application-level availability check.
+-- btree_gist lets the UUID equality and the daterange overlap share one index.
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+ALTER TABLE bookings
+  ADD CONSTRAINT bookings_no_overlap
+  EXCLUDE USING gist (
+    room_id WITH =,
+    daterange(check_in_date, check_out_date, '[)') WITH &&
+  )
+  WHERE (status NOT IN ('cancelled', 'checked_out'));
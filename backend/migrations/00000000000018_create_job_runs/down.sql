@@ -0,0 +1,6 @@
+DROP TRIGGER IF EXISTS set_rooms_status_changed_at ON rooms;
+DROP FUNCTION IF EXISTS set_room_status_changed_at();
+ALTER TABLE rooms DROP COLUMN IF EXISTS status_changed_at;
+
+DROP INDEX IF EXISTS idx_job_runs_job_name_started_at;
+DROP TABLE IF EXISTS job_runs;
@@ -0,0 +1,34 @@
+-- History of background lifecycle job executions
+CREATE TABLE job_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_name VARCHAR(50) NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ NOT NULL,
+    success BOOLEAN NOT NULL,
+    affected_count INTEGER NOT NULL DEFAULT 0,
+    -- Bookings or rooms the run changed or flagged
+    affected_ids UUID[] NOT NULL DEFAULT '{}',
+    summary TEXT NOT NULL,
+    error_message TEXT
+);
+
+CREATE INDEX idx_job_runs_job_name_started_at ON job_runs(job_name, started_at DESC);
+
+-- Track when a room last changed status (used to find rooms left dirty too long)
+ALTER TABLE rooms
+  ADD COLUMN status_changed_at TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE OR REPLACE FUNCTION set_room_status_changed_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status IS DISTINCT FROM OLD.status THEN
+        NEW.status_changed_at = NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_rooms_status_changed_at
+    BEFORE UPDATE ON rooms
+    FOR EACH ROW
+    EXECUTE FUNCTION set_room_status_changed_at();
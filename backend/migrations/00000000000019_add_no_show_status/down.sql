@@ -0,0 +1,2 @@
+-- PostgreSQL cannot drop a value from an enum; move rows back to upcoming instead
+UPDATE bookings SET status = 'upcoming' WHERE status = 'no_show';
@@ -0,0 +1,4 @@
+-- Add no_show status to booking_status enum
+-- (kept in its own migration: a new enum value cannot be used in the
+-- transaction that adds it)
+ALTER TYPE booking_status ADD VALUE IF NOT EXISTS 'no_show';
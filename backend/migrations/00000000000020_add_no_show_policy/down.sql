@@ -0,0 +1,13 @@
+DELETE FROM system_settings
+WHERE key IN ('no_show_grace_days', 'no_show_fee_enabled', 'no_show_fee_type', 'no_show_fee_amount');
+
+ALTER TABLE bookings DROP CONSTRAINT IF EXISTS bookings_no_overlap;
+ALTER TABLE bookings
+  ADD CONSTRAINT bookings_no_overlap
+  EXCLUDE USING gist (
+    room_id WITH =,
+    daterange(check_in_date, check_out_date, '[)') WITH &&
+  )
+  WHERE (status NOT IN ('cancelled', 'checked_out'));
+
+ALTER TABLE bookings DROP COLUMN IF EXISTS no_show_fee_payment_id;
@@ -0,0 +1,21 @@
+-- No-show fee payment posted for a booking (reversed when the no-show is undone)
+ALTER TABLE bookings
+  ADD COLUMN no_show_fee_payment_id UUID REFERENCES payments(id) ON DELETE SET NULL;
+
+-- No-shows release the room, like cancelled and checked-out stays
+ALTER TABLE bookings DROP CONSTRAINT IF EXISTS bookings_no_overlap;
+ALTER TABLE bookings
+  ADD CONSTRAINT bookings_no_overlap
+  EXCLUDE USING gist (
+    room_id WITH =,
+    daterange(check_in_date, check_out_date, '[)') WITH &&
+  )
+  WHERE (status NOT IN ('cancelled', 'checked_out', 'no_show'));
+
+-- Default no-show policy
+INSERT INTO system_settings (key, value, description) VALUES
+('no_show_grace_days', '1', 'Days after the check-in date before an unarrived booking becomes a no-show'),
+('no_show_fee_enabled', 'false', 'Post a no-show fee as a payment when a booking is marked no-show'),
+('no_show_fee_type', 'first_night', 'No-show fee basis (first_night, full_stay, fixed)'),
+('no_show_fee_amount', '0', 'Fixed no-show fee amount (used when no_show_fee_type is fixed)')
+ON CONFLICT (key) DO NOTHING;
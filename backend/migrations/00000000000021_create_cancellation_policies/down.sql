@@ -0,0 +1,5 @@
+ALTER TABLE rate_plans DROP COLUMN IF EXISTS cancellation_policy_id;
+
+DROP INDEX IF EXISTS idx_cancellation_policies_room_type;
+DROP TABLE IF EXISTS cancellation_policies;
+DROP TYPE IF EXISTS cancellation_penalty_type;
@@ -0,0 +1,29 @@
+-- Cancellation policies: free until N days before check-in, then a penalty
+CREATE TYPE cancellation_penalty_type AS ENUM ('percent', 'nights');
+
+CREATE TABLE cancellation_policies (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    -- Cancelling at least this many days before check-in is free
+    free_cancellation_days INTEGER NOT NULL DEFAULT 0 CHECK (free_cancellation_days >= 0),
+    penalty_type cancellation_penalty_type NOT NULL DEFAULT 'percent',
+    -- Percent of the booking price, or number of nights charged
+    penalty_value NUMERIC(10, 2) NOT NULL DEFAULT 0 CHECK (penalty_value >= 0),
+    -- Default policy for a room type (rate plans can point at any policy instead)
+    room_type room_type,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT cancellation_policies_percent_range
+        CHECK (penalty_type <> 'percent' OR penalty_value <= 100)
+);
+
+SELECT diesel_manage_updated_at('cancellation_policies');
+
+-- At most one active default policy per room type
+CREATE UNIQUE INDEX idx_cancellation_policies_room_type
+    ON cancellation_policies(room_type)
+    WHERE room_type IS NOT NULL AND is_active;
+
+ALTER TABLE rate_plans
+  ADD COLUMN cancellation_policy_id UUID REFERENCES cancellation_policies(id) ON DELETE SET NULL;
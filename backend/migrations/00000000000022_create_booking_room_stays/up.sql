@@ -0,0 +1,18 @@
+-- Room history for bookings moved mid-stay: which nights were spent in which room.
+-- Bookings that never moved have no rows; their whole stay is in bookings.room_id.
+CREATE TABLE booking_room_stays (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    room_id UUID NOT NULL REFERENCES rooms(id),
+    -- First night in the room
+    start_date DATE NOT NULL,
+    -- Morning the guest left the room (exclusive, like check_out_date)
+    end_date DATE NOT NULL,
+    -- Why the guest was moved into this room
+    reason TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT booking_room_stays_dates_check CHECK (end_date > start_date)
+);
+
+CREATE INDEX idx_booking_room_stays_booking_id ON booking_room_stays(booking_id);
+CREATE INDEX idx_booking_room_stays_room_id ON booking_room_stays(room_id);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS booking_stay_changes;
+DROP TYPE IF EXISTS stay_change_type;
+
+ALTER TABLE bookings DROP COLUMN IF EXISTS original_price;
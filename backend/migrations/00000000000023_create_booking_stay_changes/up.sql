@@ -0,0 +1,22 @@
+-- Price quoted when the booking was made; set the first time the stay or
+-- price is changed (NULL means the price is still the original quote)
+ALTER TABLE bookings
+  ADD COLUMN original_price DECIMAL(12, 0);
+
+CREATE TYPE stay_change_type AS ENUM ('extend', 'shorten');
+
+-- Audit trail of check-out date changes (extensions, early departures)
+CREATE TABLE booking_stay_changes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    change_type stay_change_type NOT NULL,
+    old_check_out_date DATE NOT NULL,
+    new_check_out_date DATE NOT NULL,
+    old_price DECIMAL(12, 0) NOT NULL,
+    new_price DECIMAL(12, 0) NOT NULL,
+    reason TEXT,
+    changed_by_user_id UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_booking_stay_changes_booking_id ON booking_stay_changes(booking_id);
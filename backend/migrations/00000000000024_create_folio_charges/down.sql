@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS folio_charges;
+DROP TYPE IF EXISTS charge_category;
@@ -0,0 +1,33 @@
+-- Guest folio: itemized charges billed to a booking on top of the room price
+CREATE TYPE charge_category AS ENUM (
+    'minibar',
+    'laundry',
+    'food_beverage',
+    'damage',
+    'late_checkout',
+    'other'
+);
+
+CREATE TABLE folio_charges (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    category charge_category NOT NULL,
+    description VARCHAR(200) NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1,
+    unit_price DECIMAL(12, 0) NOT NULL,
+    -- Tax rate in percent applied to quantity * unit_price
+    tax_rate DECIMAL(5, 2) NOT NULL DEFAULT 0,
+    tax_amount DECIMAL(12, 0) NOT NULL DEFAULT 0,
+    -- quantity * unit_price + tax_amount
+    total_amount DECIMAL(12, 0) NOT NULL,
+    created_by_user_id UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT folio_charges_quantity_check CHECK (quantity > 0),
+    CONSTRAINT folio_charges_unit_price_check CHECK (unit_price >= 0),
+    CONSTRAINT folio_charges_tax_rate_check CHECK (tax_rate >= 0 AND tax_rate <= 100)
+);
+
+SELECT diesel_manage_updated_at('folio_charges');
+
+CREATE INDEX idx_folio_charges_booking_id ON folio_charges(booking_id);
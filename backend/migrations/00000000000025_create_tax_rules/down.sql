@@ -0,0 +1,6 @@
+ALTER TABLE folio_charges DROP CONSTRAINT folio_charges_tax_rate_check;
+ALTER TABLE folio_charges
+    ADD CONSTRAINT folio_charges_tax_rate_check CHECK (tax_rate >= 0 AND tax_rate <= 100);
+
+DROP TABLE IF EXISTS folio_charge_taxes;
+DROP TABLE IF EXISTS tax_rules;
@@ -0,0 +1,48 @@
+-- Tax and service-charge rules applied to room prices and folio charges
+CREATE TABLE tax_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(100) NOT NULL,
+    -- Percent of the net amount
+    rate DECIMAL(5, 2) NOT NULL,
+    -- Inclusive rules are already part of the price; exclusive ones are added on top
+    is_inclusive BOOLEAN NOT NULL DEFAULT FALSE,
+    applies_to_room BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Folio charge categories the rule applies to
+    charge_categories charge_category[] NOT NULL DEFAULT '{}',
+    -- Rules apply to stays checking in (or charges posted) within this range
+    effective_from DATE,
+    effective_to DATE,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT tax_rules_rate_check CHECK (rate >= 0 AND rate <= 100),
+    CONSTRAINT tax_rules_effective_range CHECK (
+        effective_from IS NULL OR effective_to IS NULL OR effective_from <= effective_to
+    )
+);
+
+SELECT diesel_manage_updated_at('tax_rules');
+
+-- Tax lines of a folio charge, frozen when the charge is posted
+CREATE TABLE folio_charge_taxes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    folio_charge_id UUID NOT NULL REFERENCES folio_charges(id) ON DELETE CASCADE,
+    tax_rule_id UUID REFERENCES tax_rules(id) ON DELETE SET NULL,
+    name VARCHAR(100) NOT NULL,
+    rate DECIMAL(5, 2) NOT NULL,
+    is_inclusive BOOLEAN NOT NULL,
+    amount DECIMAL(12, 0) NOT NULL
+);
+
+CREATE INDEX idx_folio_charge_taxes_charge_id ON folio_charge_taxes(folio_charge_id);
+
+-- Existing charges carried a single exclusive rate
+INSERT INTO folio_charge_taxes (folio_charge_id, name, rate, is_inclusive, amount)
+SELECT id, 'Tax', tax_rate, FALSE, tax_amount
+FROM folio_charges
+WHERE tax_amount <> 0;
+
+ALTER TABLE folio_charges DROP CONSTRAINT folio_charges_tax_rate_check;
+ALTER TABLE folio_charges
+    ADD CONSTRAINT folio_charges_tax_rate_check CHECK (tax_rate >= 0);
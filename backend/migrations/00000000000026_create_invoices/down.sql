@@ -0,0 +1,4 @@
+DROP TRIGGER IF EXISTS invoices_immutable ON invoices;
+DROP FUNCTION IF EXISTS prevent_invoice_changes();
+DROP TABLE IF EXISTS invoices;
+DROP TABLE IF EXISTS invoice_counters;
@@ -0,0 +1,41 @@
+-- Numbered, immutable invoices; the rendered HTML and PDF live in object storage
+
+-- Gapless invoice numbering per calendar year
+CREATE TABLE invoice_counters (
+    year INTEGER PRIMARY KEY,
+    last_number INTEGER NOT NULL
+);
+
+CREATE TABLE invoices (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- INV-YYYY-NNNNNN
+    invoice_number VARCHAR(20) NOT NULL UNIQUE,
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE RESTRICT,
+    -- Snapshot of the bill when it was issued
+    guest_name VARCHAR(100) NOT NULL,
+    room_number VARCHAR(10) NOT NULL,
+    check_in_date DATE NOT NULL,
+    check_out_date DATE NOT NULL,
+    net_total DECIMAL(12, 0) NOT NULL,
+    tax_total DECIMAL(12, 0) NOT NULL,
+    total_amount DECIMAL(12, 0) NOT NULL,
+    total_paid DECIMAL(12, 2) NOT NULL,
+    balance_due DECIMAL(12, 2) NOT NULL,
+    html_object_key VARCHAR(255) NOT NULL,
+    pdf_object_key VARCHAR(255) NOT NULL,
+    issued_by_user_id UUID NOT NULL REFERENCES users(id),
+    issued_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_invoices_booking_id ON invoices(booking_id);
+
+CREATE OR REPLACE FUNCTION prevent_invoice_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'Invoices are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER invoices_immutable
+    BEFORE UPDATE OR DELETE ON invoices
+    FOR EACH ROW EXECUTE FUNCTION prevent_invoice_changes();
@@ -0,0 +1,49 @@
+-- Online payments through a payment gateway
+
+CREATE TYPE payment_intent_status AS ENUM ('pending', 'succeeded', 'failed', 'cancelled');
+
+CREATE TABLE payment_intents (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    -- Gateway name, e.g. 'mock'
+    provider VARCHAR(30) NOT NULL,
+    -- Gateway's own ID for the intent (set once the gateway accepted it)
+    provider_reference VARCHAR(100),
+    amount DECIMAL(12, 2) NOT NULL,
+    payment_type payment_type NOT NULL,
+    status payment_intent_status NOT NULL DEFAULT 'pending',
+    checkout_url TEXT,
+    -- Payment recorded when the gateway confirmed the intent
+    payment_id UUID REFERENCES payments(id) ON DELETE SET NULL,
+    failure_reason TEXT,
+    created_by_user_id UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_payment_intent_amount CHECK (amount > 0),
+    CONSTRAINT chk_payment_intent_type CHECK (payment_type != 'refund'),
+    CONSTRAINT uq_payment_intent_reference UNIQUE (provider, provider_reference)
+);
+
+CREATE INDEX idx_payment_intents_booking_id ON payment_intents(booking_id);
+
+CREATE TRIGGER update_payment_intents_updated_at
+    BEFORE UPDATE ON payment_intents
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+-- Every webhook event accepted from a gateway; the unique event ID makes
+-- redelivered events no-ops
+CREATE TABLE payment_webhook_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    provider VARCHAR(30) NOT NULL,
+    event_id VARCHAR(100) NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    payment_intent_id UUID REFERENCES payment_intents(id) ON DELETE SET NULL,
+    payload TEXT NOT NULL,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_payment_webhook_event UNIQUE (provider, event_id)
+);
+
+CREATE INDEX idx_payment_webhook_events_intent ON payment_webhook_events(payment_intent_id);
@@ -0,0 +1,14 @@
+DROP TABLE IF EXISTS payment_ledger_entries;
+DROP FUNCTION IF EXISTS prevent_ledger_changes();
+DROP TYPE IF EXISTS payment_ledger_action;
+
+DROP TRIGGER IF EXISTS payments_void_only ON payments;
+DROP FUNCTION IF EXISTS prevent_payment_changes();
+DROP INDEX IF EXISTS idx_payments_active_booking;
+
+ALTER TABLE payments
+    DROP CONSTRAINT IF EXISTS chk_payment_void,
+    DROP COLUMN IF EXISTS replaces_payment_id,
+    DROP COLUMN IF EXISTS void_reason,
+    DROP COLUMN IF EXISTS voided_by_user_id,
+    DROP COLUMN IF EXISTS voided_at;
@@ -0,0 +1,94 @@
+-- Payments are never deleted or edited in place: they are voided (with a
+-- reason) or adjusted (voided and replaced), and every change is written to
+-- an append-only ledger
+
+ALTER TABLE payments
+    ADD COLUMN voided_at TIMESTAMPTZ,
+    ADD COLUMN voided_by_user_id UUID REFERENCES users(id),
+    ADD COLUMN void_reason TEXT,
+    -- Payment this one replaced through an adjustment
+    ADD COLUMN replaces_payment_id UUID REFERENCES payments(id),
+    ADD CONSTRAINT chk_payment_void CHECK (
+        (voided_at IS NULL AND voided_by_user_id IS NULL AND void_reason IS NULL)
+        OR (voided_at IS NOT NULL AND voided_by_user_id IS NOT NULL AND void_reason IS NOT NULL)
+    );
+
+CREATE INDEX idx_payments_active_booking ON payments(booking_id) WHERE voided_at IS NULL;
+
+-- The recorded money movement of a payment cannot change; only voiding is allowed
+CREATE OR REPLACE FUNCTION prevent_payment_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.booking_id IS DISTINCT FROM OLD.booking_id
+        OR NEW.amount IS DISTINCT FROM OLD.amount
+        OR NEW.payment_type IS DISTINCT FROM OLD.payment_type
+        OR NEW.payment_method IS DISTINCT FROM OLD.payment_method
+        OR NEW.notes IS DISTINCT FROM OLD.notes
+        OR NEW.created_by_user_id IS DISTINCT FROM OLD.created_by_user_id
+        OR NEW.replaces_payment_id IS DISTINCT FROM OLD.replaces_payment_id
+        OR (OLD.voided_at IS NOT NULL AND (
+            NEW.voided_at IS DISTINCT FROM OLD.voided_at
+            OR NEW.voided_by_user_id IS DISTINCT FROM OLD.voided_by_user_id
+            OR NEW.void_reason IS DISTINCT FROM OLD.void_reason
+        ))
+    THEN
+        RAISE EXCEPTION 'Payments cannot be changed; void or adjust them instead';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER payments_void_only
+    BEFORE UPDATE ON payments
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_payment_changes();
+
+CREATE TYPE payment_ledger_action AS ENUM ('created', 'voided', 'adjusted');
+
+CREATE TABLE payment_ledger_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Payment created or voided; for adjustments, the replacement payment
+    payment_id UUID NOT NULL REFERENCES payments(id) ON DELETE RESTRICT,
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE RESTRICT,
+    action payment_ledger_action NOT NULL,
+    -- Change to the booking's paid total
+    amount_delta DECIMAL(12, 2) NOT NULL,
+    -- Payment details as of this entry
+    amount DECIMAL(12, 2) NOT NULL,
+    payment_type payment_type NOT NULL,
+    payment_method VARCHAR(50) NOT NULL,
+    -- Payment an adjustment replaced
+    previous_payment_id UUID REFERENCES payments(id) ON DELETE RESTRICT,
+    reason TEXT,
+    actor_user_id UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_ledger_reason CHECK (action = 'created' OR reason IS NOT NULL),
+    CONSTRAINT chk_ledger_previous CHECK ((action = 'adjusted') = (previous_payment_id IS NOT NULL))
+);
+
+CREATE INDEX idx_payment_ledger_booking_id ON payment_ledger_entries(booking_id);
+CREATE INDEX idx_payment_ledger_payment_id ON payment_ledger_entries(payment_id);
+CREATE INDEX idx_payment_ledger_created_at ON payment_ledger_entries(created_at);
+
+CREATE OR REPLACE FUNCTION prevent_ledger_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'The payment ledger is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER payment_ledger_append_only
+    BEFORE UPDATE OR DELETE ON payment_ledger_entries
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_ledger_changes();
+
+-- Open the ledger with the payments recorded so far
+INSERT INTO payment_ledger_entries (
+    payment_id, booking_id, action, amount_delta, amount, payment_type,
+    payment_method, actor_user_id, created_at
+)
+SELECT id, booking_id, 'created', amount, amount, payment_type,
+       payment_method, created_by_user_id, created_at
+FROM payments
+ORDER BY created_at;
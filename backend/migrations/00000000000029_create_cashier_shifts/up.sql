@@ -0,0 +1,62 @@
+-- Cashier shifts: a receptionist opens a shift with a cash float, the
+-- payments they take are attached to it, and closing it with the counted
+-- cash gives the drawer's over/short figure
+
+CREATE TYPE cashier_shift_status AS ENUM ('open', 'closed');
+
+CREATE TABLE cashier_shifts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id),
+    status cashier_shift_status NOT NULL DEFAULT 'open',
+    -- Cash in the drawer when the shift started
+    opening_float DECIMAL(12, 2) NOT NULL CHECK (opening_float >= 0),
+    opening_notes TEXT,
+    opened_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    closed_at TIMESTAMPTZ,
+    -- Cash counted in the drawer at close
+    counted_cash DECIMAL(12, 2) CHECK (counted_cash >= 0),
+    -- Opening float plus net cash taken during the shift, fixed at close
+    expected_cash DECIMAL(12, 2),
+    -- counted_cash - expected_cash: positive when over, negative when short
+    over_short DECIMAL(12, 2),
+    closing_notes TEXT,
+
+    CONSTRAINT chk_cashier_shift_close CHECK (
+        (status = 'open' AND closed_at IS NULL AND counted_cash IS NULL
+            AND expected_cash IS NULL AND over_short IS NULL)
+        OR (status = 'closed' AND closed_at IS NOT NULL AND counted_cash IS NOT NULL
+            AND expected_cash IS NOT NULL AND over_short IS NOT NULL)
+    )
+);
+
+-- A cashier has at most one open shift
+CREATE UNIQUE INDEX idx_cashier_shifts_open_user ON cashier_shifts(user_id) WHERE status = 'open';
+CREATE INDEX idx_cashier_shifts_opened_at ON cashier_shifts(opened_at);
+
+ALTER TABLE payments ADD COLUMN shift_id UUID REFERENCES cashier_shifts(id);
+
+CREATE INDEX idx_payments_shift_id ON payments(shift_id);
+
+-- The shift a payment was taken in is part of its record
+CREATE OR REPLACE FUNCTION prevent_payment_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.booking_id IS DISTINCT FROM OLD.booking_id
+        OR NEW.amount IS DISTINCT FROM OLD.amount
+        OR NEW.payment_type IS DISTINCT FROM OLD.payment_type
+        OR NEW.payment_method IS DISTINCT FROM OLD.payment_method
+        OR NEW.notes IS DISTINCT FROM OLD.notes
+        OR NEW.created_by_user_id IS DISTINCT FROM OLD.created_by_user_id
+        OR NEW.replaces_payment_id IS DISTINCT FROM OLD.replaces_payment_id
+        OR NEW.shift_id IS DISTINCT FROM OLD.shift_id
+        OR (OLD.voided_at IS NOT NULL AND (
+            NEW.voided_at IS DISTINCT FROM OLD.voided_at
+            OR NEW.voided_by_user_id IS DISTINCT FROM OLD.voided_by_user_id
+            OR NEW.void_reason IS DISTINCT FROM OLD.void_reason
+        ))
+    THEN
+        RAISE EXCEPTION 'Payments cannot be changed; void or adjust them instead';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
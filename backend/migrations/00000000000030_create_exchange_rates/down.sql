@@ -0,0 +1,30 @@
+CREATE OR REPLACE FUNCTION prevent_payment_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.booking_id IS DISTINCT FROM OLD.booking_id
+        OR NEW.amount IS DISTINCT FROM OLD.amount
+        OR NEW.payment_type IS DISTINCT FROM OLD.payment_type
+        OR NEW.payment_method IS DISTINCT FROM OLD.payment_method
+        OR NEW.notes IS DISTINCT FROM OLD.notes
+        OR NEW.created_by_user_id IS DISTINCT FROM OLD.created_by_user_id
+        OR NEW.replaces_payment_id IS DISTINCT FROM OLD.replaces_payment_id
+        OR NEW.shift_id IS DISTINCT FROM OLD.shift_id
+        OR (OLD.voided_at IS NOT NULL AND (
+            NEW.voided_at IS DISTINCT FROM OLD.voided_at
+            OR NEW.voided_by_user_id IS DISTINCT FROM OLD.voided_by_user_id
+            OR NEW.void_reason IS DISTINCT FROM OLD.void_reason
+        ))
+    THEN
+        RAISE EXCEPTION 'Payments cannot be changed; void or adjust them instead';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+ALTER TABLE payments
+    DROP CONSTRAINT IF EXISTS chk_payment_currency,
+    DROP COLUMN IF EXISTS original_amount,
+    DROP COLUMN IF EXISTS exchange_rate,
+    DROP COLUMN IF EXISTS currency;
+
+DROP TABLE IF EXISTS exchange_rates;
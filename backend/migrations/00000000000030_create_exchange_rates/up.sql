@@ -0,0 +1,55 @@
+-- Payments in foreign currencies: an admin-maintained exchange rate table,
+-- and each payment keeps the currency and rate it was taken at. `amount`
+-- stays in the base currency (VND) so totals and reports need no conversion.
+
+CREATE TABLE exchange_rates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    currency VARCHAR(3) NOT NULL CHECK (currency ~ '^[A-Z]{3}$' AND currency <> 'VND'),
+    -- Base currency units for one unit of `currency`
+    rate DECIMAL(18, 6) NOT NULL CHECK (rate > 0),
+    effective_date DATE NOT NULL,
+    created_by_user_id UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT uq_exchange_rates_currency_date UNIQUE (currency, effective_date)
+);
+
+SELECT diesel_manage_updated_at('exchange_rates');
+
+ALTER TABLE payments
+    ADD COLUMN currency VARCHAR(3) NOT NULL DEFAULT 'VND',
+    ADD COLUMN exchange_rate DECIMAL(18, 6) NOT NULL DEFAULT 1,
+    -- Amount in `currency`; only set for foreign currency payments
+    ADD COLUMN original_amount DECIMAL(12, 2),
+    ADD CONSTRAINT chk_payment_currency CHECK (
+        (currency = 'VND' AND exchange_rate = 1 AND original_amount IS NULL)
+        OR (currency <> 'VND' AND exchange_rate > 0 AND original_amount IS NOT NULL)
+    );
+
+-- The currency and rate a payment was taken at are part of its record
+CREATE OR REPLACE FUNCTION prevent_payment_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.booking_id IS DISTINCT FROM OLD.booking_id
+        OR NEW.amount IS DISTINCT FROM OLD.amount
+        OR NEW.payment_type IS DISTINCT FROM OLD.payment_type
+        OR NEW.payment_method IS DISTINCT FROM OLD.payment_method
+        OR NEW.notes IS DISTINCT FROM OLD.notes
+        OR NEW.created_by_user_id IS DISTINCT FROM OLD.created_by_user_id
+        OR NEW.replaces_payment_id IS DISTINCT FROM OLD.replaces_payment_id
+        OR NEW.shift_id IS DISTINCT FROM OLD.shift_id
+        OR NEW.currency IS DISTINCT FROM OLD.currency
+        OR NEW.exchange_rate IS DISTINCT FROM OLD.exchange_rate
+        OR NEW.original_amount IS DISTINCT FROM OLD.original_amount
+        OR (OLD.voided_at IS NOT NULL AND (
+            NEW.voided_at IS DISTINCT FROM OLD.voided_at
+            OR NEW.voided_by_user_id IS DISTINCT FROM OLD.voided_by_user_id
+            OR NEW.void_reason IS DISTINCT FROM OLD.void_reason
+        ))
+    THEN
+        RAISE EXCEPTION 'Payments cannot be changed; void or adjust them instead';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS guest_credit_entries;
+DROP FUNCTION IF EXISTS prevent_guest_credit_changes();
@@ -0,0 +1,32 @@
+-- Guest credit: money a guest overpaid on a booking, kept on their account
+-- and applied to later bookings. Entries are append-only; the balance is
+-- their sum.
+
+CREATE TABLE guest_credit_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    guest_user_id UUID NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    -- Positive when credit is added, negative when it is used
+    amount DECIMAL(12, 2) NOT NULL CHECK (amount <> 0),
+    -- Booking the credit came from or was applied to
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE RESTRICT,
+    -- Payment that moved the money on or off the booking
+    payment_id UUID NOT NULL REFERENCES payments(id) ON DELETE RESTRICT,
+    notes TEXT,
+    created_by_user_id UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_guest_credit_entries_guest ON guest_credit_entries(guest_user_id);
+CREATE INDEX idx_guest_credit_entries_booking ON guest_credit_entries(booking_id);
+
+CREATE OR REPLACE FUNCTION prevent_guest_credit_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'Guest credit entries are append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER guest_credit_entries_append_only
+    BEFORE UPDATE OR DELETE ON guest_credit_entries
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_guest_credit_changes();
@@ -0,0 +1,6 @@
+DELETE FROM system_settings WHERE key LIKE 'housekeeping_sla_%';
+
+DROP TABLE IF EXISTS housekeeping_tasks;
+DROP TYPE IF EXISTS housekeeping_priority;
+DROP TYPE IF EXISTS housekeeping_task_status;
+DROP TYPE IF EXISTS housekeeping_task_type;
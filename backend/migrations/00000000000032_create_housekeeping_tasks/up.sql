@@ -0,0 +1,62 @@
+-- Housekeeping tasks: the cleaning work queue. A task is created when a
+-- guest checks out (or moves room), or by an admin; cleaners start and
+-- finish tasks, which moves the room through Dirty -> Cleaning -> Available.
+
+CREATE TYPE housekeeping_task_type AS ENUM ('checkout_clean', 'stayover', 'deep_clean', 'turndown');
+CREATE TYPE housekeeping_task_status AS ENUM ('pending', 'in_progress', 'completed', 'cancelled');
+CREATE TYPE housekeeping_priority AS ENUM ('low', 'normal', 'high');
+
+CREATE TABLE housekeeping_tasks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    room_id UUID NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    -- Stay that left the room to clean, if any
+    booking_id UUID REFERENCES bookings(id) ON DELETE SET NULL,
+    task_type housekeeping_task_type NOT NULL,
+    status housekeeping_task_status NOT NULL DEFAULT 'pending',
+    priority housekeeping_priority NOT NULL DEFAULT 'normal',
+    assigned_cleaner_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    notes TEXT,
+    -- SLA deadline: creation time plus the task type's SLA
+    due_at TIMESTAMPTZ NOT NULL,
+    started_at TIMESTAMPTZ,
+    completed_at TIMESTAMPTZ,
+    -- NULL when created automatically
+    created_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_housekeeping_task_progress CHECK (
+        (status = 'pending' AND started_at IS NULL AND completed_at IS NULL)
+        OR (status = 'in_progress' AND started_at IS NOT NULL AND completed_at IS NULL)
+        OR (status = 'completed' AND started_at IS NOT NULL AND completed_at IS NOT NULL)
+        OR status = 'cancelled'
+    )
+);
+
+-- A room has at most one open task of each type
+CREATE UNIQUE INDEX idx_housekeeping_tasks_open_room_type
+    ON housekeeping_tasks(room_id, task_type)
+    WHERE status IN ('pending', 'in_progress');
+CREATE INDEX idx_housekeeping_tasks_status ON housekeeping_tasks(status);
+CREATE INDEX idx_housekeeping_tasks_cleaner ON housekeeping_tasks(assigned_cleaner_id);
+CREATE INDEX idx_housekeeping_tasks_created_at ON housekeeping_tasks(created_at);
+
+SELECT diesel_manage_updated_at('housekeeping_tasks');
+
+-- Rooms already waiting to be cleaned get a task
+INSERT INTO housekeeping_tasks (room_id, task_type, status, assigned_cleaner_id, due_at, started_at)
+SELECT id, 'checkout_clean',
+       CASE WHEN status = 'cleaning' THEN 'in_progress' ELSE 'pending' END::housekeeping_task_status,
+       assigned_cleaner_id,
+       status_changed_at + INTERVAL '90 minutes',
+       CASE WHEN status = 'cleaning' THEN status_changed_at END
+FROM rooms
+WHERE status IN ('dirty', 'cleaning');
+
+-- Default SLAs
+INSERT INTO system_settings (key, value, description) VALUES
+('housekeeping_sla_checkout_clean_minutes', '90', 'Minutes to finish a checkout clean'),
+('housekeeping_sla_stayover_minutes', '45', 'Minutes to finish a stayover service'),
+('housekeeping_sla_deep_clean_minutes', '240', 'Minutes to finish a deep clean'),
+('housekeeping_sla_turndown_minutes', '30', 'Minutes to finish a turndown service')
+ON CONFLICT (key) DO NOTHING;
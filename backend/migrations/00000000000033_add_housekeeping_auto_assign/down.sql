@@ -0,0 +1 @@
+DELETE FROM system_settings WHERE key = 'housekeeping_auto_assign';
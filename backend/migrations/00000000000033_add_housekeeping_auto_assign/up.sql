@@ -0,0 +1,5 @@
+-- Assign the clean a room gets when it turns Dirty to the cleaner with the
+-- lightest load (off by default; admins can always preview and apply)
+INSERT INTO system_settings (key, value, description) VALUES
+('housekeeping_auto_assign', 'false', 'Assign new room cleans to a cleaner automatically')
+ON CONFLICT (key) DO NOTHING;
@@ -0,0 +1,8 @@
+DELETE FROM system_settings WHERE key IN ('housekeeping_inspection_required', 'housekeeping_inspection_checklist');
+
+DROP TABLE IF EXISTS room_inspections;
+
+-- PostgreSQL cannot drop an enum value, so 'pending_inspection' stays on
+-- room_status (see 00000000000006_add_cleaner_role_and_status); rooms
+-- waiting for inspection go back to the cleaning queue.
+UPDATE rooms SET status = 'dirty' WHERE status = 'pending_inspection';
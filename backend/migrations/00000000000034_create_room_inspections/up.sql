@@ -0,0 +1,37 @@
+-- Room inspections: an optional step between cleaning and selling a room.
+-- With housekeeping_inspection_required on, a finished clean leaves the
+-- room pending_inspection until a supervisor passes it (Available) or
+-- fails it (back to Dirty with a rework task for the cleaner).
+
+ALTER TYPE room_status ADD VALUE 'pending_inspection';
+
+CREATE TABLE room_inspections (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    room_id UUID NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    -- Clean that was inspected
+    housekeeping_task_id UUID REFERENCES housekeeping_tasks(id) ON DELETE SET NULL,
+    -- Who did the clean, kept when the task is gone
+    cleaner_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    inspector_id UUID NOT NULL REFERENCES users(id),
+    passed BOOLEAN NOT NULL,
+    -- Checklist at the time of the inspection and the items that failed
+    checklist TEXT[] NOT NULL DEFAULT '{}',
+    failed_items TEXT[] NOT NULL DEFAULT '{}',
+    notes TEXT,
+    -- Task created to redo the clean after a failure
+    rework_task_id UUID REFERENCES housekeeping_tasks(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_room_inspection_result CHECK (
+        (passed AND cardinality(failed_items) = 0)
+        OR (NOT passed AND (cardinality(failed_items) > 0 OR notes IS NOT NULL))
+    )
+);
+
+CREATE INDEX idx_room_inspections_room ON room_inspections(room_id, created_at);
+CREATE INDEX idx_room_inspections_cleaner ON room_inspections(cleaner_id);
+
+INSERT INTO system_settings (key, value, description) VALUES
+('housekeeping_inspection_required', 'false', 'Rooms wait for a passed inspection after cleaning before becoming available'),
+('housekeeping_inspection_checklist', '["Bed made with fresh linen","Bathroom cleaned","Floors vacuumed","Amenities restocked","Bins emptied"]', 'Inspection checklist items (JSON array)')
+ON CONFLICT (key) DO NOTHING;
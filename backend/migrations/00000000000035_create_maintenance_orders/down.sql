@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS maintenance_orders;
+DROP TYPE IF EXISTS maintenance_priority;
+DROP TYPE IF EXISTS maintenance_status;
@@ -0,0 +1,46 @@
+-- Maintenance work orders: a room is out of order for an expected date
+-- range. Only open orders block bookings, and only on the dates they
+-- cover; starting an order puts the room in maintenance and closing it
+-- makes the room available again.
+
+CREATE TYPE maintenance_status AS ENUM ('open', 'in_progress', 'closed', 'cancelled');
+CREATE TYPE maintenance_priority AS ENUM ('low', 'normal', 'high', 'urgent');
+
+CREATE TABLE maintenance_orders (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    room_id UUID NOT NULL REFERENCES rooms(id) ON DELETE CASCADE,
+    description TEXT NOT NULL,
+    status maintenance_status NOT NULL DEFAULT 'open',
+    priority maintenance_priority NOT NULL DEFAULT 'normal',
+    -- Expected out-of-order dates, both inclusive
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    assigned_to_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    resolution TEXT,
+    started_at TIMESTAMPTZ,
+    closed_at TIMESTAMPTZ,
+    created_by_user_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT chk_maintenance_order_dates CHECK (end_date >= start_date),
+    CONSTRAINT chk_maintenance_order_closed CHECK (
+        (status IN ('open', 'in_progress') AND closed_at IS NULL)
+        OR (status = 'closed' AND closed_at IS NOT NULL AND resolution IS NOT NULL)
+        OR (status = 'cancelled' AND closed_at IS NOT NULL)
+    )
+);
+
+CREATE INDEX idx_maintenance_orders_room_dates ON maintenance_orders(room_id, start_date, end_date)
+    WHERE status IN ('open', 'in_progress');
+CREATE INDEX idx_maintenance_orders_status ON maintenance_orders(status);
+
+SELECT diesel_manage_updated_at('maintenance_orders');
+
+-- Rooms already in maintenance get an order up to today, so they stop
+-- blocking every future date
+INSERT INTO maintenance_orders (room_id, description, status, start_date, end_date, started_at)
+SELECT id, 'In maintenance before work orders were introduced', 'in_progress',
+       LEAST(status_changed_at::date, CURRENT_DATE), CURRENT_DATE, status_changed_at
+FROM rooms
+WHERE status = 'maintenance';
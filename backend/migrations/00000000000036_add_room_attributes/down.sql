@@ -0,0 +1,16 @@
+ALTER TABLE bookings
+    DROP CONSTRAINT IF EXISTS chk_booking_guest_count,
+    DROP COLUMN IF EXISTS guest_count;
+
+DROP INDEX IF EXISTS idx_rooms_amenities;
+DROP INDEX IF EXISTS idx_rooms_floor;
+
+ALTER TABLE rooms
+    DROP CONSTRAINT IF EXISTS chk_room_max_occupancy,
+    DROP COLUMN IF EXISTS wheelchair_accessible,
+    DROP COLUMN IF EXISTS smoking,
+    DROP COLUMN IF EXISTS amenities,
+    DROP COLUMN IF EXISTS view,
+    DROP COLUMN IF EXISTS bed_configuration,
+    DROP COLUMN IF EXISTS max_occupancy,
+    DROP COLUMN IF EXISTS floor;
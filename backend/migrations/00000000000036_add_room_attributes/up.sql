@@ -0,0 +1,33 @@
+-- Structured room attributes guests and staff can filter on, and the number
+-- of guests on each booking so it can be checked against the room's capacity
+
+ALTER TABLE rooms
+    ADD COLUMN floor INTEGER,
+    ADD COLUMN max_occupancy INTEGER NOT NULL DEFAULT 2,
+    -- e.g. '1 king', '2 twin'
+    ADD COLUMN bed_configuration VARCHAR(50),
+    -- e.g. 'city', 'garden', 'sea'
+    ADD COLUMN view VARCHAR(30),
+    ADD COLUMN amenities TEXT[] NOT NULL DEFAULT '{}',
+    ADD COLUMN smoking BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN wheelchair_accessible BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD CONSTRAINT chk_room_max_occupancy CHECK (max_occupancy BETWEEN 1 AND 20);
+
+-- Floor from the room number (the digits before the last two: 101 -> 1)
+UPDATE rooms
+SET floor = substring(number FROM '^([0-9]+)[0-9]{2}')::INTEGER
+WHERE number ~ '^[0-9]{3}';
+
+-- Capacities that used to be implied by the room type
+UPDATE rooms SET max_occupancy = CASE room_type
+    WHEN 'single' THEN 2
+    WHEN 'double' THEN 4
+    ELSE 6
+END;
+
+CREATE INDEX idx_rooms_floor ON rooms(floor);
+CREATE INDEX idx_rooms_amenities ON rooms USING GIN (amenities);
+
+ALTER TABLE bookings
+    ADD COLUMN guest_count INTEGER NOT NULL DEFAULT 1,
+    ADD CONSTRAINT chk_booking_guest_count CHECK (guest_count > 0);
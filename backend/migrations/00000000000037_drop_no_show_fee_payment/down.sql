@@ -0,0 +1,6 @@
+UPDATE system_settings
+SET description = 'Post a no-show fee as a payment when a booking is marked no-show'
+WHERE key = 'no_show_fee_enabled';
+
+ALTER TABLE bookings
+  ADD COLUMN no_show_fee_payment_id UUID REFERENCES payments(id) ON DELETE SET NULL;
@@ -0,0 +1,7 @@
+-- No-show fees are left owed on the booking price instead of being posted
+-- as a payment the guest never made
+ALTER TABLE bookings DROP COLUMN IF EXISTS no_show_fee_payment_id;
+
+UPDATE system_settings
+SET description = 'Charge a no-show fee, left owed on the booking, when it is marked no-show'
+WHERE key = 'no_show_fee_enabled';
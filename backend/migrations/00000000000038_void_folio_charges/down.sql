@@ -0,0 +1,8 @@
+DROP TRIGGER IF EXISTS folio_charges_void_only ON folio_charges;
+DROP FUNCTION IF EXISTS prevent_folio_charge_changes();
+
+ALTER TABLE folio_charges
+    DROP CONSTRAINT IF EXISTS chk_folio_charge_void,
+    DROP COLUMN IF EXISTS void_reason,
+    DROP COLUMN IF EXISTS voided_by_user_id,
+    DROP COLUMN IF EXISTS voided_at;
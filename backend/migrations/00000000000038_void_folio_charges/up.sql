@@ -0,0 +1,41 @@
+-- Folio charges are never deleted or edited in place: a charge posted by
+-- mistake is voided with a reason and stays on the folio
+
+ALTER TABLE folio_charges
+    ADD COLUMN voided_at TIMESTAMPTZ,
+    ADD COLUMN voided_by_user_id UUID REFERENCES users(id),
+    ADD COLUMN void_reason TEXT,
+    ADD CONSTRAINT chk_folio_charge_void CHECK (
+        (voided_at IS NULL AND voided_by_user_id IS NULL AND void_reason IS NULL)
+        OR (voided_at IS NOT NULL AND voided_by_user_id IS NOT NULL AND void_reason IS NOT NULL)
+    );
+
+-- The charged amounts cannot change; only voiding is allowed
+CREATE OR REPLACE FUNCTION prevent_folio_charge_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.booking_id IS DISTINCT FROM OLD.booking_id
+        OR NEW.category IS DISTINCT FROM OLD.category
+        OR NEW.description IS DISTINCT FROM OLD.description
+        OR NEW.quantity IS DISTINCT FROM OLD.quantity
+        OR NEW.unit_price IS DISTINCT FROM OLD.unit_price
+        OR NEW.tax_rate IS DISTINCT FROM OLD.tax_rate
+        OR NEW.tax_amount IS DISTINCT FROM OLD.tax_amount
+        OR NEW.total_amount IS DISTINCT FROM OLD.total_amount
+        OR NEW.created_by_user_id IS DISTINCT FROM OLD.created_by_user_id
+        OR (OLD.voided_at IS NOT NULL AND (
+            NEW.voided_at IS DISTINCT FROM OLD.voided_at
+            OR NEW.voided_by_user_id IS DISTINCT FROM OLD.voided_by_user_id
+            OR NEW.void_reason IS DISTINCT FROM OLD.void_reason
+        ))
+    THEN
+        RAISE EXCEPTION 'Folio charges cannot be changed; void them instead';
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER folio_charges_void_only
+    BEFORE UPDATE ON folio_charges
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_folio_charge_changes();
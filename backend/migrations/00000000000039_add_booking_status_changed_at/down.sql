@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS set_bookings_status_changed_at ON bookings;
+DROP FUNCTION IF EXISTS set_booking_status_changed_at();
+ALTER TABLE bookings DROP COLUMN IF EXISTS status_changed_at;
@@ -0,0 +1,28 @@
+-- Track when a booking last changed status, so cancellation penalties and
+-- no-show fees can be booked as revenue on the day they were incurred
+ALTER TABLE bookings
+    ADD COLUMN status_changed_at TIMESTAMPTZ;
+
+-- Best guess for existing bookings, without touching updated_at
+ALTER TABLE bookings DISABLE TRIGGER update_bookings_updated_at;
+UPDATE bookings SET status_changed_at = updated_at;
+ALTER TABLE bookings ENABLE TRIGGER update_bookings_updated_at;
+
+ALTER TABLE bookings
+    ALTER COLUMN status_changed_at SET NOT NULL,
+    ALTER COLUMN status_changed_at SET DEFAULT NOW();
+
+CREATE OR REPLACE FUNCTION set_booking_status_changed_at()
+RETURNS TRIGGER AS $$
+BEGIN
+    IF NEW.status IS DISTINCT FROM OLD.status THEN
+        NEW.status_changed_at = NOW();
+    END IF;
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER set_bookings_status_changed_at
+    BEFORE UPDATE ON bookings
+    FOR EACH ROW
+    EXECUTE FUNCTION set_booking_status_changed_at();
@@ -0,0 +1,4 @@
+ALTER TABLE bookings
+    DROP CONSTRAINT IF EXISTS chk_unpaid_check_out,
+    DROP COLUMN IF EXISTS unpaid_check_out_balance,
+    DROP COLUMN IF EXISTS unpaid_check_out_by_user_id;
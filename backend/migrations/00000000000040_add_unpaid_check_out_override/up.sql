@@ -0,0 +1,7 @@
+-- Who let a guest check out with an unpaid balance, and how much was owed
+ALTER TABLE bookings
+    ADD COLUMN unpaid_check_out_by_user_id UUID REFERENCES users(id),
+    ADD COLUMN unpaid_check_out_balance DECIMAL(12, 2),
+    ADD CONSTRAINT chk_unpaid_check_out CHECK (
+        (unpaid_check_out_by_user_id IS NULL) = (unpaid_check_out_balance IS NULL)
+    );
@@ -0,0 +1,3 @@
+ALTER TABLE bookings
+    DROP COLUMN IF EXISTS no_show_undone_at,
+    DROP COLUMN IF EXISTS price_before_no_show;
@@ -0,0 +1,10 @@
+-- The price a booking had before it was marked a no-show, and when a no-show
+-- was undone so the no-show job leaves the booking alone afterwards
+ALTER TABLE bookings
+    ADD COLUMN price_before_no_show DECIMAL(12, 0),
+    ADD COLUMN no_show_undone_at TIMESTAMPTZ;
+
+-- Bookings marked before this column existed kept that price in original_price
+UPDATE bookings
+SET price_before_no_show = original_price
+WHERE status = 'no_show' AND original_price IS NOT NULL;
@@ -2,15 +2,17 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::api::middleware::AuthUser;
 use crate::api::AppState;
 use crate::errors::AppError;
 use crate::models::BookingStatus;
+use crate::services::booking_service::CreateGroupBookingRequest;
 use crate::services::BookingService;
 
 /// Create booking request DTO
@@ -20,6 +22,8 @@ pub struct CreateBookingDto {
     pub room_id: Uuid,
     pub check_in_date: NaiveDate,
     pub check_out_date: NaiveDate,
+    /// Guests staying in the room (defaults to 1)
+    pub guest_count: Option<i32>,
     #[serde(default)]
     pub price: Option<bigdecimal::BigDecimal>,
 }
@@ -31,6 +35,7 @@ pub struct UpdateBookingDto {
     pub guest_name: Option<String>,
     pub check_in_date: Option<NaiveDate>,
     pub check_out_date: Option<NaiveDate>,
+    pub guest_count: Option<i32>,
 }
 
 /// Check-in request DTO (no parameters needed - check-in only allowed on check-in date)
@@ -39,6 +44,20 @@ pub struct CheckInDto {
     // Empty struct - check-in is only allowed on the actual check-in date
 }
 
+/// Room move request DTO
+#[derive(Debug, Deserialize)]
+pub struct MoveBookingDto {
+    pub room_id: Uuid,
+    pub reason: Option<String>,
+}
+
+/// Stay extension / shortening request DTO
+#[derive(Debug, Deserialize)]
+pub struct ChangeStayDto {
+    pub check_out_date: NaiveDate,
+    pub reason: Option<String>,
+}
+
 /// Query parameters for listing bookings
 #[derive(Debug, Deserialize)]
 pub struct ListBookingsQuery {
@@ -59,6 +78,7 @@ pub async fn create_booking(
         payload.room_id,
         payload.check_in_date,
         payload.check_out_date,
+        payload.guest_count.unwrap_or(1),
         payload.price,
     )?;
     Ok((StatusCode::CREATED, Json(booking)))
@@ -144,6 +164,12 @@ pub async fn update_booking(
         }
     }
     
+    // The guest count is checked against the room's capacity
+    if let Some(guest_count) = payload.guest_count {
+        let updated = booking_service.set_guest_count(id, guest_count)?;
+        return Ok((StatusCode::OK, Json(updated)));
+    }
+
     // Note: For MVP, we're returning the current booking
     // Full update implementation would use UpdateBooking changeset
     Ok((StatusCode::OK, Json(current)))
@@ -165,29 +191,179 @@ pub async fn check_in(
 pub struct CheckOutDto {
     #[serde(default)]
     pub confirm_early: bool,
+    /// Let the guest leave with an unpaid folio balance
+    #[serde(default)]
+    pub allow_unpaid_balance: bool,
 }
 
 /// Check out a guest
 pub async fn check_out(
     State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
     Path(id): Path<Uuid>,
     Json(payload): Json<CheckOutDto>,
 ) -> Result<impl IntoResponse, AppError> {
     let booking_service = BookingService::new(state.pool);
-    let booking = booking_service.check_out(id, payload.confirm_early)?;
+    let booking = booking_service.check_out(
+        id,
+        payload.confirm_early,
+        payload.allow_unpaid_balance,
+        auth_user.user_id,
+    )?;
     Ok((StatusCode::OK, Json(booking)))
 }
 
-/// Cancel a booking
+/// Cancel a booking, refunding according to its cancellation policy
 pub async fn cancel(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let cancelled = booking_service.cancel(id, auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(cancelled)))
+}
+
+/// Move an in-house guest to another room
+/// POST /bookings/:id/move
+pub async fn move_booking(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
+    Json(payload): Json<MoveBookingDto>,
 ) -> Result<impl IntoResponse, AppError> {
     let booking_service = BookingService::new(state.pool);
-    let booking = booking_service.cancel(id)?;
+    let booking = booking_service.move_booking(id, payload.room_id, payload.reason.as_deref())?;
+    Ok((StatusCode::OK, Json(booking)))
+}
+
+/// List the rooms a booking has stayed in
+/// GET /bookings/:id/room-stays
+pub async fn list_room_stays(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let stays = booking_service.list_room_stays(id)?;
+    Ok((StatusCode::OK, Json(stays)))
+}
+
+/// Extend a stay to a later check-out date
+/// POST /bookings/:id/extend
+pub async fn extend_stay(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<ChangeStayDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let booking = booking_service.extend_stay(
+        id,
+        payload.check_out_date,
+        auth_user.user_id,
+        payload.reason.as_deref(),
+    )?;
+    Ok((StatusCode::OK, Json(booking)))
+}
+
+/// Shorten a stay to an earlier check-out date
+/// POST /bookings/:id/shorten
+pub async fn shorten_stay(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<ChangeStayDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let booking = booking_service.shorten_stay(
+        id,
+        payload.check_out_date,
+        auth_user.user_id,
+        payload.reason.as_deref(),
+    )?;
     Ok((StatusCode::OK, Json(booking)))
 }
 
+/// List check-out date changes of a booking
+/// GET /bookings/:id/stay-changes
+pub async fn list_stay_changes(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let changes = booking_service.list_stay_changes(id)?;
+    Ok((StatusCode::OK, Json(changes)))
+}
+
+/// Create a group reservation with several rooms
+/// POST /bookings/groups
+pub async fn create_group(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateGroupBookingRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let group = booking_service.create_group_booking(&payload, Some(auth_user.user_id))?;
+    Ok((StatusCode::CREATED, Json(group)))
+}
+
+/// List group reservations
+/// GET /bookings/groups
+pub async fn list_groups(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let groups = booking_service.list_groups()?;
+    Ok((StatusCode::OK, Json(groups)))
+}
+
+/// Get a group reservation with its room bookings
+/// GET /bookings/groups/:id
+pub async fn get_group(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let group = booking_service.get_group(id)?;
+    Ok((StatusCode::OK, Json(group)))
+}
+
+/// Check in all upcoming rooms of a group
+/// POST /bookings/groups/:id/check-in
+pub async fn check_in_group(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let group = booking_service.check_in_group(id)?;
+    Ok((StatusCode::OK, Json(group)))
+}
+
+/// Check out all in-house rooms of a group
+/// POST /bookings/groups/:id/check-out
+pub async fn check_out_group(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+    payload: Option<Json<CheckOutDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let allow_unpaid_balance = payload.map(|Json(p)| p.allow_unpaid_balance).unwrap_or(false);
+    let booking_service = BookingService::new(state.pool);
+    let group = booking_service.check_out_group(id, allow_unpaid_balance, auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(group)))
+}
+
+/// Cancel all upcoming rooms of a group
+/// POST /bookings/groups/:id/cancel
+pub async fn cancel_group(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let booking_service = BookingService::new(state.pool);
+    let group = booking_service.cancel_group(id, auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(group)))
+}
+
 /// Sync booking statuses response
 #[allow(dead_code)]
 #[derive(Debug, Serialize)]
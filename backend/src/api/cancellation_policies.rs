@@ -0,0 +1,113 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::errors::AppError;
+use crate::models::{CancellationPenaltyType, NewCancellationPolicy, RoomType, UpdateCancellationPolicy};
+use crate::services::CancellationService;
+use crate::utils::double_option;
+
+/// Create cancellation policy request DTO
+#[derive(Debug, Deserialize)]
+pub struct CreateCancellationPolicyDto {
+    pub name: String,
+    pub free_cancellation_days: i32,
+    pub penalty_type: CancellationPenaltyType,
+    pub penalty_value: BigDecimal,
+    /// Make this the default policy for a room type
+    pub room_type: Option<RoomType>,
+    pub is_active: Option<bool>,
+}
+
+/// Update cancellation policy request DTO
+///
+/// `room_type` uses `Option<Option<_>>`: omit to keep, send `null` to clear.
+#[derive(Debug, Deserialize)]
+pub struct UpdateCancellationPolicyDto {
+    pub name: Option<String>,
+    pub free_cancellation_days: Option<i32>,
+    pub penalty_type: Option<CancellationPenaltyType>,
+    pub penalty_value: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub room_type: Option<Option<RoomType>>,
+    pub is_active: Option<bool>,
+}
+
+/// List cancellation policies
+/// GET /admin/cancellation-policies
+pub async fn list_cancellation_policies(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let cancellation_service = CancellationService::new(state.pool);
+    let policies = cancellation_service.list_policies()?;
+    Ok((StatusCode::OK, Json(policies)))
+}
+
+/// Get a cancellation policy
+/// GET /admin/cancellation-policies/:id
+pub async fn get_cancellation_policy(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let cancellation_service = CancellationService::new(state.pool);
+    let policy = cancellation_service.get_policy(id)?;
+    Ok((StatusCode::OK, Json(policy)))
+}
+
+/// Create a cancellation policy
+/// POST /admin/cancellation-policies
+pub async fn create_cancellation_policy(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateCancellationPolicyDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let cancellation_service = CancellationService::new(state.pool);
+    let policy = cancellation_service.create_policy(NewCancellationPolicy {
+        name: payload.name.trim().to_string(),
+        free_cancellation_days: payload.free_cancellation_days,
+        penalty_type: payload.penalty_type,
+        penalty_value: payload.penalty_value,
+        room_type: payload.room_type,
+        is_active: payload.is_active.unwrap_or(true),
+    })?;
+    Ok((StatusCode::CREATED, Json(policy)))
+}
+
+/// Update a cancellation policy
+/// PATCH /admin/cancellation-policies/:id
+pub async fn update_cancellation_policy(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateCancellationPolicyDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let cancellation_service = CancellationService::new(state.pool);
+    let policy = cancellation_service.update_policy(
+        id,
+        UpdateCancellationPolicy {
+            name: payload.name.map(|n| n.trim().to_string()),
+            free_cancellation_days: payload.free_cancellation_days,
+            penalty_type: payload.penalty_type,
+            penalty_value: payload.penalty_value,
+            room_type: payload.room_type,
+            is_active: payload.is_active,
+        },
+    )?;
+    Ok((StatusCode::OK, Json(policy)))
+}
+
+/// Delete a cancellation policy
+/// DELETE /admin/cancellation-policies/:id
+pub async fn delete_cancellation_policy(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let cancellation_service = CancellationService::new(state.pool);
+    cancellation_service.delete_policy(id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,52 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::services::{exchange_rate_service::SetExchangeRateRequest, ExchangeRateService};
+
+/// Query parameters for listing exchange rates
+#[derive(Debug, Deserialize)]
+pub struct ExchangeRateQuery {
+    pub currency: Option<String>,
+}
+
+/// List exchange rates
+/// GET /admin/exchange-rates
+pub async fn list_exchange_rates(
+    State(state): State<AppState>,
+    Query(query): Query<ExchangeRateQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let rate_service = ExchangeRateService::new(state.pool);
+    let rates = rate_service.list_rates(query.currency.as_deref())?;
+    Ok((StatusCode::OK, Json(rates)))
+}
+
+/// Set the rate for a currency from a date on
+/// POST /admin/exchange-rates
+pub async fn set_exchange_rate(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<SetExchangeRateRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let rate_service = ExchangeRateService::new(state.pool);
+    let rate = rate_service.set_rate(payload, auth_user.user_id)?;
+    Ok((StatusCode::CREATED, Json(rate)))
+}
+
+/// Delete an exchange rate
+/// DELETE /admin/exchange-rates/:id
+pub async fn delete_exchange_rate(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let rate_service = ExchangeRateService::new(state.pool);
+    rate_service.delete_rate(id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
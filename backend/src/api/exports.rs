@@ -0,0 +1,126 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+use crate::api::bookings::ListBookingsQuery;
+use crate::api::financial::DateRangeQuery;
+use crate::api::payments::LedgerQuery;
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::services::export_service::{
+    stream_export, BookingExport, ExportFormat, ExportSource, InventoryExport, PaymentLedgerExport,
+    RevenueExport,
+};
+use crate::services::RoomService;
+
+/// Response streaming an export as a file download
+fn export_response(format: ExportFormat, source: Box<dyn ExportSource>) -> Response {
+    let filename = format!(
+        "{}-{}.{}",
+        source.name(),
+        Utc::now().format("%Y-%m-%d"),
+        format.extension()
+    );
+
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        stream_export(format, source),
+    )
+        .into_response()
+}
+
+/// Parse an optional `YYYY-MM-DD` date the way the financial routes do
+fn parse_date(value: &Option<String>) -> Option<NaiveDate> {
+    value
+        .as_ref()
+        .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+}
+
+/// Export revenue per day
+/// GET /admin/exports/revenue/:format
+pub async fn export_revenue(
+    State(state): State<AppState>,
+    Path(format): Path<ExportFormat>,
+    Query(query): Query<DateRangeQuery>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let source = RevenueExport::new(
+        state.pool,
+        None,
+        parse_date(&query.start_date),
+        parse_date(&query.end_date),
+        query.basis(),
+    );
+    Ok(export_response(format, Box::new(source)))
+}
+
+/// Export revenue per day for one room
+/// GET /admin/exports/rooms/:roomId/revenue/:format
+pub async fn export_room_revenue(
+    State(state): State<AppState>,
+    Path((room_id, format)): Path<(Uuid, ExportFormat)>,
+    Query(query): Query<DateRangeQuery>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    RoomService::new(state.pool.clone()).get_room_by_id(room_id)?;
+
+    let source = RevenueExport::new(
+        state.pool,
+        Some(room_id),
+        parse_date(&query.start_date),
+        parse_date(&query.end_date),
+        query.basis(),
+    );
+    Ok(export_response(format, Box::new(source)))
+}
+
+/// Export bookings, filtered like the booking list
+/// GET /admin/exports/bookings/:format
+pub async fn export_bookings(
+    State(state): State<AppState>,
+    Path(format): Path<ExportFormat>,
+    Query(query): Query<ListBookingsQuery>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let source = BookingExport::new(
+        state.pool,
+        query.status,
+        query.guest_name,
+        query.from_date,
+        query.to_date,
+    );
+    Ok(export_response(format, Box::new(source)))
+}
+
+/// Export the payment ledger
+/// GET /admin/exports/payments/ledger/:format
+pub async fn export_payment_ledger(
+    State(state): State<AppState>,
+    Path(format): Path<ExportFormat>,
+    Query(query): Query<LedgerQuery>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let source = PaymentLedgerExport::new(state.pool, query.start_date, query.end_date)?;
+    Ok(export_response(format, Box::new(source)))
+}
+
+/// Export inventory valuation
+/// GET /admin/exports/inventory/:format
+pub async fn export_inventory(
+    State(state): State<AppState>,
+    Path(format): Path<ExportFormat>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    Ok(export_response(format, Box::new(InventoryExport::new(state.pool))))
+}
@@ -9,7 +9,11 @@ use uuid::Uuid;
 
 use crate::api::{middleware::AuthUser, AppState};
 use crate::errors::AppError;
-use crate::services::{BookingService, RoomService};
+use crate::models::KpiPeriod;
+use crate::services::exchange_rate_service::BASE_CURRENCY;
+use crate::services::booking_service::RevenueBasis;
+use crate::services::room_service::RoomFilter;
+use crate::services::{BookingService, KpiService, RoomService};
 use crate::utils::validate_date_format;
 
 /// Date range query parameters
@@ -17,8 +21,33 @@ use crate::utils::validate_date_format;
 pub struct DateRangeQuery {
     pub start_date: Option<String>, // YYYY-MM-DD format
     pub end_date: Option<String>,   // YYYY-MM-DD format
+    /// How revenue is recognised: `checkout` (default), `cash` or `accrual`
+    pub basis: Option<RevenueBasis>,
     #[serde(default)]
-    pub use_payments: Option<bool>, // Use actual payments instead of booking prices
+    pub use_payments: Option<bool>, // Older flag for the cash basis
+}
+
+impl DateRangeQuery {
+    /// Revenue basis asked for
+    pub fn basis(&self) -> RevenueBasis {
+        RevenueBasis::resolve(self.basis, self.use_payments)
+    }
+}
+
+/// KPI report query parameters
+#[derive(Debug, Deserialize)]
+pub struct KpiQuery {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// `day` (default), `week` or `month`
+    pub group_by: Option<KpiPeriod>,
+}
+
+/// Query parameters for the on-the-books forecast
+#[derive(Debug, Deserialize)]
+pub struct ForecastQuery {
+    /// Days to look ahead from today (default 30)
+    pub days: Option<i64>,
 }
 
 /// Room financial summary response
@@ -40,35 +69,32 @@ pub struct RoomSummary {
 /// Financial metrics response
 #[derive(Debug, Serialize)]
 pub struct RoomFinancialsResponse {
-    pub total_revenue: String, // Decimal as string for JSON
+    pub total_revenue: String, // Decimal as string for JSON (gross, same as gross_revenue)
+    pub net_revenue: String,
+    pub tax_revenue: String,
+    pub gross_revenue: String,
     pub booking_count: i64,
     pub average_revenue: Option<String>,
     pub occupancy_rate: f64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub from_payments: Option<bool>, // Indicates if revenue is from actual payments
+    pub from_payments: bool, // Revenue is money collected rather than booking prices
+    pub basis: RevenueBasis,
+    pub currency: &'static str, // Base currency; foreign payments are converted at their recorded rate
 }
 
-impl From<crate::services::RoomFinancials> for RoomFinancialsResponse {
-    fn from(financials: crate::services::RoomFinancials) -> Self {
-        Self {
-            total_revenue: financials.total_revenue.to_string(),
-            booking_count: financials.booking_count,
-            average_revenue: financials.average_revenue.map(|v| v.to_string()),
-            occupancy_rate: financials.occupancy_rate,
-            from_payments: None,
-        }
-    }
-}
-
-/// Helper to create response with payment flag
 impl RoomFinancialsResponse {
-    pub fn from_financials_with_flag(financials: crate::services::RoomFinancials, from_payments: bool) -> Self {
+    /// Response for financials calculated on `basis`
+    pub fn new(financials: crate::services::RoomFinancials, basis: RevenueBasis) -> Self {
         Self {
+            gross_revenue: financials.total_revenue.to_string(),
+            net_revenue: financials.net_revenue.to_string(),
+            tax_revenue: financials.tax_revenue.to_string(),
             total_revenue: financials.total_revenue.to_string(),
             booking_count: financials.booking_count,
             average_revenue: financials.average_revenue.map(|v| v.to_string()),
             occupancy_rate: financials.occupancy_rate,
-            from_payments: Some(from_payments),
+            from_payments: basis == RevenueBasis::Cash,
+            basis,
+            currency: BASE_CURRENCY,
         }
     }
 }
@@ -79,9 +105,9 @@ pub struct CompareRoomsRequest {
     pub room_ids: Vec<Uuid>,
     pub start_date: Option<String>,
     pub end_date: Option<String>,
+    pub basis: Option<RevenueBasis>,
     #[serde(default)]
-    #[allow(dead_code)]
-    pub use_payments: Option<bool>, // Use actual payments instead of booking prices
+    pub use_payments: Option<bool>, // Older flag for the cash basis
 }
 
 /// Compare rooms response
@@ -94,13 +120,17 @@ pub struct CompareRoomsResponse {
 #[derive(Debug, Serialize)]
 pub struct RevenueDataPoint {
     pub date: String, // YYYY-MM-DD format
-    pub revenue: String, // Decimal as string
+    pub revenue: String, // Decimal as string (gross)
+    pub net_revenue: String,
+    pub tax_revenue: String,
 }
 
 /// Revenue time-series response
 #[derive(Debug, Serialize)]
 pub struct RevenueTimeSeriesResponse {
     pub data: Vec<RevenueDataPoint>,
+    pub basis: RevenueBasis,
+    pub currency: &'static str,
 }
 
 /// List all rooms with financial summary
@@ -124,17 +154,17 @@ pub async fn list_rooms_with_financials(
         .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
 
     // Get all rooms
-    let rooms = room_service.list_rooms(None, None)?;
+    let rooms = room_service.list_rooms(&RoomFilter::default())?;
 
     // Calculate financials for each room
-    let use_payments = query.use_payments.unwrap_or(false);
+    let basis = query.basis();
     let mut summaries = Vec::new();
     for room in rooms {
-        let financials = booking_service.calculate_room_financials_with_payments(
+        let financials = booking_service.calculate_room_financials_by_basis(
             room.id,
             start_date,
             end_date,
-            use_payments,
+            basis,
         )?;
 
         summaries.push(RoomFinancialSummary {
@@ -144,7 +174,7 @@ pub async fn list_rooms_with_financials(
                 room_type: format!("{:?}", room.room_type),
                 status: format!("{:?}", room.status),
             },
-            financials: financials.into(),
+            financials: RoomFinancialsResponse::new(financials, basis),
         });
     }
 
@@ -197,12 +227,12 @@ pub async fn get_room_financials(
     }
 
     // Calculate financials
-    let use_payments = query.use_payments.unwrap_or(false);
-    let financials = booking_service.calculate_room_financials_with_payments(
+    let basis = query.basis();
+    let financials = booking_service.calculate_room_financials_by_basis(
         room_id,
         start_date,
         end_date,
-        use_payments,
+        basis,
     )?;
 
     Ok(Json(RoomFinancialSummary {
@@ -212,7 +242,7 @@ pub async fn get_room_financials(
             room_type: format!("{:?}", room.room_type),
             status: format!("{:?}", room.status),
         },
-        financials: RoomFinancialsResponse::from_financials_with_flag(financials, use_payments),
+        financials: RoomFinancialsResponse::new(financials, basis),
     }))
 }
 
@@ -252,19 +282,17 @@ pub async fn compare_rooms(
     }
 
     // Get rooms and calculate financials
+    let basis = RevenueBasis::resolve(request.basis, request.use_payments);
     let mut summaries = Vec::new();
     for room_id in request.room_ids {
         // Verify room exists
         let room = room_service.get_room_by_id(room_id)?;
 
-        let use_payments = request.start_date.as_ref().and_then(|_| Some(false))
-            .or_else(|| request.end_date.as_ref().and_then(|_| Some(false)))
-            .unwrap_or(false);
-        let financials = booking_service.calculate_room_financials_with_payments(
+        let financials = booking_service.calculate_room_financials_by_basis(
             room_id,
             start_date,
             end_date,
-            use_payments,
+            basis,
         )?;
 
         summaries.push(RoomFinancialSummary {
@@ -274,7 +302,7 @@ pub async fn compare_rooms(
                 room_type: format!("{:?}", room.room_type),
                 status: format!("{:?}", room.status),
             },
-            financials: financials.into(),
+            financials: RoomFinancialsResponse::new(financials, basis),
         });
     }
 
@@ -301,17 +329,24 @@ pub async fn get_revenue_time_series(
         .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
 
     // Get time-series data for all rooms (room_id = None)
-    let time_series = booking_service.get_revenue_time_series(None, start_date, end_date)?;
+    let basis = query.basis();
+    let time_series = booking_service.get_revenue_time_series(None, start_date, end_date, basis)?;
 
     let data: Vec<RevenueDataPoint> = time_series
         .into_iter()
         .map(|(date, revenue)| RevenueDataPoint {
             date: date.format("%Y-%m-%d").to_string(),
-            revenue: revenue.to_string(),
+            revenue: revenue.gross.to_string(),
+            net_revenue: revenue.net.to_string(),
+            tax_revenue: revenue.tax.to_string(),
         })
         .collect();
 
-    Ok(Json(RevenueTimeSeriesResponse { data }))
+    Ok(Json(RevenueTimeSeriesResponse {
+        data,
+        basis,
+        currency: BASE_CURRENCY,
+    }))
 }
 
 /// Get revenue time-series data for a specific room
@@ -339,17 +374,24 @@ pub async fn get_room_revenue_time_series(
         .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
 
     // Get time-series data for the room
-    let time_series = booking_service.get_revenue_time_series(Some(room_id), start_date, end_date)?;
+    let basis = query.basis();
+    let time_series = booking_service.get_revenue_time_series(Some(room_id), start_date, end_date, basis)?;
 
     let data: Vec<RevenueDataPoint> = time_series
         .into_iter()
         .map(|(date, revenue)| RevenueDataPoint {
             date: date.format("%Y-%m-%d").to_string(),
-            revenue: revenue.to_string(),
+            revenue: revenue.gross.to_string(),
+            net_revenue: revenue.net.to_string(),
+            tax_revenue: revenue.tax.to_string(),
         })
         .collect();
 
-    Ok(Json(RevenueTimeSeriesResponse { data }))
+    Ok(Json(RevenueTimeSeriesResponse {
+        data,
+        basis,
+        currency: BASE_CURRENCY,
+    }))
 }
 
 /// Get booking history for a specific room
@@ -377,7 +419,37 @@ pub async fn get_room_booking_history(
         .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok());
 
     // Get booking history
-    let bookings = booking_service.get_room_booking_history(room_id, start_date, end_date)?;
+    let bookings = booking_service.get_room_booking_history(room_id, start_date, end_date, query.basis())?;
 
     Ok(Json(bookings))
 }
+
+/// Get hotel KPIs (ADR, RevPAR, occupancy, stay length, lead time and
+/// cancellation rate) by room type and booking source
+/// GET /admin/financial/kpis
+pub async fn get_kpis(
+    State(state): State<AppState>,
+    Query(query): Query<KpiQuery>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let kpi_service = KpiService::new(state.pool.clone());
+    let report = kpi_service.get_kpis(
+        query.start_date,
+        query.end_date,
+        query.group_by.unwrap_or_default(),
+    )?;
+    Ok(Json(report))
+}
+
+/// Get revenue and occupancy on the books for the coming days, compared
+/// with last year and with a pickup forecast
+/// GET /admin/financial/forecast
+pub async fn get_forecast(
+    State(state): State<AppState>,
+    Query(query): Query<ForecastQuery>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let kpi_service = KpiService::new(state.pool.clone());
+    let report = kpi_service.get_forecast(query.days.unwrap_or(30))?;
+    Ok(Json(report))
+}
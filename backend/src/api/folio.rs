@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::services::{folio_service::CreateFolioChargeRequest, FolioService};
+
+/// Void folio charge request DTO
+#[derive(Debug, Deserialize)]
+pub struct VoidChargeDto {
+    pub reason: String,
+}
+
+/// Get a booking's folio (charges and payment summary)
+/// GET /bookings/:id/folio
+pub async fn get_folio(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let folio_service = FolioService::new(state.pool);
+    let folio = folio_service.get_folio(booking_id)?;
+    Ok((StatusCode::OK, Json(folio)))
+}
+
+/// Post a charge to a booking's folio
+/// POST /bookings/:id/folio/charges
+pub async fn create_charge(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateFolioChargeRequest>,
+) -> Result<impl IntoResponse, AppError> {
+    let folio_service = FolioService::new(state.pool);
+    let charge = folio_service.add_charge(booking_id, payload, auth_user.user_id)?;
+    Ok((StatusCode::CREATED, Json(charge)))
+}
+
+/// Void a charge on a booking's folio (the row is kept with the reason)
+/// DELETE /bookings/:id/folio/charges/:charge_id
+/// POST /bookings/:id/folio/charges/:charge_id/void
+pub async fn void_charge(
+    State(state): State<AppState>,
+    Path((booking_id, charge_id)): Path<(Uuid, Uuid)>,
+    Extension(auth_user): Extension<AuthUser>,
+    payload: Option<Json<VoidChargeDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let reason = payload.map(|Json(p)| p.reason).unwrap_or_default();
+    let folio_service = FolioService::new(state.pool);
+    let charge = folio_service.void_charge(booking_id, charge_id, &reason, auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(charge)))
+}
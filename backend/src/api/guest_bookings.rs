@@ -8,6 +8,7 @@ use axum::{
     http::StatusCode,
     Extension, Json,
 };
+use bigdecimal::{BigDecimal, Zero};
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -15,8 +16,8 @@ use uuid::Uuid;
 use crate::api::middleware::AuthUser;
 use crate::api::AppState;
 use crate::errors::AppError;
-use crate::models::{BookingStatus, BookingWithRoom, GuestInfo};
-use crate::services::{AuthService, BookingService};
+use crate::models::{BookingStatus, BookingWithRoom, GuestInfo, PaymentIntent, PaymentType};
+use crate::services::{AuthService, BookingService, PaymentIntentService};
 
 /// Request body for creating a guest booking
 #[derive(Debug, Deserialize)]
@@ -24,8 +25,23 @@ pub struct CreateGuestBookingRequest {
     pub room_id: Uuid,
     pub check_in_date: NaiveDate,
     pub check_out_date: NaiveDate,
-    #[serde(default)]
-    pub price: Option<bigdecimal::BigDecimal>,
+    /// Guests staying in the room (defaults to 1)
+    pub guest_count: Option<i32>,
+    /// Pay this much online as a deposit right away
+    pub deposit_amount: Option<BigDecimal>,
+}
+
+/// Response for a newly created guest booking
+#[derive(Debug, Serialize)]
+pub struct GuestBookingCreatedResponse {
+    #[serde(flatten)]
+    pub booking: BookingWithRoom,
+    /// Deposit payment to complete at its `checkout_url`, when one was requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_intent: Option<PaymentIntent>,
+    /// Why the requested deposit payment could not be opened
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_error: Option<String>,
 }
 
 /// Query parameters for listing bookings
@@ -40,6 +56,10 @@ pub struct CancelBookingResponse {
     pub id: Uuid,
     pub reference: String,
     pub status: String,
+    /// Amount kept under the cancellation policy
+    pub penalty_amount: BigDecimal,
+    /// Amount refunded to the guest
+    pub refund_amount: BigDecimal,
     pub message: String,
 }
 
@@ -53,22 +73,37 @@ pub struct CancelBookingResponse {
 /// {
 ///   "room_id": "uuid",
 ///   "check_in_date": "2025-12-15",
-///   "check_out_date": "2025-12-18"
+///   "check_out_date": "2025-12-18",
+///   "guest_count": 2,
+///   "deposit_amount": 500000
 /// }
 /// ```
 ///
+/// `deposit_amount` is optional. When given, a deposit payment intent is
+/// opened with the payment gateway and returned as `payment_intent`; the
+/// booking is kept even if that fails (the reason is in `payment_error`),
+/// and the guest can retry through `POST /guest/bookings/:id/payment-intents`.
+///
 /// # Response (201 Created)
 /// Returns the created booking with room details.
 ///
 /// # Errors
-/// - 400 Bad Request: Invalid dates or room under maintenance
+/// - 400 Bad Request: Invalid dates, too many guests for the room or room under maintenance
 /// - 404 Not Found: Room not found
 /// - 409 Conflict: Room not available for selected dates
 pub async fn create_booking(
     State(state): State<AppState>,
     Extension(auth_user): Extension<AuthUser>,
     Json(request): Json<CreateGuestBookingRequest>,
-) -> Result<(StatusCode, Json<BookingWithRoom>), AppError> {
+) -> Result<(StatusCode, Json<GuestBookingCreatedResponse>), AppError> {
+    if let Some(amount) = &request.deposit_amount {
+        if *amount <= BigDecimal::zero() {
+            return Err(AppError::ValidationError(
+                "Deposit amount must be positive".to_string(),
+            ));
+        }
+    }
+
     // Get guest info to use their name
     let auth_service = AuthService::new(state.pool.clone(), state.jwt_secret.clone());
     let guest_info: GuestInfo = auth_service.get_guest_by_id(auth_user.user_id)?;
@@ -81,10 +116,44 @@ pub async fn create_booking(
         request.room_id,
         request.check_in_date,
         request.check_out_date,
-        request.price,
+        request.guest_count.unwrap_or(1),
     )?;
 
-    Ok((StatusCode::CREATED, Json(booking)))
+    let (payment_intent, payment_error) = match request.deposit_amount {
+        Some(amount) => {
+            let intent_service =
+                PaymentIntentService::new(state.pool.clone(), state.payment_gateway.clone());
+            match intent_service
+                .create_intent(
+                    booking.booking.id,
+                    Some(amount),
+                    Some(PaymentType::Deposit),
+                    auth_user.user_id,
+                )
+                .await
+            {
+                Ok(intent) => (Some(intent), None),
+                Err(e) => {
+                    tracing::warn!(
+                        "Could not open deposit payment for booking {}: {}",
+                        booking.booking.reference,
+                        e
+                    );
+                    (None, Some(e.to_string()))
+                }
+            }
+        }
+        None => (None, None),
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        Json(GuestBookingCreatedResponse {
+            booking,
+            payment_intent,
+            payment_error,
+        }),
+    ))
 }
 
 /// GET /guest/bookings - List all bookings for the authenticated guest
@@ -109,6 +178,7 @@ pub async fn list_bookings(
         "checked_in" => Some(BookingStatus::CheckedIn),
         "checked_out" => Some(BookingStatus::CheckedOut),
         "cancelled" => Some(BookingStatus::Cancelled),
+        "no_show" => Some(BookingStatus::NoShow),
         _ => None,
     });
 
@@ -146,6 +216,9 @@ pub async fn get_booking(
 /// - It belongs to the authenticated guest
 /// - It has status "upcoming"
 ///
+/// The applicable cancellation policy decides the penalty; anything paid
+/// beyond it is refunded.
+///
 /// # Path Parameters
 /// - `id`: Booking UUID
 ///
@@ -155,6 +228,8 @@ pub async fn get_booking(
 ///   "id": "uuid",
 ///   "reference": "BK-XXXXXXXX-XXXX",
 ///   "status": "cancelled",
+///   "penalty_amount": "500000",
+///   "refund_amount": "1000000",
 ///   "message": "Booking cancelled successfully"
 /// }
 /// ```
@@ -168,12 +243,14 @@ pub async fn cancel_booking(
     Path(booking_id): Path<Uuid>,
 ) -> Result<Json<CancelBookingResponse>, AppError> {
     let booking_service = BookingService::new(state.pool.clone());
-    let booking = booking_service.cancel_guest_booking(booking_id, auth_user.user_id)?;
+    let cancelled = booking_service.cancel_guest_booking(booking_id, auth_user.user_id)?;
 
     Ok(Json(CancelBookingResponse {
-        id: booking.id,
-        reference: booking.reference,
+        id: cancelled.booking.id,
+        reference: cancelled.booking.reference,
         status: "cancelled".to_string(),
+        penalty_amount: cancelled.cancellation.penalty_amount,
+        refund_amount: cancelled.cancellation.refund_amount,
         message: "Booking cancelled successfully".to_string(),
     }))
 }
@@ -4,13 +4,14 @@ use axum::{
     response::IntoResponse,
     Json,
 };
+use bigdecimal::BigDecimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::api::{middleware::AuthUser, AppState};
 use crate::errors::AppError;
 use crate::models::{BookingWithRoom, GuestNote, UpdateUser, User};
-use crate::services::GuestService;
+use crate::services::{GuestCreditService, GuestService};
 use crate::utils::{validate_email, validate_phone, validate_search_query};
 
 /// Guest search query parameters
@@ -55,6 +56,7 @@ impl From<User> for GuestResponse {
 pub struct GuestProfileResponse {
     pub guest: GuestResponse,
     pub booking_history: Vec<BookingWithRoom>,
+    pub credit_balance: BigDecimal,
 }
 
 /// Update guest request
@@ -150,12 +152,31 @@ pub async fn get_guest_profile(
     // Fetch booking history (Calls BookingService internally to get Rooms + Prices)
     let booking_history = guest_service.get_guest_booking_history(guest_id)?;
 
+    let credit_balance = GuestCreditService::new(state.pool.clone())
+        .get_account(guest_id)?
+        .balance;
+
     Ok(Json(GuestProfileResponse {
         guest: GuestResponse::from(guest),
         booking_history,
+        credit_balance,
     }))
 }
 
+/// Get a guest's credit balance and history
+/// GET /admin/guests/:guestId/credit
+pub async fn get_guest_credit(
+    State(state): State<AppState>,
+    Path(guest_id): Path<Uuid>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let guest_service = GuestService::new(state.pool.clone());
+    guest_service.get_guest_profile(guest_id)?;
+
+    let account = GuestCreditService::new(state.pool.clone()).get_account(guest_id)?;
+    Ok(Json(account))
+}
+
 /// Update guest information
 /// PATCH /admin/guests/:guestId
 pub async fn update_guest(
@@ -0,0 +1,320 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::models::{
+    HousekeepingPolicy, HousekeepingPriority, HousekeepingTaskStatus, HousekeepingTaskType,
+    UpdateHousekeepingTask,
+};
+use crate::services::housekeeping_service::{
+    CreateHousekeepingTask, HousekeepingTaskFilter, InspectRoom,
+};
+use crate::services::HousekeepingService;
+use crate::utils::double_option;
+
+/// Query parameters for the admin task list
+#[derive(Debug, Deserialize)]
+pub struct ListTasksQuery {
+    pub status: Option<HousekeepingTaskStatus>,
+    pub task_type: Option<HousekeepingTaskType>,
+    pub room_id: Option<Uuid>,
+    pub cleaner_id: Option<Uuid>,
+}
+
+/// Create task request DTO
+#[derive(Debug, Deserialize)]
+pub struct CreateTaskDto {
+    pub room_id: Uuid,
+    pub task_type: HousekeepingTaskType,
+    pub priority: Option<HousekeepingPriority>,
+    pub assigned_cleaner_id: Option<Uuid>,
+    pub notes: Option<String>,
+}
+
+/// Update task request DTO
+///
+/// `assigned_cleaner_id` uses `Option<Option<_>>`: omit to keep, send `null` to unassign.
+#[derive(Debug, Deserialize)]
+pub struct UpdateTaskDto {
+    pub priority: Option<HousekeepingPriority>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub assigned_cleaner_id: Option<Option<Uuid>>,
+    pub notes: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// Complete or release task request DTO
+#[derive(Debug, Default, Deserialize)]
+pub struct TaskNotesDto {
+    pub notes: Option<String>,
+}
+
+/// One task to give to a cleaner
+#[derive(Debug, Deserialize)]
+pub struct AssignmentDto {
+    pub task_id: Uuid,
+    pub cleaner_id: Uuid,
+}
+
+/// Apply assignments request DTO
+///
+/// Takes the `assignments` of an auto-assign preview as-is or edited;
+/// fields other than `task_id` and `cleaner_id` are ignored.
+#[derive(Debug, Deserialize)]
+pub struct ApplyAssignmentsDto {
+    pub assignments: Vec<AssignmentDto>,
+}
+
+/// Query parameters for the inspection list
+#[derive(Debug, Deserialize)]
+pub struct ListInspectionsQuery {
+    pub room_id: Option<Uuid>,
+    pub cleaner_id: Option<Uuid>,
+    pub passed: Option<bool>,
+}
+
+/// Inspect room request DTO
+#[derive(Debug, Deserialize)]
+pub struct InspectRoomDto {
+    pub room_id: Uuid,
+    pub passed: bool,
+    #[serde(default)]
+    pub failed_items: Vec<String>,
+    pub notes: Option<String>,
+}
+
+/// Query parameters for the SLA report
+#[derive(Debug, Deserialize)]
+pub struct SlaReportQuery {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+/// List the current cleaner's queue: their tasks and unassigned ones
+/// GET /cleaner/tasks
+pub async fn list_cleaner_tasks(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let tasks = housekeeping_service.cleaner_queue(auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(tasks)))
+}
+
+/// Start a task (claims it if unassigned)
+/// POST /cleaner/tasks/:id/start
+pub async fn start_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let task = housekeeping_service.start_task(id, auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(task)))
+}
+
+/// Finish a started task
+/// POST /cleaner/tasks/:id/complete
+pub async fn complete_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+    payload: Option<Json<TaskNotesDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let task = housekeeping_service.complete_task(id, auth_user.user_id, payload.notes)?;
+    Ok((StatusCode::OK, Json(task)))
+}
+
+/// Put a started task back in the queue
+/// POST /cleaner/tasks/:id/release
+pub async fn release_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+    payload: Option<Json<TaskNotesDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let task = housekeeping_service.release_task(id, auth_user.user_id, payload.notes)?;
+    Ok((StatusCode::OK, Json(task)))
+}
+
+/// List housekeeping tasks
+/// GET /admin/housekeeping/tasks
+pub async fn list_tasks(
+    State(state): State<AppState>,
+    Query(query): Query<ListTasksQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let tasks = housekeeping_service.list_tasks(HousekeepingTaskFilter {
+        status: query.status,
+        task_type: query.task_type,
+        room_id: query.room_id,
+        cleaner_id: query.cleaner_id,
+    })?;
+    Ok((StatusCode::OK, Json(tasks)))
+}
+
+/// Create a housekeeping task
+/// POST /admin/housekeeping/tasks
+pub async fn create_task(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateTaskDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let task = housekeeping_service.create_task(
+        CreateHousekeepingTask {
+            room_id: payload.room_id,
+            task_type: payload.task_type,
+            priority: payload.priority,
+            assigned_cleaner_id: payload.assigned_cleaner_id,
+            notes: payload.notes,
+        },
+        auth_user.user_id,
+    )?;
+    Ok((StatusCode::CREATED, Json(task)))
+}
+
+/// Get a housekeeping task
+/// GET /admin/housekeeping/tasks/:id
+pub async fn get_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let task = housekeeping_service.get_task(id)?;
+    Ok((StatusCode::OK, Json(task)))
+}
+
+/// Change a task's priority, assignee, notes or due time
+/// PATCH /admin/housekeeping/tasks/:id
+pub async fn update_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTaskDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let task = housekeeping_service.update_task(
+        id,
+        UpdateHousekeepingTask {
+            priority: payload.priority,
+            assigned_cleaner_id: payload.assigned_cleaner_id,
+            notes: payload.notes,
+            due_at: payload.due_at,
+        },
+    )?;
+    Ok((StatusCode::OK, Json(task)))
+}
+
+/// Cancel a housekeeping task
+/// POST /admin/housekeeping/tasks/:id/cancel
+pub async fn cancel_task(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let task = housekeeping_service.cancel_task(id)?;
+    Ok((StatusCode::OK, Json(task)))
+}
+
+/// Preview spreading unassigned tasks across cleaners
+/// GET /admin/housekeeping/auto-assign
+pub async fn preview_auto_assign(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let plan = housekeeping_service.preview_auto_assign()?;
+    Ok((StatusCode::OK, Json(plan)))
+}
+
+/// Apply a (possibly adjusted) auto-assign preview
+/// POST /admin/housekeeping/auto-assign
+pub async fn apply_auto_assign(
+    State(state): State<AppState>,
+    Json(payload): Json<ApplyAssignmentsDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let tasks = housekeeping_service.apply_assignments(
+        payload
+            .assignments
+            .into_iter()
+            .map(|a| (a.task_id, a.cleaner_id))
+            .collect(),
+    )?;
+    Ok((StatusCode::OK, Json(tasks)))
+}
+
+/// List room inspections
+/// GET /admin/housekeeping/inspections
+pub async fn list_inspections(
+    State(state): State<AppState>,
+    Query(query): Query<ListInspectionsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let inspections =
+        housekeeping_service.list_inspections(query.room_id, query.cleaner_id, query.passed)?;
+    Ok((StatusCode::OK, Json(inspections)))
+}
+
+/// Pass or fail a room waiting for inspection
+/// POST /admin/housekeeping/inspections
+pub async fn inspect_room(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<InspectRoomDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let inspection = housekeeping_service.inspect_room(
+        payload.room_id,
+        auth_user.user_id,
+        InspectRoom {
+            passed: payload.passed,
+            failed_items: payload.failed_items,
+            notes: payload.notes,
+        },
+    )?;
+    Ok((StatusCode::CREATED, Json(inspection)))
+}
+
+/// SLA figures per task type for tasks created in a date range
+/// GET /admin/housekeeping/sla
+pub async fn get_sla_report(
+    State(state): State<AppState>,
+    Query(query): Query<SlaReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let report = housekeeping_service.sla_report(query.start_date, query.end_date)?;
+    Ok((StatusCode::OK, Json(report)))
+}
+
+/// Get the housekeeping SLAs, auto-assignment and inspection settings
+/// GET /admin/settings/housekeeping
+pub async fn get_housekeeping_policy(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let policy = housekeeping_service.get_policy()?;
+    Ok((StatusCode::OK, Json(policy)))
+}
+
+/// Update the housekeeping SLAs, auto-assignment and inspection settings
+/// PUT /admin/settings/housekeeping
+pub async fn update_housekeeping_policy(
+    State(state): State<AppState>,
+    Json(payload): Json<HousekeepingPolicy>,
+) -> Result<impl IntoResponse, AppError> {
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let policy = housekeeping_service.update_policy(payload)?;
+    Ok((StatusCode::OK, Json(policy)))
+}
@@ -0,0 +1,100 @@
+use axum::{
+    extract::{Extension, Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use uuid::Uuid;
+
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::models::Invoice;
+use crate::services::{invoice_service::InvoiceFormat, BookingService, InvoiceService};
+
+/// Response carrying a stored invoice document as a file download
+fn document_response(invoice: &Invoice, format: InvoiceFormat, data: Vec<u8>) -> Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, format.content_type().to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}.{}\"",
+                    invoice.invoice_number,
+                    format.extension()
+                ),
+            ),
+        ],
+        data,
+    )
+        .into_response()
+}
+
+/// Issue a new invoice for a booking
+/// POST /bookings/:id/invoices
+pub async fn issue_invoice(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let invoice_service = InvoiceService::new(state.pool, state.s3_client);
+    let invoice = invoice_service
+        .issue_invoice(booking_id, auth_user.user_id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(invoice)))
+}
+
+/// List the invoices issued for a booking
+/// GET /bookings/:id/invoices
+pub async fn list_invoices(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let invoice_service = InvoiceService::new(state.pool, state.s3_client);
+    let invoices = invoice_service.list_invoices(booking_id)?;
+    Ok((StatusCode::OK, Json(invoices)))
+}
+
+/// Download an invoice as HTML or PDF
+/// GET /bookings/:id/invoices/:invoice_id/:format
+pub async fn download_invoice(
+    State(state): State<AppState>,
+    Path((booking_id, invoice_id, format)): Path<(Uuid, Uuid, InvoiceFormat)>,
+) -> Result<Response, AppError> {
+    let invoice_service = InvoiceService::new(state.pool, state.s3_client);
+    let (invoice, data) = invoice_service
+        .download_invoice(booking_id, invoice_id, format)
+        .await?;
+    Ok(document_response(&invoice, format, data))
+}
+
+/// List the invoices of one of the guest's own bookings
+/// GET /guest/bookings/:id/invoices
+pub async fn list_guest_invoices(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(booking_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    BookingService::new(state.pool.clone()).get_guest_booking(booking_id, auth_user.user_id)?;
+
+    let invoice_service = InvoiceService::new(state.pool, state.s3_client);
+    let invoices = invoice_service.list_invoices(booking_id)?;
+    Ok((StatusCode::OK, Json(invoices)))
+}
+
+/// Download an invoice of one of the guest's own bookings
+/// GET /guest/bookings/:id/invoices/:invoice_id/:format
+pub async fn download_guest_invoice(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path((booking_id, invoice_id, format)): Path<(Uuid, Uuid, InvoiceFormat)>,
+) -> Result<Response, AppError> {
+    BookingService::new(state.pool.clone()).get_guest_booking(booking_id, auth_user.user_id)?;
+
+    let invoice_service = InvoiceService::new(state.pool, state.s3_client);
+    let (invoice, data) = invoice_service
+        .download_invoice(booking_id, invoice_id, format)
+        .await?;
+    Ok(document_response(&invoice, format, data))
+}
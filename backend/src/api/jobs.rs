@@ -0,0 +1,29 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+
+use crate::api::AppState;
+use crate::errors::AppError;
+use crate::services::JobService;
+
+/// Query parameters for listing job runs
+#[derive(Debug, Deserialize)]
+pub struct ListJobRunsQuery {
+    pub job_name: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// List recent background job runs
+/// GET /admin/jobs/runs
+pub async fn list_job_runs(
+    State(state): State<AppState>,
+    Query(query): Query<ListJobRunsQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let job_service = JobService::new(state.pool);
+    let runs = job_service.list_runs(query.job_name.as_deref(), query.limit.unwrap_or(50))?;
+    Ok((StatusCode::OK, Json(runs)))
+}
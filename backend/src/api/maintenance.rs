@@ -0,0 +1,169 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::models::{MaintenancePriority, MaintenanceStatus, UpdateMaintenanceOrder};
+use crate::services::maintenance_service::{CreateMaintenanceOrder, MaintenanceOrderFilter};
+use crate::services::MaintenanceService;
+use crate::utils::double_option;
+
+/// Query parameters for the work order list
+#[derive(Debug, Deserialize)]
+pub struct ListOrdersQuery {
+    pub room_id: Option<Uuid>,
+    pub status: Option<MaintenanceStatus>,
+    pub assigned_to_user_id: Option<Uuid>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// Create work order request DTO
+#[derive(Debug, Deserialize)]
+pub struct CreateOrderDto {
+    pub room_id: Uuid,
+    pub description: String,
+    pub priority: Option<MaintenancePriority>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub assigned_to_user_id: Option<Uuid>,
+}
+
+/// Update work order request DTO
+///
+/// `assigned_to_user_id` uses `Option<Option<_>>`: omit to keep, send `null` to unassign.
+#[derive(Debug, Deserialize)]
+pub struct UpdateOrderDto {
+    pub description: Option<String>,
+    pub priority: Option<MaintenancePriority>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub assigned_to_user_id: Option<Option<Uuid>>,
+}
+
+/// Close work order request DTO
+#[derive(Debug, Deserialize)]
+pub struct CloseOrderDto {
+    pub resolution: String,
+}
+
+/// Cancel work order request DTO
+#[derive(Debug, Default, Deserialize)]
+pub struct CancelOrderDto {
+    pub reason: Option<String>,
+}
+
+/// List maintenance work orders
+/// GET /admin/maintenance/orders
+pub async fn list_orders(
+    State(state): State<AppState>,
+    Query(query): Query<ListOrdersQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let maintenance_service = MaintenanceService::new(state.pool);
+    let orders = maintenance_service.list_orders(MaintenanceOrderFilter {
+        room_id: query.room_id,
+        status: query.status,
+        assigned_to_user_id: query.assigned_to_user_id,
+        from: query.from,
+        to: query.to,
+    })?;
+    Ok((StatusCode::OK, Json(orders)))
+}
+
+/// Create a maintenance work order
+/// POST /admin/maintenance/orders
+pub async fn create_order(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CreateOrderDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let maintenance_service = MaintenanceService::new(state.pool);
+    let order = maintenance_service.create_order(
+        CreateMaintenanceOrder {
+            room_id: payload.room_id,
+            description: payload.description,
+            priority: payload.priority,
+            start_date: payload.start_date,
+            end_date: payload.end_date,
+            assigned_to_user_id: payload.assigned_to_user_id,
+        },
+        auth_user.user_id,
+    )?;
+    Ok((StatusCode::CREATED, Json(order)))
+}
+
+/// Get a maintenance work order
+/// GET /admin/maintenance/orders/:id
+pub async fn get_order(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let maintenance_service = MaintenanceService::new(state.pool);
+    let order = maintenance_service.get_order(id)?;
+    Ok((StatusCode::OK, Json(order)))
+}
+
+/// Change an open work order's description, priority, dates or assignee
+/// PATCH /admin/maintenance/orders/:id
+pub async fn update_order(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateOrderDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let maintenance_service = MaintenanceService::new(state.pool);
+    let order = maintenance_service.update_order(
+        id,
+        UpdateMaintenanceOrder {
+            description: payload.description,
+            priority: payload.priority,
+            start_date: payload.start_date,
+            end_date: payload.end_date,
+            assigned_to_user_id: payload.assigned_to_user_id,
+        },
+    )?;
+    Ok((StatusCode::OK, Json(order)))
+}
+
+/// Start work, putting the room in maintenance
+/// POST /admin/maintenance/orders/:id/start
+pub async fn start_order(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let maintenance_service = MaintenanceService::new(state.pool);
+    let order = maintenance_service.start_order(id)?;
+    Ok((StatusCode::OK, Json(order)))
+}
+
+/// Close a work order with its resolution
+/// POST /admin/maintenance/orders/:id/close
+pub async fn close_order(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<CloseOrderDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let maintenance_service = MaintenanceService::new(state.pool);
+    let order = maintenance_service.close_order(id, payload.resolution)?;
+    Ok((StatusCode::OK, Json(order)))
+}
+
+/// Cancel a work order
+/// POST /admin/maintenance/orders/:id/cancel
+pub async fn cancel_order(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    payload: Option<Json<CancelOrderDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let maintenance_service = MaintenanceService::new(state.pool);
+    let order = maintenance_service.cancel_order(id, payload.reason)?;
+    Ok((StatusCode::OK, Json(order)))
+}
@@ -96,7 +96,7 @@ pub async fn require_admin(
     })?;
 
     // Check if user is admin
-    if claims.role != UserRole::Admin {
+    if !is_admin_role(claims.role) {
         return Err((
             StatusCode::FORBIDDEN,
             axum::Json(serde_json::json!({
@@ -131,6 +131,11 @@ pub fn is_admin_role(role: UserRole) -> bool {
     matches!(role, UserRole::Admin)
 }
 
+/// Helper to check if a role is considered staff (admin or receptionist)
+pub fn is_staff_role(role: UserRole) -> bool {
+    matches!(role, UserRole::Admin | UserRole::Receptionist)
+}
+
 
 /// Middleware to require guest role
 pub async fn require_guest(
@@ -181,8 +186,7 @@ pub async fn require_guest(
     Ok(next.run(request).await)
 }
 
-/// Middleware to require admin role
-#[allow(dead_code)]
+/// Middleware to require staff role (admin or receptionist)
 pub async fn require_staff(
     State(state): State<AppState>,
     mut request: Request,
@@ -210,13 +214,13 @@ pub async fn require_staff(
         )
     })?;
 
-    // Check if user is admin
-    if !is_admin_role(claims.role) {
+    // Check if user is staff
+    if !is_staff_role(claims.role) {
         return Err((
             StatusCode::FORBIDDEN,
             axum::Json(serde_json::json!({
                 "code": "FORBIDDEN",
-                "message": "Admin access required"
+                "message": "Staff access required"
             })),
         ));
     }
@@ -1,25 +1,39 @@
 pub mod auth;
 pub mod bookings;
+pub mod cancellation_policies;
 pub mod chat;
 pub mod employees;
+pub mod exchange_rates;
+pub mod exports;
 pub mod financial;
+pub mod folio;
 pub mod guest_auth;
 pub mod guest_bookings;
 pub mod guests;
+pub mod housekeeping;
+pub mod invoices;
+pub mod jobs;
+pub mod maintenance;
 pub mod middleware;
+pub mod no_show;
+pub mod payment_intents;
 pub mod payments;
+pub mod rate_plans;
 pub mod rooms;
+pub mod shifts;
+pub mod tax_rules;
 pub mod inventory;
 mod settings;
 
 use axum::{
     middleware as axum_middleware,
-    routing::{delete, get, patch, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
 use crate::db::DbPool;
 use crate::api::chat::ChatState;
+use crate::services::payment_gateway::PaymentGateway;
 use std::sync::Arc;
 
 /// Application state shared across handlers
@@ -29,6 +43,7 @@ pub struct AppState {
     pub jwt_secret: String,
     pub chat_state: Arc<ChatState>,
     pub s3_client: aws_sdk_s3::Client,
+    pub payment_gateway: Arc<dyn PaymentGateway>,
 }
 
 /// Create the API router with all routes
@@ -72,7 +87,8 @@ pub fn create_router(state: AppState) -> Router {
         // Available rooms endpoint is public (no auth required) for guests to search
         .route("/available", get(rooms::available_rooms))
         .route("/", get(rooms::list_rooms))
-        .route("/:id", get(rooms::get_room));
+        .route("/:id", get(rooms::get_room))
+        .route("/:id/quote", get(rooms::quote_room));
     
     // Protected room routes (require staff auth)
     let protected_room_routes = Router::new()
@@ -95,13 +111,111 @@ pub fn create_router(state: AppState) -> Router {
         .merge(public_room_routes)
         .merge(protected_room_routes);
 
-    // Payment routes for bookings (requires staff auth)
+    // Payment and folio routes for bookings (requires staff auth)
     let booking_payment_routes = Router::new()
         .route(
             "/:id/payments",
             get(payments::list_payments).post(payments::create_payment),
         )
         .route("/:id/payments/summary", get(payments::get_payment_summary))
+        .route("/:id/payments/ledger", get(payments::get_booking_ledger))
+        .route("/:id/payments/credit-overpayment", post(payments::credit_overpayment))
+        .route("/:id/payments/apply-credit", post(payments::apply_credit))
+        .route("/groups/:id/payments/summary", get(payments::get_group_payment_summary))
+        .route("/:id/folio", get(folio::get_folio))
+        .route("/:id/folio/charges", post(folio::create_charge))
+        .route("/:id/folio/charges/:charge_id", delete(folio::void_charge))
+        .route("/:id/folio/charges/:charge_id/void", post(folio::void_charge))
+        .route(
+            "/:id/invoices",
+            get(invoices::list_invoices).post(invoices::issue_invoice),
+        )
+        .route(
+            "/:id/invoices/:invoice_id/:format",
+            get(invoices::download_invoice),
+        )
+        .route("/:id/payment-intents", get(payment_intents::list_intents))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_staff,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Group reservations, several rooms under one reference (requires staff auth)
+    let booking_group_routes = Router::new()
+        .route("/groups", get(bookings::list_groups).post(bookings::create_group))
+        .route("/groups/:id", get(bookings::get_group))
+        .route("/groups/:id/check-in", post(bookings::check_in_group))
+        .route("/groups/:id/check-out", post(bookings::check_out_group))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_staff,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Cancellations refund the guest (requires staff auth to record who refunded)
+    let booking_cancel_routes = Router::new()
+        .route("/:id/cancel", post(bookings::cancel))
+        .route("/groups/:id/cancel", post(bookings::cancel_group))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_staff,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Check-in and check-out change room statuses and can override an unpaid
+    // balance (requires staff auth to record who did it)
+    let booking_check_routes = Router::new()
+        .route("/:id/check-in", post(bookings::check_in))
+        .route("/:id/check-out", post(bookings::check_out))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_staff,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Booking updates (requires staff auth)
+    let booking_update_routes = Router::new()
+        .route("/:id", patch(bookings::update_booking))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_staff,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Room moves change the room, room statuses and revenue split (requires staff auth)
+    let booking_move_routes = Router::new()
+        .route("/:id/move", post(bookings::move_booking))
+        .route("/:id/room-stays", get(bookings::list_room_stays))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_staff,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Stay extensions and early departures (requires staff auth to record who made them)
+    let booking_stay_routes = Router::new()
+        .route("/:id/extend", post(bookings::extend_stay))
+        .route("/:id/shorten", post(bookings::shorten_stay))
+        .route("/:id/stay-changes", get(bookings::list_stay_changes))
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
             middleware::require_staff,
@@ -116,22 +230,23 @@ pub fn create_router(state: AppState) -> Router {
             "/",
             get(bookings::list_bookings).post(bookings::create_booking),
         )
-        .route(
-            "/:id",
-            get(bookings::get_booking).patch(bookings::update_booking),
-        )
-        .route("/:id/check-in", post(bookings::check_in))
-        .route("/:id/check-out", post(bookings::check_out))
-        .route("/:id/cancel", post(bookings::cancel))
+        .route("/:id", get(bookings::get_booking))
         .route(
             "/reference/:reference",
             get(bookings::get_booking_by_reference),
         )
-        .merge(booking_payment_routes);
+        .merge(booking_payment_routes)
+        .merge(booking_check_routes)
+        .merge(booking_cancel_routes)
+        .merge(booking_group_routes)
+        .merge(booking_move_routes)
+        .merge(booking_update_routes)
+        .merge(booking_stay_routes);
 
     // Payment routes (requires staff auth)
     let payment_routes = Router::new()
-        .route("/:id", get(payments::get_payment).patch(payments::update_payment).delete(payments::delete_payment))
+        .route("/:id", get(payments::get_payment).patch(payments::update_payment).delete(payments::void_payment))
+        .route("/:id/void", post(payments::void_payment))
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
             middleware::require_staff,
@@ -141,6 +256,24 @@ pub fn create_router(state: AppState) -> Router {
             middleware::require_auth,
         ));
 
+    // Cashier shift routes (requires staff auth)
+    let shift_routes = Router::new()
+        .route("/open", post(shifts::open_shift))
+        .route("/current", get(shifts::get_current_shift))
+        .route("/current/close", post(shifts::close_current_shift))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_staff,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Payment gateway webhooks (public, verified by the gateway's signature)
+    let payment_webhook_routes = Router::new()
+        .route("/webhooks/:provider", post(payment_intents::receive_webhook));
+
     // Guest booking routes (requires guest auth)
     let guest_booking_routes = Router::new()
         .route(
@@ -149,6 +282,15 @@ pub fn create_router(state: AppState) -> Router {
         )
         .route("/:id", get(guest_bookings::get_booking))
         .route("/:id/cancel", post(guest_bookings::cancel_booking))
+        .route("/:id/invoices", get(invoices::list_guest_invoices))
+        .route(
+            "/:id/invoices/:invoice_id/:format",
+            get(invoices::download_guest_invoice),
+        )
+        .route(
+            "/:id/payment-intents",
+            get(payment_intents::list_guest_intents).post(payment_intents::create_guest_intent),
+        )
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
             middleware::require_guest,
@@ -158,6 +300,10 @@ pub fn create_router(state: AppState) -> Router {
     let cleaner_routes = Router::new()
         .route("/rooms", get(rooms::list_cleaner_rooms))
         .route("/rooms/:id/status", patch(rooms::update_cleaner_room_status))
+        .route("/tasks", get(housekeeping::list_cleaner_tasks))
+        .route("/tasks/:id/start", post(housekeeping::start_task))
+        .route("/tasks/:id/complete", post(housekeeping::complete_task))
+        .route("/tasks/:id/release", post(housekeeping::release_task))
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
             middleware::require_cleaner,
@@ -191,6 +337,16 @@ pub fn create_router(state: AppState) -> Router {
         .route("/financial/revenue/time-series", get(financial::get_revenue_time_series))
         .route("/financial/rooms/:roomId/revenue/time-series", get(financial::get_room_revenue_time_series))
         .route("/financial/rooms/:roomId/bookings", get(financial::get_room_booking_history))
+        .route("/financial/kpis", get(financial::get_kpis))
+        .route("/financial/forecast", get(financial::get_forecast))
+        .route("/financial/shifts", get(shifts::list_shift_reports))
+        .route("/financial/shifts/:id", get(shifts::get_shift_report))
+        .route("/payments/ledger", get(payments::list_ledger))
+        .route("/exports/revenue/:format", get(exports::export_revenue))
+        .route("/exports/rooms/:roomId/revenue/:format", get(exports::export_room_revenue))
+        .route("/exports/bookings/:format", get(exports::export_bookings))
+        .route("/exports/payments/ledger/:format", get(exports::export_payment_ledger))
+        .route("/exports/inventory/:format", get(exports::export_inventory))
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
             middleware::require_admin,
@@ -206,6 +362,170 @@ pub fn create_router(state: AppState) -> Router {
         .route("/guests/search", get(guests::search_guests))
         .route("/guests/:guestId", get(guests::get_guest_profile).patch(guests::update_guest))
         .route("/guests/:guestId/notes", get(guests::get_guest_notes).post(guests::add_guest_note))
+        .route("/guests/:guestId/credit", get(guests::get_guest_credit))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin rate plan management routes (requires admin auth)
+    let admin_rate_plan_routes = Router::new()
+        .route("/rate-plans", get(rate_plans::list_rate_plans).post(rate_plans::create_rate_plan))
+        .route(
+            "/rate-plans/:id",
+            get(rate_plans::get_rate_plan)
+                .patch(rate_plans::update_rate_plan)
+                .delete(rate_plans::delete_rate_plan),
+        )
+        .route("/rate-plans/:id/overrides", put(rate_plans::upsert_rate_overrides))
+        .route("/rate-plans/:id/overrides/:date", delete(rate_plans::delete_rate_override))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin cancellation policy management routes (requires admin auth)
+    let admin_cancellation_policy_routes = Router::new()
+        .route(
+            "/cancellation-policies",
+            get(cancellation_policies::list_cancellation_policies)
+                .post(cancellation_policies::create_cancellation_policy),
+        )
+        .route(
+            "/cancellation-policies/:id",
+            get(cancellation_policies::get_cancellation_policy)
+                .patch(cancellation_policies::update_cancellation_policy)
+                .delete(cancellation_policies::delete_cancellation_policy),
+        )
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin tax and service-charge rule management routes (requires admin auth)
+    let admin_tax_rule_routes = Router::new()
+        .route(
+            "/tax-rules",
+            get(tax_rules::list_tax_rules).post(tax_rules::create_tax_rule),
+        )
+        .route(
+            "/tax-rules/:id",
+            get(tax_rules::get_tax_rule)
+                .patch(tax_rules::update_tax_rule)
+                .delete(tax_rules::delete_tax_rule),
+        )
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin exchange rate management routes (requires admin auth)
+    let admin_exchange_rate_routes = Router::new()
+        .route(
+            "/exchange-rates",
+            get(exchange_rates::list_exchange_rates).post(exchange_rates::set_exchange_rate),
+        )
+        .route("/exchange-rates/:id", delete(exchange_rates::delete_exchange_rate))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin no-show policy and manual no-show handling (requires admin auth)
+    let admin_no_show_routes = Router::new()
+        .route(
+            "/settings/no-show",
+            get(no_show::get_no_show_policy).put(no_show::update_no_show_policy),
+        )
+        .route("/bookings/:id/no-show", post(no_show::mark_no_show))
+        .route("/bookings/:id/no-show/undo", post(no_show::undo_no_show))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin housekeeping task queue, SLAs and inspections (requires admin auth)
+    let admin_housekeeping_routes = Router::new()
+        .route(
+            "/housekeeping/tasks",
+            get(housekeeping::list_tasks).post(housekeeping::create_task),
+        )
+        .route(
+            "/housekeeping/tasks/:id",
+            get(housekeeping::get_task).patch(housekeeping::update_task),
+        )
+        .route("/housekeeping/tasks/:id/cancel", post(housekeeping::cancel_task))
+        .route(
+            "/housekeeping/auto-assign",
+            get(housekeeping::preview_auto_assign).post(housekeeping::apply_auto_assign),
+        )
+        .route(
+            "/housekeeping/inspections",
+            get(housekeeping::list_inspections).post(housekeeping::inspect_room),
+        )
+        .route("/housekeeping/sla", get(housekeeping::get_sla_report))
+        .route(
+            "/settings/housekeeping",
+            get(housekeeping::get_housekeeping_policy).put(housekeeping::update_housekeeping_policy),
+        )
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin maintenance work orders (requires admin auth)
+    let admin_maintenance_routes = Router::new()
+        .route(
+            "/maintenance/orders",
+            get(maintenance::list_orders).post(maintenance::create_order),
+        )
+        .route(
+            "/maintenance/orders/:id",
+            get(maintenance::get_order).patch(maintenance::update_order),
+        )
+        .route("/maintenance/orders/:id/start", post(maintenance::start_order))
+        .route("/maintenance/orders/:id/close", post(maintenance::close_order))
+        .route("/maintenance/orders/:id/cancel", post(maintenance::cancel_order))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_admin,
+        ))
+        .layer(axum_middleware::from_fn_with_state(
+            state.clone(),
+            middleware::require_auth,
+        ));
+
+    // Admin background job history (requires admin auth)
+    let admin_job_routes = Router::new()
+        .route("/jobs/runs", get(jobs::list_job_runs))
         .layer(axum_middleware::from_fn_with_state(
             state.clone(),
             middleware::require_admin,
@@ -276,7 +596,8 @@ pub fn create_router(state: AppState) -> Router {
         .nest("/auth", auth_routes)
         .nest("/rooms", room_routes)
         .nest("/bookings", booking_routes)
-        .nest("/payments", payment_routes)
+        .nest("/payments", payment_routes.merge(payment_webhook_routes))
+        .nest("/shifts", shift_routes)
         .nest("/guest/bookings", guest_booking_routes)
         .nest("/cleaner", cleaner_routes)
         .nest(
@@ -284,6 +605,14 @@ pub fn create_router(state: AppState) -> Router {
             admin_employee_routes
                 .merge(admin_financial_routes)
                 .merge(admin_guest_routes)
+                .merge(admin_rate_plan_routes)
+                .merge(admin_job_routes)
+                .merge(admin_cancellation_policy_routes)
+                .merge(admin_tax_rule_routes)
+                .merge(admin_exchange_rate_routes)
+                .merge(admin_no_show_routes)
+                .merge(admin_housekeeping_routes)
+                .merge(admin_maintenance_routes)
                 .merge(admin_settings_routes),
         )
         .nest("/inventory", inventory_routes.merge(admin_inventory_routes))
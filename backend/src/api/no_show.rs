@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::errors::AppError;
+use crate::models::NoShowPolicy;
+use crate::services::NoShowService;
+
+/// Get the hotel's no-show policy
+/// GET /admin/settings/no-show
+pub async fn get_no_show_policy(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let no_show_service = NoShowService::new(state.pool);
+    let policy = no_show_service.get_policy()?;
+    Ok((StatusCode::OK, Json(policy)))
+}
+
+/// Update the hotel's no-show policy
+/// PUT /admin/settings/no-show
+pub async fn update_no_show_policy(
+    State(state): State<AppState>,
+    Json(payload): Json<NoShowPolicy>,
+) -> Result<impl IntoResponse, AppError> {
+    let no_show_service = NoShowService::new(state.pool);
+    let policy = no_show_service.update_policy(payload)?;
+    Ok((StatusCode::OK, Json(policy)))
+}
+
+/// Mark a booking as a no-show
+/// POST /admin/bookings/:id/no-show
+pub async fn mark_no_show(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let no_show_service = NoShowService::new(state.pool);
+    let booking = no_show_service.mark_no_show(id)?;
+    Ok((StatusCode::OK, Json(booking)))
+}
+
+/// Undo a no-show, restoring the booking to upcoming
+/// POST /admin/bookings/:id/no-show/undo
+pub async fn undo_no_show(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let no_show_service = NoShowService::new(state.pool);
+    let booking = no_show_service.undo_no_show(id)?;
+    Ok((StatusCode::OK, Json(booking)))
+}
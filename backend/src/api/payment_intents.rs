@@ -0,0 +1,83 @@
+use axum::{
+    body::Bytes,
+    extract::{Extension, Path, State},
+    http::{HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use bigdecimal::BigDecimal;
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::models::PaymentType;
+use crate::services::{BookingService, PaymentIntentService};
+
+/// Request body for starting an online payment
+#[derive(Debug, Deserialize)]
+pub struct CreatePaymentIntentDto {
+    /// Defaults to the remaining balance
+    pub amount: Option<BigDecimal>,
+    /// Defaults to `full` when settling the balance, otherwise `partial`
+    pub payment_type: Option<PaymentType>,
+}
+
+/// List the online payments of a booking
+/// GET /bookings/:id/payment-intents
+pub async fn list_intents(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let intent_service = PaymentIntentService::new(state.pool, state.payment_gateway);
+    let intents = intent_service.list_intents(booking_id)?;
+    Ok((StatusCode::OK, Json(intents)))
+}
+
+/// Start an online payment for one of the guest's own bookings
+/// POST /guest/bookings/:id/payment-intents
+pub async fn create_guest_intent(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(booking_id): Path<Uuid>,
+    Json(payload): Json<CreatePaymentIntentDto>,
+) -> Result<impl IntoResponse, AppError> {
+    BookingService::new(state.pool.clone()).get_guest_booking(booking_id, auth_user.user_id)?;
+
+    let intent_service = PaymentIntentService::new(state.pool, state.payment_gateway);
+    let intent = intent_service
+        .create_intent(booking_id, payload.amount, payload.payment_type, auth_user.user_id)
+        .await?;
+    Ok((StatusCode::CREATED, Json(intent)))
+}
+
+/// List the online payments of one of the guest's own bookings
+/// GET /guest/bookings/:id/payment-intents
+pub async fn list_guest_intents(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Path(booking_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    BookingService::new(state.pool.clone()).get_guest_booking(booking_id, auth_user.user_id)?;
+
+    let intent_service = PaymentIntentService::new(state.pool, state.payment_gateway);
+    let intents = intent_service.list_intents(booking_id)?;
+    Ok((StatusCode::OK, Json(intents)))
+}
+
+/// Receive a signed webhook from a payment gateway
+/// POST /payments/webhooks/:provider
+///
+/// Public: authenticity comes from the gateway's signature. Redelivered
+/// events are acknowledged without recording anything twice.
+pub async fn receive_webhook(
+    State(state): State<AppState>,
+    Path(provider): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<impl IntoResponse, AppError> {
+    let intent_service = PaymentIntentService::new(state.pool, state.payment_gateway);
+    let outcome = intent_service.handle_webhook(&provider, &headers, &body)?;
+    Ok((StatusCode::OK, Json(json!({ "status": outcome }))))
+}
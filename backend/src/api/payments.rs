@@ -1,34 +1,64 @@
 use axum::{
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
-use serde::Deserialize;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::api::{middleware::AuthUser, AppState};
 use crate::errors::AppError;
-use crate::models::PaymentType;
-use crate::services::PaymentService;
-use bigdecimal::BigDecimal;
+use crate::models::{Payment, PaymentType};
+use crate::services::{payment_service::CreatePaymentRequest, GuestCreditService, PaymentService};
+use bigdecimal::{BigDecimal, Zero};
 
-/// Create payment request DTO
-#[derive(Debug, Deserialize)]
-pub struct CreatePaymentDto {
-    pub amount: BigDecimal,
-    pub payment_type: PaymentType,
-    pub payment_method: String,
-    pub notes: Option<String>,
-}
-
-/// Update payment request DTO
+/// Adjust payment request DTO
 #[derive(Debug, Deserialize)]
 pub struct UpdatePaymentDto {
     pub amount: Option<BigDecimal>,
     pub payment_type: Option<PaymentType>,
     pub payment_method: Option<String>,
     pub notes: Option<Option<String>>,
+    /// Why the payment is being corrected
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// Void payment request DTO
+#[derive(Debug, Deserialize)]
+pub struct VoidPaymentDto {
+    pub reason: String,
+}
+
+/// Move overpayment to guest credit request DTO
+#[derive(Debug, Default, Deserialize)]
+pub struct CreditOverpaymentDto {
+    pub notes: Option<String>,
+}
+
+/// Apply guest credit request DTO
+#[derive(Debug, Default, Deserialize)]
+pub struct ApplyCreditDto {
+    /// Defaults to as much of the remaining balance as the credit covers
+    pub amount: Option<BigDecimal>,
+}
+
+/// Created payment, with how much the booking is now overpaid by if it is
+#[derive(Debug, Serialize)]
+pub struct PaymentCreatedResponse {
+    #[serde(flatten)]
+    pub payment: Payment,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overpaid_amount: Option<BigDecimal>,
+}
+
+/// Query parameters for the payment ledger
+#[derive(Debug, Deserialize)]
+pub struct LedgerQuery {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
 }
 
 /// Create a new payment for a booking
@@ -37,19 +67,48 @@ pub async fn create_payment(
     State(state): State<AppState>,
     Path(booking_id): Path<Uuid>,
     Extension(auth_user): Extension<AuthUser>,
-    Json(payload): Json<CreatePaymentDto>,
+    Json(payload): Json<CreatePaymentRequest>,
 ) -> Result<impl IntoResponse, AppError> {
     let payment_service = PaymentService::new(state.pool);
     
-    let payment = payment_service.create_payment(
-        booking_id,
-        payload.amount,
-        payload.payment_type,
-        payload.payment_method,
-        payload.notes,
-        auth_user.user_id,
-    )?;
+    let payment = payment_service.create_payment(booking_id, payload, auth_user.user_id)?;
+    let summary = payment_service.get_payment_summary(booking_id)?;
+    let overpaid_amount = Some(summary.overpaid_amount).filter(|a| !a.is_zero());
     
+    Ok((
+        StatusCode::CREATED,
+        Json(PaymentCreatedResponse {
+            payment,
+            overpaid_amount,
+        }),
+    ))
+}
+
+/// Move what a booking was overpaid to the guest's credit
+/// POST /bookings/:id/payments/credit-overpayment
+pub async fn credit_overpayment(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+    payload: Option<Json<CreditOverpaymentDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let credit_service = GuestCreditService::new(state.pool);
+    let entry = credit_service.credit_overpayment(booking_id, payload.notes, auth_user.user_id)?;
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+/// Pay a booking from the guest's credit
+/// POST /bookings/:id/payments/apply-credit
+pub async fn apply_credit(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+    Extension(auth_user): Extension<AuthUser>,
+    payload: Option<Json<ApplyCreditDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let payload = payload.map(|Json(p)| p).unwrap_or_default();
+    let credit_service = GuestCreditService::new(state.pool);
+    let payment = credit_service.apply_credit(booking_id, payload.amount, auth_user.user_id)?;
     Ok((StatusCode::CREATED, Json(payment)))
 }
 
@@ -77,6 +136,18 @@ pub async fn get_payment_summary(
     Ok((StatusCode::OK, Json(summary)))
 }
 
+/// Get the combined payment summary for a group reservation
+/// GET /bookings/groups/:id/payments/summary
+pub async fn get_group_payment_summary(
+    State(state): State<AppState>,
+    Path(group_id): Path<Uuid>,
+    Extension(_auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let payment_service = PaymentService::new(state.pool);
+    let summary = payment_service.get_group_payment_summary(group_id)?;
+    Ok((StatusCode::OK, Json(summary)))
+}
+
 /// Get a payment by ID
 /// GET /payments/:id
 pub async fn get_payment(
@@ -89,12 +160,13 @@ pub async fn get_payment(
     Ok((StatusCode::OK, Json(payment)))
 }
 
-/// Update a payment
+/// Adjust a payment: the original is voided and kept, and a corrected
+/// replacement is returned
 /// PATCH /payments/:id
 pub async fn update_payment(
     State(state): State<AppState>,
     Path(payment_id): Path<Uuid>,
-    Extension(_auth_user): Extension<AuthUser>,
+    Extension(auth_user): Extension<AuthUser>,
     Json(payload): Json<UpdatePaymentDto>,
 ) -> Result<impl IntoResponse, AppError> {
     let payment_service = PaymentService::new(state.pool);
@@ -106,18 +178,43 @@ pub async fn update_payment(
         notes: payload.notes,
     };
     
-    let payment = payment_service.update_payment(payment_id, update)?;
+    let payment = payment_service.adjust_payment(payment_id, update, &payload.reason, auth_user.user_id)?;
     Ok((StatusCode::OK, Json(payment)))
 }
 
-/// Delete a payment
+/// Void a payment (the row is kept with the reason)
 /// DELETE /payments/:id
-pub async fn delete_payment(
+/// POST /payments/:id/void
+pub async fn void_payment(
     State(state): State<AppState>,
     Path(payment_id): Path<Uuid>,
-    Extension(_auth_user): Extension<AuthUser>,
+    Extension(auth_user): Extension<AuthUser>,
+    payload: Option<Json<VoidPaymentDto>>,
+) -> Result<impl IntoResponse, AppError> {
+    let reason = payload.map(|Json(p)| p.reason).unwrap_or_default();
+    let payment_service = PaymentService::new(state.pool);
+    let payment = payment_service.void_payment(payment_id, &reason, auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(payment)))
+}
+
+/// Payment ledger of a booking
+/// GET /bookings/:id/payments/ledger
+pub async fn get_booking_ledger(
+    State(state): State<AppState>,
+    Path(booking_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let payment_service = PaymentService::new(state.pool);
+    let entries = payment_service.get_ledger_by_booking(booking_id)?;
+    Ok((StatusCode::OK, Json(entries)))
+}
+
+/// Payment ledger across all bookings for a date range
+/// GET /admin/payments/ledger
+pub async fn list_ledger(
+    State(state): State<AppState>,
+    Query(query): Query<LedgerQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let payment_service = PaymentService::new(state.pool);
-    payment_service.delete_payment(payment_id)?;
-    Ok(StatusCode::NO_CONTENT)
+    let entries = payment_service.list_ledger(query.start_date, query.end_date)?;
+    Ok((StatusCode::OK, Json(entries)))
 }
@@ -0,0 +1,187 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::errors::AppError;
+use crate::models::{NewRateOverride, NewRatePlan, RoomType, UpdateRatePlan};
+use crate::services::PricingService;
+use crate::utils::double_option;
+
+/// Query parameters for listing rate plans
+#[derive(Debug, Deserialize)]
+pub struct ListRatePlansQuery {
+    pub room_type: Option<RoomType>,
+    pub room_id: Option<Uuid>,
+}
+
+/// Create rate plan request DTO
+#[derive(Debug, Deserialize)]
+pub struct CreateRatePlanDto {
+    pub name: String,
+    pub room_type: Option<RoomType>,
+    pub room_id: Option<Uuid>,
+    pub base_rate: BigDecimal,
+    pub weekend_rate: Option<BigDecimal>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub min_stay_nights: Option<i32>,
+    pub priority: Option<i32>,
+    pub is_active: Option<bool>,
+    pub cancellation_policy_id: Option<Uuid>,
+}
+
+/// Update rate plan request DTO
+///
+/// Nullable fields use `Option<Option<_>>`: omit to keep, send `null` to clear.
+#[derive(Debug, Deserialize)]
+pub struct UpdateRatePlanDto {
+    pub name: Option<String>,
+    pub base_rate: Option<BigDecimal>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub weekend_rate: Option<Option<BigDecimal>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub start_date: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub end_date: Option<Option<NaiveDate>>,
+    pub min_stay_nights: Option<i32>,
+    pub priority: Option<i32>,
+    pub is_active: Option<bool>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub cancellation_policy_id: Option<Option<Uuid>>,
+}
+
+/// Single date override in an upsert request
+#[derive(Debug, Deserialize)]
+pub struct RateOverrideDto {
+    pub stay_date: NaiveDate,
+    pub price: BigDecimal,
+    pub min_stay_nights: Option<i32>,
+}
+
+/// Upsert overrides request DTO
+#[derive(Debug, Deserialize)]
+pub struct UpsertRateOverridesDto {
+    pub overrides: Vec<RateOverrideDto>,
+}
+
+/// List rate plans
+/// GET /admin/rate-plans
+pub async fn list_rate_plans(
+    State(state): State<AppState>,
+    Query(query): Query<ListRatePlansQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    let plans = pricing_service.list_rate_plans(query.room_type, query.room_id)?;
+    Ok((StatusCode::OK, Json(plans)))
+}
+
+/// Get a rate plan with its overrides
+/// GET /admin/rate-plans/:id
+pub async fn get_rate_plan(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    let plan = pricing_service.get_rate_plan(id)?;
+    Ok((StatusCode::OK, Json(plan)))
+}
+
+/// Create a rate plan
+/// POST /admin/rate-plans
+pub async fn create_rate_plan(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateRatePlanDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    let plan = pricing_service.create_rate_plan(NewRatePlan {
+        name: payload.name.trim().to_string(),
+        room_type: payload.room_type,
+        room_id: payload.room_id,
+        base_rate: payload.base_rate,
+        weekend_rate: payload.weekend_rate,
+        start_date: payload.start_date,
+        end_date: payload.end_date,
+        min_stay_nights: payload.min_stay_nights.unwrap_or(1),
+        priority: payload.priority.unwrap_or(0),
+        is_active: payload.is_active.unwrap_or(true),
+        cancellation_policy_id: payload.cancellation_policy_id,
+    })?;
+    Ok((StatusCode::CREATED, Json(plan)))
+}
+
+/// Update a rate plan
+/// PATCH /admin/rate-plans/:id
+pub async fn update_rate_plan(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateRatePlanDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    let plan = pricing_service.update_rate_plan(
+        id,
+        UpdateRatePlan {
+            name: payload.name.map(|n| n.trim().to_string()),
+            base_rate: payload.base_rate,
+            weekend_rate: payload.weekend_rate,
+            start_date: payload.start_date,
+            end_date: payload.end_date,
+            min_stay_nights: payload.min_stay_nights,
+            priority: payload.priority,
+            is_active: payload.is_active,
+            cancellation_policy_id: payload.cancellation_policy_id,
+        },
+    )?;
+    Ok((StatusCode::OK, Json(plan)))
+}
+
+/// Delete a rate plan
+/// DELETE /admin/rate-plans/:id
+pub async fn delete_rate_plan(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    pricing_service.delete_rate_plan(id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Create or replace date overrides for a rate plan
+/// PUT /admin/rate-plans/:id/overrides
+pub async fn upsert_rate_overrides(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpsertRateOverridesDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    let overrides = payload
+        .overrides
+        .into_iter()
+        .map(|o| NewRateOverride {
+            rate_plan_id: id,
+            stay_date: o.stay_date,
+            price: o.price,
+            min_stay_nights: o.min_stay_nights,
+        })
+        .collect();
+    let saved = pricing_service.upsert_overrides(id, overrides)?;
+    Ok((StatusCode::OK, Json(saved)))
+}
+
+/// Remove the override for a single date
+/// DELETE /admin/rate-plans/:id/overrides/:date
+pub async fn delete_rate_override(
+    State(state): State<AppState>,
+    Path((id, stay_date)): Path<(Uuid, NaiveDate)>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    pricing_service.delete_override(id, stay_date)?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -4,7 +4,6 @@ use axum::{
     response::IntoResponse,
     Json,
 };
-use diesel::prelude::*;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -12,23 +11,39 @@ use uuid::Uuid;
 use crate::api::AppState;
 use crate::errors::AppError;
 use crate::models::{Room, RoomStatus, RoomType};
-use crate::services::{BookingService, RoomService};
+use crate::services::room_service::{RoomAttributes, RoomAttributesUpdate, RoomFilter};
+use crate::services::{BookingService, HousekeepingService, PricingService, RoomService};
 use crate::api::middleware::AuthUser;
-use crate::schema::rooms::dsl as rooms_dsl;
+use crate::utils::double_option;
 
 /// Create room request DTO
 #[derive(Debug, Deserialize)]
 pub struct CreateRoomDto {
     pub number: String,
     pub room_type: RoomType,
+    #[serde(flatten)]
+    pub attributes: RoomAttributes,
 }
 
 /// Update room request DTO
+///
+/// `floor`, `bed_configuration` and `view` use `Option<Option<_>>`: omit to
+/// keep, send `null` to clear.
 #[derive(Debug, Deserialize)]
 pub struct UpdateRoomDto {
     pub room_type: Option<RoomType>,
     pub status: Option<RoomStatus>,
     pub assigned_cleaner_id: Option<Uuid>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub floor: Option<Option<i32>>,
+    pub max_occupancy: Option<i32>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub bed_configuration: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub view: Option<Option<String>>,
+    pub amenities: Option<Vec<String>>,
+    pub smoking: Option<bool>,
+    pub wheelchair_accessible: Option<bool>,
 }
 
 /// Query parameters for listing rooms
@@ -36,14 +51,49 @@ pub struct UpdateRoomDto {
 pub struct ListRoomsQuery {
     pub status: Option<RoomStatus>,
     pub room_type: Option<RoomType>,
+    pub floor: Option<i32>,
+    /// Only rooms sleeping at least this many guests
+    pub guests: Option<i32>,
+    pub bed_configuration: Option<String>,
+    pub view: Option<String>,
+    /// Comma-separated amenities the room must all have
+    pub amenities: Option<String>,
+    pub smoking: Option<bool>,
+    pub wheelchair_accessible: Option<bool>,
+}
+
+impl ListRoomsQuery {
+    fn to_filter(&self) -> RoomFilter {
+        RoomFilter {
+            status: self.status,
+            room_type: self.room_type,
+            floor: self.floor,
+            guests: self.guests,
+            bed_configuration: self.bed_configuration.clone(),
+            view: self.view.clone(),
+            amenities: self
+                .amenities
+                .as_deref()
+                .map(|a| a.split(',').map(str::to_string).collect())
+                .unwrap_or_default(),
+            smoking: self.smoking,
+            wheelchair_accessible: self.wheelchair_accessible,
+        }
+    }
 }
 
-/// Query parameters for available rooms
+/// Query parameters for available rooms (room filters come from [`ListRoomsQuery`])
 #[derive(Debug, Deserialize)]
 pub struct AvailableRoomsQuery {
     pub check_in_date: NaiveDate,
     pub check_out_date: NaiveDate,
-    pub room_type: Option<RoomType>,
+}
+
+/// Query parameters for a room price quote
+#[derive(Debug, Deserialize)]
+pub struct QuoteQuery {
+    pub check_in_date: NaiveDate,
+    pub check_out_date: NaiveDate,
 }
 
 /// Room availability response
@@ -60,7 +110,7 @@ pub async fn list_rooms(
     Query(query): Query<ListRoomsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let room_service = RoomService::new(state.pool);
-    let rooms = room_service.list_rooms(query.status, query.room_type)?;
+    let rooms = room_service.list_rooms(&query.to_filter())?;
     Ok((StatusCode::OK, Json(rooms)))
 }
 
@@ -80,7 +130,7 @@ pub async fn create_room(
     Json(payload): Json<CreateRoomDto>,
 ) -> Result<impl IntoResponse, AppError> {
     let room_service = RoomService::new(state.pool);
-    let room = room_service.create_room(&payload.number, payload.room_type)?;
+    let room = room_service.create_room(&payload.number, payload.room_type, payload.attributes)?;
     Ok((StatusCode::CREATED, Json(room)))
 }
 
@@ -97,7 +147,23 @@ pub async fn update_room(
     // For now, let's assume if it's sent, we update it.
     let assigned_id_update = payload.assigned_cleaner_id.map(Some);
     
-    let room = room_service.update_room(id, payload.room_type, payload.status, assigned_id_update)?;
+    let attributes = RoomAttributesUpdate {
+        floor: payload.floor,
+        max_occupancy: payload.max_occupancy,
+        bed_configuration: payload.bed_configuration,
+        view: payload.view,
+        amenities: payload.amenities,
+        smoking: payload.smoking,
+        wheelchair_accessible: payload.wheelchair_accessible,
+    };
+
+    let room = room_service.update_room(
+        id,
+        payload.room_type,
+        payload.status,
+        assigned_id_update,
+        attributes,
+    )?;
     Ok((StatusCode::OK, Json(room)))
 }
 
@@ -105,26 +171,19 @@ pub async fn update_room(
 pub async fn available_rooms(
     State(state): State<AppState>,
     Query(query): Query<AvailableRoomsQuery>,
+    Query(filter): Query<ListRoomsQuery>,
 ) -> Result<impl IntoResponse, AppError> {
     let room_service = RoomService::new(state.pool.clone());
     let booking_service = BookingService::new(state.pool);
 
-    // Get all rooms (optionally filtered by type)
-    let rooms = room_service.list_rooms(None, query.room_type)?;
+    // Get the rooms matching the attribute filters
+    let rooms = room_service.list_rooms(&filter.to_filter())?;
 
     // Check availability for each room
     let mut available_rooms: Vec<AvailableRoom> = Vec::new();
     for room in rooms {
-        // Maintenance rooms are always unavailable
-        if room.status == RoomStatus::Maintenance {
-            available_rooms.push(AvailableRoom {
-                room,
-                is_available: false,
-            });
-            continue;
-        }
-
-        // check_availability handles both booking conflicts and room status checks
+        // check_availability handles booking conflicts, maintenance work
+        // orders and room status checks
         // (e.g., Occupied rooms can't be booked for same-day check-in, but future bookings are OK)
         let is_available = booking_service.check_availability(
             room.id,
@@ -139,6 +198,18 @@ pub async fn available_rooms(
     Ok((StatusCode::OK, Json(available_rooms)))
 }
 
+/// Quote the price of a stay in a room from the rate calendar
+/// GET /rooms/:id/quote
+pub async fn quote_room(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<QuoteQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let pricing_service = PricingService::new(state.pool);
+    let quote = pricing_service.quote(id, query.check_in_date, query.check_out_date)?;
+    Ok((StatusCode::OK, Json(quote)))
+}
+
 /// Query parameters for cleaner room listing
 #[derive(Debug, Deserialize)]
 pub struct CleanerRoomsQuery {
@@ -161,13 +232,19 @@ pub async fn list_cleaner_rooms(
     let room_service = RoomService::new(state.pool);
     // Default to dirty rooms if no status filter is provided
     let status_filter = query.status.or(Some(RoomStatus::Dirty));
-    let rooms = room_service.list_rooms(status_filter, query.room_type)?;
+    let rooms = room_service.list_rooms(&RoomFilter {
+        status: status_filter,
+        ..RoomFilter::by_type(query.room_type)
+    })?;
     Ok((StatusCode::OK, Json(rooms)))
 }
 
 /// Update room status (cleaner endpoint)
 /// Cleaners can transition rooms: Dirty → Cleaning → Available
 /// Cleaners cannot set room status to Occupied or Maintenance
+///
+/// Acts on the room's housekeeping task: Cleaning starts it, Available
+/// completes it and Dirty puts it back in the queue.
 pub async fn update_cleaner_room_status(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
@@ -182,59 +259,8 @@ pub async fn update_cleaner_room_status(
         )));
     }
 
-    let room_service = RoomService::new(state.pool.clone());
-
-    // Get current room to validate transition
-    let current_room = room_service.get_room_by_id(id)?;
-
-    // Validate status transition
-    if !current_room.status.can_transition_to(payload.status) {
-        return Err(AppError::InvalidStatusTransition(format!(
-            "Cannot transition room from {:?} to {:?}",
-            current_room.status, payload.status
-        )));
-    }
-
-    // Optimistic concurrency: only update if status hasn't changed since we read it
-    let mut conn = state
-        .pool
-        .get()
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-    // Build update query - use tuple syntax when setting multiple fields
-    let rows_updated = if payload.status == RoomStatus::Available {
-        // If finishing cleaning, clear the assignment and update status
-        diesel::update(
-            rooms_dsl::rooms
-                .filter(rooms_dsl::id.eq(id))
-                .filter(rooms_dsl::status.eq(current_room.status)),
-        )
-        .set((
-            rooms_dsl::status.eq(payload.status),
-            rooms_dsl::assigned_cleaner_id.eq(None::<Uuid>),
-        ))
-        .execute(&mut conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?
-    } else {
-        // Only update status
-        diesel::update(
-            rooms_dsl::rooms
-                .filter(rooms_dsl::id.eq(id))
-                .filter(rooms_dsl::status.eq(current_room.status)),
-        )
-        .set(rooms_dsl::status.eq(payload.status))
-        .execute(&mut conn)
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?
-    };
-
-    if rows_updated == 0 {
-        return Err(AppError::Conflict(
-            "Room status was updated by someone else. Please refresh and try again."
-                .to_string(),
-        ));
-    }
-
-    let updated_room = room_service.get_room_by_id(id)?;
+    let housekeeping_service = HousekeepingService::new(state.pool);
+    let updated_room = housekeeping_service.set_room_status(id, auth_user.user_id, payload.status)?;
     Ok((StatusCode::OK, Json(updated_room)))
 }
 
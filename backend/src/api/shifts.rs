@@ -0,0 +1,94 @@
+use axum::{
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::{middleware::AuthUser, AppState};
+use crate::errors::AppError;
+use crate::services::CashierShiftService;
+
+/// Open shift request DTO
+#[derive(Debug, Deserialize)]
+pub struct OpenShiftDto {
+    pub opening_float: BigDecimal,
+    pub notes: Option<String>,
+}
+
+/// Close shift request DTO
+#[derive(Debug, Deserialize)]
+pub struct CloseShiftDto {
+    pub counted_cash: BigDecimal,
+    pub notes: Option<String>,
+}
+
+/// Query parameters for the shift report
+#[derive(Debug, Deserialize)]
+pub struct ShiftReportQuery {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub user_id: Option<Uuid>,
+}
+
+/// Open a cashier shift for the current user
+/// POST /shifts/open
+pub async fn open_shift(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<OpenShiftDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let shift_service = CashierShiftService::new(state.pool);
+    let shift = shift_service.open_shift(auth_user.user_id, payload.opening_float, payload.notes)?;
+    Ok((StatusCode::CREATED, Json(shift)))
+}
+
+/// Current user's open shift with the cash expected in the drawer so far
+/// GET /shifts/current
+pub async fn get_current_shift(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+) -> Result<impl IntoResponse, AppError> {
+    let shift_service = CashierShiftService::new(state.pool);
+    let report = shift_service.current_shift(auth_user.user_id)?;
+    Ok((StatusCode::OK, Json(report)))
+}
+
+/// Close the current user's shift with the counted cash
+/// POST /shifts/current/close
+pub async fn close_current_shift(
+    State(state): State<AppState>,
+    Extension(auth_user): Extension<AuthUser>,
+    Json(payload): Json<CloseShiftDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let shift_service = CashierShiftService::new(state.pool);
+    let report =
+        shift_service.close_current_shift(auth_user.user_id, payload.counted_cash, payload.notes)?;
+    Ok((StatusCode::OK, Json(report)))
+}
+
+/// Shift report for shifts opened in a date range
+/// GET /admin/financial/shifts
+pub async fn list_shift_reports(
+    State(state): State<AppState>,
+    Query(query): Query<ShiftReportQuery>,
+) -> Result<impl IntoResponse, AppError> {
+    let shift_service = CashierShiftService::new(state.pool);
+    let reports = shift_service.list_shift_reports(query.start_date, query.end_date, query.user_id)?;
+    Ok((StatusCode::OK, Json(reports)))
+}
+
+/// Report on a single shift
+/// GET /admin/financial/shifts/:id
+pub async fn get_shift_report(
+    State(state): State<AppState>,
+    Path(shift_id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let shift_service = CashierShiftService::new(state.pool);
+    let report = shift_service.get_shift_report(shift_id)?;
+    Ok((StatusCode::OK, Json(report)))
+}
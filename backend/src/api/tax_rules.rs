@@ -0,0 +1,126 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::api::AppState;
+use crate::errors::AppError;
+use crate::models::{ChargeCategory, NewTaxRule, UpdateTaxRule};
+use crate::services::TaxService;
+use crate::utils::double_option;
+
+/// Create tax rule request DTO
+#[derive(Debug, Deserialize)]
+pub struct CreateTaxRuleDto {
+    pub name: String,
+    pub rate: BigDecimal,
+    pub is_inclusive: Option<bool>,
+    pub applies_to_room: Option<bool>,
+    pub charge_categories: Option<Vec<ChargeCategory>>,
+    pub effective_from: Option<NaiveDate>,
+    pub effective_to: Option<NaiveDate>,
+    pub sort_order: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+/// Update tax rule request DTO
+///
+/// Effective dates use `Option<Option<_>>`: omit to keep, send `null` to clear.
+#[derive(Debug, Deserialize)]
+pub struct UpdateTaxRuleDto {
+    pub name: Option<String>,
+    pub rate: Option<BigDecimal>,
+    pub is_inclusive: Option<bool>,
+    pub applies_to_room: Option<bool>,
+    pub charge_categories: Option<Vec<ChargeCategory>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub effective_from: Option<Option<NaiveDate>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub effective_to: Option<Option<NaiveDate>>,
+    pub sort_order: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+/// List tax rules
+/// GET /admin/tax-rules
+pub async fn list_tax_rules(
+    State(state): State<AppState>,
+) -> Result<impl IntoResponse, AppError> {
+    let tax_service = TaxService::new(state.pool);
+    let rules = tax_service.list_rules()?;
+    Ok((StatusCode::OK, Json(rules)))
+}
+
+/// Get a tax rule
+/// GET /admin/tax-rules/:id
+pub async fn get_tax_rule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let tax_service = TaxService::new(state.pool);
+    let rule = tax_service.get_rule(id)?;
+    Ok((StatusCode::OK, Json(rule)))
+}
+
+/// Create a tax rule
+/// POST /admin/tax-rules
+pub async fn create_tax_rule(
+    State(state): State<AppState>,
+    Json(payload): Json<CreateTaxRuleDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let tax_service = TaxService::new(state.pool);
+    let rule = tax_service.create_rule(NewTaxRule {
+        name: payload.name.trim().to_string(),
+        rate: payload.rate,
+        is_inclusive: payload.is_inclusive.unwrap_or(false),
+        applies_to_room: payload.applies_to_room.unwrap_or(true),
+        charge_categories: payload.charge_categories.unwrap_or_default(),
+        effective_from: payload.effective_from,
+        effective_to: payload.effective_to,
+        sort_order: payload.sort_order.unwrap_or(0),
+        is_active: payload.is_active.unwrap_or(true),
+    })?;
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+/// Update a tax rule
+/// PATCH /admin/tax-rules/:id
+pub async fn update_tax_rule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(payload): Json<UpdateTaxRuleDto>,
+) -> Result<impl IntoResponse, AppError> {
+    let tax_service = TaxService::new(state.pool);
+    let rule = tax_service.update_rule(
+        id,
+        UpdateTaxRule {
+            name: payload.name.map(|n| n.trim().to_string()),
+            rate: payload.rate,
+            is_inclusive: payload.is_inclusive,
+            applies_to_room: payload.applies_to_room,
+            charge_categories: payload.charge_categories,
+            effective_from: payload.effective_from,
+            effective_to: payload.effective_to,
+            sort_order: payload.sort_order,
+            is_active: payload.is_active,
+        },
+    )?;
+    Ok((StatusCode::OK, Json(rule)))
+}
+
+/// Delete a tax rule
+/// DELETE /admin/tax-rules/:id
+pub async fn delete_tax_rule(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<impl IntoResponse, AppError> {
+    let tax_service = TaxService::new(state.pool);
+    tax_service.delete_rule(id)?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -1,4 +1,5 @@
 use std::env;
+use std::str::FromStr;
 
 /// Application configuration loaded from environment variables
 #[derive(Debug, Clone)]
@@ -12,6 +13,184 @@ pub struct Config {
     pub minio_public_url: String,
     pub minio_root_user: String,
     pub minio_root_password: String,
+    pub jobs: JobsConfig,
+    pub payments: PaymentConfig,
+}
+
+/// Online payment gateway configuration
+#[derive(Debug, Clone)]
+pub struct PaymentConfig {
+    /// Gateway to use (`mock` is the only built-in provider)
+    pub provider: String,
+    /// Shared secret used to verify the gateway's webhook signatures
+    pub webhook_secret: String,
+    /// Whether the mock gateway may be used (development and tests only)
+    pub allow_mock: bool,
+}
+
+impl PaymentConfig {
+    /// Load gateway settings from environment variables
+    ///
+    /// Both the provider and the webhook secret must be set; there is no
+    /// default for either. The secret is never logged.
+    pub fn from_env() -> Self {
+        let provider = match env::var("PAYMENT_PROVIDER") {
+            Ok(provider) if !provider.trim().is_empty() => provider.trim().to_ascii_lowercase(),
+            _ => {
+                eprintln!("ERROR: PAYMENT_PROVIDER environment variable is not set!");
+                std::process::exit(1);
+            }
+        };
+
+        let webhook_secret = match env::var("PAYMENT_WEBHOOK_SECRET") {
+            Ok(secret) if !secret.is_empty() => secret,
+            _ => {
+                eprintln!("ERROR: PAYMENT_WEBHOOK_SECRET environment variable is not set!");
+                eprintln!("Please set PAYMENT_WEBHOOK_SECRET in your .env file or environment variables.");
+                std::process::exit(1);
+            }
+        };
+
+        Self {
+            provider,
+            webhook_secret,
+            allow_mock: env_or("PAYMENT_ALLOW_MOCK", false),
+        }
+    }
+}
+
+/// Run interval and on/off switch for a single background job
+#[derive(Debug, Clone, Copy)]
+pub struct JobSchedule {
+    pub enabled: bool,
+    pub interval_secs: u64,
+}
+
+/// What the no-show job does with Upcoming bookings past their grace period
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoShowAction {
+    /// Only record the bookings in the job run
+    Report,
+    /// Mark the bookings as no-shows (releasing the room, leaving any fee owed)
+    Mark,
+}
+
+impl FromStr for NoShowAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "report" => Ok(NoShowAction::Report),
+            "mark" => Ok(NoShowAction::Mark),
+            other => Err(format!("unknown no-show action '{}'", other)),
+        }
+    }
+}
+
+/// No-show job for Upcoming bookings that never checked in
+///
+/// The grace period and fee come from the hotel's no-show policy in
+/// `system_settings`; this only controls the schedule and what the job does.
+#[derive(Debug, Clone, Copy)]
+pub struct NoShowJobConfig {
+    pub schedule: JobSchedule,
+    pub action: NoShowAction,
+}
+
+/// Sweep that flags rooms left Dirty for too long
+#[derive(Debug, Clone, Copy)]
+pub struct DirtyRoomSweepConfig {
+    pub schedule: JobSchedule,
+    pub max_dirty_hours: i64,
+}
+
+/// Background lifecycle job configuration
+#[derive(Debug, Clone, Copy)]
+pub struct JobsConfig {
+    /// Master switch for the in-process scheduler
+    pub enabled: bool,
+    pub overstay: JobSchedule,
+    pub no_show: NoShowJobConfig,
+    pub dirty_room_sweep: DirtyRoomSweepConfig,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            overstay: JobSchedule {
+                enabled: true,
+                interval_secs: 900,
+            },
+            no_show: NoShowJobConfig {
+                schedule: JobSchedule {
+                    enabled: true,
+                    interval_secs: 3600,
+                },
+                action: NoShowAction::Mark,
+            },
+            dirty_room_sweep: DirtyRoomSweepConfig {
+                schedule: JobSchedule {
+                    enabled: true,
+                    interval_secs: 1800,
+                },
+                max_dirty_hours: 6,
+            },
+        }
+    }
+}
+
+impl JobsConfig {
+    /// Load job settings from environment variables, falling back to defaults
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        Self {
+            enabled: env_or("JOBS_ENABLED", defaults.enabled),
+            overstay: JobSchedule {
+                enabled: env_or("OVERSTAY_JOB_ENABLED", defaults.overstay.enabled),
+                interval_secs: env_or("OVERSTAY_JOB_INTERVAL_SECS", defaults.overstay.interval_secs),
+            },
+            no_show: NoShowJobConfig {
+                schedule: JobSchedule {
+                    enabled: env_or("NO_SHOW_JOB_ENABLED", defaults.no_show.schedule.enabled),
+                    interval_secs: env_or(
+                        "NO_SHOW_JOB_INTERVAL_SECS",
+                        defaults.no_show.schedule.interval_secs,
+                    ),
+                },
+                action: env_or("NO_SHOW_ACTION", defaults.no_show.action),
+            },
+            dirty_room_sweep: DirtyRoomSweepConfig {
+                schedule: JobSchedule {
+                    enabled: env_or(
+                        "DIRTY_ROOM_JOB_ENABLED",
+                        defaults.dirty_room_sweep.schedule.enabled,
+                    ),
+                    interval_secs: env_or(
+                        "DIRTY_ROOM_JOB_INTERVAL_SECS",
+                        defaults.dirty_room_sweep.schedule.interval_secs,
+                    ),
+                },
+                max_dirty_hours: env_or(
+                    "DIRTY_ROOM_MAX_HOURS",
+                    defaults.dirty_room_sweep.max_dirty_hours,
+                )
+                .max(1),
+            },
+        }
+    }
+}
+
+/// Parse an optional environment variable, keeping the default when unset or invalid
+fn env_or<T: FromStr>(key: &str, default: T) -> T {
+    match env::var(key) {
+        Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!("Invalid value '{}' for {}, using default", raw, key);
+            default
+        }),
+        Err(_) => default,
+    }
 }
 
 impl Config {
@@ -110,6 +289,8 @@ impl Config {
                     tracing::info!("MINIO_ROOT_PASSWORD not set, using default: {}", default);
                     default
                 }),
+            jobs: JobsConfig::from_env(),
+            payments: PaymentConfig::from_env(),
         }
     }
 }
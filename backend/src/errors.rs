@@ -35,6 +35,9 @@ pub enum AppError {
     #[error("Conflict: {0}")]
     Conflict(String),
 
+    #[error("Unpaid balance: {0}")]
+    UnpaidBalance(String),
+
     #[error("Database error: {0}")]
     DatabaseError(String),
 
@@ -71,6 +74,9 @@ impl IntoResponse for AppError {
                 msg.clone(),
             ),
             AppError::Conflict(msg) => (StatusCode::CONFLICT, "CONFLICT", msg.clone()),
+            AppError::UnpaidBalance(msg) => {
+                (StatusCode::CONFLICT, "UNPAID_BALANCE", msg.clone())
+            }
             AppError::DatabaseError(msg) => {
                 tracing::error!("Database error: {}", msg);
                 (
@@ -98,6 +104,9 @@ impl IntoResponse for AppError {
     }
 }
 
+/// Exclusion constraint preventing overlapping active bookings for a room
+pub const BOOKING_OVERLAP_CONSTRAINT: &str = "bookings_no_overlap";
+
 impl From<diesel::result::Error> for AppError {
     fn from(err: diesel::result::Error) -> Self {
         match err {
@@ -105,6 +114,14 @@ impl From<diesel::result::Error> for AppError {
                 AppError::NotFound("Resource not found".to_string())
             }
             diesel::result::Error::DatabaseError(_kind, info) => {
+                // Overlapping stays rejected by the `bookings_no_overlap`
+                // exclusion constraint (another request won the race).
+                if info.constraint_name() == Some(BOOKING_OVERLAP_CONSTRAINT) {
+                    return AppError::RoomUnavailable(
+                        "Room is already booked for the selected dates".to_string(),
+                    );
+                }
+
                 // If we wrapped an AppError earlier into a DatabaseError via
                 // `app_error_to_diesel`, the original AppError's Display text
                 // is available in `info.message()`. Try to map it back to the
@@ -114,7 +131,7 @@ impl From<diesel::result::Error> for AppError {
                 if let Some(rest) = msg.strip_prefix("Validation error: ") {
                     return AppError::ValidationError(rest.to_string());
                 }
-                if let Some(rest) = msg.strip_prefix("Invalid status-+ transition: ") {
+                if let Some(rest) = msg.strip_prefix("Invalid status transition: ") {
                     return AppError::InvalidStatusTransition(rest.to_string());
                 }
                 if let Some(rest) = msg.strip_prefix("Room unavailable: ") {
@@ -123,6 +140,9 @@ impl From<diesel::result::Error> for AppError {
                 if let Some(rest) = msg.strip_prefix("Conflict: ") {
                     return AppError::Conflict(rest.to_string());
                 }
+                if let Some(rest) = msg.strip_prefix("Unpaid balance: ") {
+                    return AppError::UnpaidBalance(rest.to_string());
+                }
 
                 AppError::DatabaseError(msg)
             }
@@ -3,6 +3,7 @@ pub mod config;
 pub mod db;
 pub mod errors;
 pub mod models;
+pub mod scheduler;
 pub mod schema;
 pub mod services;
 pub mod utils;
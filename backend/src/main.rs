@@ -3,6 +3,7 @@ mod config;
 mod db;
 mod errors;
 mod models;
+mod scheduler;
 mod schema;
 mod services;
 mod utils;
@@ -18,6 +19,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use crate::api::{create_router, AppState};
 use crate::config::Config;
 use crate::db::create_pool;
+use crate::services::payment_gateway::build_gateway;
 
 #[tokio::main]
 async fn main() {
@@ -66,6 +68,9 @@ async fn main() {
     // Attempt to apply DB fixes for enum normalization / stale statuses
     crate::db::apply_stale_statuses_fix(&pool);
 
+    // Start booking/room lifecycle jobs (overstay, no-show, dirty room sweep)
+    crate::scheduler::start(pool.clone(), config.jobs);
+
     tracing::info!("Final MinIO Config Check:");
     tracing::info!("  MINIO_URL: {}", config.minio_url);
     tracing::info!("  MINIO_ROOT_USER: {}", config.minio_root_user);
@@ -91,12 +96,24 @@ async fn main() {
     let s3_client = aws_sdk_s3::Client::from_conf(s3_config);
     tracing::info!("S3 client initialized successfully");
 
+    let payment_gateway = build_gateway(
+        &config.payments.provider,
+        &config.payments.webhook_secret,
+        config.payments.allow_mock,
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("ERROR: {}", e);
+        std::process::exit(1);
+    });
+    tracing::info!("Payment gateway: {}", payment_gateway.name());
+
     // Create application state
     let state = AppState {
         pool,
         jwt_secret: config.jwt_secret,
         chat_state: std::sync::Arc::new(crate::api::chat::ChatState::default()),
         s3_client,
+        payment_gateway,
     };
 
     // Configure CORS
@@ -20,6 +20,7 @@ pub enum BookingStatus {
     CheckedOut,
     Cancelled,
     Overstay,
+    NoShow,
 }
 
 /// Booking model representing a guest reservation
@@ -43,6 +44,25 @@ pub struct Booking {
     pub creation_source: String,
     /// Booking price/revenue
     pub price: BigDecimal,
+    /// Rate plan that priced the arrival night (None when the room's list price was used)
+    pub rate_plan_id: Option<Uuid>,
+    /// Group reservation this room booking belongs to
+    pub group_id: Option<Uuid>,
+    /// Price first quoted, kept once the stay or price has been changed
+    pub original_price: Option<BigDecimal>,
+    /// Guests staying in the room
+    pub guest_count: i32,
+    /// When the status last changed (maintained by a database trigger)
+    pub status_changed_at: DateTime<Utc>,
+    /// Staff member who let the guest check out with money still owed
+    pub unpaid_check_out_by_user_id: Option<Uuid>,
+    /// Balance owed when that override was used (the group's combined
+    /// balance for a group check-out)
+    pub unpaid_check_out_balance: Option<BigDecimal>,
+    /// Price before the booking was marked a no-show, restored by an undo
+    pub price_before_no_show: Option<BigDecimal>,
+    /// When a no-show was last undone; the no-show job skips these bookings
+    pub no_show_undone_at: Option<DateTime<Utc>>,
 }
 
 /// New booking for insertion
@@ -57,6 +77,9 @@ pub struct NewBooking<'a> {
     pub created_by_user_id: Option<Uuid>,
     pub creation_source: &'a str,
     pub price: BigDecimal,
+    pub rate_plan_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    pub guest_count: i32,
 }
 
 /// Booking update changeset
@@ -95,6 +118,9 @@ impl BookingStatus {
             // Upcoming can go to checked_in or cancelled
             (BookingStatus::Upcoming, BookingStatus::CheckedIn) => true,
             (BookingStatus::Upcoming, BookingStatus::Cancelled) => true,
+            // Upcoming guests who never arrive become no-shows; staff can undo it
+            (BookingStatus::Upcoming, BookingStatus::NoShow) => true,
+            (BookingStatus::NoShow, BookingStatus::Upcoming) => true,
             // CheckedIn can go to checked_out
             (BookingStatus::CheckedIn, BookingStatus::CheckedOut) => true,
             (BookingStatus::CheckedIn, BookingStatus::Overstay) => true, // Automatic via handle_stale_bookings
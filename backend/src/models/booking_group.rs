@@ -0,0 +1,47 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::booking_groups;
+
+use super::{BookingWithRoom, GroupPaymentSummary};
+
+/// Group reservation owning several room bookings under one reference
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = booking_groups)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BookingGroup {
+    pub id: Uuid,
+    /// Group reference in format GR-YYYYMMDD-XXXX
+    pub reference: String,
+    /// Group or event name (e.g. tour operator, wedding party)
+    pub name: String,
+    /// Person the front desk deals with for the whole group
+    pub contact_name: String,
+    pub notes: Option<String>,
+    pub created_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New booking group for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = booking_groups)]
+pub struct NewBookingGroup<'a> {
+    pub reference: &'a str,
+    pub name: &'a str,
+    pub contact_name: &'a str,
+    pub notes: Option<&'a str>,
+    pub created_by_user_id: Option<Uuid>,
+}
+
+/// Booking group with its room bookings for API responses
+#[derive(Debug, Clone, Serialize)]
+pub struct BookingGroupWithBookings {
+    #[serde(flatten)]
+    pub group: BookingGroup,
+    pub bookings: Vec<BookingWithRoom>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payment_summary: Option<GroupPaymentSummary>,
+}
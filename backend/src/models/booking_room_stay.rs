@@ -0,0 +1,37 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::booking_room_stays;
+
+/// Nights a moved booking spent in one room
+///
+/// Only bookings moved mid-stay have these rows; together they cover the
+/// whole stay from check-in to check-out.
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = booking_room_stays)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BookingRoomStay {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub room_id: Uuid,
+    /// First night in the room
+    pub start_date: NaiveDate,
+    /// Morning the guest left the room (exclusive)
+    pub end_date: NaiveDate,
+    /// Why the guest was moved into this room
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New room stay for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = booking_room_stays)]
+pub struct NewBookingRoomStay<'a> {
+    pub booking_id: Uuid,
+    pub room_id: Uuid,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub reason: Option<&'a str>,
+}
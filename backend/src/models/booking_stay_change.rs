@@ -0,0 +1,52 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::booking_stay_changes;
+
+/// Direction of a check-out date change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::StayChangeType"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum StayChangeType {
+    /// Nights added (extension or overstay settled at check-out)
+    Extend,
+    /// Nights removed (early departure)
+    Shorten,
+}
+
+/// Recorded change of a booking's check-out date and price
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = booking_stay_changes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BookingStayChange {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub change_type: StayChangeType,
+    pub old_check_out_date: NaiveDate,
+    pub new_check_out_date: NaiveDate,
+    pub old_price: BigDecimal,
+    pub new_price: BigDecimal,
+    pub reason: Option<String>,
+    /// Staff member who made the change (Pupinn for automatic changes)
+    pub changed_by_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New stay change for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = booking_stay_changes)]
+pub struct NewBookingStayChange<'a> {
+    pub booking_id: Uuid,
+    pub change_type: StayChangeType,
+    pub old_check_out_date: NaiveDate,
+    pub new_check_out_date: NaiveDate,
+    pub old_price: BigDecimal,
+    pub new_price: BigDecimal,
+    pub reason: Option<&'a str>,
+    pub changed_by_user_id: Uuid,
+}
@@ -0,0 +1,87 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::cancellation_policies;
+
+use super::{Booking, RoomType};
+
+/// How the late-cancellation penalty is measured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::CancellationPenaltyType"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum CancellationPenaltyType {
+    /// Percentage of the booking price
+    Percent,
+    /// Number of nights at the booking's average nightly rate
+    Nights,
+}
+
+/// Cancellation policy attached to a rate plan or used as a room type default
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = cancellation_policies)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CancellationPolicy {
+    pub id: Uuid,
+    pub name: String,
+    /// Cancelling at least this many days before check-in is free
+    pub free_cancellation_days: i32,
+    pub penalty_type: CancellationPenaltyType,
+    /// Percent (0-100) or number of nights, depending on `penalty_type`
+    pub penalty_value: BigDecimal,
+    /// Room type this policy is the default for
+    pub room_type: Option<RoomType>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New cancellation policy for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = cancellation_policies)]
+pub struct NewCancellationPolicy {
+    pub name: String,
+    pub free_cancellation_days: i32,
+    pub penalty_type: CancellationPenaltyType,
+    pub penalty_value: BigDecimal,
+    pub room_type: Option<RoomType>,
+    pub is_active: bool,
+}
+
+/// Cancellation policy update changeset
+#[derive(Debug, AsChangeset, Default)]
+#[diesel(table_name = cancellation_policies)]
+pub struct UpdateCancellationPolicy {
+    pub name: Option<String>,
+    pub free_cancellation_days: Option<i32>,
+    pub penalty_type: Option<CancellationPenaltyType>,
+    pub penalty_value: Option<BigDecimal>,
+    pub room_type: Option<Option<RoomType>>,
+    pub is_active: Option<bool>,
+}
+
+/// Money outcome of cancelling a booking
+#[derive(Debug, Clone, Serialize)]
+pub struct CancellationOutcome {
+    /// Policy applied (None means free cancellation)
+    pub policy_id: Option<Uuid>,
+    pub policy_name: Option<String>,
+    /// Amount the hotel keeps
+    pub penalty_amount: BigDecimal,
+    /// Amount refunded to the guest (posted as one refund payment per
+    /// method the guest paid with)
+    pub refund_amount: BigDecimal,
+    pub refund_payment_ids: Vec<Uuid>,
+}
+
+/// Cancelled booking with its refund/penalty for API responses
+#[derive(Debug, Clone, Serialize)]
+pub struct CancelledBooking {
+    #[serde(flatten)]
+    pub booking: Booking,
+    pub cancellation: CancellationOutcome,
+}
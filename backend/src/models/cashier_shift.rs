@@ -0,0 +1,81 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::cashier_shifts;
+
+use super::Payment;
+
+/// Cashier shift status enum matching PostgreSQL cashier_shift_status type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::CashierShiftStatus"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum CashierShiftStatus {
+    Open,
+    Closed,
+}
+
+/// A cashier's shift at the front desk cash drawer
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = cashier_shifts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CashierShift {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: CashierShiftStatus,
+    /// Cash in the drawer when the shift started
+    pub opening_float: BigDecimal,
+    pub opening_notes: Option<String>,
+    pub opened_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    /// Cash counted in the drawer at close
+    pub counted_cash: Option<BigDecimal>,
+    /// Opening float plus net cash taken, fixed at close
+    pub expected_cash: Option<BigDecimal>,
+    /// Counted minus expected cash: positive when over, negative when short
+    pub over_short: Option<BigDecimal>,
+    pub closing_notes: Option<String>,
+}
+
+/// New cashier shift for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = cashier_shifts)]
+pub struct NewCashierShift {
+    pub user_id: Uuid,
+    pub opening_float: BigDecimal,
+    pub opening_notes: Option<String>,
+}
+
+/// Payments of one method and currency taken during a shift
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ShiftMethodTotal {
+    pub payment_method: String,
+    pub currency: String,
+    pub payment_count: i64,
+    /// Net of refunds, in `currency`
+    pub total: BigDecimal,
+    /// Net of refunds, in the base currency
+    pub base_total: BigDecimal,
+}
+
+/// Shift with its payments and cash drawer reconciliation
+#[derive(Debug, Clone, Serialize)]
+pub struct ShiftReport {
+    pub shift: CashierShift,
+    pub cashier_name: Option<String>,
+    /// Totals per payment method and currency, voided payments left out
+    pub method_totals: Vec<ShiftMethodTotal>,
+    /// Base currency cash taken minus cash refunded
+    pub net_cash: BigDecimal,
+    /// Base currency cash that should be in the drawer (live for open
+    /// shifts); foreign cash is listed in `method_totals`
+    pub expected_cash: BigDecimal,
+    /// Only known once the shift is closed
+    pub over_short: Option<BigDecimal>,
+    /// All payments attached to the shift, voided ones included
+    pub payments: Vec<Payment>,
+}
@@ -0,0 +1,33 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::exchange_rates;
+
+/// Rate for converting a foreign currency to the base currency from a date on
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = exchange_rates)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct ExchangeRate {
+    pub id: Uuid,
+    /// ISO 4217 code, e.g. `USD`
+    pub currency: String,
+    /// Base currency units for one unit of `currency`
+    pub rate: BigDecimal,
+    pub effective_date: NaiveDate,
+    pub created_by_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New exchange rate for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = exchange_rates)]
+pub struct NewExchangeRate {
+    pub currency: String,
+    pub rate: BigDecimal,
+    pub effective_date: NaiveDate,
+    pub created_by_user_id: Uuid,
+}
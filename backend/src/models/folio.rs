@@ -0,0 +1,80 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::folio_charges;
+
+use super::{FolioChargeTax, PaymentSummary};
+
+/// Kind of extra charge billed to a guest folio
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::ChargeCategory"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum ChargeCategory {
+    Minibar,
+    Laundry,
+    FoodBeverage,
+    Damage,
+    LateCheckout,
+    Other,
+}
+
+/// Itemized charge on a booking's folio
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = folio_charges)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FolioCharge {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub category: ChargeCategory,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: BigDecimal,
+    /// Combined rate in percent of the tax lines
+    pub tax_rate: BigDecimal,
+    pub tax_amount: BigDecimal,
+    /// Quantity * unit price plus exclusive tax
+    pub total_amount: BigDecimal,
+    pub created_by_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Set when the charge was voided; voided charges are no longer owed
+    pub voided_at: Option<DateTime<Utc>>,
+    pub voided_by_user_id: Option<Uuid>,
+    pub void_reason: Option<String>,
+}
+
+/// New folio charge for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = folio_charges)]
+pub struct NewFolioCharge {
+    pub booking_id: Uuid,
+    pub category: ChargeCategory,
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: BigDecimal,
+    pub tax_rate: BigDecimal,
+    pub tax_amount: BigDecimal,
+    pub total_amount: BigDecimal,
+    pub created_by_user_id: Uuid,
+}
+
+/// Folio charge with its tax lines
+#[derive(Debug, Clone, Serialize)]
+pub struct FolioChargeWithTaxes {
+    #[serde(flatten)]
+    pub charge: FolioCharge,
+    pub taxes: Vec<FolioChargeTax>,
+}
+
+/// Full folio of a booking: room price, itemized charges and payment status
+#[derive(Debug, Clone, Serialize)]
+pub struct Folio {
+    pub charges: Vec<FolioChargeWithTaxes>,
+    #[serde(flatten)]
+    pub summary: PaymentSummary,
+}
@@ -0,0 +1,45 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::guest_credit_entries;
+
+/// Credit added to or used from a guest's account
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = guest_credit_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct GuestCreditEntry {
+    pub id: Uuid,
+    pub guest_user_id: Uuid,
+    /// Positive when credit is added, negative when it is used
+    pub amount: BigDecimal,
+    /// Booking the credit came from or was applied to
+    pub booking_id: Uuid,
+    /// Payment that moved the money on or off the booking
+    pub payment_id: Uuid,
+    pub notes: Option<String>,
+    pub created_by_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New guest credit entry for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = guest_credit_entries)]
+pub struct NewGuestCreditEntry {
+    pub guest_user_id: Uuid,
+    pub amount: BigDecimal,
+    pub booking_id: Uuid,
+    pub payment_id: Uuid,
+    pub notes: Option<String>,
+    pub created_by_user_id: Uuid,
+}
+
+/// A guest's credit balance with its history, newest first
+#[derive(Debug, Clone, Serialize)]
+pub struct GuestCreditAccount {
+    pub guest_user_id: Uuid,
+    pub balance: BigDecimal,
+    pub entries: Vec<GuestCreditEntry>,
+}
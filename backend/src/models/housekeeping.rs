@@ -0,0 +1,368 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::{housekeeping_tasks, room_inspections};
+
+use super::{RoomStatus, RoomType};
+
+/// Housekeeping task type enum matching PostgreSQL housekeeping_task_type type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::HousekeepingTaskType"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum HousekeepingTaskType {
+    /// Full clean of a room a guest has left
+    CheckoutClean,
+    /// Service of an occupied room
+    Stayover,
+    DeepClean,
+    /// Evening service of an occupied room
+    Turndown,
+}
+
+impl HousekeepingTaskType {
+    pub const ALL: [HousekeepingTaskType; 4] = [
+        HousekeepingTaskType::CheckoutClean,
+        HousekeepingTaskType::Stayover,
+        HousekeepingTaskType::DeepClean,
+        HousekeepingTaskType::Turndown,
+    ];
+
+    /// Whether the task takes the room through Dirty -> Cleaning -> Available
+    /// (the other types are done around a guest in the room)
+    pub fn cleans_room(&self) -> bool {
+        matches!(
+            self,
+            HousekeepingTaskType::CheckoutClean | HousekeepingTaskType::DeepClean
+        )
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HousekeepingTaskType::CheckoutClean => "checkout_clean",
+            HousekeepingTaskType::Stayover => "stayover",
+            HousekeepingTaskType::DeepClean => "deep_clean",
+            HousekeepingTaskType::Turndown => "turndown",
+        }
+    }
+}
+
+/// Housekeeping task status enum matching PostgreSQL housekeeping_task_status type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::HousekeepingTaskStatus"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum HousekeepingTaskStatus {
+    Pending,
+    InProgress,
+    Completed,
+    Cancelled,
+}
+
+impl HousekeepingTaskStatus {
+    pub const OPEN: [HousekeepingTaskStatus; 2] =
+        [HousekeepingTaskStatus::Pending, HousekeepingTaskStatus::InProgress];
+
+    /// Still in the queue
+    pub fn is_open(&self) -> bool {
+        Self::OPEN.contains(self)
+    }
+}
+
+/// Housekeeping priority enum matching PostgreSQL housekeeping_priority type
+/// (ordered lowest first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::HousekeepingPriority"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum HousekeepingPriority {
+    Low,
+    Normal,
+    High,
+}
+
+/// A piece of cleaning work on a room
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = housekeeping_tasks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct HousekeepingTask {
+    pub id: Uuid,
+    pub room_id: Uuid,
+    /// Stay that left the room to clean, if any
+    pub booking_id: Option<Uuid>,
+    pub task_type: HousekeepingTaskType,
+    pub status: HousekeepingTaskStatus,
+    pub priority: HousekeepingPriority,
+    pub assigned_cleaner_id: Option<Uuid>,
+    pub notes: Option<String>,
+    /// SLA deadline
+    pub due_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// None when created automatically
+    pub created_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New housekeeping task for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = housekeeping_tasks)]
+pub struct NewHousekeepingTask {
+    pub room_id: Uuid,
+    pub booking_id: Option<Uuid>,
+    pub task_type: HousekeepingTaskType,
+    pub priority: HousekeepingPriority,
+    pub assigned_cleaner_id: Option<Uuid>,
+    pub notes: Option<String>,
+    pub due_at: DateTime<Utc>,
+    pub created_by_user_id: Option<Uuid>,
+}
+
+/// Housekeeping task update changeset (admin)
+#[derive(Debug, AsChangeset, Default)]
+#[diesel(table_name = housekeeping_tasks)]
+pub struct UpdateHousekeepingTask {
+    pub priority: Option<HousekeepingPriority>,
+    pub assigned_cleaner_id: Option<Option<Uuid>>,
+    pub notes: Option<String>,
+    pub due_at: Option<DateTime<Utc>>,
+}
+
+/// Where a task stands against its SLA
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlaStatus {
+    /// Open and not yet due
+    OnTrack,
+    /// Open and past due
+    Overdue,
+    /// Completed by the due time
+    Met,
+    /// Completed late
+    Missed,
+}
+
+/// Task with the room it is on, as shown in queues
+#[derive(Debug, Clone, Serialize)]
+pub struct HousekeepingTaskView {
+    #[serde(flatten)]
+    pub task: HousekeepingTask,
+    pub room_number: String,
+    pub room_status: RoomStatus,
+    /// A guest is due to check in to the room today
+    pub arrival_today: bool,
+    /// None for cancelled tasks
+    pub sla_status: Option<SlaStatus>,
+}
+
+/// SLA figures of one task type
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SlaTypeSummary {
+    pub task_type: HousekeepingTaskType,
+    pub total: i64,
+    pub completed: i64,
+    pub met: i64,
+    pub missed: i64,
+    pub open: i64,
+    pub overdue: i64,
+    /// Percentage of completed tasks finished on time
+    pub compliance_rate: Option<f64>,
+    /// Average minutes from creation to completion
+    pub average_minutes_to_complete: Option<f64>,
+}
+
+/// Setting keys for housekeeping SLAs
+pub const HOUSEKEEPING_SLA_CHECKOUT_CLEAN_KEY: &str = "housekeeping_sla_checkout_clean_minutes";
+pub const HOUSEKEEPING_SLA_STAYOVER_KEY: &str = "housekeeping_sla_stayover_minutes";
+pub const HOUSEKEEPING_SLA_DEEP_CLEAN_KEY: &str = "housekeeping_sla_deep_clean_minutes";
+pub const HOUSEKEEPING_SLA_TURNDOWN_KEY: &str = "housekeeping_sla_turndown_minutes";
+pub const HOUSEKEEPING_AUTO_ASSIGN_KEY: &str = "housekeeping_auto_assign";
+pub const HOUSEKEEPING_INSPECTION_REQUIRED_KEY: &str = "housekeeping_inspection_required";
+/// Stored as a JSON array of strings
+pub const HOUSEKEEPING_INSPECTION_CHECKLIST_KEY: &str = "housekeeping_inspection_checklist";
+
+/// Minutes allowed to finish each task type, whether new cleans are
+/// assigned automatically and whether cleaned rooms are inspected, stored
+/// as individual `system_settings` rows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HousekeepingPolicy {
+    pub checkout_clean_sla_minutes: i64,
+    pub stayover_sla_minutes: i64,
+    pub deep_clean_sla_minutes: i64,
+    pub turndown_sla_minutes: i64,
+    /// Assign the clean a room gets when it turns Dirty to a cleaner
+    #[serde(default)]
+    pub auto_assign: bool,
+    /// Cleaned rooms wait in PendingInspection until a supervisor passes them
+    #[serde(default)]
+    pub inspection_required: bool,
+    /// What a supervisor checks
+    #[serde(default = "default_inspection_checklist")]
+    pub inspection_checklist: Vec<String>,
+}
+
+fn default_inspection_checklist() -> Vec<String> {
+    [
+        "Bed made with fresh linen",
+        "Bathroom cleaned",
+        "Floors vacuumed",
+        "Amenities restocked",
+        "Bins emptied",
+    ]
+    .iter()
+    .map(|item| item.to_string())
+    .collect()
+}
+
+impl Default for HousekeepingPolicy {
+    fn default() -> Self {
+        Self {
+            checkout_clean_sla_minutes: 90,
+            stayover_sla_minutes: 45,
+            deep_clean_sla_minutes: 240,
+            turndown_sla_minutes: 30,
+            auto_assign: false,
+            inspection_required: false,
+            inspection_checklist: default_inspection_checklist(),
+        }
+    }
+}
+
+impl HousekeepingPolicy {
+    /// Build the policy from setting rows, using defaults for missing or invalid values
+    pub fn from_settings(settings: &HashMap<String, String>) -> Self {
+        let defaults = Self::default();
+        let minutes = |key: &str, default: i64| {
+            settings
+                .get(key)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            checkout_clean_sla_minutes: minutes(
+                HOUSEKEEPING_SLA_CHECKOUT_CLEAN_KEY,
+                defaults.checkout_clean_sla_minutes,
+            ),
+            stayover_sla_minutes: minutes(HOUSEKEEPING_SLA_STAYOVER_KEY, defaults.stayover_sla_minutes),
+            deep_clean_sla_minutes: minutes(
+                HOUSEKEEPING_SLA_DEEP_CLEAN_KEY,
+                defaults.deep_clean_sla_minutes,
+            ),
+            turndown_sla_minutes: minutes(HOUSEKEEPING_SLA_TURNDOWN_KEY, defaults.turndown_sla_minutes),
+            auto_assign: settings
+                .get(HOUSEKEEPING_AUTO_ASSIGN_KEY)
+                .map(|v| v == "true")
+                .unwrap_or(defaults.auto_assign),
+            inspection_required: settings
+                .get(HOUSEKEEPING_INSPECTION_REQUIRED_KEY)
+                .map(|v| v == "true")
+                .unwrap_or(defaults.inspection_required),
+            inspection_checklist: settings
+                .get(HOUSEKEEPING_INSPECTION_CHECKLIST_KEY)
+                .and_then(|v| serde_json::from_str(v).ok())
+                .unwrap_or(defaults.inspection_checklist),
+        }
+    }
+
+    /// Setting rows representing this policy
+    pub fn to_settings(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (HOUSEKEEPING_SLA_CHECKOUT_CLEAN_KEY, self.checkout_clean_sla_minutes.to_string()),
+            (HOUSEKEEPING_SLA_STAYOVER_KEY, self.stayover_sla_minutes.to_string()),
+            (HOUSEKEEPING_SLA_DEEP_CLEAN_KEY, self.deep_clean_sla_minutes.to_string()),
+            (HOUSEKEEPING_SLA_TURNDOWN_KEY, self.turndown_sla_minutes.to_string()),
+            (HOUSEKEEPING_AUTO_ASSIGN_KEY, self.auto_assign.to_string()),
+            (HOUSEKEEPING_INSPECTION_REQUIRED_KEY, self.inspection_required.to_string()),
+            (
+                HOUSEKEEPING_INSPECTION_CHECKLIST_KEY,
+                serde_json::to_string(&self.inspection_checklist).unwrap_or_default(),
+            ),
+        ]
+    }
+
+    /// Minutes allowed for a task type
+    pub fn sla_minutes(&self, task_type: HousekeepingTaskType) -> i64 {
+        match task_type {
+            HousekeepingTaskType::CheckoutClean => self.checkout_clean_sla_minutes,
+            HousekeepingTaskType::Stayover => self.stayover_sla_minutes,
+            HousekeepingTaskType::DeepClean => self.deep_clean_sla_minutes,
+            HousekeepingTaskType::Turndown => self.turndown_sla_minutes,
+        }
+    }
+}
+
+/// A cleaner's open work, as auto-assignment sees it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CleanerWorkload {
+    pub cleaner_id: Uuid,
+    pub cleaner_name: Option<String>,
+    /// Estimated minutes of the open tasks already assigned
+    pub current_minutes: i64,
+    /// Estimated minutes once the plan is applied
+    pub planned_minutes: i64,
+    pub planned_tasks: i64,
+}
+
+/// A task auto-assignment would give to a cleaner
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProposedAssignment {
+    pub task_id: Uuid,
+    pub cleaner_id: Uuid,
+    pub room_id: Uuid,
+    pub room_number: String,
+    pub room_type: RoomType,
+    pub floor: Option<i32>,
+    pub task_type: HousekeepingTaskType,
+    pub estimated_minutes: i64,
+    pub cleaner_name: Option<String>,
+}
+
+/// Auto-assignment preview, to adjust and apply
+#[derive(Debug, Clone, Serialize)]
+pub struct AutoAssignPlan {
+    pub assignments: Vec<ProposedAssignment>,
+    pub cleaners: Vec<CleanerWorkload>,
+}
+
+/// A supervisor's check of a cleaned room
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = room_inspections)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RoomInspection {
+    pub id: Uuid,
+    pub room_id: Uuid,
+    /// Clean that was inspected
+    pub housekeeping_task_id: Option<Uuid>,
+    /// Who did the clean
+    pub cleaner_id: Option<Uuid>,
+    pub inspector_id: Uuid,
+    pub passed: bool,
+    /// Checklist at the time of the inspection
+    pub checklist: Vec<String>,
+    pub failed_items: Vec<String>,
+    pub notes: Option<String>,
+    /// Task created to redo the clean after a failure
+    pub rework_task_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New room inspection for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = room_inspections)]
+pub struct NewRoomInspection {
+    pub room_id: Uuid,
+    pub housekeeping_task_id: Option<Uuid>,
+    pub cleaner_id: Option<Uuid>,
+    pub inspector_id: Uuid,
+    pub passed: bool,
+    pub checklist: Vec<String>,
+    pub failed_items: Vec<String>,
+    pub notes: Option<String>,
+    pub rework_task_id: Option<Uuid>,
+}
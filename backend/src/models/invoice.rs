@@ -0,0 +1,52 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::invoices;
+
+/// Issued invoice (immutable once written)
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = invoices)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Invoice {
+    pub id: Uuid,
+    pub invoice_number: String,
+    pub booking_id: Uuid,
+    pub guest_name: String,
+    pub room_number: String,
+    pub check_in_date: NaiveDate,
+    pub check_out_date: NaiveDate,
+    pub net_total: BigDecimal,
+    pub tax_total: BigDecimal,
+    pub total_amount: BigDecimal,
+    pub total_paid: BigDecimal,
+    pub balance_due: BigDecimal,
+    #[serde(skip_serializing)]
+    pub html_object_key: String,
+    #[serde(skip_serializing)]
+    pub pdf_object_key: String,
+    pub issued_by_user_id: Uuid,
+    pub issued_at: DateTime<Utc>,
+}
+
+/// New invoice for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = invoices)]
+pub struct NewInvoice {
+    pub invoice_number: String,
+    pub booking_id: Uuid,
+    pub guest_name: String,
+    pub room_number: String,
+    pub check_in_date: NaiveDate,
+    pub check_out_date: NaiveDate,
+    pub net_total: BigDecimal,
+    pub tax_total: BigDecimal,
+    pub total_amount: BigDecimal,
+    pub total_paid: BigDecimal,
+    pub balance_due: BigDecimal,
+    pub html_object_key: String,
+    pub pdf_object_key: String,
+    pub issued_by_user_id: Uuid,
+}
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::job_runs;
+
+/// Record of a single background job execution
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = job_runs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct JobRun {
+    pub id: Uuid,
+    pub job_name: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub success: bool,
+    pub affected_count: i32,
+    /// Bookings or rooms the run changed or flagged
+    pub affected_ids: Vec<Uuid>,
+    pub summary: String,
+    pub error_message: Option<String>,
+}
+
+/// New job run for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = job_runs)]
+pub struct NewJobRun<'a> {
+    pub job_name: &'a str,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub success: bool,
+    pub affected_count: i32,
+    pub affected_ids: Vec<Uuid>,
+    pub summary: String,
+    pub error_message: Option<String>,
+}
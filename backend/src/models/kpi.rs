@@ -0,0 +1,134 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::RoomType;
+
+/// Length of the periods a KPI report is grouped by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KpiPeriod {
+    #[default]
+    Day,
+    /// Monday to Sunday
+    Week,
+    Month,
+}
+
+/// Hotel performance indicators for a date range
+///
+/// Revenue is net room revenue, spread over the nights stayed. Stay length,
+/// lead time and cancellations are for bookings arriving in the range.
+#[derive(Debug, Clone, Serialize)]
+pub struct Kpis {
+    pub available_room_nights: i64,
+    pub room_nights_sold: i64,
+    pub room_revenue: BigDecimal,
+    /// Percentage of available room nights sold
+    pub occupancy_rate: f64,
+    /// Average daily rate: revenue per room night sold
+    pub adr: Option<BigDecimal>,
+    /// Revenue per available room night
+    pub revpar: Option<BigDecimal>,
+    pub arrivals: i64,
+    /// Average length of stay in nights
+    pub average_length_of_stay: Option<f64>,
+    /// Average days between booking and arrival
+    pub average_lead_time_days: Option<f64>,
+    pub cancelled_count: i64,
+    /// Percentage of bookings for the range that were cancelled
+    pub cancellation_rate: f64,
+}
+
+/// KPIs of the rooms of one type
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomTypeKpis {
+    pub room_type: RoomType,
+    #[serde(flatten)]
+    pub kpis: Kpis,
+}
+
+/// KPIs of the bookings from one source (`creation_source`)
+///
+/// Occupancy and RevPAR are against the whole hotel, so they show each
+/// source's share.
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceKpis {
+    pub source: String,
+    #[serde(flatten)]
+    pub kpis: Kpis,
+}
+
+/// KPIs for the whole hotel with the breakdowns
+#[derive(Debug, Clone, Serialize)]
+pub struct KpiBreakdown {
+    #[serde(flatten)]
+    pub kpis: Kpis,
+    pub by_room_type: Vec<RoomTypeKpis>,
+    pub by_source: Vec<SourceKpis>,
+}
+
+/// KPIs of one period of a report
+#[derive(Debug, Clone, Serialize)]
+pub struct KpiPeriodReport {
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    #[serde(flatten)]
+    pub breakdown: KpiBreakdown,
+}
+
+/// KPI report for a date range, overall and per period
+#[derive(Debug, Clone, Serialize)]
+pub struct KpiReport {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub group_by: KpiPeriod,
+    pub currency: &'static str,
+    pub overall: KpiBreakdown,
+    pub periods: Vec<KpiPeriodReport>,
+}
+
+/// Room nights and net room revenue for a stay date or a range of them
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastFigures {
+    pub room_nights: i64,
+    pub revenue: BigDecimal,
+    /// Percentage of the room nights there are
+    pub occupancy_rate: f64,
+}
+
+/// One stay date of an on-the-books report
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastDay {
+    pub date: NaiveDate,
+    /// Same weekday last year
+    pub last_year_date: NaiveDate,
+    /// Committed by upcoming and in-house bookings
+    pub on_the_books: ForecastFigures,
+    /// What was stayed on the same date last year
+    pub last_year: ForecastFigures,
+    /// Of last year's stays, those booked with no more notice than is left now
+    pub last_year_pickup: ForecastFigures,
+    /// On the books plus last year's pickup
+    pub forecast: ForecastFigures,
+}
+
+/// Totals of an on-the-books report
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastTotals {
+    pub on_the_books: ForecastFigures,
+    pub last_year: ForecastFigures,
+    pub forecast: ForecastFigures,
+}
+
+/// Revenue and occupancy on the books for the coming days, against last year
+/// and with a pickup forecast
+#[derive(Debug, Clone, Serialize)]
+pub struct ForecastReport {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub room_count: i64,
+    pub currency: &'static str,
+    pub totals: ForecastTotals,
+    pub daily: Vec<ForecastDay>,
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::maintenance_orders;
+
+use super::RoomStatus;
+
+/// Maintenance work order status enum matching PostgreSQL maintenance_status type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::MaintenanceStatus"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum MaintenanceStatus {
+    /// Reported, work not started yet
+    Open,
+    /// Being worked on; the room is in maintenance
+    InProgress,
+    /// Fixed, with a resolution
+    Closed,
+    Cancelled,
+}
+
+impl MaintenanceStatus {
+    pub const OPEN: [MaintenanceStatus; 2] = [MaintenanceStatus::Open, MaintenanceStatus::InProgress];
+
+    /// Still blocking its dates
+    pub fn is_open(&self) -> bool {
+        Self::OPEN.contains(self)
+    }
+}
+
+/// Maintenance priority enum matching PostgreSQL maintenance_priority type
+/// (ordered lowest first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::MaintenancePriority"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum MaintenancePriority {
+    Low,
+    Normal,
+    High,
+    Urgent,
+}
+
+/// Work order taking a room out of order
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = maintenance_orders)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct MaintenanceOrder {
+    pub id: Uuid,
+    pub room_id: Uuid,
+    pub description: String,
+    pub status: MaintenanceStatus,
+    pub priority: MaintenancePriority,
+    /// First out-of-order date
+    pub start_date: NaiveDate,
+    /// Last out-of-order date (inclusive)
+    pub end_date: NaiveDate,
+    pub assigned_to_user_id: Option<Uuid>,
+    pub resolution: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub created_by_user_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MaintenanceOrder {
+    /// Whether the order blocks any night of a stay from `check_in_date` to
+    /// `check_out_date` (check-out day excluded)
+    pub fn blocks(&self, check_in_date: NaiveDate, check_out_date: NaiveDate) -> bool {
+        self.status.is_open() && self.start_date < check_out_date && self.end_date >= check_in_date
+    }
+}
+
+/// New maintenance order for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = maintenance_orders)]
+pub struct NewMaintenanceOrder {
+    pub room_id: Uuid,
+    pub description: String,
+    pub priority: MaintenancePriority,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub assigned_to_user_id: Option<Uuid>,
+    pub created_by_user_id: Option<Uuid>,
+}
+
+/// Maintenance order update changeset
+#[derive(Debug, AsChangeset, Default)]
+#[diesel(table_name = maintenance_orders)]
+pub struct UpdateMaintenanceOrder {
+    pub description: Option<String>,
+    pub priority: Option<MaintenancePriority>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub assigned_to_user_id: Option<Option<Uuid>>,
+}
+
+/// Work order with the room it is on
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceOrderView {
+    #[serde(flatten)]
+    pub order: MaintenanceOrder,
+    pub room_number: String,
+    pub room_status: RoomStatus,
+}
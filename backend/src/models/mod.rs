@@ -1,17 +1,50 @@
 pub mod booking;
+pub mod booking_group;
+pub mod booking_room_stay;
+pub mod booking_stay_change;
+pub mod cancellation_policy;
+pub mod cashier_shift;
+pub mod exchange_rate;
+pub mod folio;
+pub mod guest_credit;
 pub mod guest_note;
+pub mod housekeeping;
+pub mod invoice;
+pub mod job_run;
+pub mod kpi;
+pub mod maintenance;
 pub mod payment;
+pub mod payment_intent;
+pub mod rate_plan;
 pub mod room;
+pub mod tax_rule;
 pub mod user;
 pub mod inventory;
 pub mod message;
 pub mod setting;
 
 pub use booking::*;
+pub use booking_group::*;
+pub use booking_room_stay::*;
+pub use booking_stay_change::*;
+pub use cancellation_policy::*;
+pub use cashier_shift::*;
+pub use exchange_rate::*;
+pub use folio::*;
+pub use guest_credit::*;
 pub use guest_note::*;
+pub use housekeeping::*;
+pub use invoice::*;
+pub use job_run::*;
+pub use kpi::*;
+pub use maintenance::*;
 pub use payment::*;
+pub use payment_intent::*;
+pub use rate_plan::*;
 pub use room::*;
+pub use tax_rule::*;
 pub use user::*;
 pub use inventory::*;
+#[allow(unused_imports)]
 pub use setting::*;
 
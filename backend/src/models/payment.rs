@@ -5,9 +5,9 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use bigdecimal::BigDecimal;
-use crate::schema::payments;
+use crate::schema::{payment_ledger_entries, payments};
 
-use super::Booking;
+use super::{Booking, TaxLine};
 
 /// Payment type enum matching PostgreSQL payment_type type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
@@ -36,6 +36,27 @@ pub struct Payment {
     pub created_by_user_id: Uuid,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set when the payment was voided; voided payments no longer count as paid
+    pub voided_at: Option<DateTime<Utc>>,
+    pub voided_by_user_id: Option<Uuid>,
+    pub void_reason: Option<String>,
+    /// Payment this one replaced through an adjustment
+    pub replaces_payment_id: Option<Uuid>,
+    /// Cashier shift open for the recording user when the payment was taken
+    pub shift_id: Option<Uuid>,
+    /// Currency the payment was taken in; `amount` is always in the base currency
+    pub currency: String,
+    /// Base currency units for one unit of `currency` at the time of payment
+    pub exchange_rate: BigDecimal,
+    /// Amount in `currency`; only set for foreign currency payments
+    pub original_amount: Option<BigDecimal>,
+}
+
+impl Payment {
+    /// Amount in the currency the payment was taken in
+    pub fn amount_in_currency(&self) -> &BigDecimal {
+        self.original_amount.as_ref().unwrap_or(&self.amount)
+    }
 }
 
 /// New payment for insertion
@@ -48,11 +69,14 @@ pub struct NewPayment {
     pub payment_method: String,
     pub notes: Option<String>,
     pub created_by_user_id: Uuid,
+    pub currency: String,
+    pub exchange_rate: BigDecimal,
+    pub original_amount: Option<BigDecimal>,
 }
 
-/// Payment update changeset
-#[derive(Debug, AsChangeset, Default)]
-#[diesel(table_name = payments)]
+/// Corrected values for a payment adjustment (unset fields are kept;
+/// `amount` is in the payment's own currency)
+#[derive(Debug, Default)]
 pub struct UpdatePayment {
     pub amount: Option<BigDecimal>,
     pub payment_type: Option<PaymentType>,
@@ -60,14 +84,108 @@ pub struct UpdatePayment {
     pub notes: Option<Option<String>>, // Option<Option> to allow setting to NULL
 }
 
+/// Kind of change recorded in the payment ledger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::PaymentLedgerAction"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum PaymentLedgerAction {
+    Created,
+    Voided,
+    Adjusted,
+}
+
+/// Append-only record of a payment change
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = payment_ledger_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PaymentLedgerEntry {
+    pub id: Uuid,
+    /// Payment created or voided; for adjustments, the replacement payment
+    pub payment_id: Uuid,
+    pub booking_id: Uuid,
+    pub action: PaymentLedgerAction,
+    /// Change to the booking's paid total
+    pub amount_delta: BigDecimal,
+    pub amount: BigDecimal,
+    pub payment_type: PaymentType,
+    pub payment_method: String,
+    /// Payment an adjustment replaced
+    pub previous_payment_id: Option<Uuid>,
+    pub reason: Option<String>,
+    pub actor_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+/// New ledger entry for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = payment_ledger_entries)]
+pub struct NewPaymentLedgerEntry {
+    pub payment_id: Uuid,
+    pub booking_id: Uuid,
+    pub action: PaymentLedgerAction,
+    pub amount_delta: BigDecimal,
+    pub amount: BigDecimal,
+    pub payment_type: PaymentType,
+    pub payment_method: String,
+    pub previous_payment_id: Option<Uuid>,
+    pub reason: Option<String>,
+    pub actor_user_id: Uuid,
+}
+
 /// Payment summary for a booking
 #[derive(Debug, Clone, Serialize)]
 pub struct PaymentSummary {
     pub booking_id: Uuid,
+    /// Room price of the stay
+    pub room_price: BigDecimal,
+    /// Tax on the room price (inclusive and exclusive)
+    pub room_tax: BigDecimal,
+    /// Folio charges (minibar, laundry, ...) including tax
+    pub charges_total: BigDecimal,
+    /// Amount due before tax
+    pub net_total: BigDecimal,
+    /// All tax and service charges on the bill
+    pub tax_total: BigDecimal,
+    /// Tax lines of the bill, one per rule and rate
+    pub taxes: Vec<TaxLine>,
+    /// Gross amount due: room price with exclusive taxes plus folio charges
+    pub total_price: BigDecimal,
+    pub total_paid: BigDecimal,
+    pub remaining_balance: BigDecimal,
+    /// Paid beyond the amount due; can be moved to the guest's credit
+    pub overpaid_amount: BigDecimal,
+    pub payment_count: i64,
+    /// Base currency all amounts above are in
+    pub currency: String,
+    /// What was paid in each currency, converted at the rates used
+    pub paid_by_currency: Vec<CurrencyTotal>,
+}
+
+/// Payments taken in one currency
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CurrencyTotal {
+    pub currency: String,
+    pub payment_count: i64,
+    /// Total in `currency`
+    pub amount: BigDecimal,
+    /// Total in the base currency
+    pub base_amount: BigDecimal,
+}
+
+/// Combined payment summary across the room bookings of a group
+#[derive(Debug, Clone, Serialize)]
+pub struct GroupPaymentSummary {
+    pub group_id: Uuid,
+    pub net_total: BigDecimal,
+    pub tax_total: BigDecimal,
     pub total_price: BigDecimal,
     pub total_paid: BigDecimal,
     pub remaining_balance: BigDecimal,
     pub payment_count: i64,
+    /// Base currency all amounts above are in
+    pub currency: String,
+    pub bookings: Vec<PaymentSummary>,
 }
 
 /// Payment with booking details for API responses
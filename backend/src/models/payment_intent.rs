@@ -0,0 +1,79 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, Utc};
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::schema::{payment_intents, payment_webhook_events};
+
+use super::PaymentType;
+
+/// Payment intent status enum matching PostgreSQL payment_intent_status type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[ExistingTypePath = "crate::schema::sql_types::PaymentIntentStatus"]
+#[serde(rename_all = "snake_case")]
+#[DbValueStyle = "snake_case"]
+pub enum PaymentIntentStatus {
+    Pending,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+/// Online payment requested from a payment gateway
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = payment_intents)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PaymentIntent {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub provider: String,
+    pub provider_reference: Option<String>,
+    pub amount: BigDecimal,
+    pub payment_type: PaymentType,
+    pub status: PaymentIntentStatus,
+    /// Where the guest completes the payment
+    pub checkout_url: Option<String>,
+    /// Payment recorded once the gateway confirmed the intent
+    pub payment_id: Option<Uuid>,
+    pub failure_reason: Option<String>,
+    pub created_by_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New payment intent for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = payment_intents)]
+pub struct NewPaymentIntent {
+    pub booking_id: Uuid,
+    pub provider: String,
+    pub amount: BigDecimal,
+    pub payment_type: PaymentType,
+    pub created_by_user_id: Uuid,
+}
+
+/// Webhook event accepted from a payment gateway
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = payment_webhook_events)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PaymentWebhookEvent {
+    pub id: Uuid,
+    pub provider: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payment_intent_id: Option<Uuid>,
+    pub payload: String,
+    pub received_at: DateTime<Utc>,
+}
+
+/// New webhook event for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = payment_webhook_events)]
+pub struct NewPaymentWebhookEvent {
+    pub provider: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub payload: String,
+}
@@ -0,0 +1,104 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::{rate_overrides, rate_plans};
+
+use super::RoomType;
+
+/// Rate plan defining nightly prices for a room type or a specific room
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = rate_plans)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RatePlan {
+    pub id: Uuid,
+    pub name: String,
+    /// Room type the plan applies to (exclusive with `room_id`)
+    pub room_type: Option<RoomType>,
+    /// Specific room the plan applies to (exclusive with `room_type`)
+    pub room_id: Option<Uuid>,
+    /// Nightly rate for Sunday-Thursday nights
+    pub base_rate: BigDecimal,
+    /// Nightly rate for Friday and Saturday nights (falls back to `base_rate`)
+    pub weekend_rate: Option<BigDecimal>,
+    /// Seasonal window start (inclusive), open-ended when None
+    pub start_date: Option<NaiveDate>,
+    /// Seasonal window end (inclusive), open-ended when None
+    pub end_date: Option<NaiveDate>,
+    pub min_stay_nights: i32,
+    /// Higher priority wins when several plans cover the same night
+    pub priority: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Cancellation policy for bookings priced by this plan
+    pub cancellation_policy_id: Option<Uuid>,
+}
+
+/// New rate plan for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = rate_plans)]
+pub struct NewRatePlan {
+    pub name: String,
+    pub room_type: Option<RoomType>,
+    pub room_id: Option<Uuid>,
+    pub base_rate: BigDecimal,
+    pub weekend_rate: Option<BigDecimal>,
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub min_stay_nights: i32,
+    pub priority: i32,
+    pub is_active: bool,
+    pub cancellation_policy_id: Option<Uuid>,
+}
+
+/// Rate plan update changeset
+#[derive(Debug, AsChangeset, Default)]
+#[diesel(table_name = rate_plans)]
+pub struct UpdateRatePlan {
+    pub name: Option<String>,
+    pub base_rate: Option<BigDecimal>,
+    pub weekend_rate: Option<Option<BigDecimal>>,
+    pub start_date: Option<Option<NaiveDate>>,
+    pub end_date: Option<Option<NaiveDate>>,
+    pub min_stay_nights: Option<i32>,
+    pub priority: Option<i32>,
+    pub is_active: Option<bool>,
+    pub cancellation_policy_id: Option<Option<Uuid>>,
+}
+
+/// Price override for a single night of a rate plan
+#[derive(Debug, Clone, Queryable, Identifiable, Associations, Selectable, Serialize)]
+#[diesel(table_name = rate_overrides)]
+#[diesel(belongs_to(RatePlan, foreign_key = rate_plan_id))]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct RateOverride {
+    pub id: Uuid,
+    pub rate_plan_id: Uuid,
+    pub stay_date: NaiveDate,
+    pub price: BigDecimal,
+    /// Minimum stay for arrivals spanning this night (falls back to the plan's rule)
+    pub min_stay_nights: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New rate override for insertion (upserted on plan + date)
+#[derive(Debug, Insertable)]
+#[diesel(table_name = rate_overrides)]
+pub struct NewRateOverride {
+    pub rate_plan_id: Uuid,
+    pub stay_date: NaiveDate,
+    pub price: BigDecimal,
+    pub min_stay_nights: Option<i32>,
+}
+
+/// Rate plan with its date overrides for API responses
+#[derive(Debug, Clone, Serialize)]
+pub struct RatePlanWithOverrides {
+    #[serde(flatten)]
+    pub rate_plan: RatePlan,
+    pub overrides: Vec<RateOverride>,
+}
@@ -9,7 +9,7 @@ use crate::schema::rooms;
 use crate::models::UserRole;
 
 /// Room type enum matching PostgreSQL room_type type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, DbEnum)]
 #[ExistingTypePath = "crate::schema::sql_types::RoomType"]
 #[serde(rename_all = "snake_case")]
 #[DbValueStyle = "snake_case"]
@@ -19,6 +19,17 @@ pub enum RoomType {
     Suite,
 }
 
+impl RoomType {
+    /// Guests a room of this type sleeps unless configured otherwise
+    pub fn default_max_occupancy(&self) -> i32 {
+        match self {
+            RoomType::Single => 2,
+            RoomType::Double => 4,
+            RoomType::Suite => 6,
+        }
+    }
+}
+
 /// Room status enum matching PostgreSQL room_status type
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, DbEnum)]
 #[ExistingTypePath = "crate::schema::sql_types::RoomStatus"]
@@ -30,6 +41,8 @@ pub enum RoomStatus {
     Maintenance,
     Dirty,
     Cleaning,
+    /// Cleaned, waiting for a supervisor's inspection
+    PendingInspection,
 }
 
 /// Room model representing a hotel room
@@ -45,6 +58,26 @@ pub struct Room {
     pub updated_at: DateTime<Utc>,
     pub price: BigDecimal,
     pub assigned_cleaner_id: Option<Uuid>,
+    /// When the status last changed (maintained by a database trigger)
+    pub status_changed_at: DateTime<Utc>,
+    pub floor: Option<i32>,
+    /// Most guests the room sleeps
+    pub max_occupancy: i32,
+    /// Beds in the room, e.g. "1 king" or "2 twin"
+    pub bed_configuration: Option<String>,
+    /// What the room looks out on, e.g. "city" or "sea"
+    pub view: Option<String>,
+    /// Lowercase amenity names, e.g. "balcony", "bathtub"
+    pub amenities: Vec<String>,
+    pub smoking: bool,
+    pub wheelchair_accessible: bool,
+}
+
+impl Room {
+    /// Whether the room sleeps `guest_count` guests
+    pub fn fits(&self, guest_count: i32) -> bool {
+        guest_count <= self.max_occupancy
+    }
 }
 
 /// New room for insertion
@@ -54,6 +87,13 @@ pub struct NewRoom<'a> {
     pub number: &'a str,
     pub room_type: RoomType,
     pub price: BigDecimal,
+    pub floor: Option<i32>,
+    pub max_occupancy: i32,
+    pub bed_configuration: Option<String>,
+    pub view: Option<String>,
+    pub amenities: Vec<String>,
+    pub smoking: bool,
+    pub wheelchair_accessible: bool,
 }
 
 /// Room update changeset
@@ -64,6 +104,13 @@ pub struct UpdateRoom {
     pub status: Option<RoomStatus>,
     pub price: Option<BigDecimal>,
     pub assigned_cleaner_id: Option<Option<Uuid>>,
+    pub floor: Option<Option<i32>>,
+    pub max_occupancy: Option<i32>,
+    pub bed_configuration: Option<Option<String>>,
+    pub view: Option<Option<String>>,
+    pub amenities: Option<Vec<String>>,
+    pub smoking: Option<bool>,
+    pub wheelchair_accessible: Option<bool>,
 }
 
 impl RoomStatus {
@@ -86,6 +133,11 @@ impl RoomStatus {
             (RoomStatus::Cleaning, RoomStatus::Available) => true, // Cleaner finishes work
             (RoomStatus::Cleaning, RoomStatus::Dirty) => true, // Rework needed
             (RoomStatus::Cleaning, RoomStatus::Occupied) => true, // Check-in while cleaning (staff override)
+            // Inspection workflow transitions (when inspections are enabled)
+            (RoomStatus::Cleaning, RoomStatus::PendingInspection) => true, // Cleaner finishes work
+            (RoomStatus::PendingInspection, RoomStatus::Available) => true, // Inspection passed
+            (RoomStatus::PendingInspection, RoomStatus::Dirty) => true, // Inspection failed
+            (RoomStatus::PendingInspection, RoomStatus::Occupied) => true, // Check-in before inspection (staff override)
             // Same status is always valid (no-op)
             (a, b) if *a == b => true,
             // All other transitions are invalid
@@ -97,9 +149,11 @@ impl RoomStatus {
     /// Returns true if the role can set this status, false otherwise
     pub fn is_allowed_for_role(&self, role: UserRole) -> bool {
         match (self, role) {
-            // Cleaners cannot set rooms to Occupied or Maintenance
+            // Cleaners cannot set rooms to Occupied, Maintenance or PendingInspection
             (RoomStatus::Occupied, UserRole::Cleaner) => false,
             (RoomStatus::Maintenance, UserRole::Cleaner) => false,
+            // Finishing a clean decides whether the room waits for inspection
+            (RoomStatus::PendingInspection, UserRole::Cleaner) => false,
             // All other statuses are allowed for cleaners
             _ => true,
         }
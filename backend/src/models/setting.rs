@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use bigdecimal::{BigDecimal, Zero};
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -17,4 +21,113 @@ pub struct SystemSetting {
 #[diesel(table_name = system_settings)]
 pub struct UpdateSystemSetting {
     pub value: String,
-}
\ No newline at end of file
+}
+/// Setting keys for the no-show policy
+pub const NO_SHOW_GRACE_DAYS_KEY: &str = "no_show_grace_days";
+pub const NO_SHOW_FEE_ENABLED_KEY: &str = "no_show_fee_enabled";
+pub const NO_SHOW_FEE_TYPE_KEY: &str = "no_show_fee_type";
+pub const NO_SHOW_FEE_AMOUNT_KEY: &str = "no_show_fee_amount";
+
+/// How the no-show fee is calculated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NoShowFeeType {
+    /// Average nightly rate of the booking
+    FirstNight,
+    /// The whole booking price
+    FullStay,
+    /// `fee_amount` regardless of the booking
+    Fixed,
+}
+
+impl NoShowFeeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NoShowFeeType::FirstNight => "first_night",
+            NoShowFeeType::FullStay => "full_stay",
+            NoShowFeeType::Fixed => "fixed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "first_night" => Some(NoShowFeeType::FirstNight),
+            "full_stay" => Some(NoShowFeeType::FullStay),
+            "fixed" => Some(NoShowFeeType::Fixed),
+            _ => None,
+        }
+    }
+}
+
+/// Hotel no-show policy, stored as individual `system_settings` rows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NoShowPolicy {
+    /// Days after the check-in date before an unarrived booking is a no-show
+    pub grace_days: i64,
+    /// Charge a no-show fee, left owed on the booking, when it is marked no-show
+    pub fee_enabled: bool,
+    pub fee_type: NoShowFeeType,
+    /// Fee charged when `fee_type` is `fixed`
+    pub fee_amount: BigDecimal,
+}
+
+impl Default for NoShowPolicy {
+    fn default() -> Self {
+        Self {
+            grace_days: 1,
+            fee_enabled: false,
+            fee_type: NoShowFeeType::FirstNight,
+            fee_amount: BigDecimal::zero(),
+        }
+    }
+}
+
+impl NoShowPolicy {
+    /// Build the policy from setting rows, using defaults for missing or invalid values
+    pub fn from_settings(settings: &HashMap<String, String>) -> Self {
+        let defaults = Self::default();
+        Self {
+            grace_days: settings
+                .get(NO_SHOW_GRACE_DAYS_KEY)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(defaults.grace_days),
+            fee_enabled: settings
+                .get(NO_SHOW_FEE_ENABLED_KEY)
+                .map(|v| v == "true")
+                .unwrap_or(defaults.fee_enabled),
+            fee_type: settings
+                .get(NO_SHOW_FEE_TYPE_KEY)
+                .and_then(|v| NoShowFeeType::parse(v))
+                .unwrap_or(defaults.fee_type),
+            fee_amount: settings
+                .get(NO_SHOW_FEE_AMOUNT_KEY)
+                .and_then(|v| BigDecimal::from_str(v.trim()).ok())
+                .unwrap_or(defaults.fee_amount),
+        }
+    }
+
+    /// Setting rows representing this policy
+    pub fn to_settings(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (NO_SHOW_GRACE_DAYS_KEY, self.grace_days.to_string()),
+            (NO_SHOW_FEE_ENABLED_KEY, self.fee_enabled.to_string()),
+            (NO_SHOW_FEE_TYPE_KEY, self.fee_type.as_str().to_string()),
+            (NO_SHOW_FEE_AMOUNT_KEY, self.fee_amount.to_string()),
+        ]
+    }
+
+    /// Fee owed for a no-show booking (zero when fees are disabled)
+    pub fn fee_for(&self, booking_price: &BigDecimal, nights: i64) -> BigDecimal {
+        if !self.fee_enabled {
+            return BigDecimal::zero();
+        }
+
+        match self.fee_type {
+            NoShowFeeType::FirstNight => {
+                (booking_price / BigDecimal::from(nights.max(1))).round(2)
+            }
+            NoShowFeeType::FullStay => booking_price.clone(),
+            NoShowFeeType::Fixed => self.fee_amount.clone(),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::schema::{folio_charge_taxes, tax_rules};
+
+use super::ChargeCategory;
+
+/// Tax or service-charge rule (e.g. VAT 10%, service charge 5%)
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = tax_rules)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct TaxRule {
+    pub id: Uuid,
+    pub name: String,
+    /// Percent of the net amount
+    pub rate: BigDecimal,
+    /// Already included in the price (true) or added on top (false)
+    pub is_inclusive: bool,
+    pub applies_to_room: bool,
+    /// Folio charge categories the rule applies to
+    pub charge_categories: Vec<ChargeCategory>,
+    pub effective_from: Option<NaiveDate>,
+    pub effective_to: Option<NaiveDate>,
+    pub sort_order: i32,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// New tax rule for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = tax_rules)]
+pub struct NewTaxRule {
+    pub name: String,
+    pub rate: BigDecimal,
+    pub is_inclusive: bool,
+    pub applies_to_room: bool,
+    pub charge_categories: Vec<ChargeCategory>,
+    pub effective_from: Option<NaiveDate>,
+    pub effective_to: Option<NaiveDate>,
+    pub sort_order: i32,
+    pub is_active: bool,
+}
+
+/// Tax rule update changeset
+#[derive(Debug, AsChangeset, Default)]
+#[diesel(table_name = tax_rules)]
+pub struct UpdateTaxRule {
+    pub name: Option<String>,
+    pub rate: Option<BigDecimal>,
+    pub is_inclusive: Option<bool>,
+    pub applies_to_room: Option<bool>,
+    pub charge_categories: Option<Vec<ChargeCategory>>,
+    pub effective_from: Option<Option<NaiveDate>>,
+    pub effective_to: Option<Option<NaiveDate>>,
+    pub sort_order: Option<i32>,
+    pub is_active: Option<bool>,
+}
+
+/// One tax line of a bill
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TaxLine {
+    /// Rule that produced the line (None for a manual rate)
+    pub tax_rule_id: Option<Uuid>,
+    pub name: String,
+    pub rate: BigDecimal,
+    pub is_inclusive: bool,
+    pub amount: BigDecimal,
+}
+
+/// Net, tax and gross split of an amount
+#[derive(Debug, Clone, Serialize)]
+pub struct TaxBreakdown {
+    pub net: BigDecimal,
+    pub tax: BigDecimal,
+    pub gross: BigDecimal,
+    pub lines: Vec<TaxLine>,
+}
+
+/// Tax line stored with a folio charge
+#[derive(Debug, Clone, Queryable, Identifiable, Selectable, Serialize)]
+#[diesel(table_name = folio_charge_taxes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct FolioChargeTax {
+    pub id: Uuid,
+    pub folio_charge_id: Uuid,
+    pub tax_rule_id: Option<Uuid>,
+    pub name: String,
+    pub rate: BigDecimal,
+    pub is_inclusive: bool,
+    pub amount: BigDecimal,
+}
+
+/// New folio charge tax line for insertion
+#[derive(Debug, Insertable)]
+#[diesel(table_name = folio_charge_taxes)]
+pub struct NewFolioChargeTax {
+    pub folio_charge_id: Uuid,
+    pub tax_rule_id: Option<Uuid>,
+    pub name: String,
+    pub rate: BigDecimal,
+    pub is_inclusive: bool,
+    pub amount: BigDecimal,
+}
+
+impl From<FolioChargeTax> for TaxLine {
+    fn from(tax: FolioChargeTax) -> Self {
+        Self {
+            tax_rule_id: tax.tax_rule_id,
+            name: tax.name,
+            rate: tax.rate,
+            is_inclusive: tax.is_inclusive,
+            amount: tax.amount,
+        }
+    }
+}
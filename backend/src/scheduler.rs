@@ -0,0 +1,77 @@
+//! In-process scheduler for booking and room lifecycle jobs
+//!
+//! Each enabled job runs on its own tokio interval. The job body is blocking
+//! Diesel code, so it is moved onto the blocking thread pool, and every run is
+//! recorded in `job_runs` by `JobService::execute`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::time::MissedTickBehavior;
+
+use crate::config::{JobSchedule, JobsConfig};
+use crate::db::DbPool;
+use crate::errors::AppResult;
+use crate::services::job_service::{
+    JobOutcome, JobService, DIRTY_ROOM_SWEEP_JOB, NO_SHOW_JOB, OVERSTAY_JOB,
+};
+
+/// Start all enabled lifecycle jobs in the background
+pub fn start(pool: DbPool, config: JobsConfig) {
+    if !config.enabled {
+        tracing::info!("Background jobs disabled (JOBS_ENABLED=false)");
+        return;
+    }
+
+    spawn_job(pool.clone(), OVERSTAY_JOB, config.overstay, |service| {
+        service.run_overstay_detection()
+    });
+
+    let no_show = config.no_show;
+    spawn_job(pool.clone(), NO_SHOW_JOB, no_show.schedule, move |service| {
+        service.run_no_show(&no_show)
+    });
+
+    let dirty_room_sweep = config.dirty_room_sweep;
+    spawn_job(pool, DIRTY_ROOM_SWEEP_JOB, dirty_room_sweep.schedule, move |service| {
+        service.run_dirty_room_sweep(&dirty_room_sweep)
+    });
+}
+
+/// Run `job` every `schedule.interval_secs`, starting immediately
+fn spawn_job<F>(pool: DbPool, job_name: &'static str, schedule: JobSchedule, job: F)
+where
+    F: Fn(&JobService) -> AppResult<JobOutcome> + Send + Sync + 'static,
+{
+    if !schedule.enabled {
+        tracing::info!("Job '{}' disabled", job_name);
+        return;
+    }
+
+    let interval_secs = schedule.interval_secs.max(1);
+    tracing::info!("Scheduling job '{}' every {}s", job_name, interval_secs);
+
+    let job = Arc::new(job);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+        // A slow run should not be followed by a burst of catch-up runs
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+
+            let service = JobService::new(pool.clone());
+            let job = Arc::clone(&job);
+            let run = tokio::task::spawn_blocking(move || service.execute(job_name, |s| job(s))).await;
+
+            match run {
+                Ok(Ok(record)) if record.success => {
+                    tracing::info!("Job '{}': {}", job_name, record.summary)
+                }
+                Ok(Ok(_)) => {}
+                Ok(Err(e)) => tracing::error!("Job '{}' could not be recorded: {}", job_name, e),
+                Err(e) => tracing::error!("Job '{}' panicked: {}", job_name, e),
+            }
+        }
+    });
+}
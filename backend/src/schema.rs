@@ -24,6 +24,50 @@ pub mod sql_types {
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "inventory_status"))]
     pub struct InventoryStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "cancellation_penalty_type"))]
+    pub struct CancellationPenaltyType;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "stay_change_type"))]
+    pub struct StayChangeType;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "charge_category"))]
+    pub struct ChargeCategory;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "payment_intent_status"))]
+    pub struct PaymentIntentStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "payment_ledger_action"))]
+    pub struct PaymentLedgerAction;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "cashier_shift_status"))]
+    pub struct CashierShiftStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "housekeeping_task_type"))]
+    pub struct HousekeepingTaskType;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "housekeeping_task_status"))]
+    pub struct HousekeepingTaskStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "housekeeping_priority"))]
+    pub struct HousekeepingPriority;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "maintenance_status"))]
+    pub struct MaintenanceStatus;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "maintenance_priority"))]
+    pub struct MaintenancePriority;
 }
 
 diesel::table! {
@@ -46,6 +90,177 @@ diesel::table! {
         #[max_length = 10]
         creation_source -> Varchar,
         price -> Numeric,
+        rate_plan_id -> Nullable<Uuid>,
+        group_id -> Nullable<Uuid>,
+        original_price -> Nullable<Numeric>,
+        guest_count -> Int4,
+        status_changed_at -> Timestamptz,
+        unpaid_check_out_by_user_id -> Nullable<Uuid>,
+        unpaid_check_out_balance -> Nullable<Numeric>,
+        price_before_no_show -> Nullable<Numeric>,
+        no_show_undone_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    booking_room_stays (id) {
+        id -> Uuid,
+        booking_id -> Uuid,
+        room_id -> Uuid,
+        start_date -> Date,
+        end_date -> Date,
+        reason -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::StayChangeType;
+
+    booking_stay_changes (id) {
+        id -> Uuid,
+        booking_id -> Uuid,
+        change_type -> StayChangeType,
+        old_check_out_date -> Date,
+        new_check_out_date -> Date,
+        old_price -> Numeric,
+        new_price -> Numeric,
+        reason -> Nullable<Text>,
+        changed_by_user_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ChargeCategory;
+
+    folio_charges (id) {
+        id -> Uuid,
+        booking_id -> Uuid,
+        category -> ChargeCategory,
+        #[max_length = 200]
+        description -> Varchar,
+        quantity -> Int4,
+        unit_price -> Numeric,
+        tax_rate -> Numeric,
+        tax_amount -> Numeric,
+        total_amount -> Numeric,
+        created_by_user_id -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        voided_at -> Nullable<Timestamptz>,
+        voided_by_user_id -> Nullable<Uuid>,
+        void_reason -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    folio_charge_taxes (id) {
+        id -> Uuid,
+        folio_charge_id -> Uuid,
+        tax_rule_id -> Nullable<Uuid>,
+        #[max_length = 100]
+        name -> Varchar,
+        rate -> Numeric,
+        is_inclusive -> Bool,
+        amount -> Numeric,
+    }
+}
+
+diesel::table! {
+    booking_groups (id) {
+        id -> Uuid,
+        #[max_length = 20]
+        reference -> Varchar,
+        #[max_length = 100]
+        name -> Varchar,
+        #[max_length = 100]
+        contact_name -> Varchar,
+        notes -> Nullable<Text>,
+        created_by_user_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::CancellationPenaltyType;
+    use super::sql_types::RoomType;
+
+    cancellation_policies (id) {
+        id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
+        free_cancellation_days -> Int4,
+        penalty_type -> CancellationPenaltyType,
+        penalty_value -> Numeric,
+        room_type -> Nullable<RoomType>,
+        is_active -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    guest_credit_entries (id) {
+        id -> Uuid,
+        guest_user_id -> Uuid,
+        amount -> Numeric,
+        booking_id -> Uuid,
+        payment_id -> Uuid,
+        notes -> Nullable<Text>,
+        created_by_user_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::HousekeepingTaskType;
+    use super::sql_types::HousekeepingTaskStatus;
+    use super::sql_types::HousekeepingPriority;
+
+    housekeeping_tasks (id) {
+        id -> Uuid,
+        room_id -> Uuid,
+        booking_id -> Nullable<Uuid>,
+        task_type -> HousekeepingTaskType,
+        status -> HousekeepingTaskStatus,
+        priority -> HousekeepingPriority,
+        assigned_cleaner_id -> Nullable<Uuid>,
+        notes -> Nullable<Text>,
+        due_at -> Timestamptz,
+        started_at -> Nullable<Timestamptz>,
+        completed_at -> Nullable<Timestamptz>,
+        created_by_user_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::MaintenanceStatus;
+    use super::sql_types::MaintenancePriority;
+
+    maintenance_orders (id) {
+        id -> Uuid,
+        room_id -> Uuid,
+        description -> Text,
+        status -> MaintenanceStatus,
+        priority -> MaintenancePriority,
+        start_date -> Date,
+        end_date -> Date,
+        assigned_to_user_id -> Nullable<Uuid>,
+        resolution -> Nullable<Text>,
+        started_at -> Nullable<Timestamptz>,
+        closed_at -> Nullable<Timestamptz>,
+        created_by_user_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -75,6 +290,32 @@ diesel::table! {
         updated_at -> Timestamptz,
         price -> Numeric,
         assigned_cleaner_id -> Nullable<Uuid>,
+        status_changed_at -> Timestamptz,
+        floor -> Nullable<Int4>,
+        max_occupancy -> Int4,
+        #[max_length = 50]
+        bed_configuration -> Nullable<Varchar>,
+        #[max_length = 30]
+        view -> Nullable<Varchar>,
+        amenities -> Array<Text>,
+        smoking -> Bool,
+        wheelchair_accessible -> Bool,
+    }
+}
+
+diesel::table! {
+    room_inspections (id) {
+        id -> Uuid,
+        room_id -> Uuid,
+        housekeeping_task_id -> Nullable<Uuid>,
+        cleaner_id -> Nullable<Uuid>,
+        inspector_id -> Uuid,
+        passed -> Bool,
+        checklist -> Array<Text>,
+        failed_items -> Array<Text>,
+        notes -> Nullable<Text>,
+        rework_task_id -> Nullable<Uuid>,
+        created_at -> Timestamptz,
     }
 }
 
@@ -103,6 +344,67 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::PaymentType;
+    use super::sql_types::PaymentIntentStatus;
+
+    payment_intents (id) {
+        id -> Uuid,
+        booking_id -> Uuid,
+        #[max_length = 30]
+        provider -> Varchar,
+        #[max_length = 100]
+        provider_reference -> Nullable<Varchar>,
+        amount -> Numeric,
+        payment_type -> PaymentType,
+        status -> PaymentIntentStatus,
+        checkout_url -> Nullable<Text>,
+        payment_id -> Nullable<Uuid>,
+        failure_reason -> Nullable<Text>,
+        created_by_user_id -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    payment_webhook_events (id) {
+        id -> Uuid,
+        #[max_length = 30]
+        provider -> Varchar,
+        #[max_length = 100]
+        event_id -> Varchar,
+        #[max_length = 50]
+        event_type -> Varchar,
+        payment_intent_id -> Nullable<Uuid>,
+        payload -> Text,
+        received_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::PaymentLedgerAction;
+    use super::sql_types::PaymentType;
+
+    payment_ledger_entries (id) {
+        id -> Uuid,
+        payment_id -> Uuid,
+        booking_id -> Uuid,
+        action -> PaymentLedgerAction,
+        amount_delta -> Numeric,
+        amount -> Numeric,
+        payment_type -> PaymentType,
+        #[max_length = 50]
+        payment_method -> Varchar,
+        previous_payment_id -> Nullable<Uuid>,
+        reason -> Nullable<Text>,
+        actor_user_id -> Uuid,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::PaymentType;
@@ -118,6 +420,47 @@ diesel::table! {
         created_by_user_id -> Uuid,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        voided_at -> Nullable<Timestamptz>,
+        voided_by_user_id -> Nullable<Uuid>,
+        void_reason -> Nullable<Text>,
+        replaces_payment_id -> Nullable<Uuid>,
+        shift_id -> Nullable<Uuid>,
+        #[max_length = 3]
+        currency -> Varchar,
+        exchange_rate -> Numeric,
+        original_amount -> Nullable<Numeric>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::CashierShiftStatus;
+
+    cashier_shifts (id) {
+        id -> Uuid,
+        user_id -> Uuid,
+        status -> CashierShiftStatus,
+        opening_float -> Numeric,
+        opening_notes -> Nullable<Text>,
+        opened_at -> Timestamptz,
+        closed_at -> Nullable<Timestamptz>,
+        counted_cash -> Nullable<Numeric>,
+        expected_cash -> Nullable<Numeric>,
+        over_short -> Nullable<Numeric>,
+        closing_notes -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    exchange_rates (id) {
+        id -> Uuid,
+        #[max_length = 3]
+        currency -> Varchar,
+        rate -> Numeric,
+        effective_date -> Date,
+        created_by_user_id -> Uuid,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
     }
 }
 
@@ -139,6 +482,54 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    job_runs (id) {
+        id -> Uuid,
+        #[max_length = 50]
+        job_name -> Varchar,
+        started_at -> Timestamptz,
+        finished_at -> Timestamptz,
+        success -> Bool,
+        affected_count -> Int4,
+        affected_ids -> Array<Uuid>,
+        summary -> Text,
+        error_message -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    invoice_counters (year) {
+        year -> Int4,
+        last_number -> Int4,
+    }
+}
+
+diesel::table! {
+    invoices (id) {
+        id -> Uuid,
+        #[max_length = 20]
+        invoice_number -> Varchar,
+        booking_id -> Uuid,
+        #[max_length = 100]
+        guest_name -> Varchar,
+        #[max_length = 10]
+        room_number -> Varchar,
+        check_in_date -> Date,
+        check_out_date -> Date,
+        net_total -> Numeric,
+        tax_total -> Numeric,
+        total_amount -> Numeric,
+        total_paid -> Numeric,
+        balance_due -> Numeric,
+        #[max_length = 255]
+        html_object_key -> Varchar,
+        #[max_length = 255]
+        pdf_object_key -> Varchar,
+        issued_by_user_id -> Uuid,
+        issued_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     messages (id) {
         id -> Uuid,
@@ -162,19 +553,130 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::RoomType;
+
+    rate_plans (id) {
+        id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
+        room_type -> Nullable<RoomType>,
+        room_id -> Nullable<Uuid>,
+        base_rate -> Numeric,
+        weekend_rate -> Nullable<Numeric>,
+        start_date -> Nullable<Date>,
+        end_date -> Nullable<Date>,
+        min_stay_nights -> Int4,
+        priority -> Int4,
+        is_active -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        cancellation_policy_id -> Nullable<Uuid>,
+    }
+}
+
+diesel::table! {
+    rate_overrides (id) {
+        id -> Uuid,
+        rate_plan_id -> Uuid,
+        stay_date -> Date,
+        price -> Numeric,
+        min_stay_nights -> Nullable<Int4>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::ChargeCategory;
+
+    tax_rules (id) {
+        id -> Uuid,
+        #[max_length = 100]
+        name -> Varchar,
+        rate -> Numeric,
+        is_inclusive -> Bool,
+        applies_to_room -> Bool,
+        charge_categories -> Array<ChargeCategory>,
+        effective_from -> Nullable<Date>,
+        effective_to -> Nullable<Date>,
+        sort_order -> Int4,
+        is_active -> Bool,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(booking_groups -> users (created_by_user_id));
+diesel::joinable!(booking_room_stays -> bookings (booking_id));
+diesel::joinable!(booking_room_stays -> rooms (room_id));
+diesel::joinable!(booking_stay_changes -> bookings (booking_id));
+diesel::joinable!(booking_stay_changes -> users (changed_by_user_id));
+diesel::joinable!(cashier_shifts -> users (user_id));
+diesel::joinable!(bookings -> booking_groups (group_id));
 diesel::joinable!(bookings -> rooms (room_id));
+diesel::joinable!(bookings -> rate_plans (rate_plan_id));
 diesel::joinable!(bookings -> users (created_by_user_id));
+diesel::joinable!(exchange_rates -> users (created_by_user_id));
+diesel::joinable!(folio_charge_taxes -> folio_charges (folio_charge_id));
+diesel::joinable!(folio_charge_taxes -> tax_rules (tax_rule_id));
+diesel::joinable!(folio_charges -> bookings (booking_id));
+diesel::joinable!(folio_charges -> users (created_by_user_id));
+diesel::joinable!(guest_credit_entries -> bookings (booking_id));
+diesel::joinable!(guest_credit_entries -> payments (payment_id));
+diesel::joinable!(housekeeping_tasks -> bookings (booking_id));
+diesel::joinable!(housekeeping_tasks -> rooms (room_id));
+diesel::joinable!(housekeeping_tasks -> users (assigned_cleaner_id));
+diesel::joinable!(invoices -> bookings (booking_id));
+diesel::joinable!(invoices -> users (issued_by_user_id));
+diesel::joinable!(maintenance_orders -> rooms (room_id));
+diesel::joinable!(maintenance_orders -> users (assigned_to_user_id));
+diesel::joinable!(payment_intents -> bookings (booking_id));
+diesel::joinable!(payment_intents -> payments (payment_id));
+diesel::joinable!(payment_intents -> users (created_by_user_id));
+diesel::joinable!(payment_ledger_entries -> bookings (booking_id));
+diesel::joinable!(payment_ledger_entries -> users (actor_user_id));
+diesel::joinable!(payment_webhook_events -> payment_intents (payment_intent_id));
 diesel::joinable!(payments -> bookings (booking_id));
+diesel::joinable!(payments -> cashier_shifts (shift_id));
 diesel::joinable!(payments -> users (created_by_user_id));
+diesel::joinable!(room_inspections -> rooms (room_id));
+diesel::joinable!(room_inspections -> users (inspector_id));
 diesel::joinable!(rooms -> users (assigned_cleaner_id));
+diesel::joinable!(rate_plans -> rooms (room_id));
+diesel::joinable!(rate_plans -> cancellation_policies (cancellation_policy_id));
+diesel::joinable!(rate_overrides -> rate_plans (rate_plan_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    booking_groups,
+    booking_room_stays,
+    booking_stay_changes,
     bookings,
+    cancellation_policies,
+    cashier_shifts,
+    exchange_rates,
+    folio_charge_taxes,
+    folio_charges,
+    guest_credit_entries,
     guest_interaction_notes,
+    housekeeping_tasks,
     inventory_items,
+    invoice_counters,
+    invoices,
+    job_runs,
+    maintenance_orders,
     messages,
+    payment_intents,
+    payment_ledger_entries,
+    payment_webhook_events,
     payments,
+    rate_overrides,
+    rate_plans,
+    room_inspections,
     rooms,
+    tax_rules,
     users,
     system_settings,
 );
@@ -16,7 +16,11 @@ use crate::{
     db::DbPool,
     schema::{system_settings, messages},
     models::message::Message,
-    services::{BookingService, RoomService},
+    models::{Room, RoomType},
+    services::{
+        booking_service::validate_guest_count, exchange_rate_service::BASE_CURRENCY,
+        room_service::RoomFilter, BookingService, PricingService, RoomService,
+    },
 };
 use uuid::Uuid;
 
@@ -44,6 +48,20 @@ struct SearchRoomsInput {
     check_out_date: String,
     #[schemars(description = "Optional filter for room type: single, double, or suite")]
     room_type: Option<String>,
+    #[schemars(description = "Optional number of guests; only rooms sleeping at least this many are returned")]
+    guests: Option<i32>,
+    #[schemars(description = "Optional floor number")]
+    floor: Option<i32>,
+    #[schemars(description = "Optional bed configuration, e.g. '1 king' or '2 twin'")]
+    bed_configuration: Option<String>,
+    #[schemars(description = "Optional view, e.g. 'city', 'garden' or 'sea'")]
+    view: Option<String>,
+    #[schemars(description = "Optional amenities the room must all have, e.g. ['balcony', 'bathtub']")]
+    amenities: Option<Vec<String>>,
+    #[schemars(description = "Optional smoking preference: true for smoking rooms, false for non-smoking rooms")]
+    smoking: Option<bool>,
+    #[schemars(description = "Set to true to only return wheelchair-accessible rooms")]
+    wheelchair_accessible: Option<bool>,
 }
 
 /// Tool input for creating a booking proposal
@@ -55,6 +73,58 @@ struct CreateBookingProposalInput {
     check_in_date: String,
     #[schemars(description = "Check-out date in YYYY-MM-DD format")]
     check_out_date: String,
+    #[schemars(description = "Number of guests staying in the room (defaults to 1)")]
+    guest_count: Option<i32>,
+}
+
+/// Describe a room's attributes for the model, e.g. "sleeps 2, 1 king, city view, floor 3"
+fn describe_room(room: &Room) -> String {
+    let mut parts = vec![format!("sleeps {}", room.max_occupancy)];
+    if let Some(beds) = &room.bed_configuration {
+        parts.push(beds.clone());
+    }
+    if let Some(view) = &room.view {
+        parts.push(format!("{} view", view));
+    }
+    if let Some(floor) = room.floor {
+        parts.push(format!("floor {}", floor));
+    }
+    parts.push(if room.smoking { "smoking" } else { "non-smoking" }.to_string());
+    if room.wheelchair_accessible {
+        parts.push("wheelchair accessible".to_string());
+    }
+    if !room.amenities.is_empty() {
+        parts.push(format!("amenities: {}", room.amenities.join(", ")));
+    }
+    parts.join(", ")
+}
+
+/// One line per room type with its capacity and starting list price, from the rooms on file
+fn describe_room_types(rooms: &[Room]) -> String {
+    let mut lines = Vec::new();
+    for room_type in [RoomType::Single, RoomType::Double, RoomType::Suite] {
+        let of_type: Vec<&Room> = rooms.iter().filter(|r| r.room_type == room_type).collect();
+        let (Some(min_guests), Some(max_guests), Some(from_price)) = (
+            of_type.iter().map(|r| r.max_occupancy).min(),
+            of_type.iter().map(|r| r.max_occupancy).max(),
+            of_type.iter().map(|r| &r.price).min(),
+        ) else {
+            continue;
+        };
+        let guests = if min_guests == max_guests {
+            format!("up to {} guests", max_guests)
+        } else {
+            format!("up to {}-{} guests depending on the room", min_guests, max_guests)
+        };
+        lines.push(format!(
+            "{:?} ({}, from {} {}/night)",
+            room_type,
+            guests,
+            from_price.round(0),
+            BASE_CURRENCY
+        ));
+    }
+    lines.join(", ")
 }
 
 /// Tool for searching available rooms
@@ -74,7 +144,7 @@ impl Tool for SearchRoomsTool {
         let parameters = serde_json::to_value(schemars::schema_for!(SearchRoomsInput)).unwrap();
         ToolDefinition {
             name: Self::NAME.to_string(),
-            description: "Search for available rooms based on check-in and check-out dates. Returns a list of available rooms with their details including room type, number, and price per night.".to_string(),
+            description: "Search for available rooms based on check-in and check-out dates, optionally filtered by guests, floor, bed configuration, view, amenities, smoking and wheelchair accessibility. Returns a list of available rooms with their details including room type, number, capacity and attributes, total price for the stay and any minimum-stay requirement.".to_string(),
             parameters,
         }
     }
@@ -98,18 +168,25 @@ impl Tool for SearchRoomsTool {
 
         let room_service = RoomService::new(self.pool.clone());
         let booking_service = BookingService::new(self.pool.clone());
-
-        // Get all rooms (optionally filtered by type)
-        let rooms = room_service.list_rooms(None, room_type)
+        let pricing_service = PricingService::new(self.pool.clone());
+
+        // Get the rooms matching the requested attributes
+        let filter = RoomFilter {
+            guests: args.guests,
+            floor: args.floor,
+            bed_configuration: args.bed_configuration,
+            view: args.view,
+            amenities: args.amenities.unwrap_or_default(),
+            smoking: args.smoking,
+            wheelchair_accessible: args.wheelchair_accessible,
+            ..RoomFilter::by_type(room_type)
+        };
+        let rooms = room_service.list_rooms(&filter)
             .map_err(|e| ToolError::Database(format!("Failed to list rooms: {}", e)))?;
 
         // Check availability for each room
         let mut available_rooms = Vec::new();
         for room in rooms {
-            if room.status == crate::models::RoomStatus::Maintenance {
-                continue;
-            }
-
             let is_available = booking_service.check_availability(
                 room.id,
                 check_in,
@@ -118,18 +195,32 @@ impl Tool for SearchRoomsTool {
             ).map_err(|e| ToolError::Database(format!("Failed to check availability: {}", e)))?;
 
             if is_available {
+                // Quote from the rate calendar so the proposal total matches
+                let quote = pricing_service.quote(room.id, check_in, check_out)
+                    .map_err(|e| ToolError::InvalidInput(format!("Failed to price room: {}", e)))?;
+
+                let min_stay_note = if quote.meets_min_stay() {
+                    String::new()
+                } else {
+                    format!(" (requires a minimum stay of {} nights for these dates)", quote.min_stay_nights)
+                };
+
                 available_rooms.push(format!(
-                    "Room {}: {:?} room, Price: {} VND per night, Room ID: {}",
+                    "Room {}: {:?} room ({}), Total: {} {} for {} nights{}, Room ID: {}",
                     room.number,
                     room.room_type,
-                    room.price,
+                    describe_room(&room),
+                    quote.total_price,
+                    BASE_CURRENCY,
+                    quote.nights,
+                    min_stay_note,
                     room.id
                 ));
             }
         }
 
         if available_rooms.is_empty() {
-            Ok("No rooms are available for the selected dates. Please try different dates or contact the front desk for assistance.".to_string())
+            Ok("No rooms matching these requirements are available for the selected dates. Please try different dates or requirements, or contact the front desk for assistance.".to_string())
         } else {
             Ok(format!("Available rooms:\n{}", available_rooms.join("\n")))
         }
@@ -153,7 +244,7 @@ impl Tool for CreateBookingProposalTool {
         let parameters = serde_json::to_value(schemars::schema_for!(CreateBookingProposalInput)).unwrap();
         ToolDefinition {
             name: Self::NAME.to_string(),
-            description: "Create a booking proposal for the user to review. This will generate a booking card that the user can confirm or cancel. Only call this when you have all required information: room_id, check_in_date, and check_out_date.".to_string(),
+            description: "Create a booking proposal for the user to review. This will generate a booking card that the user can confirm or cancel. Only call this when you have all required information: room_id, check_in_date, check_out_date and guest_count.".to_string(),
             parameters,
         }
     }
@@ -174,13 +265,29 @@ impl Tool for CreateBookingProposalTool {
         let room = room_service.get_room_by_id(room_id)
             .map_err(|e| ToolError::NotFound(format!("Failed to get room details: {}", e)))?;
 
-        // Calculate nights and total price
         let nights = (check_out - check_in).num_days();
         if nights <= 0 {
             return Err(ToolError::InvalidInput("Check-out date must be after check-in date".to_string()));
         }
 
-        let total_price = &room.price * BigDecimal::from(nights);
+        let guest_count = args.guest_count.unwrap_or(1);
+        validate_guest_count(&room, guest_count)
+            .map_err(|e| ToolError::InvalidInput(e.to_string()))?;
+
+        // Price through the rate calendar, exactly as the booking will be charged
+        let pricing_service = PricingService::new(self.pool.clone());
+        let quote = pricing_service.quote(room_id, check_in, check_out)
+            .map_err(|e| ToolError::InvalidInput(format!("Failed to price room: {}", e)))?;
+
+        if !quote.meets_min_stay() {
+            return Err(ToolError::InvalidInput(format!(
+                "Room {} requires a minimum stay of {} nights for these dates",
+                room.number, quote.min_stay_nights
+            )));
+        }
+
+        let total_price = quote.total_price;
+        let price_per_night = (&total_price / BigDecimal::from(nights)).round(0);
 
         // Create booking proposal JSON
         let proposal = serde_json::json!({
@@ -189,9 +296,11 @@ impl Tool for CreateBookingProposalTool {
             "room_type": format!("{:?}", room.room_type).to_lowercase(),
             "check_in_date": args.check_in_date,
             "check_out_date": args.check_out_date,
+            "guest_count": guest_count,
             "total_price": total_price.to_string(),
             "nights": nights,
-            "price_per_night": room.price.to_string()
+            "price_per_night": price_per_night.to_string(),
+            "nightly_rates": quote.nightly_rates
         });
 
         // Return the proposal as a special formatted message
@@ -250,42 +359,45 @@ impl AiService {
              history_text.push_str(&format!("{}: {}\n", sender, msg.content));
         }
 
+        // Room types as configured, so capacities and prices stay current
+        let room_types = RoomService::new(self.pool.clone())
+            .list_rooms(&RoomFilter::default())
+            .map(|rooms| describe_room_types(&rooms))
+            .unwrap_or_default();
+
         // Updated preamble with booking capabilities
         let preamble = format!(
             "You are Pupinn, the virtual concierge for the Pupinn Hotel. \
             You are chatting with a user named {}. \
-            
             HOTEL INFORMATION: \
             - Name: Pupinn \
-            - Room Types Offered: Single (1-2 guests, ~1,000,000 VND/night), Double (2-4 guests, ~1,500,000 VND/night), and Suite (4+ guests, luxury, ~2,500,000 VND/night). \
+            - Room Types Offered: {room_types}. \
+            - Rooms also differ by floor, bed configuration, view, amenities, smoking policy and wheelchair accessibility. \
             - Guest Services: Guests can search for rooms, book stays, and manage reservations through the chat or Guest Portal. \
-            
             YOUR CAPABILITIES: \
             You have access to the following tools: \
-            1. search_available_rooms: Search for available rooms by date range and optional room type \
+            1. search_available_rooms: Search for available rooms by date range, optionally filtered by room type, number of guests and room attributes \
             2. create_booking_proposal: Create a booking proposal that the user can confirm or cancel \
-            
             BOOKING WORKFLOW: \
             1. When a user wants to book a room, gather the following information through conversation: \
                - Check-in date (must be specific, e.g., '2026-02-20', not 'next week') \
                - Check-out date (must be specific) \
-               - Room type preference (single, double, or suite) - ask about number of guests to recommend \
-            2. Once you have check-in and check-out dates, use search_available_rooms to find options \
+               - Number of guests \
+               - Any preferences (room type, view, bed configuration, amenities, smoking, accessibility) \
+            2. Once you have the dates and number of guests, use search_available_rooms with the guests and any preferences to find options \
             3. Help the user choose a room based on their needs (number of guests, budget, preferences) \
-            4. When the user confirms their choice, use create_booking_proposal with the room_id from search results \
+            4. When the user confirms their choice, use create_booking_proposal with the room_id from search results and the number of guests \
             5. IMPORTANT: After calling create_booking_proposal, the tool will return a message starting with 'BOOKING_PROPOSAL:' followed by JSON data. \
                You MUST include this EXACT output in your response, followed by your conversational message. \
                Example: 'BOOKING_PROPOSAL:{{...json data...}} I've created a booking proposal for you! Please review the details in the card above and click Book to confirm.' \
             6. The system will automatically display a booking card with Book and Cancel buttons for the user \
-            
             GUIDELINES: \
             - Tone: Helpful, professional, and welcoming \
             - Always ask for specific dates (YYYY-MM-DD format) - if user says 'next week' or 'Tet Holiday', ask for exact dates \
-            - Recommend room types based on number of guests: 1-2 → Single, 2-4 → Double, 4+ → Suite \
+            - Only offer rooms that sleep the number of guests; always pass the number of guests to search_available_rooms \
             - If no rooms are available, suggest alternative dates \
             - After creating a booking proposal, include the tool's BOOKING_PROPOSAL output in your response, then add a friendly message \
             - If user cancels a proposal, ask why and offer alternatives \
-            
             Here is the recent conversation history:\n\
             {}\n\
             User's new message is below.", 
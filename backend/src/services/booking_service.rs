@@ -1,11 +1,11 @@
 use chrono::{NaiveDate, Utc, Duration};
 use diesel::prelude::*;
-use diesel::dsl::{count, sum, avg};
 use diesel::result::{QueryResult, DatabaseErrorInformation};
 use rand::Rng;
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, Zero};
+use std::collections::{BTreeMap, HashSet};
 use std::str::FromStr;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// Simple error wrapper for database errors
@@ -41,28 +41,340 @@ impl DatabaseErrorInformation for StringError {
     }
 }
 
+/// Carry an AppError through a diesel transaction; `AppError::from` maps it back
+fn app_error_to_diesel(e: AppError) -> diesel::result::Error {
+    diesel::result::Error::DatabaseError(
+        diesel::result::DatabaseErrorKind::CheckViolation,
+        Box::new(StringError(e.to_string())) as Box<dyn DatabaseErrorInformation + Send + Sync>,
+    )
+}
+
 use crate::db::DbPool;
 use crate::errors::{AppError, AppResult};
 use crate::models::{
-    Booking, BookingStatus, BookingWithRoom, BookingWithPayments, NewBooking, Room, RoomStatus, RoomType, UpdateBooking,
+    Booking, BookingGroup, BookingGroupWithBookings, BookingRoomStay, BookingStatus, BookingWithRoom,
+    BookingStayChange, BookingWithPayments, CancelledBooking, NewBooking, NewBookingGroup,
+    NewBookingRoomStay, NewBookingStayChange, Payment, Room, RoomStatus, RoomType, StayChangeType, TaxBreakdown,
+};
+use crate::schema::{booking_groups, booking_room_stays, booking_stay_changes, bookings, payments, rooms};
+use crate::services::guest_credit_service::GUEST_CREDIT_PAYMENT_METHOD;
+use crate::services::tax_service::{applicable_rules, apply_taxes, TaxTarget};
+use crate::services::{
+    CancellationService, HousekeepingService, MaintenanceService, PaymentIntentService,
+    PaymentService, PricingService, TaxService,
 };
-use crate::schema::{bookings, rooms};
 
 /// Booking service for managing reservations
 pub struct BookingService {
     pool: DbPool,
 }
 
+/// Net, tax and gross amounts of revenue
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RevenueSplit {
+    pub net: BigDecimal,
+    pub tax: BigDecimal,
+    pub gross: BigDecimal,
+}
+
+impl RevenueSplit {
+    /// Add a taxed amount
+    pub fn add(&mut self, breakdown: &TaxBreakdown) {
+        self.net += &breakdown.net;
+        self.tax += &breakdown.tax;
+        self.gross += &breakdown.gross;
+    }
+}
+
 /// Financial metrics for a room
 #[derive(Debug, Clone, Serialize)]
 pub struct RoomFinancials {
     pub room_id: Uuid,
+    /// Gross room revenue (tax included)
     pub total_revenue: BigDecimal,
+    pub net_revenue: BigDecimal,
+    pub tax_revenue: BigDecimal,
     pub booking_count: i64,
     pub average_revenue: Option<BigDecimal>,
     pub occupancy_rate: f64,
 }
 
+/// Group reservation request payload
+#[derive(Debug, Deserialize)]
+pub struct CreateGroupBookingRequest {
+    pub name: String,
+    pub contact_name: String,
+    pub notes: Option<String>,
+    /// Default stay dates for every room in the group
+    pub check_in_date: NaiveDate,
+    pub check_out_date: NaiveDate,
+    pub rooms: Vec<GroupRoomRequest>,
+}
+
+/// One room within a group reservation request
+#[derive(Debug, Deserialize)]
+pub struct GroupRoomRequest {
+    pub room_id: Uuid,
+    /// Occupant name (defaults to the group contact)
+    pub guest_name: Option<String>,
+    /// Per-room dates for early arrivals / late departures
+    pub check_in_date: Option<NaiveDate>,
+    pub check_out_date: Option<NaiveDate>,
+    /// Negotiated group total for this room (defaults to the quoted rate)
+    pub price: Option<BigDecimal>,
+    /// Guests staying in this room (defaults to 1)
+    pub guest_count: Option<i32>,
+}
+
+/// Maximum number of rooms in a single group reservation
+pub const MAX_GROUP_ROOMS: usize = 50;
+
+/// Stay segments `(room_id, first night, end date)` of a booking
+///
+/// A booking that was never moved is one segment in its current room.
+pub fn room_segments(booking: &Booking, stays: &[BookingRoomStay]) -> Vec<(Uuid, NaiveDate, NaiveDate)> {
+    let mut segments: Vec<(Uuid, NaiveDate, NaiveDate)> = stays
+        .iter()
+        .filter(|s| s.booking_id == booking.id)
+        .map(|s| (s.room_id, s.start_date, s.end_date))
+        .collect();
+
+    if segments.is_empty() {
+        segments.push((booking.room_id, booking.check_in_date, booking.check_out_date));
+    }
+
+    segments.sort_by_key(|(_, start, _)| *start);
+    segments
+}
+
+/// Nights of a booking spent in `room_id`, optionally clipped to `[start, end]`
+pub fn nights_in_room(
+    booking: &Booking,
+    stays: &[BookingRoomStay],
+    room_id: Uuid,
+    window: Option<(NaiveDate, NaiveDate)>,
+) -> i64 {
+    room_segments(booking, stays)
+        .into_iter()
+        .filter(|(segment_room, _, _)| *segment_room == room_id)
+        .map(|(_, start, end)| match window {
+            Some((from, to)) => (end.min(to) - start.max(from)).num_days().max(0),
+            None => (end - start).num_days().max(0),
+        })
+        .sum()
+}
+
+/// Part of the booking price earned by `room_id`, pro rata by nights
+///
+/// The last segment takes the rounding remainder so the shares of all rooms
+/// add up to the booking price exactly.
+pub fn room_revenue_share(booking: &Booking, stays: &[BookingRoomStay], room_id: Uuid) -> BigDecimal {
+    let segments = room_segments(booking, stays);
+    let total_nights = (booking.check_out_date - booking.check_in_date).num_days();
+
+    if total_nights <= 0 || segments.len() == 1 {
+        return if segments[0].0 == room_id {
+            booking.price.clone()
+        } else {
+            BigDecimal::from(0)
+        };
+    }
+
+    let mut allocated = BigDecimal::from(0);
+    let mut share = BigDecimal::from(0);
+    for (i, (segment_room, start, end)) in segments.iter().enumerate() {
+        let part = if i == segments.len() - 1 {
+            &booking.price - &allocated
+        } else {
+            let nights = (*end - *start).num_days();
+            (&booking.price * BigDecimal::from(nights) / BigDecimal::from(total_nights)).round(2)
+        };
+        allocated += &part;
+        if *segment_room == room_id {
+            share += part;
+        }
+    }
+
+    share
+}
+
+/// How financial reports recognise revenue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevenueBasis {
+    /// Price of checked-out bookings, on the check-out date
+    #[default]
+    Checkout,
+    /// Money collected (payments minus refunds), on the payment date
+    Cash,
+    /// Price of stays spread evenly over the nights stayed
+    Accrual,
+}
+
+impl RevenueBasis {
+    /// Basis asked for, falling back to the older `use_payments` flag
+    pub fn resolve(basis: Option<RevenueBasis>, use_payments: Option<bool>) -> Self {
+        match (basis, use_payments) {
+            (Some(basis), _) => basis,
+            (None, Some(true)) => RevenueBasis::Cash,
+            _ => RevenueBasis::Checkout,
+        }
+    }
+}
+
+/// Price of each night of a booking with the room it was spent in
+///
+/// The last night takes the rounding remainder so the nights add up to the
+/// booking price exactly.
+pub fn nightly_revenue(booking: &Booking, stays: &[BookingRoomStay]) -> Vec<(NaiveDate, Uuid, BigDecimal)> {
+    let total_nights = (booking.check_out_date - booking.check_in_date).num_days();
+    if total_nights <= 0 {
+        return Vec::new();
+    }
+
+    let segments = room_segments(booking, stays);
+    let nightly = (&booking.price / BigDecimal::from(total_nights)).round(2);
+
+    let mut allocated = BigDecimal::zero();
+    (0..total_nights)
+        .map(|i| {
+            let night = booking.check_in_date + Duration::days(i);
+            let room_id = segments
+                .iter()
+                .find(|(_, start, end)| *start <= night && night < *end)
+                .map(|(room_id, _, _)| *room_id)
+                .unwrap_or(booking.room_id);
+            let amount = if i == total_nights - 1 {
+                &booking.price - &allocated
+            } else {
+                nightly.clone()
+            };
+            allocated += &amount;
+            (night, room_id, amount)
+        })
+        .collect()
+}
+
+/// Part of a payment collected for `room_id`, in the same proportion as the
+/// room's share of the booking price
+pub fn room_payment_share(
+    amount: &BigDecimal,
+    booking: &Booking,
+    stays: &[BookingRoomStay],
+    room_id: Uuid,
+) -> BigDecimal {
+    if booking.price <= BigDecimal::zero() {
+        return if booking.room_id == room_id {
+            amount.clone()
+        } else {
+            BigDecimal::zero()
+        };
+    }
+
+    let share = room_revenue_share(booking, stays, room_id);
+    if share == booking.price {
+        return amount.clone();
+    }
+
+    (amount * share / &booking.price).round(2)
+}
+
+/// Split money collected into net and tax in the proportion of the booking's
+/// own tax breakdown
+pub fn split_collected(amount: &BigDecimal, booking_taxes: &TaxBreakdown) -> TaxBreakdown {
+    let tax = if booking_taxes.gross.is_zero() {
+        BigDecimal::zero()
+    } else {
+        (amount * &booking_taxes.tax / &booking_taxes.gross).round(0)
+    };
+
+    TaxBreakdown {
+        net: amount - &tax,
+        tax,
+        gross: amount.clone(),
+        lines: Vec::new(),
+    }
+}
+
+/// Check a new check-out date for `booking` and tell which way the stay changes
+///
+/// Upcoming and in-house stays can change; an in-house stay cannot be cut
+/// short to before today.
+pub fn validate_stay_change(
+    booking: &Booking,
+    new_check_out_date: NaiveDate,
+    today: NaiveDate,
+) -> AppResult<StayChangeType> {
+    if !matches!(
+        booking.status,
+        BookingStatus::Upcoming | BookingStatus::CheckedIn | BookingStatus::Overstay
+    ) {
+        return Err(AppError::InvalidStatusTransition(format!(
+            "Cannot change the stay of a booking with status {:?}",
+            booking.status
+        )));
+    }
+
+    if new_check_out_date == booking.check_out_date {
+        return Err(AppError::ValidationError(format!(
+            "Check-out is already on {}",
+            new_check_out_date
+        )));
+    }
+
+    if new_check_out_date <= booking.check_in_date {
+        return Err(AppError::ValidationError(
+            "Check-out date must be after check-in date".to_string(),
+        ));
+    }
+
+    let in_house = matches!(booking.status, BookingStatus::CheckedIn | BookingStatus::Overstay);
+    if in_house && new_check_out_date < today {
+        return Err(AppError::ValidationError(format!(
+            "Check-out date cannot be before today ({})",
+            today
+        )));
+    }
+
+    Ok(if new_check_out_date > booking.check_out_date {
+        StayChangeType::Extend
+    } else {
+        StayChangeType::Shorten
+    })
+}
+
+/// New booking price after adding or removing nights priced at `changed_nights_price`
+///
+/// Nights that were not touched keep the price already agreed.
+pub fn stay_change_price(
+    current_price: &BigDecimal,
+    changed_nights_price: &BigDecimal,
+    change_type: StayChangeType,
+) -> BigDecimal {
+    match change_type {
+        StayChangeType::Extend => current_price + changed_nights_price,
+        StayChangeType::Shorten => (current_price - changed_nights_price).max(BigDecimal::from(0)),
+    }
+}
+
+/// Check `guest_count` guests can stay in `room`
+pub fn validate_guest_count(room: &Room, guest_count: i32) -> AppResult<()> {
+    if guest_count < 1 {
+        return Err(AppError::ValidationError(
+            "A booking needs at least one guest".to_string(),
+        ));
+    }
+
+    if !room.fits(guest_count) {
+        return Err(AppError::ValidationError(format!(
+            "Room {} sleeps at most {} guests",
+            room.number, room.max_occupancy
+        )));
+    }
+
+    Ok(())
+}
+
 impl BookingService {
     /// Create a new BookingService instance
     pub fn new(pool: DbPool) -> Self {
@@ -160,43 +472,55 @@ impl BookingService {
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
+        Self::check_availability_with_conn(
+            &mut conn,
+            room_id,
+            check_in_date,
+            check_out_date,
+            exclude_booking_id,
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Availability check using an existing connection (usable inside transactions)
+    pub fn check_availability_with_conn(
+        conn: &mut PgConnection,
+        room_id: Uuid,
+        check_in_date: NaiveDate,
+        check_out_date: NaiveDate,
+        exclude_booking_id: Option<Uuid>,
+    ) -> QueryResult<bool> {
         // Find overlapping bookings that block availability
-        let mut query = bookings::table
-            .filter(bookings::room_id.eq(room_id))
-            .filter(bookings::status.ne(BookingStatus::Cancelled))
-            .filter(bookings::status.ne(BookingStatus::CheckedOut))
-            .filter(bookings::check_in_date.lt(check_out_date))
-            .filter(bookings::check_out_date.gt(check_in_date))
-            .into_boxed();
+        let conflicting = Self::overlapping_bookings_with_conn(
+            conn,
+            room_id,
+            check_in_date,
+            check_out_date,
+            exclude_booking_id,
+        )?;
 
-        if let Some(booking_id) = exclude_booking_id {
-            query = query.filter(bookings::id.ne(booking_id));
+        // Open maintenance work orders block only the dates they cover
+        let out_of_order = MaintenanceService::blocking_orders_with_conn(
+            conn,
+            room_id,
+            check_in_date,
+            check_out_date,
+        )?;
+        if !out_of_order.is_empty() {
+            return Ok(false);
         }
 
-        let conflicting: Vec<Booking> = query
-            .load(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
         // Check room status only for immediate bookings (check-in today)
-        // Future bookings can be made on Dirty/Cleaning/Occupied rooms since
-        // they will be available by the check-in date.
-        // Maintenance rooms are always blocked as maintenance duration is unpredictable.
+        // Future bookings can be made on Dirty/Cleaning/Occupied/Maintenance
+        // rooms since they will be available by the check-in date.
         let today = Utc::now().date_naive();
-        let room_rec: Room = rooms::table
-            .find(room_id)
-            .first(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-        // Maintenance always blocks bookings
-        if room_rec.status == RoomStatus::Maintenance {
-            return Ok(false);
-        }
+        let room_rec: Room = rooms::table.find(room_id).first(conn)?;
 
         // For same-day check-in, room must be Available or at least cleanable
         if check_in_date == today {
             // Room must be Available, Dirty, or Cleaning for same-day check-in
-            // Occupied rooms cannot accept same-day bookings
-            if room_rec.status == RoomStatus::Occupied {
+            // Occupied and Maintenance rooms cannot accept same-day bookings
+            if matches!(room_rec.status, RoomStatus::Occupied | RoomStatus::Maintenance) {
                 return Ok(false);
             }
         }
@@ -204,6 +528,51 @@ impl BookingService {
         Ok(conflicting.is_empty())
     }
 
+    /// Fail with the work order's dates when maintenance blocks any night
+    /// of the stay
+    fn ensure_in_order_with_conn(
+        conn: &mut PgConnection,
+        room: &Room,
+        check_in_date: NaiveDate,
+        check_out_date: NaiveDate,
+    ) -> AppResult<()> {
+        let orders =
+            MaintenanceService::blocking_orders_with_conn(conn, room.id, check_in_date, check_out_date)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match orders.first() {
+            Some(order) => Err(AppError::RoomUnavailable(format!(
+                "Room {} is out of order for maintenance from {} to {}",
+                room.number, order.start_date, order.end_date
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Bookings holding `room_id` for any night in `[check_in_date, check_out_date)`
+    pub fn overlapping_bookings_with_conn(
+        conn: &mut PgConnection,
+        room_id: Uuid,
+        check_in_date: NaiveDate,
+        check_out_date: NaiveDate,
+        exclude_booking_id: Option<Uuid>,
+    ) -> QueryResult<Vec<Booking>> {
+        let mut query = bookings::table
+            .filter(bookings::room_id.eq(room_id))
+            .filter(bookings::status.ne(BookingStatus::Cancelled))
+            .filter(bookings::status.ne(BookingStatus::CheckedOut))
+            .filter(bookings::status.ne(BookingStatus::NoShow))
+            .filter(bookings::check_in_date.lt(check_out_date))
+            .filter(bookings::check_out_date.gt(check_in_date))
+            .into_boxed();
+
+        if let Some(booking_id) = exclude_booking_id {
+            query = query.filter(bookings::id.ne(booking_id));
+        }
+
+        query.order(bookings::check_in_date.asc()).load(conn)
+    }
+
     /// Create a new booking
     pub fn create_booking(
         &self,
@@ -211,6 +580,7 @@ impl BookingService {
         room_id: Uuid,
         check_in_date: NaiveDate,
         check_out_date: NaiveDate,
+        guest_count: i32,
         price: Option<BigDecimal>,
     ) -> AppResult<Booking> {
         self.validate_dates(check_in_date, check_out_date)?;
@@ -220,61 +590,71 @@ impl BookingService {
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let room: Room = rooms::table
-            .find(room_id)
-            .first(&mut conn)
-            .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
-
-        // Maintenance rooms are always blocked
-        if room.status == RoomStatus::Maintenance {
-            return Err(AppError::RoomUnavailable(format!(
-                "Room {} is under maintenance",
-                room.number
-            )));
-        }
+        // Lock the room so a work order or another booking cannot take the
+        // dates between the checks below and the insert
+        conn.transaction::<_, AppError, _>(|conn| {
+            let room: Room = rooms::table
+                .find(room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
 
-        // check_availability handles both booking conflicts and room status checks
-        if !self.check_availability(room_id, check_in_date, check_out_date, None)? {
-            return Err(AppError::RoomUnavailable(format!(
-                "Room {} is not available for the selected dates",
-                room.number
-            )));
-        }
+            validate_guest_count(&room, guest_count)?;
 
-        let reference = self.generate_reference()?;
+            // Dates covered by a maintenance work order are blocked
+            Self::ensure_in_order_with_conn(conn, &room, check_in_date, check_out_date)?;
 
-        if guest_name.trim().is_empty() {
-            return Err(AppError::ValidationError(
-                "Guest name is required".to_string(),
-            ));
-        }
+            // check_availability handles both booking conflicts and room status checks
+            let available =
+                Self::check_availability_with_conn(conn, room_id, check_in_date, check_out_date, None)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            if !available {
+                return Err(AppError::RoomUnavailable(format!(
+                    "Room {} is not available for the selected dates",
+                    room.number
+                )));
+            }
 
-        if guest_name.len() > 100 {
-            return Err(AppError::ValidationError(
-                "Guest name must be 100 characters or less".to_string(),
-            ));
-        }
+            let reference = self.generate_reference()?;
 
-        let booking_price = price.unwrap_or_else(|| {
-            let nights = (check_out_date - check_in_date).num_days();
-            &room.price * BigDecimal::from(nights.max(1))
-        });
+            if guest_name.trim().is_empty() {
+                return Err(AppError::ValidationError(
+                    "Guest name is required".to_string(),
+                ));
+            }
 
-        let new_booking = NewBooking {
-            reference: &reference,
-            guest_name: guest_name.trim(),
-            room_id,
-            check_in_date,
-            check_out_date,
-            created_by_user_id: None,
-            creation_source: "staff",
-            price: booking_price,
-        };
+            if guest_name.len() > 100 {
+                return Err(AppError::ValidationError(
+                    "Guest name must be 100 characters or less".to_string(),
+                ));
+            }
 
-        diesel::insert_into(bookings::table)
-            .values(&new_booking)
-            .get_result(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))
+            let quote = PricingService::quote_with_conn(conn, &room, check_in_date, check_out_date)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            quote.enforce_min_stay()?;
+
+            // Staff may agree a custom total; otherwise charge the quoted rate
+            let booking_price = price.unwrap_or(quote.total_price);
+
+            let new_booking = NewBooking {
+                reference: &reference,
+                guest_name: guest_name.trim(),
+                room_id,
+                check_in_date,
+                check_out_date,
+                created_by_user_id: None,
+                creation_source: "staff",
+                price: booking_price,
+                rate_plan_id: quote.rate_plan_id,
+                group_id: None,
+                guest_count,
+            };
+
+            diesel::insert_into(bookings::table)
+                .values(&new_booking)
+                .get_result(conn)
+                .map_err(AppError::from)
+        })
     }
 
     /// Get a booking by ID
@@ -442,7 +822,7 @@ impl BookingService {
         room_id: Uuid,
         check_in_date: NaiveDate,
         check_out_date: NaiveDate,
-        price: Option<BigDecimal>,
+        guest_count: i32,
     ) -> AppResult<BookingWithRoom> {
         self.validate_dates(check_in_date, check_out_date)?;
 
@@ -451,59 +831,67 @@ impl BookingService {
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let room: Room = rooms::table
-            .find(room_id)
-            .first(&mut conn)
-            .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
-
-        // Maintenance rooms are always blocked
-        if room.status == RoomStatus::Maintenance {
-            return Err(AppError::RoomUnavailable(format!(
-                "Room {} is under maintenance",
-                room.number
-            )));
-        }
-
-        // check_availability handles both booking conflicts and room status checks
-        if !self.check_availability(room_id, check_in_date, check_out_date, None)? {
-            return Err(AppError::RoomUnavailable(format!(
-                "Room {} is not available for the selected dates",
-                room.number
-            )));
-        }
+        // Lock the room so a work order or another booking cannot take the
+        // dates between the checks below and the insert
+        conn.transaction::<_, AppError, _>(|conn| {
+            let room: Room = rooms::table
+                .find(room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
 
-        let reference = self.generate_reference()?;
+            validate_guest_count(&room, guest_count)?;
 
-        if guest_name.trim().is_empty() {
-            return Err(AppError::ValidationError(
-                "Guest name is required".to_string(),
-            ));
-        }
+            // Dates covered by a maintenance work order are blocked
+            Self::ensure_in_order_with_conn(conn, &room, check_in_date, check_out_date)?;
 
-        let booking_price = price.unwrap_or_else(|| {
-            let nights = (check_out_date - check_in_date).num_days();
-            &room.price * BigDecimal::from(nights.max(1))
-        });
+            // check_availability handles both booking conflicts and room status checks
+            let available =
+                Self::check_availability_with_conn(conn, room_id, check_in_date, check_out_date, None)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            if !available {
+                return Err(AppError::RoomUnavailable(format!(
+                    "Room {} is not available for the selected dates",
+                    room.number
+                )));
+            }
 
-        let new_booking = NewBooking {
-            reference: &reference,
-            guest_name: guest_name.trim(),
-            room_id,
-            check_in_date,
-            check_out_date,
-            created_by_user_id: Some(user_id),
-            creation_source: "guest",
-            price: booking_price,
-        };
+            let reference = self.generate_reference()?;
 
-        let booking: Booking = diesel::insert_into(bookings::table)
-            .values(&new_booking)
-            .get_result(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            if guest_name.trim().is_empty() {
+                return Err(AppError::ValidationError(
+                    "Guest name is required".to_string(),
+                ));
+            }
 
-        Ok(BookingWithRoom {
-            booking,
-            room: Some(room),
+            // Guests are always charged the quoted rate
+            let quote = PricingService::quote_with_conn(conn, &room, check_in_date, check_out_date)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            quote.enforce_min_stay()?;
+
+            let new_booking = NewBooking {
+                reference: &reference,
+                guest_name: guest_name.trim(),
+                room_id,
+                check_in_date,
+                check_out_date,
+                created_by_user_id: Some(user_id),
+                creation_source: "guest",
+                price: quote.total_price,
+                rate_plan_id: quote.rate_plan_id,
+                group_id: None,
+                guest_count,
+            };
+
+            let booking: Booking = diesel::insert_into(bookings::table)
+                .values(&new_booking)
+                .get_result(conn)
+                .map_err(AppError::from)?;
+
+            Ok(BookingWithRoom {
+                booking,
+                room: Some(room),
+            })
         })
     }
 
@@ -563,41 +951,36 @@ impl BookingService {
         Ok(booking_with_room)
     }
 
-    /// Cancel a booking for a specific user
+    /// Cancel a booking for a specific user, applying the cancellation policy
     pub fn cancel_guest_booking(
         &self,
         booking_id: Uuid,
         user_id: Uuid,
-    ) -> AppResult<Booking> {
+    ) -> AppResult<CancelledBooking> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let booking: Booking = bookings::table
-            .find(booking_id)
-            .first(&mut conn)
-            .map_err(|_| AppError::NotFound("Booking not found".to_string()))?;
-
-        if booking.created_by_user_id != Some(user_id) {
-            return Err(AppError::NotFound("Booking not found".to_string()));
-        }
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking: Booking = bookings::table
+                .find(booking_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound("Booking not found".to_string()))?;
 
-        if booking.status != BookingStatus::Upcoming {
-            return Err(AppError::InvalidStatusTransition(
-                "Only upcoming bookings can be cancelled".to_string(),
-            ));
-        }
+            if booking.created_by_user_id != Some(user_id) {
+                return Err(AppError::NotFound("Booking not found".to_string()));
+            }
 
-        let update = UpdateBooking {
-            status: Some(BookingStatus::Cancelled),
-            ..Default::default()
-        };
+            if booking.status != BookingStatus::Upcoming {
+                return Err(AppError::InvalidStatusTransition(
+                    "Only upcoming bookings can be cancelled".to_string(),
+                ));
+            }
 
-        diesel::update(bookings::table.find(booking_id))
-            .set(&update)
-            .get_result(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))
+            Self::cancel_with_conn(conn, booking, user_id)
+        })
     }
 
     /// Check in a guest
@@ -607,208 +990,1167 @@ impl BookingService {
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        fn app_error_to_diesel(e: AppError) -> diesel::result::Error {
-            diesel::result::Error::DatabaseError(
-                diesel::result::DatabaseErrorKind::CheckViolation,
-                Box::new(StringError(e.to_string())) as Box<dyn DatabaseErrorInformation + Send + Sync>,
-            )
+        conn.transaction::<_, diesel::result::Error, _>(|conn| Self::check_in_with_conn(conn, booking_id))
+            .map_err(AppError::from)
+    }
+
+    /// Check-in steps using an existing connection (run inside a transaction)
+    fn check_in_with_conn(conn: &mut PgConnection, booking_id: Uuid) -> QueryResult<Booking> {
+        let booking: Booking = bookings::table
+            .find(booking_id)
+            .first(conn)
+            .map_err(|_| diesel::result::Error::NotFound)?;
+
+        if !booking.status.can_transition_to(BookingStatus::CheckedIn) {
+            return Err(app_error_to_diesel(AppError::InvalidStatusTransition(format!(
+                "Cannot check in booking with status {:?}",
+                booking.status
+            ))));
         }
 
-        conn.transaction::<_, diesel::result::Error, _>(|conn| {
-            let booking: Booking = bookings::table
-                .find(booking_id)
-                .first(conn)
-                .map_err(|_| diesel::result::Error::NotFound)?;
+        let today = Utc::now().date_naive();
 
-            if !booking.status.can_transition_to(BookingStatus::CheckedIn) {
-                return Err(app_error_to_diesel(AppError::InvalidStatusTransition(format!(
-                    "Cannot check in booking with status {:?}",
-                    booking.status
-                ))));
-            }
+        // Check-in opens on the check-in date; late arrivals (within the
+        // no-show grace period) may still check in before the stay ends.
+        if booking.check_in_date > today || booking.check_out_date <= today {
+            return Err(app_error_to_diesel(AppError::ValidationError(format!(
+                "Check-in is only allowed from the check-in date ({}) until the day before check-out ({}). Today is {}.",
+                booking.check_in_date,
+                booking.check_out_date,
+                today
+            ))));
+        }
 
-            let today = Utc::now().date_naive();
-            
-            // Only allow check-in on the actual check-in date
-            if booking.check_in_date != today {
-                return Err(app_error_to_diesel(AppError::ValidationError(format!(
-                    "Check-in is only allowed on the check-in date ({}). Today is {}.",
-                    booking.check_in_date,
-                    today
-                ))));
-            }
+        let current_room: Room = rooms::table
+            .find(booking.room_id)
+            .first(conn)
+            .map_err(|_| diesel::result::Error::NotFound)?;
 
-            let current_room: Room = rooms::table
-                .find(booking.room_id)
-                .first(conn)
-                .map_err(|_| diesel::result::Error::NotFound)?;
+        if current_room.status == RoomStatus::Maintenance {
+            return Err(app_error_to_diesel(AppError::RoomUnavailable(
+                "Room is under maintenance".to_string(),
+            )));
+        }
 
-            if current_room.status == RoomStatus::Maintenance {
-                return Err(app_error_to_diesel(AppError::RoomUnavailable(
-                    "Room is under maintenance".to_string(),
-                )));
+        // Check if room is currently occupied
+        if current_room.status == RoomStatus::Occupied {
+            // Check if there's an active booking that's still checked in
+            let active_booking: Option<Booking> = bookings::table
+                .filter(bookings::room_id.eq(booking.room_id))
+                .filter(bookings::id.ne(booking_id))
+                .filter(bookings::status.eq(BookingStatus::CheckedIn))
+                .filter(bookings::check_out_date.gt(today))
+                .first(conn)
+                .optional()
+                .map_err(|e| diesel::result::Error::DatabaseError(
+                    diesel::result::DatabaseErrorKind::CheckViolation,
+                    Box::new(StringError(format!("Failed to check room occupancy: {}", e))) as Box<dyn DatabaseErrorInformation + Send + Sync>,
+                ))?;
+
+            if let Some(active) = active_booking {
+                return Err(app_error_to_diesel(AppError::RoomUnavailable(format!(
+                    "Room is currently occupied by another guest until {}",
+                    active.check_out_date
+                ))));
             }
+        }
 
-            // Check if room is currently occupied
-            if current_room.status == RoomStatus::Occupied {
-                // Check if there's an active booking that's still checked in
-                let active_booking: Option<Booking> = bookings::table
-                    .filter(bookings::room_id.eq(booking.room_id))
-                    .filter(bookings::id.ne(booking_id))
-                    .filter(bookings::status.eq(BookingStatus::CheckedIn))
-                    .filter(bookings::check_out_date.gt(today))
-                    .first(conn)
-                    .optional()
-                    .map_err(|e| diesel::result::Error::DatabaseError(
-                        diesel::result::DatabaseErrorKind::CheckViolation,
-                        Box::new(StringError(format!("Failed to check room occupancy: {}", e))) as Box<dyn DatabaseErrorInformation + Send + Sync>,
-                    ))?;
-
-                if active_booking.is_some() {
-                    let active = active_booking.unwrap();
-                    return Err(app_error_to_diesel(AppError::RoomUnavailable(format!(
-                        "Room is currently occupied by another guest until {}",
-                        active.check_out_date
-                    ))));
-                }
-            }
+        // Normal check-in: only update status
+        let rows_updated = diesel::update(
+            bookings::table
+                .find(booking_id)
+                .filter(bookings::status.eq(booking.status)),
+        )
+        .set(bookings::status.eq(BookingStatus::CheckedIn))
+        .execute(conn)?;
+
+        if rows_updated == 0 {
+            return Err(app_error_to_diesel(AppError::Conflict(
+                "Booking status was updated by another operation.".to_string(),
+            )));
+        }
 
-            // Normal check-in: only update status
-            let rows_updated = diesel::update(
-                bookings::table
-                    .find(booking_id)
-                    .filter(bookings::status.eq(booking.status)),
-            )
-            .set(bookings::status.eq(BookingStatus::CheckedIn))
+        // Set room to Occupied directly (no need to set Available first)
+        diesel::update(rooms::table.find(booking.room_id))
+            .set(rooms::status.eq(RoomStatus::Occupied))
             .execute(conn)?;
 
-            if rows_updated == 0 {
-                return Err(app_error_to_diesel(AppError::Conflict(
-                    "Booking status was updated by another operation.".to_string(),
-                )));
-            }
+        // Checked in before the room was cleaned (staff override)
+        HousekeepingService::cancel_room_cleans_with_conn(conn, booking.room_id)
+            .map_err(app_error_to_diesel)?;
 
-            // Set room to Occupied directly (no need to set Available first)
-            diesel::update(rooms::table.find(booking.room_id))
-                .set(rooms::status.eq(RoomStatus::Occupied))
-                .execute(conn)?;
+        bookings::table
+            .find(booking_id)
+            .first(conn)
+            .map_err(|_| diesel::result::Error::NotFound)
+    }
 
+    /// Check out a guest
+    ///
+    /// Check-out is refused while the folio has an unpaid balance unless
+    /// `allow_unpaid_balance` is set; the booking then records who let the
+    /// guest leave owing money.
+    pub fn check_out(
+        &self,
+        booking_id: Uuid,
+        _confirm_early: bool,
+        allow_unpaid_balance: bool,
+        actor_id: Uuid,
+    ) -> AppResult<Booking> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let booking = Self::check_out_with_conn(conn, booking_id, actor_id)?;
+
+            let summary = PaymentService::summary_with_conn(conn, &booking)?;
+            let overridden = Self::ensure_settled(
+                &summary.remaining_balance,
+                allow_unpaid_balance,
+                &format!("Booking {}", booking.reference),
+            )
+            .map_err(app_error_to_diesel)?;
+
+            if overridden {
+                return Self::record_unpaid_check_out_with_conn(
+                    conn,
+                    &[booking.id],
+                    actor_id,
+                    &summary.remaining_balance,
+                )
+                .map(|mut updated| updated.remove(0));
+            }
+
+            Ok(booking)
+        })
+        .map_err(AppError::from)
+    }
+
+    /// Refuse check-out while money is still owed, unless explicitly allowed
+    ///
+    /// Returns true when an unpaid balance was let through.
+    fn ensure_settled(balance: &BigDecimal, allow_unpaid_balance: bool, label: &str) -> AppResult<bool> {
+        if *balance <= BigDecimal::zero() {
+            return Ok(false);
+        }
+
+        if !allow_unpaid_balance {
+            return Err(AppError::UnpaidBalance(format!(
+                "{} has an outstanding balance of {}",
+                label, balance
+            )));
+        }
+
+        tracing::warn!("{} checked out with an unpaid balance of {}", label, balance);
+        Ok(true)
+    }
+
+    /// Record who let the bookings check out with `balance` still owed
+    fn record_unpaid_check_out_with_conn(
+        conn: &mut PgConnection,
+        booking_ids: &[Uuid],
+        actor_id: Uuid,
+        balance: &BigDecimal,
+    ) -> QueryResult<Vec<Booking>> {
+        diesel::update(bookings::table.filter(bookings::id.eq_any(booking_ids)))
+            .set((
+                bookings::unpaid_check_out_by_user_id.eq(actor_id),
+                bookings::unpaid_check_out_balance.eq(balance),
+            ))
+            .get_results(conn)
+    }
+
+    /// Check-out steps using an existing connection (run inside a transaction)
+    ///
+    /// `actor_id` is recorded on the stay change of an early or late departure.
+    fn check_out_with_conn(
+        conn: &mut PgConnection,
+        booking_id: Uuid,
+        actor_id: Uuid,
+    ) -> QueryResult<Booking> {
+        let booking: Booking = bookings::table
+            .find(booking_id)
+            .first(conn)
+            .map_err(|_| diesel::result::Error::NotFound)?;
+
+        if !booking.status.can_transition_to(BookingStatus::CheckedOut) {
+            return Err(app_error_to_diesel(AppError::InvalidStatusTransition(format!(
+                "Cannot check out booking with status {:?}.",
+                booking.status
+            ))));
+        }
+
+        // Allow staff to perform an early check-out without requiring an explicit
+        // confirmation flag. Previously we prevented check-outs when the
+        // booking's check-out date was in the future unless `confirm_early`
+        // was true — this blocked legitimate early check-outs initiated by
+        // staff or guests. For now, permit early check-outs unconditionally.
+        let _today = Utc::now().date_naive();
+
+        let current_room: Room = rooms::table
+            .find(booking.room_id)
+            .first(conn)
+            .map_err(|_| diesel::result::Error::NotFound)?;
+
+        // Note: can_transition_to already validated that only CheckedIn or Overstay
+        // bookings can check out, so no additional status check needed here.
+
+        // Set the booking's check_out_date to the actual departure (today, or
+        // at least `check_in_date + 1` since the database enforces
+        // `check_out_date > check_in_date`). An early or late departure is
+        // re-priced and recorded as a stay change; the original quote is kept.
+        let today = Utc::now().date_naive();
+        let min_checkout = booking.check_in_date + Duration::days(1);
+        let desired_checkout = if today > min_checkout { today } else { min_checkout };
+
+        let (new_price, original_price) = if desired_checkout == booking.check_out_date {
+            (booking.price.clone(), booking.original_price.clone())
+        } else {
+            let (change_type, reason) = if desired_checkout > booking.check_out_date {
+                (StayChangeType::Extend, "Late departure at check-out")
+            } else {
+                (StayChangeType::Shorten, "Early departure at check-out")
+            };
+
+            let new_price = Self::apply_stay_change_with_conn(
+                conn,
+                &booking,
+                &current_room,
+                desired_checkout,
+                change_type,
+                actor_id,
+                Some(reason),
+            )
+            .map_err(app_error_to_diesel)?;
+
+            (new_price, booking.original_price.clone().or(Some(booking.price.clone())))
+        };
+
+        // Perform the update and return the updated booking row. Using
+        // `get_result` surfaces database errors with better context.
+        let updated_booking: Booking = diesel::update(
             bookings::table
                 .find(booking_id)
+                .filter(bookings::status.eq(booking.status)),
+        )
+        .set((
+            bookings::status.eq(BookingStatus::CheckedOut),
+            bookings::check_out_date.eq(desired_checkout),
+            bookings::price.eq(new_price),
+            bookings::original_price.eq(original_price),
+        ))
+        .get_result(conn)?;
+
+        // Mark the room as dirty after successful check-out and queue its clean
+        diesel::update(rooms::table.find(booking.room_id))
+            .set(rooms::status.eq(RoomStatus::Dirty))
+            .execute(conn)?;
+        HousekeepingService::open_room_clean_with_conn(conn, booking.room_id, Some(booking.id))
+            .map_err(app_error_to_diesel)?;
+
+        Ok(updated_booking)
+    }
+
+    /// Cancel a booking, applying its cancellation policy
+    ///
+    /// Any refund is recorded as made by `actor_id`, on their open cashier shift.
+    pub fn cancel(&self, booking_id: Uuid, actor_id: Uuid) -> AppResult<CancelledBooking> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking: Booking = bookings::table
+                .find(booking_id)
+                .for_update()
                 .first(conn)
-                .map_err(|_| diesel::result::Error::NotFound)
+                .map_err(|_| AppError::NotFound(format!("Booking '{}' not found", booking_id)))?;
+
+            if !booking.status.can_transition_to(BookingStatus::Cancelled) {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Cannot cancel booking with status {:?}.",
+                    booking.status
+                )));
+            }
+
+            Self::cancel_with_conn(conn, booking, actor_id)
         })
-        .map_err(|e| AppError::from(e))
     }
 
-    /// Check out a guest
-    pub fn check_out(&self, booking_id: Uuid, _confirm_early: bool) -> AppResult<Booking> {
+    /// Cancellation steps for a locked, cancellable booking (run inside a transaction)
+    ///
+    /// Refunds what was paid beyond the policy penalty; the penalty becomes
+    /// the booking price so revenue reports keep what the hotel retained.
+    fn cancel_with_conn(
+        conn: &mut PgConnection,
+        booking: Booking,
+        actor_id: Uuid,
+    ) -> AppResult<CancelledBooking> {
+        let today = Utc::now().date_naive();
+        let cancellation = CancellationService::apply_with_conn(conn, &booking, today, actor_id)?;
+        PaymentIntentService::cancel_pending_with_conn(conn, booking.id)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let booking = diesel::update(bookings::table.find(booking.id))
+            .set((
+                bookings::status.eq(BookingStatus::Cancelled),
+                bookings::price.eq(&cancellation.penalty_amount),
+                bookings::original_price.eq(booking.original_price.clone().or(Some(booking.price.clone()))),
+            ))
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(CancelledBooking {
+            booking,
+            cancellation,
+        })
+    }
+
+    /// Change how many guests stay on an upcoming or in-house booking
+    pub fn set_guest_count(&self, booking_id: Uuid, guest_count: i32) -> AppResult<Booking> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        fn app_error_to_diesel(e: AppError) -> diesel::result::Error {
-            diesel::result::Error::DatabaseError(
-                diesel::result::DatabaseErrorKind::CheckViolation,
-                Box::new(StringError(e.to_string())) as Box<dyn DatabaseErrorInformation + Send + Sync>,
-            )
-        }
-
-        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+        conn.transaction::<_, AppError, _>(|conn| {
             let booking: Booking = bookings::table
                 .find(booking_id)
+                .for_update()
                 .first(conn)
-                .map_err(|_| diesel::result::Error::NotFound)?;
+                .map_err(|_| AppError::NotFound(format!("Booking '{}' not found", booking_id)))?;
 
-            if !booking.status.can_transition_to(BookingStatus::CheckedOut) {
-                return Err(app_error_to_diesel(AppError::InvalidStatusTransition(format!(
-                    "Cannot check out booking with status {:?}.",
+            if !booking.status.blocks_availability() {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Cannot change the guests of a booking with status {:?}",
                     booking.status
-                ))));
+                )));
             }
 
-            // Allow staff to perform an early check-out without requiring an explicit
-            // confirmation flag. Previously we prevented check-outs when the
-            // booking's check-out date was in the future unless `confirm_early`
-            // was true — this blocked legitimate early check-outs initiated by
-            // staff or guests. For now, permit early check-outs unconditionally.
-            let _today = Utc::now().date_naive();
-
-            let current_room: Room = rooms::table
+            let room: Room = rooms::table
                 .find(booking.room_id)
                 .first(conn)
-                .map_err(|_| diesel::result::Error::NotFound)?;
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            validate_guest_count(&room, guest_count)?;
+
+            diesel::update(bookings::table.find(booking_id))
+                .set(bookings::guest_count.eq(guest_count))
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))
+        })
+    }
+
+    /// Move an in-house guest to another room for the rest of the stay
+    ///
+    /// The target room must be Available and free until check-out. The old
+    /// room is left Dirty, the new one Occupied, and the nights spent in each
+    /// room are recorded in `booking_room_stays`.
+    pub fn move_booking(
+        &self,
+        booking_id: Uuid,
+        new_room_id: Uuid,
+        reason: Option<&str>,
+    ) -> AppResult<BookingWithRoom> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let reason = reason.map(str::trim).filter(|r| !r.is_empty());
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking: Booking = bookings::table
+                .find(booking_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Booking '{}' not found", booking_id)))?;
 
-            // Note: can_transition_to already validated that only CheckedIn or Overstay
-            // bookings can check out, so no additional status check needed here.
+            if booking.status != BookingStatus::CheckedIn {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Only checked-in bookings can be moved (status is {:?})",
+                    booking.status
+                )));
+            }
+
+            if booking.room_id == new_room_id {
+                return Err(AppError::ValidationError(
+                    "The guest is already in this room".to_string(),
+                ));
+            }
 
-            // Update the booking's check_out_date to today (or at least
-            // `check_in_date + 1`) and adjust the price so financial reports
-            // reflect the actual stay. Database enforces `check_out_date >
-            // check_in_date`, so ensure we respect that constraint.
             let today = Utc::now().date_naive();
-            let min_checkout = booking.check_in_date + Duration::days(1);
-            let desired_checkout = if today > min_checkout { today } else { min_checkout };
+            if today >= booking.check_out_date {
+                return Err(AppError::ValidationError(format!(
+                    "The stay ends on {}; check the guest out instead",
+                    booking.check_out_date
+                )));
+            }
+            let move_date = today.max(booking.check_in_date);
 
-            let nights_i64 = (desired_checkout - booking.check_in_date).num_days().max(1);
-            let nights = BigDecimal::from(nights_i64);
-            let new_price = current_room.price.clone() * nights;
+            let new_room: Room = rooms::table
+                .find(new_room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Room '{}' not found", new_room_id)))?;
 
-            // Perform the update and return the updated booking row. Using
-            // `get_result` surfaces database errors with better context.
-            let updated_booking: Booking = diesel::update(
-                bookings::table
-                    .find(booking_id)
-                    .filter(bookings::status.eq(booking.status)),
+            if new_room.status != RoomStatus::Available {
+                return Err(AppError::RoomUnavailable(format!(
+                    "Room {} is not ready for a guest (status is {:?})",
+                    new_room.number, new_room.status
+                )));
+            }
+
+            validate_guest_count(&new_room, booking.guest_count)?;
+
+            let available = Self::check_availability_with_conn(
+                conn,
+                new_room_id,
+                move_date,
+                booking.check_out_date,
+                Some(booking_id),
             )
-            .set((
-                bookings::status.eq(BookingStatus::CheckedOut),
-                bookings::check_out_date.eq(desired_checkout),
-                bookings::price.eq(new_price.clone()),
-            ))
-            .get_result(conn)?;
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-            // Mark the room as dirty after successful check-out
-            diesel::update(rooms::table.find(booking.room_id))
+            if !available {
+                return Err(AppError::RoomUnavailable(format!(
+                    "Room {} is booked before {}",
+                    new_room.number, booking.check_out_date
+                )));
+            }
+
+            // Close the current room's segment at the move date
+            let current_stay: Option<BookingRoomStay> = booking_room_stays::table
+                .filter(booking_room_stays::booking_id.eq(booking_id))
+                .order(booking_room_stays::start_date.desc())
+                .first(conn)
+                .optional()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            match current_stay {
+                Some(stay) if stay.start_date >= move_date => {
+                    // Moved again the same day: no night was spent in that room
+                    diesel::delete(booking_room_stays::table.find(stay.id))
+                        .execute(conn)
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                }
+                Some(stay) => {
+                    diesel::update(booking_room_stays::table.find(stay.id))
+                        .set(booking_room_stays::end_date.eq(move_date))
+                        .execute(conn)
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                }
+                None if booking.check_in_date < move_date => {
+                    diesel::insert_into(booking_room_stays::table)
+                        .values(&NewBookingRoomStay {
+                            booking_id,
+                            room_id: booking.room_id,
+                            start_date: booking.check_in_date,
+                            end_date: move_date,
+                            reason: None,
+                        })
+                        .execute(conn)
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                }
+                None => {}
+            }
+
+            diesel::insert_into(booking_room_stays::table)
+                .values(&NewBookingRoomStay {
+                    booking_id,
+                    room_id: new_room_id,
+                    start_date: move_date,
+                    end_date: booking.check_out_date,
+                    reason,
+                })
+                .execute(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            let old_room_id = booking.room_id;
+            let booking: Booking = diesel::update(bookings::table.find(booking_id))
+                .set(bookings::room_id.eq(new_room_id))
+                .get_result(conn)
+                .map_err(AppError::from)?;
+
+            diesel::update(rooms::table.find(old_room_id))
                 .set(rooms::status.eq(RoomStatus::Dirty))
-                .execute(conn)?;
+                .execute(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            HousekeepingService::open_room_clean_with_conn(conn, old_room_id, Some(booking_id))?;
 
-            Ok(updated_booking)
+            let room: Room = diesel::update(rooms::table.find(new_room_id))
+                .set(rooms::status.eq(RoomStatus::Occupied))
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            Ok(BookingWithRoom {
+                booking,
+                room: Some(room),
+            })
         })
-        .map_err(|e| AppError::from(e))
     }
 
-    /// Cancel a booking
-    pub fn cancel(&self, booking_id: Uuid) -> AppResult<Booking> {
+    /// Room history of a booking (empty if the guest never moved)
+    pub fn list_room_stays(&self, booking_id: Uuid) -> AppResult<Vec<BookingRoomStay>> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let booking: Booking = bookings::table
-            .find(booking_id)
-            .first(&mut conn)
-            .map_err(|_| AppError::NotFound(format!("Booking '{}' not found", booking_id)))?;
+        let exists: bool = diesel::select(diesel::dsl::exists(bookings::table.find(booking_id)))
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if !exists {
+            return Err(AppError::NotFound(format!("Booking '{}' not found", booking_id)));
+        }
+
+        booking_room_stays::table
+            .filter(booking_room_stays::booking_id.eq(booking_id))
+            .order(booking_room_stays::start_date.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Extend a stay to a later check-out date
+    ///
+    /// The room must be free for the added nights, which are priced from the
+    /// rate calendar on top of the agreed price.
+    pub fn extend_stay(
+        &self,
+        booking_id: Uuid,
+        new_check_out_date: NaiveDate,
+        actor_id: Uuid,
+        reason: Option<&str>,
+    ) -> AppResult<Booking> {
+        self.change_stay(booking_id, new_check_out_date, StayChangeType::Extend, actor_id, reason)
+    }
+
+    /// Shorten a stay to an earlier check-out date, taking the removed nights off the price
+    pub fn shorten_stay(
+        &self,
+        booking_id: Uuid,
+        new_check_out_date: NaiveDate,
+        actor_id: Uuid,
+        reason: Option<&str>,
+    ) -> AppResult<Booking> {
+        self.change_stay(booking_id, new_check_out_date, StayChangeType::Shorten, actor_id, reason)
+    }
+
+    fn change_stay(
+        &self,
+        booking_id: Uuid,
+        new_check_out_date: NaiveDate,
+        expected: StayChangeType,
+        actor_id: Uuid,
+        reason: Option<&str>,
+    ) -> AppResult<Booking> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let reason = reason.map(str::trim).filter(|r| !r.is_empty());
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking: Booking = bookings::table
+                .find(booking_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Booking '{}' not found", booking_id)))?;
+
+            let today = Utc::now().date_naive();
+            let change_type = validate_stay_change(&booking, new_check_out_date, today)?;
+            if change_type != expected {
+                return Err(AppError::ValidationError(format!(
+                    "New check-out date {} is {} the current one ({})",
+                    new_check_out_date,
+                    if change_type == StayChangeType::Extend { "after" } else { "before" },
+                    booking.check_out_date
+                )));
+            }
+
+            // Locked like a new booking, so a work order cannot be placed
+            // over the extra nights while they are checked
+            let room: Room = rooms::table
+                .find(booking.room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Room '{}' not found", booking.room_id)))?;
+
+            if change_type == StayChangeType::Extend {
+                let conflicts = Self::overlapping_bookings_with_conn(
+                    conn,
+                    room.id,
+                    booking.check_out_date,
+                    new_check_out_date,
+                    Some(booking_id),
+                )
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+                if let Some(next) = conflicts.first() {
+                    return Err(AppError::RoomUnavailable(format!(
+                        "Room {} is booked from {}; move the guest to extend past that date",
+                        room.number,
+                        next.check_in_date.max(booking.check_out_date)
+                    )));
+                }
+
+                Self::ensure_in_order_with_conn(conn, &room, booking.check_out_date, new_check_out_date)?;
+            }
 
-        if !booking.status.can_transition_to(BookingStatus::Cancelled) {
-            return Err(AppError::InvalidStatusTransition(format!(
-                "Cannot cancel booking with status {:?}.",
+            let new_price = Self::apply_stay_change_with_conn(
+                conn,
+                &booking,
+                &room,
+                new_check_out_date,
+                change_type,
+                actor_id,
+                reason,
+            )?;
+
+            // An overstay that is extended past today is a regular stay again
+            let status = if booking.status == BookingStatus::Overstay && new_check_out_date > today {
+                BookingStatus::CheckedIn
+            } else {
                 booking.status
-            )));
-        }
+            };
+
+            diesel::update(bookings::table.find(booking_id))
+                .set((
+                    bookings::check_out_date.eq(new_check_out_date),
+                    bookings::price.eq(new_price),
+                    bookings::original_price.eq(booking.original_price.clone().or(Some(booking.price.clone()))),
+                    bookings::status.eq(status),
+                ))
+                .get_result(conn)
+                .map_err(AppError::from)
+        })
+    }
 
-        let update = UpdateBooking {
-            status: Some(BookingStatus::Cancelled),
-            ..Default::default()
+    /// Price the changed nights, resize the room history and record the change
+    ///
+    /// Returns the new booking price; the caller updates the booking row.
+    fn apply_stay_change_with_conn(
+        conn: &mut PgConnection,
+        booking: &Booking,
+        room: &Room,
+        new_check_out_date: NaiveDate,
+        change_type: StayChangeType,
+        actor_id: Uuid,
+        reason: Option<&str>,
+    ) -> AppResult<BigDecimal> {
+        let (from, to) = match change_type {
+            StayChangeType::Extend => (booking.check_out_date, new_check_out_date),
+            StayChangeType::Shorten => (new_check_out_date, booking.check_out_date),
         };
 
-        diesel::update(bookings::table.find(booking_id))
-            .set(&update)
+        let quote = PricingService::quote_with_conn(conn, room, from, to)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let new_price = stay_change_price(&booking.price, &quote.total_price, change_type);
+
+        // Keep the room history of moved bookings in line with the new dates
+        diesel::delete(
+            booking_room_stays::table
+                .filter(booking_room_stays::booking_id.eq(booking.id))
+                .filter(booking_room_stays::start_date.ge(new_check_out_date)),
+        )
+        .execute(conn)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        diesel::update(
+            booking_room_stays::table
+                .filter(booking_room_stays::booking_id.eq(booking.id))
+                .filter(
+                    booking_room_stays::end_date
+                        .eq(booking.check_out_date)
+                        .or(booking_room_stays::end_date.gt(new_check_out_date)),
+                ),
+        )
+        .set(booking_room_stays::end_date.eq(new_check_out_date))
+        .execute(conn)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        diesel::insert_into(booking_stay_changes::table)
+            .values(&NewBookingStayChange {
+                booking_id: booking.id,
+                change_type,
+                old_check_out_date: booking.check_out_date,
+                new_check_out_date,
+                old_price: booking.price.clone(),
+                new_price: new_price.clone(),
+                reason,
+                changed_by_user_id: actor_id,
+            })
+            .execute(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(new_price)
+    }
+
+    /// List the check-out date changes of a booking, oldest first
+    pub fn list_stay_changes(&self, booking_id: Uuid) -> AppResult<Vec<BookingStayChange>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let exists: bool = diesel::select(diesel::dsl::exists(bookings::table.find(booking_id)))
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if !exists {
+            return Err(AppError::NotFound(format!("Booking '{}' not found", booking_id)));
+        }
+
+        booking_stay_changes::table
+            .filter(booking_stay_changes::booking_id.eq(booking_id))
+            .order(booking_stay_changes::created_at.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Generate a unique group reference in format GR-YYYYMMDD-XXXX
+    fn generate_group_reference(&self) -> AppResult<String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let today = Utc::now().format("%Y%m%d").to_string();
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..10 {
+            let suffix: String = (0..4)
+                .map(|_| {
+                    let idx = rng.gen_range(0..36);
+                    if idx < 10 {
+                        (b'0' + idx) as char
+                    } else {
+                        (b'A' + idx - 10) as char
+                    }
+                })
+                .collect();
+
+            let reference = format!("GR-{}-{}", today, suffix);
+
+            let exists: bool = diesel::select(diesel::dsl::exists(
+                booking_groups::table.filter(booking_groups::reference.eq(&reference)),
+            ))
             .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if !exists {
+                return Ok(reference);
+            }
+        }
+
+        Err(AppError::InternalError(
+            "Failed to generate unique group reference".to_string(),
+        ))
+    }
+
+    /// Create a group reservation with one booking per room.
+    ///
+    /// All rooms are locked and checked inside a single transaction, so either
+    /// every room is booked or none is.
+    pub fn create_group_booking(
+        &self,
+        request: &CreateGroupBookingRequest,
+        created_by_user_id: Option<Uuid>,
+    ) -> AppResult<BookingGroupWithBookings> {
+        if request.name.trim().is_empty() {
+            return Err(AppError::ValidationError(
+                "Group name is required".to_string(),
+            ));
+        }
+
+        if request.contact_name.trim().is_empty() || request.contact_name.len() > 100 {
+            return Err(AppError::ValidationError(
+                "Contact name is required and must be 100 characters or less".to_string(),
+            ));
+        }
+
+        if request.rooms.is_empty() {
+            return Err(AppError::ValidationError(
+                "A group booking needs at least one room".to_string(),
+            ));
+        }
+
+        if request.rooms.len() > MAX_GROUP_ROOMS {
+            return Err(AppError::ValidationError(format!(
+                "A group booking can hold at most {} rooms",
+                MAX_GROUP_ROOMS
+            )));
+        }
+
+        // Resolve per-room dates and names up front
+        let mut stays = Vec::with_capacity(request.rooms.len());
+        for room_request in &request.rooms {
+            let check_in_date = room_request.check_in_date.unwrap_or(request.check_in_date);
+            let check_out_date = room_request.check_out_date.unwrap_or(request.check_out_date);
+            self.validate_dates(check_in_date, check_out_date)?;
+
+            let guest_name = room_request
+                .guest_name
+                .as_deref()
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .unwrap_or(request.contact_name.trim());
+
+            if guest_name.len() > 100 {
+                return Err(AppError::ValidationError(
+                    "Guest name must be 100 characters or less".to_string(),
+                ));
+            }
+
+            if room_request.price.as_ref().is_some_and(|price| price < &BigDecimal::zero()) {
+                return Err(AppError::ValidationError(
+                    "Room price cannot be negative".to_string(),
+                ));
+            }
+
+            stays.push((room_request, guest_name, check_in_date, check_out_date));
+        }
+
+        let mut room_ids: Vec<Uuid> = request.rooms.iter().map(|r| r.room_id).collect();
+        room_ids.sort();
+        room_ids.dedup();
+        if room_ids.len() != request.rooms.len() {
+            return Err(AppError::ValidationError(
+                "Each room can only appear once in a group booking".to_string(),
+            ));
+        }
+
+        let group_reference = self.generate_group_reference()?;
+        let mut references = Vec::with_capacity(stays.len());
+        while references.len() < stays.len() {
+            let reference = self.generate_reference()?;
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let (group, created) = conn
+            .transaction::<_, diesel::result::Error, _>(|conn| {
+                // Lock the rooms in a stable order so concurrent group
+                // bookings on overlapping rooms serialize instead of deadlocking
+                let locked_rooms: Vec<Room> = rooms::table
+                    .filter(rooms::id.eq_any(&room_ids))
+                    .order(rooms::id.asc())
+                    .for_update()
+                    .load(conn)?;
+
+                if locked_rooms.len() != room_ids.len() {
+                    let missing: Vec<String> = room_ids
+                        .iter()
+                        .filter(|id| !locked_rooms.iter().any(|r| r.id == **id))
+                        .map(|id| id.to_string())
+                        .collect();
+                    return Err(app_error_to_diesel(AppError::ValidationError(format!(
+                        "Rooms not found: {}",
+                        missing.join(", ")
+                    ))));
+                }
+
+                // Check every room before inserting anything
+                let mut unavailable = Vec::new();
+                let mut quotes = Vec::with_capacity(stays.len());
+                for (room_request, _, check_in_date, check_out_date) in &stays {
+                    let room = locked_rooms
+                        .iter()
+                        .find(|r| r.id == room_request.room_id)
+                        .expect("room was locked above");
+
+                    validate_guest_count(room, room_request.guest_count.unwrap_or(1))
+                        .map_err(app_error_to_diesel)?;
+                    Self::ensure_in_order_with_conn(conn, room, *check_in_date, *check_out_date)
+                        .map_err(app_error_to_diesel)?;
+
+                    if !Self::check_availability_with_conn(
+                        conn,
+                        room.id,
+                        *check_in_date,
+                        *check_out_date,
+                        None,
+                    )? {
+                        unavailable.push(room.number.clone());
+                        continue;
+                    }
+
+                    let quote =
+                        PricingService::quote_with_conn(conn, room, *check_in_date, *check_out_date)?;
+                    quote.enforce_min_stay().map_err(app_error_to_diesel)?;
+                    quotes.push(quote);
+                }
+
+                if !unavailable.is_empty() {
+                    return Err(app_error_to_diesel(AppError::RoomUnavailable(format!(
+                        "Rooms not available for the selected dates: {}",
+                        unavailable.join(", ")
+                    ))));
+                }
+
+                let group: BookingGroup = diesel::insert_into(booking_groups::table)
+                    .values(&NewBookingGroup {
+                        reference: &group_reference,
+                        name: request.name.trim(),
+                        contact_name: request.contact_name.trim(),
+                        notes: request.notes.as_deref(),
+                        created_by_user_id,
+                    })
+                    .get_result(conn)?;
+
+                let mut created = Vec::with_capacity(stays.len());
+                for (((room_request, guest_name, check_in_date, check_out_date), quote), reference) in
+                    stays.iter().zip(quotes).zip(&references)
+                {
+                    let booking: Booking = diesel::insert_into(bookings::table)
+                        .values(&NewBooking {
+                            reference,
+                            guest_name,
+                            room_id: room_request.room_id,
+                            check_in_date: *check_in_date,
+                            check_out_date: *check_out_date,
+                            created_by_user_id,
+                            creation_source: "staff",
+                            price: room_request.price.clone().unwrap_or(quote.total_price),
+                            rate_plan_id: quote.rate_plan_id,
+                            group_id: Some(group.id),
+                            guest_count: room_request.guest_count.unwrap_or(1),
+                        })
+                        .get_result(conn)?;
+
+                    let room = locked_rooms.iter().find(|r| r.id == booking.room_id).cloned();
+                    created.push(BookingWithRoom { booking, room });
+                }
+
+                Ok((group, created))
+            })
+            .map_err(AppError::from)?;
+
+        Ok(BookingGroupWithBookings {
+            group,
+            bookings: created,
+            payment_summary: None,
+        })
+    }
+
+    /// Get a group reservation with its room bookings
+    pub fn get_group(&self, group_id: Uuid) -> AppResult<BookingGroupWithBookings> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let group: BookingGroup = booking_groups::table
+            .find(group_id)
+            .first(&mut conn)
+            .map_err(|_| AppError::NotFound(format!("Booking group with ID '{}' not found", group_id)))?;
+
+        let results: Vec<(Booking, Room)> = bookings::table
+            .inner_join(rooms::table)
+            .filter(bookings::group_id.eq(group_id))
+            .order(rooms::number.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(BookingGroupWithBookings {
+            group,
+            bookings: results
+                .into_iter()
+                .map(|(booking, room)| BookingWithRoom { booking, room: Some(room) })
+                .collect(),
+            payment_summary: None,
+        })
+    }
+
+    /// List group reservations, newest first
+    pub fn list_groups(&self) -> AppResult<Vec<BookingGroup>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        booking_groups::table
+            .order(booking_groups::created_at.desc())
+            .load(&mut conn)
             .map_err(|e| AppError::DatabaseError(e.to_string()))
     }
 
+    /// Load the ids of group members currently in one of `statuses`
+    fn group_member_ids(
+        conn: &mut PgConnection,
+        group_id: Uuid,
+        statuses: &[BookingStatus],
+    ) -> QueryResult<Vec<Uuid>> {
+        let exists: bool = diesel::select(diesel::dsl::exists(booking_groups::table.find(group_id)))
+            .get_result(conn)?;
+        if !exists {
+            return Err(diesel::result::Error::NotFound);
+        }
+
+        bookings::table
+            .filter(bookings::group_id.eq(group_id))
+            .filter(bookings::status.eq_any(statuses))
+            .order(bookings::id.asc())
+            .select(bookings::id)
+            .load(conn)
+    }
+
+    /// Check in the upcoming bookings of a group that are due, in one transaction
+    ///
+    /// Rooms of the group may arrive on different dates: only bookings whose
+    /// stay covers today are checked in, later arrivals stay Upcoming.
+    pub fn check_in_group(&self, group_id: Uuid) -> AppResult<BookingGroupWithBookings> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let today = Utc::now().date_naive();
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let member_ids = Self::group_member_ids(conn, group_id, &[BookingStatus::Upcoming])?;
+            if member_ids.is_empty() {
+                return Err(app_error_to_diesel(AppError::InvalidStatusTransition(
+                    "Group has no upcoming bookings to check in".to_string(),
+                )));
+            }
+
+            let due_ids: Vec<Uuid> = bookings::table
+                .filter(bookings::id.eq_any(&member_ids))
+                .filter(bookings::check_in_date.le(today))
+                .filter(bookings::check_out_date.gt(today))
+                .order(bookings::id.asc())
+                .select(bookings::id)
+                .load(conn)?;
+            if due_ids.is_empty() {
+                return Err(app_error_to_diesel(AppError::ValidationError(format!(
+                    "No upcoming booking of the group is due to check in today ({})",
+                    today
+                ))));
+            }
+
+            for booking_id in due_ids {
+                Self::check_in_with_conn(conn, booking_id)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => {
+                AppError::NotFound(format!("Booking group with ID '{}' not found", group_id))
+            }
+            other => AppError::from(other),
+        })?;
+
+        self.get_group(group_id)
+    }
+
+    /// Check out every in-house booking of a group in one transaction
+    ///
+    /// Groups often settle on a single booking, so the unpaid balance check
+    /// uses the combined balance of the whole group.
+    pub fn check_out_group(
+        &self,
+        group_id: Uuid,
+        allow_unpaid_balance: bool,
+        actor_id: Uuid,
+    ) -> AppResult<BookingGroupWithBookings> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let member_ids = Self::group_member_ids(
+                conn,
+                group_id,
+                &[BookingStatus::CheckedIn, BookingStatus::Overstay],
+            )?;
+            if member_ids.is_empty() {
+                return Err(app_error_to_diesel(AppError::InvalidStatusTransition(
+                    "Group has no checked-in bookings to check out".to_string(),
+                )));
+            }
+
+            for booking_id in &member_ids {
+                Self::check_out_with_conn(conn, *booking_id, actor_id)?;
+            }
+
+            let members: Vec<Booking> = bookings::table
+                .filter(bookings::group_id.eq(group_id))
+                .load(conn)?;
+            let mut balance = BigDecimal::zero();
+            for member in &members {
+                let summary = PaymentService::summary_with_conn(conn, member)?;
+                if member.status == BookingStatus::Cancelled {
+                    balance -= &summary.total_paid;
+                } else {
+                    balance += &summary.remaining_balance;
+                }
+            }
+
+            if Self::ensure_settled(&balance, allow_unpaid_balance, "Group")
+                .map_err(app_error_to_diesel)?
+            {
+                Self::record_unpaid_check_out_with_conn(conn, &member_ids, actor_id, &balance)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| match e {
+            diesel::result::Error::NotFound => {
+                AppError::NotFound(format!("Booking group with ID '{}' not found", group_id))
+            }
+            other => AppError::from(other),
+        })?;
+
+        self.get_group(group_id)
+    }
+
+    /// Cancel every upcoming booking of a group in one transaction
+    ///
+    /// Each room is refunded according to its own cancellation policy.
+    pub fn cancel_group(&self, group_id: Uuid, actor_id: Uuid) -> AppResult<BookingGroupWithBookings> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let member_ids = Self::group_member_ids(conn, group_id, &[BookingStatus::Upcoming])
+                .map_err(|e| match e {
+                    diesel::result::Error::NotFound => AppError::NotFound(format!(
+                        "Booking group with ID '{}' not found",
+                        group_id
+                    )),
+                    other => AppError::from(other),
+                })?;
+            if member_ids.is_empty() {
+                return Err(AppError::InvalidStatusTransition(
+                    "Group has no upcoming bookings to cancel".to_string(),
+                ));
+            }
+
+            for booking_id in member_ids {
+                let booking: Booking = bookings::table
+                    .find(booking_id)
+                    .for_update()
+                    .first(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                Self::cancel_with_conn(conn, booking, actor_id)?;
+            }
+            Ok(())
+        })?;
+
+        self.get_group(group_id)
+    }
+
     /// Calculate financial metrics for a room
     #[allow(dead_code)]
     pub fn calculate_room_financials(
@@ -817,80 +2159,104 @@ impl BookingService {
         start_date: Option<NaiveDate>,
         end_date: Option<NaiveDate>,
     ) -> AppResult<RoomFinancials> {
-        self.calculate_room_financials_with_payments(room_id, start_date, end_date, false)
+        self.calculate_room_financials_by_basis(room_id, start_date, end_date, RevenueBasis::Checkout)
     }
 
-    /// Calculate financial metrics for a room, optionally using actual payments
-    pub fn calculate_room_financials_with_payments(
+    /// Calculate financial metrics for a room with revenue on the given basis
+    pub fn calculate_room_financials_by_basis(
         &self,
         room_id: Uuid,
         start_date: Option<NaiveDate>,
         end_date: Option<NaiveDate>,
-        _use_payments: bool,
+        basis: RevenueBasis,
     ) -> AppResult<RoomFinancials> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let build_query = || {
-            let mut query = bookings::table
-                .into_boxed()
-                .filter(bookings::room_id.eq(room_id))
-                .filter(bookings::status.eq(BookingStatus::CheckedOut));
-
-            // If both start and end are provided, select bookings that overlap
-            // the date interval [start, end] (i.e. check_in_date <= end AND
-            // check_out_date >= start). If only one bound is provided, fall
-            // back to filtering by check_out_date as before.
-            if let (Some(start), Some(end)) = (start_date, end_date) {
-                query = query.filter(bookings::check_in_date.le(end));
+        // Bookings currently in the room plus those moved through it
+        let mut query = bookings::table
+            .into_boxed()
+            .filter(
+                bookings::room_id.eq(room_id).or(bookings::id.eq_any(
+                    booking_room_stays::table
+                        .filter(booking_room_stays::room_id.eq(room_id))
+                        .select(booking_room_stays::booking_id),
+                )),
+            )
+            .filter(bookings::status.eq(BookingStatus::CheckedOut));
+
+        // If both start and end are provided, select bookings that overlap
+        // the date interval [start, end] (i.e. check_in_date <= end AND
+        // check_out_date >= start). If only one bound is provided, fall
+        // back to filtering by check_out_date as before.
+        if let (Some(start), Some(end)) = (start_date, end_date) {
+            query = query.filter(bookings::check_in_date.le(end));
+            query = query.filter(bookings::check_out_date.ge(start));
+        } else {
+            if let Some(start) = start_date {
                 query = query.filter(bookings::check_out_date.ge(start));
-            } else {
-                if let Some(start) = start_date {
-                    query = query.filter(bookings::check_out_date.ge(start));
-                }
-                if let Some(end) = end_date {
-                    query = query.filter(bookings::check_out_date.le(end));
-                }
             }
+            if let Some(end) = end_date {
+                query = query.filter(bookings::check_out_date.le(end));
+            }
+        }
 
-            query
-        };
-
-        let total_revenue: Option<BigDecimal> = build_query()
-            .select(sum(bookings::price))
-            .first(&mut conn)
+        let room_bookings: Vec<Booking> = query
+            .load(&mut conn)
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let booking_count: i64 = build_query()
-            .select(count(bookings::id))
-            .first(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let booking_ids: Vec<Uuid> = room_bookings.iter().map(|b| b.id).collect();
+        let stays = Self::stays_with_conn(&mut conn, &booking_ids)?;
 
-        let average_revenue: Option<BigDecimal> = build_query()
-            .select(avg(bookings::price))
-            .first(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let (revenue, booking_count) = match basis {
+            RevenueBasis::Checkout => {
+                let rules = TaxService::active_rules_with_conn(&mut conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+                // Revenue of moved bookings is split by the nights spent in each room
+                let mut revenue = RevenueSplit::default();
+                let mut booking_count: i64 = 0;
+                for booking in &room_bookings {
+                    if nights_in_room(booking, &stays, room_id, None) > 0 || booking.room_id == room_id {
+                        let share = room_revenue_share(booking, &stays, room_id);
+                        revenue.add(&apply_taxes(
+                            &share,
+                            &applicable_rules(&rules, TaxTarget::Room, booking.check_in_date),
+                        ));
+                        booking_count += 1;
+                    }
+                }
+                (revenue, booking_count)
+            }
+            basis => {
+                let entries =
+                    Self::revenue_entries_with_conn(&mut conn, basis, Some(room_id), start_date, end_date)?;
+
+                let mut revenue = RevenueSplit::default();
+                let mut counted: HashSet<Uuid> = HashSet::new();
+                for (_, booking_id, split) in &entries {
+                    revenue.add(split);
+                    counted.insert(*booking_id);
+                }
+                (revenue, counted.len() as i64)
+            }
+        };
+        let total_revenue = revenue.gross;
+
+        let average_revenue = if booking_count > 0 {
+            Some((&total_revenue / BigDecimal::from(booking_count)).round(2))
+        } else {
+            None
+        };
 
         let occupancy_rate = if let (Some(start), Some(end)) = (start_date, end_date) {
             let total_days = (end - start).num_days() as f64 + 1.0;
             if total_days > 0.0 {
-                let bookings_in_period: Vec<Booking> = bookings::table
-                    .filter(bookings::room_id.eq(room_id))
-                    .filter(bookings::status.eq(BookingStatus::CheckedOut))
-                    .filter(bookings::check_in_date.le(end))
-                    .filter(bookings::check_out_date.ge(start))
-                    .load(&mut conn)
-                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-                let total_days_occupied: i64 = bookings_in_period
+                let total_days_occupied: i64 = room_bookings
                     .iter()
-                    .map(|b| {
-                        let booking_start = b.check_in_date.max(start);
-                        let booking_end = b.check_out_date.min(end);
-                        (booking_end - booking_start).num_days().max(0)
-                    })
+                    .map(|b| nights_in_room(b, &stays, room_id, Some((start, end))))
                     .sum();
 
                 (total_days_occupied as f64 / total_days) * 100.0
@@ -903,10 +2269,12 @@ impl BookingService {
 
         Ok(RoomFinancials {
             room_id,
-            total_revenue: total_revenue.unwrap_or_else(|| BigDecimal::from(0)),
+            total_revenue,
+            net_revenue: revenue.net,
+            tax_revenue: revenue.tax,
             booking_count,
             average_revenue,
-            occupancy_rate: occupancy_rate.min(100.0).max(0.0),
+            occupancy_rate: occupancy_rate.clamp(0.0, 100.0),
         })
     }
 
@@ -916,72 +2284,68 @@ impl BookingService {
         room_id: Option<Uuid>,
         start_date: Option<NaiveDate>,
         end_date: Option<NaiveDate>,
-    ) -> AppResult<Vec<(NaiveDate, BigDecimal)>> {
+        basis: RevenueBasis,
+    ) -> AppResult<Vec<(NaiveDate, RevenueSplit)>> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        let mut query = bookings::table
-            .into_boxed()
-            .filter(bookings::status.eq(BookingStatus::CheckedOut));
-
-        if let Some(room_id) = room_id {
-            query = query.filter(bookings::room_id.eq(room_id));
-        }
-
-        if let Some(start) = start_date {
-            query = query.filter(bookings::check_out_date.ge(start));
-        }
-
-        if let Some(end) = end_date {
-            query = query.filter(bookings::check_out_date.le(end));
-        }
-
-        let bookings_list: Vec<Booking> = query
-            .order(bookings::check_out_date.asc())
-            .load(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let entries = Self::revenue_entries_with_conn(&mut conn, basis, room_id, start_date, end_date)?;
 
         // Group by date and sum revenue
-        use std::collections::HashMap;
-        let mut revenue_by_date: HashMap<NaiveDate, BigDecimal> = HashMap::new();
-
-        for booking in bookings_list {
-            let date = booking.check_out_date;
-            let revenue = revenue_by_date.entry(date).or_insert_with(|| BigDecimal::from(0));
-            *revenue += &booking.price;
+        let mut revenue_by_date: BTreeMap<NaiveDate, RevenueSplit> = BTreeMap::new();
+        for (date, _, split) in &entries {
+            revenue_by_date.entry(*date).or_default().add(split);
         }
 
-        let mut result: Vec<(NaiveDate, BigDecimal)> = revenue_by_date.into_iter().collect();
-        result.sort_by_key(|(date, _)| *date);
-
-        Ok(result)
+        Ok(revenue_by_date.into_iter().collect())
     }
 
     /// Get booking history for a specific room
+    ///
+    /// Under the cash and accrual bases these are the bookings with revenue
+    /// in the date range rather than those checked out in it.
     pub fn get_room_booking_history(
         &self,
         room_id: Uuid,
         start_date: Option<NaiveDate>,
         end_date: Option<NaiveDate>,
+        basis: RevenueBasis,
     ) -> AppResult<Vec<BookingWithRoom>> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
+        // Include stays moved out of the room before check-out
         let mut query = bookings::table
             .into_boxed()
-            .filter(bookings::room_id.eq(room_id))
-            .filter(bookings::status.eq(BookingStatus::CheckedOut));
-
-        if let Some(start) = start_date {
-            query = query.filter(bookings::check_out_date.ge(start));
-        }
+            .filter(
+                bookings::room_id.eq(room_id).or(bookings::id.eq_any(
+                    booking_room_stays::table
+                        .filter(booking_room_stays::room_id.eq(room_id))
+                        .select(booking_room_stays::booking_id),
+                )),
+            );
+
+        if basis == RevenueBasis::Checkout {
+            query = query.filter(bookings::status.eq(BookingStatus::CheckedOut));
+
+            if let Some(start) = start_date {
+                query = query.filter(bookings::check_out_date.ge(start));
+            }
 
-        if let Some(end) = end_date {
-            query = query.filter(bookings::check_out_date.le(end));
+            if let Some(end) = end_date {
+                query = query.filter(bookings::check_out_date.le(end));
+            }
+        } else {
+            let booking_ids: HashSet<Uuid> =
+                Self::revenue_entries_with_conn(&mut conn, basis, Some(room_id), start_date, end_date)?
+                    .into_iter()
+                    .map(|(_, booking_id, _)| booking_id)
+                    .collect();
+            query = query.filter(bookings::id.eq_any(booking_ids.into_iter().collect::<Vec<_>>()));
         }
 
         let booking_list: Vec<Booking> = query
@@ -1006,6 +2370,190 @@ impl BookingService {
         Ok(result)
     }
 
+    /// Revenue recognised on the given basis as `(date, booking_id, split)`,
+    /// optionally only what was earned by one room
+    ///
+    /// - checkout: booking price on the check-out date of checked-out bookings
+    /// - cash: payments and refunds on the day they were taken, leaving out
+    ///   voided payments and moves to and from guest credit
+    /// - accrual: each night of a stay that has started, on the night, and
+    ///   the penalty or fee kept on a cancelled or no-show booking on the day
+    ///   its status changed
+    fn revenue_entries_with_conn(
+        conn: &mut PgConnection,
+        basis: RevenueBasis,
+        room_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+    ) -> AppResult<Vec<(NaiveDate, Uuid, TaxBreakdown)>> {
+        let in_range = |date: NaiveDate| {
+            start_date.map_or(true, |start| date >= start) && end_date.map_or(true, |end| date <= end)
+        };
+
+        let room_booking_ids: Option<Vec<Uuid>> = match room_id {
+            Some(room_id) => Some(
+                bookings::table
+                    .filter(
+                        bookings::room_id.eq(room_id).or(bookings::id.eq_any(
+                            booking_room_stays::table
+                                .filter(booking_room_stays::room_id.eq(room_id))
+                                .select(booking_room_stays::booking_id),
+                        )),
+                    )
+                    .select(bookings::id)
+                    .load(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let rules = TaxService::active_rules_with_conn(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let room_rules = |booking: &Booking| applicable_rules(&rules, TaxTarget::Room, booking.check_in_date);
+
+        let mut entries = Vec::new();
+        match basis {
+            RevenueBasis::Checkout => {
+                let mut query = bookings::table
+                    .into_boxed()
+                    .filter(bookings::status.eq(BookingStatus::CheckedOut));
+                if let Some(ids) = &room_booking_ids {
+                    query = query.filter(bookings::id.eq_any(ids));
+                }
+                if let Some(start) = start_date {
+                    query = query.filter(bookings::check_out_date.ge(start));
+                }
+                if let Some(end) = end_date {
+                    query = query.filter(bookings::check_out_date.le(end));
+                }
+
+                let checked_out: Vec<Booking> = query
+                    .load(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                let ids: Vec<Uuid> = checked_out.iter().map(|b| b.id).collect();
+                let stays = Self::stays_with_conn(conn, &ids)?;
+
+                for booking in &checked_out {
+                    let amount = match room_id {
+                        Some(room_id) => room_revenue_share(booking, &stays, room_id),
+                        None => booking.price.clone(),
+                    };
+                    entries.push((booking.check_out_date, booking.id, apply_taxes(&amount, &room_rules(booking))));
+                }
+            }
+            RevenueBasis::Cash => {
+                let mut query = payments::table
+                    .inner_join(bookings::table)
+                    .filter(payments::voided_at.is_null())
+                    .filter(payments::payment_method.ne(GUEST_CREDIT_PAYMENT_METHOD))
+                    .select((Payment::as_select(), Booking::as_select()))
+                    .into_boxed();
+                if let Some(ids) = &room_booking_ids {
+                    query = query.filter(bookings::id.eq_any(ids));
+                }
+                if let Some(start) = start_date {
+                    query = query.filter(payments::created_at.ge(start.and_hms_opt(0, 0, 0).unwrap().and_utc()));
+                }
+                if let Some(end) = end_date {
+                    let next_day = (end + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc();
+                    query = query.filter(payments::created_at.lt(next_day));
+                }
+
+                let collected: Vec<(Payment, Booking)> = query
+                    .load(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                let ids: Vec<Uuid> = collected.iter().map(|(_, b)| b.id).collect();
+                let stays = Self::stays_with_conn(conn, &ids)?;
+
+                for (payment, booking) in &collected {
+                    let amount = match room_id {
+                        Some(room_id) => room_payment_share(&payment.amount, booking, &stays, room_id),
+                        None => payment.amount.clone(),
+                    };
+                    if amount.is_zero() {
+                        continue;
+                    }
+                    let booking_taxes = apply_taxes(&booking.price, &room_rules(booking));
+                    entries.push((
+                        payment.created_at.date_naive(),
+                        booking.id,
+                        split_collected(&amount, &booking_taxes),
+                    ));
+                }
+            }
+            RevenueBasis::Accrual => {
+                let mut query = bookings::table.into_boxed().filter(bookings::status.eq_any([
+                    BookingStatus::CheckedIn,
+                    BookingStatus::Overstay,
+                    BookingStatus::CheckedOut,
+                ]));
+                if let Some(ids) = &room_booking_ids {
+                    query = query.filter(bookings::id.eq_any(ids));
+                }
+                if let Some(start) = start_date {
+                    query = query.filter(bookings::check_out_date.gt(start));
+                }
+                if let Some(end) = end_date {
+                    query = query.filter(bookings::check_in_date.le(end));
+                }
+
+                let stayed: Vec<Booking> = query
+                    .load(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                let ids: Vec<Uuid> = stayed.iter().map(|b| b.id).collect();
+                let stays = Self::stays_with_conn(conn, &ids)?;
+
+                for booking in &stayed {
+                    let rules = room_rules(booking);
+                    for (night, night_room, amount) in nightly_revenue(booking, &stays) {
+                        if room_id.map_or(true, |room_id| room_id == night_room) && in_range(night) {
+                            entries.push((night, booking.id, apply_taxes(&amount, &rules)));
+                        }
+                    }
+                }
+
+                // Penalties and fees kept on bookings that never stayed are
+                // earned on the day the booking was cancelled or marked
+                let mut kept_query = bookings::table
+                    .into_boxed()
+                    .filter(bookings::status.eq_any([BookingStatus::Cancelled, BookingStatus::NoShow]))
+                    .filter(bookings::price.gt(BigDecimal::zero()));
+                if let Some(ids) = &room_booking_ids {
+                    kept_query = kept_query.filter(bookings::id.eq_any(ids));
+                }
+                if let Some(start) = start_date {
+                    kept_query = kept_query
+                        .filter(bookings::status_changed_at.ge(start.and_hms_opt(0, 0, 0).unwrap().and_utc()));
+                }
+                if let Some(end) = end_date {
+                    let next_day = (end + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc();
+                    kept_query = kept_query.filter(bookings::status_changed_at.lt(next_day));
+                }
+
+                let kept: Vec<Booking> = kept_query
+                    .load(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+                for booking in &kept {
+                    entries.push((
+                        booking.status_changed_at.date_naive(),
+                        booking.id,
+                        apply_taxes(&booking.price, &room_rules(booking)),
+                    ));
+                }
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn stays_with_conn(conn: &mut PgConnection, booking_ids: &[Uuid]) -> AppResult<Vec<BookingRoomStay>> {
+        booking_room_stays::table
+            .filter(booking_room_stays::booking_id.eq_any(booking_ids))
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
     /// Handle stale bookings
     pub fn handle_stale_bookings(&self, conn: &mut PgConnection) -> QueryResult<(usize, usize)> {
         use crate::schema::bookings::dsl::*;
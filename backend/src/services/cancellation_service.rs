@@ -0,0 +1,416 @@
+use bigdecimal::{BigDecimal, One, RoundingMode, Zero};
+use chrono::NaiveDate;
+use diesel::dsl::sum;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, CancellationOutcome, CancellationPenaltyType, CancellationPolicy, NewCancellationPolicy,
+    NewPayment, PaymentType, RoomType, UpdateCancellationPolicy,
+};
+use crate::schema::{cancellation_policies, payments, rate_plans, rooms};
+use crate::services::exchange_rate_service::BASE_CURRENCY;
+use crate::services::guest_credit_service::GUEST_CREDIT_PAYMENT_METHOD;
+use crate::services::{FolioService, GuestCreditService, PaymentService, TaxService};
+
+/// Penalty kept by the hotel when a booking is cancelled on `today`
+///
+/// No policy (or an inactive one) means free cancellation. The penalty never
+/// exceeds the booking price.
+pub fn cancellation_penalty(
+    policy: Option<&CancellationPolicy>,
+    booking_price: &BigDecimal,
+    check_in_date: NaiveDate,
+    check_out_date: NaiveDate,
+    today: NaiveDate,
+) -> BigDecimal {
+    let policy = match policy {
+        Some(p) if p.is_active => p,
+        _ => return BigDecimal::zero(),
+    };
+
+    let days_before = (check_in_date - today).num_days();
+    if days_before >= i64::from(policy.free_cancellation_days) {
+        return BigDecimal::zero();
+    }
+
+    let penalty = match policy.penalty_type {
+        CancellationPenaltyType::Percent => {
+            booking_price * &policy.penalty_value / BigDecimal::from(100)
+        }
+        CancellationPenaltyType::Nights => {
+            let nights = (check_out_date - check_in_date).num_days().max(1);
+            let charged_nights = policy.penalty_value.clone().min(BigDecimal::from(nights));
+            booking_price * charged_nights / BigDecimal::from(nights)
+        }
+    };
+
+    penalty.round(2).min(booking_price.clone())
+}
+
+/// Amount to refund once the penalty is kept (never negative)
+pub fn refund_due(total_paid: &BigDecimal, penalty: &BigDecimal) -> BigDecimal {
+    (total_paid - penalty).max(BigDecimal::zero())
+}
+
+/// Split a refund across the methods the guest paid with
+///
+/// `paid_by_method` is the net amount (payments minus earlier refunds) per
+/// method. Each method gets back its share of the refund in proportion to
+/// what was paid with it, and never more than that, so guest credit only goes
+/// back to the guest's account for what was paid from it. Rounding leftovers
+/// go to the first methods with room for them. Without any net payment the
+/// whole refund is made as `other`.
+pub fn split_refund(
+    refund: &BigDecimal,
+    paid_by_method: &[(String, BigDecimal)],
+) -> Vec<(String, BigDecimal)> {
+    let paid: Vec<&(String, BigDecimal)> = paid_by_method
+        .iter()
+        .filter(|(_, amount)| *amount > BigDecimal::zero())
+        .collect();
+    let total_paid: BigDecimal = paid.iter().map(|(_, amount)| amount.clone()).sum();
+
+    if total_paid <= BigDecimal::zero() {
+        return vec![("other".to_string(), refund.clone())];
+    }
+
+    let mut shares: Vec<(String, BigDecimal)> = paid
+        .iter()
+        .map(|(method, amount)| {
+            let share = (refund * amount / &total_paid)
+                .with_scale_round(2, RoundingMode::Down)
+                .min(amount.clone());
+            (method.clone(), share)
+        })
+        .collect();
+
+    let mut left: BigDecimal = refund - shares.iter().map(|(_, share)| share.clone()).sum::<BigDecimal>();
+    for ((_, share), (_, amount)) in shares.iter_mut().zip(paid.iter()) {
+        if left <= BigDecimal::zero() {
+            break;
+        }
+        let extra = (amount - &*share).min(left.clone());
+        *share += &extra;
+        left -= extra;
+    }
+
+    shares.retain(|(_, share)| *share > BigDecimal::zero());
+    shares
+}
+
+/// Validate the fields shared by new and updated policies
+fn validate_policy_fields(
+    name: &str,
+    free_cancellation_days: i32,
+    penalty_type: CancellationPenaltyType,
+    penalty_value: &BigDecimal,
+) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Cancellation policy name is required".to_string(),
+        ));
+    }
+
+    if name.len() > 100 {
+        return Err(AppError::ValidationError(
+            "Cancellation policy name must be 100 characters or less".to_string(),
+        ));
+    }
+
+    if free_cancellation_days < 0 {
+        return Err(AppError::ValidationError(
+            "Free cancellation days cannot be negative".to_string(),
+        ));
+    }
+
+    if *penalty_value < BigDecimal::zero() {
+        return Err(AppError::ValidationError(
+            "Penalty cannot be negative".to_string(),
+        ));
+    }
+
+    let max_percent = BigDecimal::from(100);
+    if penalty_type == CancellationPenaltyType::Percent && penalty_value > &max_percent {
+        return Err(AppError::ValidationError(
+            "Percentage penalty cannot exceed 100".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Cancellation policy service: policy management and refund calculation
+pub struct CancellationService {
+    pool: DbPool,
+}
+
+impl CancellationService {
+    /// Create a new CancellationService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List all cancellation policies
+    pub fn list_policies(&self) -> AppResult<Vec<CancellationPolicy>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        cancellation_policies::table
+            .order(cancellation_policies::name.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Get a cancellation policy by ID
+    pub fn get_policy(&self, policy_id: Uuid) -> AppResult<CancellationPolicy> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        cancellation_policies::table
+            .find(policy_id)
+            .first(&mut conn)
+            .map_err(|_| {
+                AppError::NotFound(format!("Cancellation policy with ID '{}' not found", policy_id))
+            })
+    }
+
+    /// Create a cancellation policy
+    pub fn create_policy(&self, new_policy: NewCancellationPolicy) -> AppResult<CancellationPolicy> {
+        validate_policy_fields(
+            &new_policy.name,
+            new_policy.free_cancellation_days,
+            new_policy.penalty_type,
+            &new_policy.penalty_value,
+        )?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if let (Some(room_type), true) = (new_policy.room_type, new_policy.is_active) {
+            Self::ensure_no_room_type_default(&mut conn, room_type, None)?;
+        }
+
+        diesel::insert_into(cancellation_policies::table)
+            .values(&new_policy)
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Update a cancellation policy
+    pub fn update_policy(
+        &self,
+        policy_id: Uuid,
+        update: UpdateCancellationPolicy,
+    ) -> AppResult<CancellationPolicy> {
+        let current = self.get_policy(policy_id)?;
+
+        validate_policy_fields(
+            update.name.as_deref().unwrap_or(&current.name),
+            update.free_cancellation_days.unwrap_or(current.free_cancellation_days),
+            update.penalty_type.unwrap_or(current.penalty_type),
+            update.penalty_value.as_ref().unwrap_or(&current.penalty_value),
+        )?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let room_type = update.room_type.unwrap_or(current.room_type);
+        let is_active = update.is_active.unwrap_or(current.is_active);
+        if let (Some(room_type), true) = (room_type, is_active) {
+            Self::ensure_no_room_type_default(&mut conn, room_type, Some(policy_id))?;
+        }
+
+        let has_changes = update.name.is_some()
+            || update.free_cancellation_days.is_some()
+            || update.penalty_type.is_some()
+            || update.penalty_value.is_some()
+            || update.room_type.is_some()
+            || update.is_active.is_some();
+        if !has_changes {
+            return Ok(current);
+        }
+
+        diesel::update(cancellation_policies::table.find(policy_id))
+            .set(&update)
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Delete a cancellation policy (rate plans using it fall back to the room type default)
+    pub fn delete_policy(&self, policy_id: Uuid) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let deleted = diesel::delete(cancellation_policies::table.find(policy_id))
+            .execute(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!(
+                "Cancellation policy with ID '{}' not found",
+                policy_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn ensure_no_room_type_default(
+        conn: &mut PgConnection,
+        room_type: RoomType,
+        exclude_id: Option<Uuid>,
+    ) -> AppResult<()> {
+        let mut query = cancellation_policies::table
+            .filter(cancellation_policies::room_type.eq(room_type))
+            .filter(cancellation_policies::is_active.eq(true))
+            .into_boxed();
+
+        if let Some(id) = exclude_id {
+            query = query.filter(cancellation_policies::id.ne(id));
+        }
+
+        let existing: Option<CancellationPolicy> = query
+            .first(conn)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if let Some(existing) = existing {
+            return Err(AppError::Conflict(format!(
+                "Policy '{}' is already the active default for {:?} rooms",
+                existing.name, room_type
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Policy governing a booking: its rate plan's policy, else the room type default
+    pub fn resolve_policy_with_conn(
+        conn: &mut PgConnection,
+        booking: &Booking,
+    ) -> QueryResult<Option<CancellationPolicy>> {
+        if let Some(rate_plan_id) = booking.rate_plan_id {
+            let plan_policy: Option<CancellationPolicy> = rate_plans::table
+                .inner_join(cancellation_policies::table)
+                .filter(rate_plans::id.eq(rate_plan_id))
+                .filter(cancellation_policies::is_active.eq(true))
+                .select(CancellationPolicy::as_select())
+                .first(conn)
+                .optional()?;
+
+            if plan_policy.is_some() {
+                return Ok(plan_policy);
+            }
+        }
+
+        let room_type: RoomType = rooms::table
+            .find(booking.room_id)
+            .select(rooms::room_type)
+            .first(conn)?;
+
+        cancellation_policies::table
+            .filter(cancellation_policies::room_type.eq(room_type))
+            .filter(cancellation_policies::is_active.eq(true))
+            .first(conn)
+            .optional()
+    }
+
+    /// Work out the penalty for cancelling `booking` today and post the refund
+    ///
+    /// Runs inside the caller's transaction; the caller updates the booking
+    /// status and price afterwards.
+    pub fn apply_with_conn(
+        conn: &mut PgConnection,
+        booking: &Booking,
+        today: NaiveDate,
+        actor_id: Uuid,
+    ) -> AppResult<CancellationOutcome> {
+        let policy = Self::resolve_policy_with_conn(conn, booking)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let penalty = cancellation_penalty(
+            policy.as_ref(),
+            &booking.price,
+            booking.check_in_date,
+            booking.check_out_date,
+            today,
+        );
+
+        // The guest keeps owing tax on the penalty and any folio charges
+        let penalty_gross = TaxService::room_breakdown_with_conn(conn, &penalty, booking.check_in_date)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .gross;
+        let charges = FolioService::charges_totals_with_conn(conn, booking.id)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let total_paid = PaymentService::total_paid_with_conn(conn, booking.id)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let refund = refund_due(&total_paid, &(penalty_gross + charges.gross));
+
+        let mut refund_payment_ids = Vec::new();
+        if refund > BigDecimal::zero() {
+            // Refund each method its share of what was paid with it
+            let paid_by_method: Vec<(String, BigDecimal)> = payments::table
+                .filter(payments::booking_id.eq(booking.id))
+                .filter(payments::voided_at.is_null())
+                .group_by(payments::payment_method)
+                .select((payments::payment_method, sum(payments::amount)))
+                .order(payments::payment_method.asc())
+                .load::<(String, Option<BigDecimal>)>(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                .into_iter()
+                .map(|(method, amount)| (method, amount.unwrap_or_else(BigDecimal::zero)))
+                .collect();
+
+            let notes = match &policy {
+                Some(p) => format!("Cancellation refund ({})", p.name),
+                None => "Cancellation refund".to_string(),
+            };
+
+            for (method, amount) in split_refund(&refund, &paid_by_method) {
+                let payment = PaymentService::create_payment_with_conn(
+                    conn,
+                    NewPayment {
+                        booking_id: booking.id,
+                        amount: -amount,
+                        payment_type: PaymentType::Refund,
+                        payment_method: method,
+                        notes: Some(notes.clone()),
+                        created_by_user_id: actor_id,
+                        currency: BASE_CURRENCY.to_string(),
+                        exchange_rate: BigDecimal::one(),
+                        original_amount: None,
+                    },
+                )?;
+
+                // Credit the guest paid with goes back to their account
+                if payment.payment_method == GUEST_CREDIT_PAYMENT_METHOD {
+                    GuestCreditService::return_refund_with_conn(conn, booking, &payment, actor_id)?;
+                }
+
+                refund_payment_ids.push(payment.id);
+            }
+        }
+
+        Ok(CancellationOutcome {
+            policy_id: policy.as_ref().map(|p| p.id),
+            policy_name: policy.map(|p| p.name),
+            penalty_amount: penalty,
+            refund_amount: refund,
+            refund_payment_ids,
+        })
+    }
+}
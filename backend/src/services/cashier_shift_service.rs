@@ -0,0 +1,295 @@
+use std::collections::BTreeMap;
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    CashierShift, CashierShiftStatus, NewCashierShift, Payment, ShiftMethodTotal, ShiftReport,
+};
+use crate::schema::{cashier_shifts, payments, users};
+use crate::services::exchange_rate_service::BASE_CURRENCY;
+
+/// Payment method that goes through the cash drawer
+pub const CASH_PAYMENT_METHOD: &str = "cash";
+
+/// Check a drawer amount (opening float or counted cash)
+pub fn validate_cash_amount(amount: &BigDecimal, field: &str) -> AppResult<()> {
+    if *amount < BigDecimal::zero() {
+        return Err(AppError::ValidationError(format!(
+            "{} cannot be negative",
+            field
+        )));
+    }
+    Ok(())
+}
+
+/// Per-method and per-currency totals and net cash of a shift's payments
+///
+/// Voided payments are left out; refunds are negative and reduce the totals.
+/// Net cash only counts base currency cash, which is what the drawer float
+/// and count are in.
+pub fn summarize_shift_payments(payments: &[Payment]) -> (Vec<ShiftMethodTotal>, BigDecimal) {
+    let mut totals: BTreeMap<(&str, &str), (i64, BigDecimal, BigDecimal)> = BTreeMap::new();
+
+    for payment in payments.iter().filter(|p| p.voided_at.is_none()) {
+        let entry = totals
+            .entry((payment.payment_method.as_str(), payment.currency.as_str()))
+            .or_insert_with(|| (0, BigDecimal::zero(), BigDecimal::zero()));
+        entry.0 += 1;
+        entry.1 += payment.amount_in_currency();
+        entry.2 += &payment.amount;
+    }
+
+    let net_cash = totals
+        .get(&(CASH_PAYMENT_METHOD, BASE_CURRENCY))
+        .map(|(_, _, base_total)| base_total.clone())
+        .unwrap_or_else(BigDecimal::zero);
+
+    let method_totals = totals
+        .into_iter()
+        .map(|((method, currency), (payment_count, total, base_total))| ShiftMethodTotal {
+            payment_method: method.to_string(),
+            currency: currency.to_string(),
+            payment_count,
+            total,
+            base_total,
+        })
+        .collect();
+
+    (method_totals, net_cash)
+}
+
+/// Reconcile a cash drawer: `(expected_cash, over_short)`
+///
+/// Expected cash is the opening float plus the net cash taken; over/short is
+/// what was counted minus that, positive when the drawer is over.
+pub fn reconcile_drawer(
+    opening_float: &BigDecimal,
+    net_cash: &BigDecimal,
+    counted_cash: &BigDecimal,
+) -> (BigDecimal, BigDecimal) {
+    let expected = opening_float + net_cash;
+    let over_short = counted_cash - &expected;
+    (expected, over_short)
+}
+
+/// Cashier shift service: opening and closing cash drawer shifts
+pub struct CashierShiftService {
+    pool: DbPool,
+}
+
+impl CashierShiftService {
+    /// Create a new CashierShiftService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Open a shift for a cashier with the cash float in the drawer
+    pub fn open_shift(
+        &self,
+        user_id: Uuid,
+        opening_float: BigDecimal,
+        opening_notes: Option<String>,
+    ) -> AppResult<CashierShift> {
+        validate_cash_amount(&opening_float, "Opening float")?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let already_open = Self::open_shift_id_with_conn(&mut conn, user_id)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        if already_open.is_some() {
+            return Err(AppError::Conflict(
+                "You already have an open shift; close it first".to_string(),
+            ));
+        }
+
+        diesel::insert_into(cashier_shifts::table)
+            .values(&NewCashierShift {
+                user_id,
+                opening_float,
+                opening_notes,
+            })
+            .get_result(&mut conn)
+            .map_err(|e| match e {
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => AppError::Conflict(
+                    "You already have an open shift; close it first".to_string(),
+                ),
+                e => AppError::DatabaseError(e.to_string()),
+            })
+    }
+
+    /// ID of the cashier's open shift, if any
+    ///
+    /// The row is share-locked so a shift cannot close while a payment is
+    /// being attached to it.
+    pub fn open_shift_id_with_conn(conn: &mut PgConnection, user_id: Uuid) -> QueryResult<Option<Uuid>> {
+        cashier_shifts::table
+            .filter(cashier_shifts::user_id.eq(user_id))
+            .filter(cashier_shifts::status.eq(CashierShiftStatus::Open))
+            .select(cashier_shifts::id)
+            .for_share()
+            .first(conn)
+            .optional()
+    }
+
+    /// Report on the cashier's open shift, with the cash expected so far
+    pub fn current_shift(&self, user_id: Uuid) -> AppResult<ShiftReport> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let shift: CashierShift = cashier_shifts::table
+            .filter(cashier_shifts::user_id.eq(user_id))
+            .filter(cashier_shifts::status.eq(CashierShiftStatus::Open))
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound("You have no open shift".to_string()))?;
+
+        Self::report_with_conn(&mut conn, shift)
+    }
+
+    /// Close the cashier's open shift with the cash counted in the drawer
+    pub fn close_current_shift(
+        &self,
+        user_id: Uuid,
+        counted_cash: BigDecimal,
+        closing_notes: Option<String>,
+    ) -> AppResult<ShiftReport> {
+        validate_cash_amount(&counted_cash, "Counted cash")?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let shift: CashierShift = cashier_shifts::table
+                .filter(cashier_shifts::user_id.eq(user_id))
+                .filter(cashier_shifts::status.eq(CashierShiftStatus::Open))
+                .for_update()
+                .first(conn)
+                .optional()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+                .ok_or_else(|| AppError::NotFound("You have no open shift".to_string()))?;
+
+            let shift_payments = Self::shift_payments_with_conn(conn, shift.id)?;
+            let (_, net_cash) = summarize_shift_payments(&shift_payments);
+            let (expected_cash, over_short) =
+                reconcile_drawer(&shift.opening_float, &net_cash, &counted_cash);
+
+            let shift: CashierShift = diesel::update(cashier_shifts::table.find(shift.id))
+                .set((
+                    cashier_shifts::status.eq(CashierShiftStatus::Closed),
+                    cashier_shifts::closed_at.eq(Utc::now()),
+                    cashier_shifts::counted_cash.eq(&counted_cash),
+                    cashier_shifts::expected_cash.eq(&expected_cash),
+                    cashier_shifts::over_short.eq(&over_short),
+                    cashier_shifts::closing_notes.eq(closing_notes),
+                ))
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            Self::report_with_conn(conn, shift)
+        })
+    }
+
+    /// Report on any shift
+    pub fn get_shift_report(&self, shift_id: Uuid) -> AppResult<ShiftReport> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let shift: CashierShift = cashier_shifts::table
+            .find(shift_id)
+            .first(&mut conn)
+            .map_err(|_| AppError::NotFound(format!("Shift with ID '{}' not found", shift_id)))?;
+
+        Self::report_with_conn(&mut conn, shift)
+    }
+
+    /// Reports on the shifts opened in a date range, optionally for one cashier
+    pub fn list_shift_reports(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        user_id: Option<Uuid>,
+    ) -> AppResult<Vec<ShiftReport>> {
+        if end_date < start_date {
+            return Err(AppError::ValidationError(
+                "End date must not be before start date".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let from = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let to = (end_date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        let mut query = cashier_shifts::table
+            .filter(cashier_shifts::opened_at.ge(from))
+            .filter(cashier_shifts::opened_at.lt(to))
+            .into_boxed();
+        if let Some(user_id) = user_id {
+            query = query.filter(cashier_shifts::user_id.eq(user_id));
+        }
+
+        let shifts: Vec<CashierShift> = query
+            .order(cashier_shifts::opened_at.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        shifts
+            .into_iter()
+            .map(|shift| Self::report_with_conn(&mut conn, shift))
+            .collect()
+    }
+
+    fn shift_payments_with_conn(conn: &mut PgConnection, shift_id: Uuid) -> AppResult<Vec<Payment>> {
+        payments::table
+            .filter(payments::shift_id.eq(shift_id))
+            .order(payments::created_at.asc())
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    fn report_with_conn(conn: &mut PgConnection, shift: CashierShift) -> AppResult<ShiftReport> {
+        let shift_payments = Self::shift_payments_with_conn(conn, shift.id)?;
+        let (method_totals, net_cash) = summarize_shift_payments(&shift_payments);
+
+        let (full_name, username): (Option<String>, Option<String>) = users::table
+            .find(shift.user_id)
+            .select((users::full_name, users::username))
+            .first(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // A closed shift keeps the figures it was closed with
+        let expected_cash = shift
+            .expected_cash
+            .clone()
+            .unwrap_or_else(|| &shift.opening_float + &net_cash);
+
+        Ok(ShiftReport {
+            cashier_name: full_name.or(username),
+            method_totals,
+            net_cash,
+            expected_cash,
+            over_short: shift.over_short.clone(),
+            payments: shift_payments,
+            shift,
+        })
+    }
+}
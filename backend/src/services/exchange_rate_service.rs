@@ -0,0 +1,165 @@
+use bigdecimal::{BigDecimal, One, Zero};
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{ExchangeRate, NewExchangeRate};
+use crate::schema::exchange_rates;
+
+/// Currency all prices, balances and reports are kept in
+pub const BASE_CURRENCY: &str = "VND";
+
+/// Exchange rate request payload
+#[derive(Debug, Deserialize)]
+pub struct SetExchangeRateRequest {
+    pub currency: String,
+    /// Base currency units for one unit of `currency`
+    pub rate: BigDecimal,
+    /// Defaults to today
+    pub effective_date: Option<NaiveDate>,
+}
+
+/// Upper-case a currency code and check it is three letters (ISO 4217 style)
+pub fn normalize_currency(code: &str) -> AppResult<String> {
+    let code = code.trim().to_ascii_uppercase();
+    if code.len() != 3 || !code.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(AppError::ValidationError(format!(
+            "Invalid currency code '{}'; use a three-letter code such as USD",
+            code
+        )));
+    }
+    Ok(code)
+}
+
+/// Convert an amount in a foreign currency to the base currency
+///
+/// Rounded to whole base currency units like the rest of the money handling.
+pub fn convert_to_base(amount: &BigDecimal, rate: &BigDecimal) -> BigDecimal {
+    (amount * rate).round(0)
+}
+
+/// Exchange rate service: the admin-maintained rate table
+pub struct ExchangeRateService {
+    pool: DbPool,
+}
+
+impl ExchangeRateService {
+    /// Create a new ExchangeRateService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List rates, newest first, optionally for one currency
+    pub fn list_rates(&self, currency: Option<&str>) -> AppResult<Vec<ExchangeRate>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut query = exchange_rates::table.into_boxed();
+        if let Some(currency) = currency {
+            query = query.filter(exchange_rates::currency.eq(normalize_currency(currency)?));
+        }
+
+        query
+            .order((exchange_rates::currency.asc(), exchange_rates::effective_date.desc()))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Set the rate for a currency from a date on, replacing any rate already
+    /// set for that date
+    pub fn set_rate(
+        &self,
+        request: SetExchangeRateRequest,
+        actor_id: Uuid,
+    ) -> AppResult<ExchangeRate> {
+        let currency = normalize_currency(&request.currency)?;
+        if currency == BASE_CURRENCY {
+            return Err(AppError::ValidationError(format!(
+                "{} is the base currency and needs no exchange rate",
+                BASE_CURRENCY
+            )));
+        }
+
+        if request.rate <= BigDecimal::zero() {
+            return Err(AppError::ValidationError(
+                "Exchange rate must be positive".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        diesel::insert_into(exchange_rates::table)
+            .values(&NewExchangeRate {
+                currency,
+                rate: request.rate.clone(),
+                effective_date: request
+                    .effective_date
+                    .unwrap_or_else(|| Utc::now().date_naive()),
+                created_by_user_id: actor_id,
+            })
+            .on_conflict((exchange_rates::currency, exchange_rates::effective_date))
+            .do_update()
+            .set((
+                exchange_rates::rate.eq(&request.rate),
+                exchange_rates::created_by_user_id.eq(actor_id),
+            ))
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Delete a rate (payments keep the rate they were taken at)
+    pub fn delete_rate(&self, rate_id: Uuid) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let deleted = diesel::delete(exchange_rates::table.find(rate_id))
+            .execute(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!(
+                "Exchange rate with ID '{}' not found",
+                rate_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Rate in effect for a currency on a date: the latest one set on or
+    /// before it (always 1 for the base currency)
+    pub fn rate_on_with_conn(
+        conn: &mut PgConnection,
+        currency: &str,
+        date: NaiveDate,
+    ) -> AppResult<BigDecimal> {
+        if currency == BASE_CURRENCY {
+            return Ok(BigDecimal::one());
+        }
+
+        exchange_rates::table
+            .filter(exchange_rates::currency.eq(currency))
+            .filter(exchange_rates::effective_date.le(date))
+            .order(exchange_rates::effective_date.desc())
+            .select(exchange_rates::rate)
+            .first(conn)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| {
+                AppError::ValidationError(format!(
+                    "No exchange rate for {} on or before {}",
+                    currency, date
+                ))
+            })
+    }
+}
@@ -0,0 +1,599 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use axum::body::{Body, Bytes};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use rust_xlsxwriter::{Format, Workbook};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, BookingStatus, InventoryItem, PaymentLedgerEntry, RoomType,
+};
+use crate::schema::{bookings, inventory_items, payment_ledger_entries, rooms, users};
+use crate::services::booking_service::RevenueBasis;
+use crate::services::BookingService;
+
+/// Rows read from the database per page
+pub const EXPORT_PAGE_SIZE: i64 = 500;
+
+/// Chunks buffered ahead of a slow client
+const EXPORT_CHANNEL_CAPACITY: usize = 8;
+
+/// Size of the chunks an XLSX file is sent in
+const XLSX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Rows a worksheet holds, including the header row
+const XLSX_MAX_ROWS: u32 = 1_048_576;
+
+/// File format of an export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ExportFormat {
+    /// MIME type of the file
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        }
+    }
+
+    /// File extension
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Xlsx => "xlsx",
+        }
+    }
+}
+
+/// One cell of an exported row
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExportCell {
+    Text(String),
+    Number(BigDecimal),
+    Integer(i64),
+    Empty,
+}
+
+impl ExportCell {
+    /// Text of an optional value, empty when there is none
+    pub fn text(value: Option<impl Into<String>>) -> Self {
+        value.map_or(ExportCell::Empty, |v| ExportCell::Text(v.into()))
+    }
+
+    /// Date as `YYYY-MM-DD`
+    pub fn date(date: NaiveDate) -> Self {
+        ExportCell::Text(date.format("%Y-%m-%d").to_string())
+    }
+
+    /// Timestamp as `YYYY-MM-DD HH:MM:SS` in UTC
+    pub fn timestamp(at: DateTime<Utc>) -> Self {
+        ExportCell::Text(at.format("%Y-%m-%d %H:%M:%S").to_string())
+    }
+
+    /// Enum value as it is spelled in the JSON API
+    pub fn label(value: impl Serialize) -> Self {
+        match serde_json::to_value(value) {
+            Ok(serde_json::Value::String(s)) => ExportCell::Text(s),
+            _ => ExportCell::Empty,
+        }
+    }
+
+    /// Cell as CSV text (numbers exactly as stored, text made formula-safe)
+    pub fn to_text(&self) -> String {
+        match self {
+            ExportCell::Text(s) => formula_safe(s),
+            ExportCell::Number(n) => n.to_string(),
+            ExportCell::Integer(i) => i.to_string(),
+            ExportCell::Empty => String::new(),
+        }
+    }
+}
+
+/// Text a spreadsheet will show as written rather than run as a formula
+///
+/// Guest names, notes and reasons are free text, so a leading `=`, `+`, `-`,
+/// `@`, tab or carriage return is escaped with a `'`.
+pub fn formula_safe(text: &str) -> String {
+    if text.starts_with(['=', '+', '-', '@', '\t', '\r']) {
+        format!("'{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// CSV text of a page of rows
+pub fn csv_rows(rows: &[Vec<ExportCell>]) -> AppResult<Vec<u8>> {
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+    for row in rows {
+        writer
+            .write_record(row.iter().map(ExportCell::to_text))
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+    }
+    writer
+        .into_inner()
+        .map_err(|e| AppError::InternalError(e.to_string()))
+}
+
+/// Rows of an export, read a page at a time
+pub trait ExportSource: Send {
+    /// File and worksheet name
+    fn name(&self) -> &'static str;
+
+    /// Column headers
+    fn headers(&self) -> &'static [&'static str];
+
+    /// Next page of rows; `None` once every row has been read
+    fn next_page(&mut self) -> AppResult<Option<Vec<Vec<ExportCell>>>>;
+}
+
+/// Stream an export as a response body
+///
+/// Rows are read and written on a blocking thread and handed over a small
+/// channel, so only a page of rows is held at a time and a slow client holds
+/// back the database reads. An XLSX file has to be zipped whole, so its rows
+/// go to a temporary file first (the worksheet is written in constant memory)
+/// and the file is sent in chunks.
+pub fn stream_export(format: ExportFormat, mut source: Box<dyn ExportSource>) -> Body {
+    let (tx, rx) = mpsc::channel::<io::Result<Bytes>>(EXPORT_CHANNEL_CAPACITY);
+
+    tokio::task::spawn_blocking(move || {
+        let result = match format {
+            ExportFormat::Csv => write_csv(source.as_mut(), &tx),
+            ExportFormat::Xlsx => write_xlsx(source.as_mut(), &tx),
+        };
+        if let Err(e) = result {
+            tracing::error!("Export {} failed: {}", source.name(), e);
+            let _ = tx.blocking_send(Err(io::Error::other(e.to_string())));
+        }
+    });
+
+    Body::from_stream(ReceiverStream::new(rx))
+}
+
+/// Send a chunk; false once the client has gone away
+fn send_chunk(tx: &mpsc::Sender<io::Result<Bytes>>, chunk: Vec<u8>) -> bool {
+    tx.blocking_send(Ok(Bytes::from(chunk))).is_ok()
+}
+
+fn write_csv(source: &mut dyn ExportSource, tx: &mpsc::Sender<io::Result<Bytes>>) -> AppResult<()> {
+    let headers: Vec<ExportCell> = source
+        .headers()
+        .iter()
+        .map(|h| ExportCell::Text(h.to_string()))
+        .collect();
+    if !send_chunk(tx, csv_rows(&[headers])?) {
+        return Ok(());
+    }
+
+    while let Some(rows) = source.next_page()? {
+        if !send_chunk(tx, csv_rows(&rows)?) {
+            return Ok(());
+        }
+    }
+    Ok(())
+}
+
+/// Temporary file removed when dropped
+struct TempFile(PathBuf);
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+fn write_xlsx(source: &mut dyn ExportSource, tx: &mpsc::Sender<io::Result<Bytes>>) -> AppResult<()> {
+    let xlsx_error = |e: rust_xlsxwriter::XlsxError| AppError::InternalError(e.to_string());
+
+    let file = TempFile(std::env::temp_dir().join(format!("export-{}.xlsx", Uuid::new_v4())));
+    let mut workbook = Workbook::new();
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet_with_constant_memory();
+    worksheet.set_name(source.name()).map_err(xlsx_error)?;
+
+    for (col, header) in source.headers().iter().enumerate() {
+        worksheet
+            .write_string_with_format(0, col as u16, *header, &bold)
+            .map_err(xlsx_error)?;
+    }
+
+    let mut row: u32 = 1;
+    while let Some(rows) = source.next_page()? {
+        for cells in rows {
+            if row >= XLSX_MAX_ROWS {
+                return Err(AppError::ValidationError(
+                    "Too many rows for a spreadsheet; narrow the filters or export CSV".to_string(),
+                ));
+            }
+            for (col, cell) in cells.iter().enumerate() {
+                let col = col as u16;
+                match cell {
+                    ExportCell::Text(s) => worksheet.write_string(row, col, formula_safe(s)),
+                    ExportCell::Number(n) => worksheet.write_number(row, col, n.to_f64().unwrap_or(0.0)),
+                    ExportCell::Integer(i) => worksheet.write_number(row, col, *i as f64),
+                    ExportCell::Empty => continue,
+                }
+                .map_err(xlsx_error)?;
+            }
+            row += 1;
+        }
+    }
+
+    workbook.save(&file.0).map_err(xlsx_error)?;
+
+    let mut reader = File::open(&file.0).map_err(|e| AppError::InternalError(e.to_string()))?;
+    loop {
+        let mut chunk = vec![0; XLSX_CHUNK_SIZE];
+        let read = reader
+            .read(&mut chunk)
+            .map_err(|e| AppError::InternalError(e.to_string()))?;
+        if read == 0 {
+            return Ok(());
+        }
+        chunk.truncate(read);
+        if !send_chunk(tx, chunk) {
+            return Ok(());
+        }
+    }
+}
+
+/// Revenue per day, on the same basis and dates as the revenue time series
+pub struct RevenueExport {
+    pool: DbPool,
+    room_id: Option<Uuid>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    basis: RevenueBasis,
+    done: bool,
+}
+
+impl RevenueExport {
+    pub fn new(
+        pool: DbPool,
+        room_id: Option<Uuid>,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        basis: RevenueBasis,
+    ) -> Self {
+        Self {
+            pool,
+            room_id,
+            start_date,
+            end_date,
+            basis,
+            done: false,
+        }
+    }
+}
+
+impl ExportSource for RevenueExport {
+    fn name(&self) -> &'static str {
+        "revenue"
+    }
+
+    fn headers(&self) -> &'static [&'static str] {
+        &["Date", "Net revenue", "Tax", "Gross revenue"]
+    }
+
+    fn next_page(&mut self) -> AppResult<Option<Vec<Vec<ExportCell>>>> {
+        // One row per day, so the series is read in one go
+        if self.done {
+            return Ok(None);
+        }
+        self.done = true;
+
+        let series = BookingService::new(self.pool.clone()).get_revenue_time_series(
+            self.room_id,
+            self.start_date,
+            self.end_date,
+            self.basis,
+        )?;
+
+        Ok(Some(
+            series
+                .into_iter()
+                .map(|(date, revenue)| {
+                    vec![
+                        ExportCell::date(date),
+                        ExportCell::Number(revenue.net),
+                        ExportCell::Number(revenue.tax),
+                        ExportCell::Number(revenue.gross),
+                    ]
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Bookings, filtered like the booking list
+pub struct BookingExport {
+    pool: DbPool,
+    status: Option<BookingStatus>,
+    guest_name: Option<String>,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+    offset: i64,
+}
+
+impl BookingExport {
+    pub fn new(
+        pool: DbPool,
+        status: Option<BookingStatus>,
+        guest_name: Option<String>,
+        from_date: Option<NaiveDate>,
+        to_date: Option<NaiveDate>,
+    ) -> Self {
+        Self {
+            pool,
+            status,
+            guest_name,
+            from_date,
+            to_date,
+            offset: 0,
+        }
+    }
+}
+
+impl ExportSource for BookingExport {
+    fn name(&self) -> &'static str {
+        "bookings"
+    }
+
+    fn headers(&self) -> &'static [&'static str] {
+        &[
+            "Reference",
+            "Guest",
+            "Room",
+            "Room type",
+            "Check-in",
+            "Check-out",
+            "Nights",
+            "Status",
+            "Source",
+            "Price",
+            "Created at",
+        ]
+    }
+
+    fn next_page(&mut self) -> AppResult<Option<Vec<Vec<ExportCell>>>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut query = bookings::table
+            .inner_join(rooms::table)
+            .select((Booking::as_select(), rooms::number, rooms::room_type))
+            .into_boxed();
+
+        if let Some(status) = self.status {
+            query = query.filter(bookings::status.eq(status));
+        }
+        if let Some(name) = &self.guest_name {
+            query = query.filter(bookings::guest_name.ilike(format!("%{}%", name)));
+        }
+        if let Some(from) = self.from_date {
+            query = query.filter(bookings::check_in_date.ge(from));
+        }
+        if let Some(to) = self.to_date {
+            query = query.filter(bookings::check_in_date.le(to));
+        }
+
+        let page: Vec<(Booking, String, RoomType)> = query
+            .order((bookings::check_in_date.asc(), bookings::id.asc()))
+            .offset(self.offset)
+            .limit(EXPORT_PAGE_SIZE)
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if page.is_empty() {
+            return Ok(None);
+        }
+        self.offset += page.len() as i64;
+
+        Ok(Some(
+            page.into_iter()
+                .map(|(booking, room_number, room_type)| {
+                    vec![
+                        ExportCell::Text(booking.reference),
+                        ExportCell::Text(booking.guest_name),
+                        ExportCell::Text(room_number),
+                        ExportCell::label(room_type),
+                        ExportCell::date(booking.check_in_date),
+                        ExportCell::date(booking.check_out_date),
+                        ExportCell::Integer((booking.check_out_date - booking.check_in_date).num_days()),
+                        ExportCell::label(booking.status),
+                        ExportCell::Text(booking.creation_source),
+                        ExportCell::Number(booking.price),
+                        ExportCell::timestamp(booking.created_at),
+                    ]
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Payment ledger entries recorded between two dates (inclusive)
+pub struct PaymentLedgerExport {
+    pool: DbPool,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    offset: i64,
+}
+
+impl PaymentLedgerExport {
+    pub fn new(pool: DbPool, start_date: NaiveDate, end_date: NaiveDate) -> AppResult<Self> {
+        if end_date < start_date {
+            return Err(AppError::ValidationError(
+                "End date must not be before start date".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            pool,
+            from: start_date.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+            to: (end_date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc(),
+            offset: 0,
+        })
+    }
+}
+
+impl ExportSource for PaymentLedgerExport {
+    fn name(&self) -> &'static str {
+        "payment-ledger"
+    }
+
+    fn headers(&self) -> &'static [&'static str] {
+        &[
+            "Recorded at",
+            "Booking",
+            "Action",
+            "Payment type",
+            "Payment method",
+            "Amount",
+            "Change to paid total",
+            "Reason",
+            "By",
+            "Payment ID",
+        ]
+    }
+
+    fn next_page(&mut self) -> AppResult<Option<Vec<Vec<ExportCell>>>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let page: Vec<(PaymentLedgerEntry, String, Option<String>, Option<String>)> =
+            payment_ledger_entries::table
+                .inner_join(bookings::table)
+                .inner_join(users::table.on(users::id.eq(payment_ledger_entries::actor_user_id)))
+                .filter(payment_ledger_entries::created_at.ge(self.from))
+                .filter(payment_ledger_entries::created_at.lt(self.to))
+                .select((
+                    PaymentLedgerEntry::as_select(),
+                    bookings::reference,
+                    users::full_name,
+                    users::username,
+                ))
+                .order((payment_ledger_entries::created_at.asc(), payment_ledger_entries::id.asc()))
+                .offset(self.offset)
+                .limit(EXPORT_PAGE_SIZE)
+                .load(&mut conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if page.is_empty() {
+            return Ok(None);
+        }
+        self.offset += page.len() as i64;
+
+        Ok(Some(
+            page.into_iter()
+                .map(|(entry, reference, full_name, username)| {
+                    vec![
+                        ExportCell::timestamp(entry.created_at),
+                        ExportCell::Text(reference),
+                        ExportCell::label(entry.action),
+                        ExportCell::label(entry.payment_type),
+                        ExportCell::Text(entry.payment_method),
+                        ExportCell::Number(entry.amount),
+                        ExportCell::Number(entry.amount_delta),
+                        ExportCell::text(entry.reason),
+                        ExportCell::text(full_name.or(username)),
+                        ExportCell::Text(entry.payment_id.to_string()),
+                    ]
+                })
+                .collect(),
+        ))
+    }
+}
+
+/// Inventory items with their value, ending with the total value
+pub struct InventoryExport {
+    pool: DbPool,
+    offset: i64,
+    total: Option<BigDecimal>,
+}
+
+impl InventoryExport {
+    pub fn new(pool: DbPool) -> Self {
+        Self {
+            pool,
+            offset: 0,
+            total: Some(BigDecimal::from(0)),
+        }
+    }
+}
+
+impl ExportSource for InventoryExport {
+    fn name(&self) -> &'static str {
+        "inventory"
+    }
+
+    fn headers(&self) -> &'static [&'static str] {
+        &["Item", "Status", "Quantity", "Unit price", "Value"]
+    }
+
+    fn next_page(&mut self) -> AppResult<Option<Vec<Vec<ExportCell>>>> {
+        // The total row is the last page
+        let Some(total) = self.total.as_mut() else {
+            return Ok(None);
+        };
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let page: Vec<InventoryItem> = inventory_items::table
+            .order((inventory_items::name.asc(), inventory_items::id.asc()))
+            .offset(self.offset)
+            .limit(EXPORT_PAGE_SIZE)
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if page.is_empty() {
+            let total = self.total.take().unwrap_or_default();
+            return Ok(Some(vec![vec![
+                ExportCell::Text("Total".to_string()),
+                ExportCell::Empty,
+                ExportCell::Empty,
+                ExportCell::Empty,
+                ExportCell::Number(total),
+            ]]));
+        }
+        self.offset += page.len() as i64;
+
+        Ok(Some(
+            page.into_iter()
+                .map(|item| {
+                    let value = &item.price * BigDecimal::from(item.quantity);
+                    *total += &value;
+                    vec![
+                        ExportCell::Text(item.name),
+                        ExportCell::label(item.status),
+                        ExportCell::Integer(item.quantity as i64),
+                        ExportCell::Number(item.price),
+                        ExportCell::Number(value),
+                    ]
+                })
+                .collect(),
+        ))
+    }
+}
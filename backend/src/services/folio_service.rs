@@ -0,0 +1,340 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::Utc;
+use diesel::dsl::sum;
+use diesel::prelude::*;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, BookingStatus, ChargeCategory, Folio, FolioCharge, FolioChargeTax, FolioChargeWithTaxes,
+    NewFolioCharge, NewFolioChargeTax, TaxBreakdown, TaxLine,
+};
+use crate::schema::{bookings, folio_charge_taxes, folio_charges};
+use crate::services::tax_service::{applicable_rules, apply_taxes, merge_tax_lines, TaxTarget};
+use crate::services::{PaymentService, TaxService};
+
+/// Folio charge request payload
+#[derive(Debug, Deserialize)]
+pub struct CreateFolioChargeRequest {
+    pub category: ChargeCategory,
+    pub description: String,
+    /// Defaults to 1
+    pub quantity: Option<i32>,
+    pub unit_price: BigDecimal,
+    /// Manual exclusive tax rate in percent; omit to apply the tax rules
+    /// configured for the category
+    pub tax_rate: Option<BigDecimal>,
+}
+
+/// Tax and total of a charge line: `(tax_amount, total_amount)`
+///
+/// Amounts are rounded to whole currency units like the rest of the folio.
+pub fn charge_amounts(
+    quantity: i32,
+    unit_price: &BigDecimal,
+    tax_rate: &BigDecimal,
+) -> (BigDecimal, BigDecimal) {
+    let net = unit_price * BigDecimal::from(quantity);
+    let tax = (&net * tax_rate / BigDecimal::from(100)).round(0);
+    let total = net + &tax;
+    (tax, total)
+}
+
+/// Breakdown of a charge taxed at a single manual exclusive rate
+fn manual_breakdown(quantity: i32, unit_price: &BigDecimal, tax_rate: &BigDecimal) -> TaxBreakdown {
+    let (tax, gross) = charge_amounts(quantity, unit_price, tax_rate);
+    let lines = if tax.is_zero() {
+        Vec::new()
+    } else {
+        vec![TaxLine {
+            tax_rule_id: None,
+            name: "Tax".to_string(),
+            rate: tax_rate.clone(),
+            is_inclusive: false,
+            amount: tax.clone(),
+        }]
+    };
+
+    TaxBreakdown {
+        net: &gross - &tax,
+        tax,
+        gross,
+        lines,
+    }
+}
+
+/// Folio service: itemized charges billed to a booking
+pub struct FolioService {
+    pool: DbPool,
+}
+
+impl FolioService {
+    /// Create a new FolioService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Get the folio of a booking: its charges (voided ones included) and
+    /// payment summary
+    pub fn get_folio(&self, booking_id: Uuid) -> AppResult<Folio> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let booking: Booking = bookings::table
+            .find(booking_id)
+            .first(&mut conn)
+            .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))?;
+
+        let charges: Vec<FolioCharge> = folio_charges::table
+            .filter(folio_charges::booking_id.eq(booking_id))
+            .order(folio_charges::created_at.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let charge_ids: Vec<Uuid> = charges.iter().map(|c| c.id).collect();
+        let taxes: Vec<FolioChargeTax> = folio_charge_taxes::table
+            .filter(folio_charge_taxes::folio_charge_id.eq_any(&charge_ids))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let charges = charges
+            .into_iter()
+            .map(|charge| FolioChargeWithTaxes {
+                taxes: taxes
+                    .iter()
+                    .filter(|t| t.folio_charge_id == charge.id)
+                    .cloned()
+                    .collect(),
+                charge,
+            })
+            .collect();
+
+        let summary = PaymentService::summary_with_conn(&mut conn, &booking)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(Folio { charges, summary })
+    }
+
+    /// Post a charge to a booking's folio
+    pub fn add_charge(
+        &self,
+        booking_id: Uuid,
+        request: CreateFolioChargeRequest,
+        created_by_user_id: Uuid,
+    ) -> AppResult<FolioChargeWithTaxes> {
+        let description = request.description.trim();
+        if description.is_empty() {
+            return Err(AppError::ValidationError(
+                "Charge description is required".to_string(),
+            ));
+        }
+
+        if description.len() > 200 {
+            return Err(AppError::ValidationError(
+                "Charge description must be 200 characters or less".to_string(),
+            ));
+        }
+
+        let quantity = request.quantity.unwrap_or(1);
+        if quantity < 1 {
+            return Err(AppError::ValidationError(
+                "Quantity must be at least 1".to_string(),
+            ));
+        }
+
+        if request.unit_price < BigDecimal::zero() {
+            return Err(AppError::ValidationError(
+                "Unit price cannot be negative".to_string(),
+            ));
+        }
+
+        // Stored in whole currency units; a fraction would be rounded away
+        if !request.unit_price.is_integer() {
+            return Err(AppError::ValidationError(
+                "Unit price must be a whole amount".to_string(),
+            ));
+        }
+
+        let max_rate = BigDecimal::from(100);
+        if let Some(rate) = &request.tax_rate {
+            if *rate < BigDecimal::zero() || rate > &max_rate {
+                return Err(AppError::ValidationError(
+                    "Tax rate must be between 0 and 100".to_string(),
+                ));
+            }
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking = Self::open_folio_booking(conn, booking_id)?;
+
+            let breakdown = match &request.tax_rate {
+                Some(rate) => manual_breakdown(quantity, &request.unit_price, rate),
+                None => {
+                    let rules = TaxService::active_rules_with_conn(conn)
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                    let amount = &request.unit_price * BigDecimal::from(quantity);
+                    apply_taxes(
+                        &amount,
+                        &applicable_rules(
+                            &rules,
+                            TaxTarget::Charge(request.category),
+                            Utc::now().date_naive(),
+                        ),
+                    )
+                }
+            };
+            let tax_rate: BigDecimal = breakdown.lines.iter().map(|l| l.rate.clone()).sum();
+
+            let charge: FolioCharge = diesel::insert_into(folio_charges::table)
+                .values(&NewFolioCharge {
+                    booking_id: booking.id,
+                    category: request.category,
+                    description: description.to_string(),
+                    quantity,
+                    unit_price: request.unit_price.clone(),
+                    tax_rate,
+                    tax_amount: breakdown.tax,
+                    total_amount: breakdown.gross,
+                    created_by_user_id,
+                })
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            let new_taxes: Vec<NewFolioChargeTax> = breakdown
+                .lines
+                .into_iter()
+                .map(|line| NewFolioChargeTax {
+                    folio_charge_id: charge.id,
+                    tax_rule_id: line.tax_rule_id,
+                    name: line.name,
+                    rate: line.rate,
+                    is_inclusive: line.is_inclusive,
+                    amount: line.amount,
+                })
+                .collect();
+
+            let taxes: Vec<FolioChargeTax> = if new_taxes.is_empty() {
+                Vec::new()
+            } else {
+                diesel::insert_into(folio_charge_taxes::table)
+                    .values(&new_taxes)
+                    .get_results(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            };
+
+            Ok(FolioChargeWithTaxes { charge, taxes })
+        })
+    }
+
+    /// Void a charge posted by mistake; the row is kept but no longer owed
+    pub fn void_charge(
+        &self,
+        booking_id: Uuid,
+        charge_id: Uuid,
+        reason: &str,
+        actor_id: Uuid,
+    ) -> AppResult<FolioCharge> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(AppError::ValidationError(
+                "A reason is required to void a charge".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            Self::open_folio_booking(conn, booking_id)?;
+
+            let charge: FolioCharge = folio_charges::table
+                .filter(folio_charges::id.eq(charge_id))
+                .filter(folio_charges::booking_id.eq(booking_id))
+                .for_update()
+                .first(conn)
+                .map_err(|_| {
+                    AppError::NotFound(format!(
+                        "Charge with ID '{}' not found on this booking",
+                        charge_id
+                    ))
+                })?;
+
+            if charge.voided_at.is_some() {
+                return Err(AppError::Conflict(format!(
+                    "Charge '{}' has already been voided",
+                    charge_id
+                )));
+            }
+
+            diesel::update(folio_charges::table.find(charge_id))
+                .set((
+                    folio_charges::voided_at.eq(Utc::now()),
+                    folio_charges::voided_by_user_id.eq(actor_id),
+                    folio_charges::void_reason.eq(reason),
+                ))
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))
+        })
+    }
+
+    /// Load a booking whose folio still accepts changes
+    ///
+    /// Charges can be posted and voided before and during the stay. The
+    /// folio is closed once the guest has checked out (the bill was settled
+    /// and may have been invoiced) and on cancelled or no-show bookings.
+    fn open_folio_booking(conn: &mut PgConnection, booking_id: Uuid) -> AppResult<Booking> {
+        let booking: Booking = bookings::table
+            .find(booking_id)
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))?;
+
+        if matches!(
+            booking.status,
+            BookingStatus::CheckedOut | BookingStatus::Cancelled | BookingStatus::NoShow
+        ) {
+            return Err(AppError::InvalidStatusTransition(format!(
+                "The folio of a booking with status {:?} is closed",
+                booking.status
+            )));
+        }
+
+        Ok(booking)
+    }
+
+    /// Net, tax and gross totals of the folio charges of a booking, leaving
+    /// out voided ones
+    pub fn charges_totals_with_conn(conn: &mut PgConnection, booking_id: Uuid) -> QueryResult<TaxBreakdown> {
+        let (gross, tax): (Option<BigDecimal>, Option<BigDecimal>) = folio_charges::table
+            .filter(folio_charges::booking_id.eq(booking_id))
+            .filter(folio_charges::voided_at.is_null())
+            .select((sum(folio_charges::total_amount), sum(folio_charges::tax_amount)))
+            .first(conn)?;
+        let gross = gross.unwrap_or_else(BigDecimal::zero);
+        let tax = tax.unwrap_or_else(BigDecimal::zero);
+
+        let lines: Vec<FolioChargeTax> = folio_charge_taxes::table
+            .inner_join(folio_charges::table)
+            .filter(folio_charges::booking_id.eq(booking_id))
+            .filter(folio_charges::voided_at.is_null())
+            .select(FolioChargeTax::as_select())
+            .load(conn)?;
+
+        Ok(TaxBreakdown {
+            net: &gross - &tax,
+            tax,
+            gross,
+            lines: merge_tax_lines(lines.into_iter().map(TaxLine::from)),
+        })
+    }
+}
@@ -0,0 +1,283 @@
+use bigdecimal::{BigDecimal, One, Zero};
+use diesel::dsl::sum;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, BookingStatus, GuestCreditAccount, GuestCreditEntry, NewGuestCreditEntry, NewPayment,
+    Payment, PaymentType, User, UserRole,
+};
+use crate::schema::{bookings, guest_credit_entries, users};
+use crate::services::exchange_rate_service::BASE_CURRENCY;
+use crate::services::payment_intent_service::default_payment_type;
+use crate::services::PaymentService;
+
+/// Payment method of the payments that move money between a booking and a
+/// guest's credit
+pub const GUEST_CREDIT_PAYMENT_METHOD: &str = "guest_credit";
+
+/// Credit to apply to a booking
+///
+/// Defaults to as much as the balance covers of what is still owed; an
+/// explicit amount must be positive and fit both.
+pub fn credit_to_apply(
+    requested: Option<&BigDecimal>,
+    balance: &BigDecimal,
+    remaining_balance: &BigDecimal,
+) -> AppResult<BigDecimal> {
+    let available = balance.min(remaining_balance).clone();
+    let amount = requested.cloned().unwrap_or_else(|| available.clone());
+
+    if amount <= BigDecimal::zero() {
+        return Err(AppError::ValidationError(if requested.is_some() {
+            "Credit amount must be positive".to_string()
+        } else if *balance <= BigDecimal::zero() {
+            "The guest has no credit".to_string()
+        } else {
+            "The booking has nothing left to pay".to_string()
+        }));
+    }
+
+    if amount > *balance {
+        return Err(AppError::ValidationError(format!(
+            "Credit amount {} exceeds the guest's credit balance of {}",
+            amount, balance
+        )));
+    }
+
+    if amount > *remaining_balance {
+        return Err(AppError::ValidationError(format!(
+            "Credit amount {} exceeds the remaining balance of {}",
+            amount, remaining_balance
+        )));
+    }
+
+    Ok(amount)
+}
+
+/// Guest credit service: overpayments kept on a guest's account
+pub struct GuestCreditService {
+    pool: DbPool,
+}
+
+impl GuestCreditService {
+    /// Create a new GuestCreditService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// A guest's credit balance and history
+    pub fn get_account(&self, guest_id: Uuid) -> AppResult<GuestCreditAccount> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let balance = Self::balance_with_conn(&mut conn, guest_id)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let entries: Vec<GuestCreditEntry> = guest_credit_entries::table
+            .filter(guest_credit_entries::guest_user_id.eq(guest_id))
+            .order(guest_credit_entries::created_at.desc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(GuestCreditAccount {
+            guest_user_id: guest_id,
+            balance,
+            entries,
+        })
+    }
+
+    /// Credit balance of a guest
+    pub fn balance_with_conn(conn: &mut PgConnection, guest_id: Uuid) -> QueryResult<BigDecimal> {
+        let balance: Option<BigDecimal> = guest_credit_entries::table
+            .filter(guest_credit_entries::guest_user_id.eq(guest_id))
+            .select(sum(guest_credit_entries::amount))
+            .first(conn)?;
+
+        Ok(balance.unwrap_or_else(BigDecimal::zero))
+    }
+
+    /// Move what a booking was overpaid to its guest's credit
+    ///
+    /// A refund with method `guest_credit` takes the overpayment off the
+    /// booking, and a matching entry adds it to the guest's account.
+    pub fn credit_overpayment(
+        &self,
+        booking_id: Uuid,
+        notes: Option<String>,
+        actor_id: Uuid,
+    ) -> AppResult<GuestCreditEntry> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking = Self::lock_booking_with_conn(conn, booking_id)?;
+            let guest_id = Self::lock_guest_with_conn(conn, &booking)?;
+
+            let summary = PaymentService::summary_with_conn(conn, &booking)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let overpaid = summary.overpaid_amount;
+            if overpaid <= BigDecimal::zero() {
+                return Err(AppError::ValidationError(
+                    "The booking is not overpaid".to_string(),
+                ));
+            }
+
+            let payment = PaymentService::create_payment_with_conn(
+                conn,
+                Self::credit_payment(
+                    &booking,
+                    -overpaid.clone(),
+                    PaymentType::Refund,
+                    "Overpayment moved to guest credit",
+                    actor_id,
+                ),
+            )?;
+
+            Self::record_entry_with_conn(conn, guest_id, &payment, overpaid, notes, actor_id)
+        })
+    }
+
+    /// Pay a booking from its guest's credit
+    ///
+    /// `amount` defaults to as much as the credit covers of the remaining
+    /// balance.
+    pub fn apply_credit(
+        &self,
+        booking_id: Uuid,
+        amount: Option<BigDecimal>,
+        actor_id: Uuid,
+    ) -> AppResult<Payment> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking = Self::lock_booking_with_conn(conn, booking_id)?;
+            if matches!(booking.status, BookingStatus::Cancelled | BookingStatus::NoShow) {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Cannot apply credit to a booking with status {:?}.",
+                    booking.status
+                )));
+            }
+
+            let guest_id = Self::lock_guest_with_conn(conn, &booking)?;
+            let balance = Self::balance_with_conn(conn, guest_id)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let summary = PaymentService::summary_with_conn(conn, &booking)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            let amount = credit_to_apply(amount.as_ref(), &balance, &summary.remaining_balance)?;
+            let payment_type = default_payment_type(&amount, &summary.remaining_balance);
+
+            let payment = PaymentService::create_payment_with_conn(
+                conn,
+                Self::credit_payment(&booking, amount.clone(), payment_type, "Paid from guest credit", actor_id),
+            )?;
+
+            Self::record_entry_with_conn(conn, guest_id, &payment, -amount, None, actor_id)?;
+
+            Ok(payment)
+        })
+    }
+
+    /// Return a refunded guest credit payment to the guest's account
+    /// (run inside the refund's transaction)
+    pub fn return_refund_with_conn(
+        conn: &mut PgConnection,
+        booking: &Booking,
+        refund: &Payment,
+        actor_id: Uuid,
+    ) -> AppResult<()> {
+        let guest_id = Self::lock_guest_with_conn(conn, booking)?;
+        Self::record_entry_with_conn(
+            conn,
+            guest_id,
+            refund,
+            -refund.amount.clone(),
+            refund.notes.clone(),
+            actor_id,
+        )?;
+        Ok(())
+    }
+
+    fn lock_booking_with_conn(conn: &mut PgConnection, booking_id: Uuid) -> AppResult<Booking> {
+        bookings::table
+            .find(booking_id)
+            .for_update()
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))
+    }
+
+    /// The guest account a booking belongs to, locked so credit balance
+    /// changes for a guest happen one at a time
+    fn lock_guest_with_conn(conn: &mut PgConnection, booking: &Booking) -> AppResult<Uuid> {
+        let not_linked = || {
+            AppError::ValidationError(format!(
+                "Booking {} is not linked to a guest account",
+                booking.reference
+            ))
+        };
+
+        let guest_id = booking.created_by_user_id.ok_or_else(not_linked)?;
+        let guest: User = users::table
+            .find(guest_id)
+            .for_update()
+            .first(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if guest.role != UserRole::Guest {
+            return Err(not_linked());
+        }
+
+        Ok(guest.id)
+    }
+
+    fn credit_payment(
+        booking: &Booking,
+        amount: BigDecimal,
+        payment_type: PaymentType,
+        notes: &str,
+        actor_id: Uuid,
+    ) -> NewPayment {
+        NewPayment {
+            booking_id: booking.id,
+            amount,
+            payment_type,
+            payment_method: GUEST_CREDIT_PAYMENT_METHOD.to_string(),
+            notes: Some(notes.to_string()),
+            created_by_user_id: actor_id,
+            currency: BASE_CURRENCY.to_string(),
+            exchange_rate: BigDecimal::one(),
+            original_amount: None,
+        }
+    }
+
+    fn record_entry_with_conn(
+        conn: &mut PgConnection,
+        guest_id: Uuid,
+        payment: &Payment,
+        amount: BigDecimal,
+        notes: Option<String>,
+        actor_id: Uuid,
+    ) -> AppResult<GuestCreditEntry> {
+        diesel::insert_into(guest_credit_entries::table)
+            .values(&NewGuestCreditEntry {
+                guest_user_id: guest_id,
+                amount,
+                booking_id: payment.booking_id,
+                payment_id: payment.id,
+                notes,
+                created_by_user_id: actor_id,
+            })
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+}
@@ -0,0 +1,1448 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DieselError};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    AutoAssignPlan, BookingStatus, CleanerWorkload, HousekeepingPolicy, HousekeepingPriority,
+    HousekeepingTask, HousekeepingTaskStatus, HousekeepingTaskType, HousekeepingTaskView,
+    NewHousekeepingTask, NewRoomInspection, ProposedAssignment, Room, RoomInspection, RoomStatus,
+    RoomType, SlaStatus, SlaTypeSummary, UpdateHousekeepingTask, User, UserRole,
+};
+use crate::schema::{bookings, housekeeping_tasks, room_inspections, rooms, system_settings, users};
+
+/// Upper bound for an SLA, to catch typos like minutes entered as seconds
+pub const MAX_SLA_MINUTES: i64 = 7 * 24 * 60;
+
+/// Priority of a new task when none is given: rooms with a guest arriving
+/// today come first, deep cleans can wait
+pub fn default_priority(task_type: HousekeepingTaskType, arrival_today: bool) -> HousekeepingPriority {
+    if arrival_today {
+        HousekeepingPriority::High
+    } else if task_type == HousekeepingTaskType::DeepClean {
+        HousekeepingPriority::Low
+    } else {
+        HousekeepingPriority::Normal
+    }
+}
+
+/// Where a task stands against its due time (None once cancelled)
+pub fn sla_status(
+    status: HousekeepingTaskStatus,
+    due_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Option<SlaStatus> {
+    match status {
+        HousekeepingTaskStatus::Cancelled => None,
+        HousekeepingTaskStatus::Completed => {
+            let completed_at = completed_at.unwrap_or(now);
+            Some(if completed_at <= due_at {
+                SlaStatus::Met
+            } else {
+                SlaStatus::Missed
+            })
+        }
+        HousekeepingTaskStatus::Pending | HousekeepingTaskStatus::InProgress => {
+            Some(if now <= due_at {
+                SlaStatus::OnTrack
+            } else {
+                SlaStatus::Overdue
+            })
+        }
+    }
+}
+
+/// Order a cleaner's queue: rooms with a guest arriving today first, then
+/// by priority, then by due time
+pub fn sort_queue(tasks: &mut [HousekeepingTaskView]) {
+    tasks.sort_by_key(|t| (Reverse(t.arrival_today), Reverse(t.task.priority), t.task.due_at));
+}
+
+/// SLA figures per task type (types without tasks are left out)
+pub fn summarize_sla(tasks: &[HousekeepingTask], now: DateTime<Utc>) -> Vec<SlaTypeSummary> {
+    let mut by_type: BTreeMap<HousekeepingTaskType, (SlaTypeSummary, i64)> = BTreeMap::new();
+
+    for task in tasks {
+        let (summary, total_minutes) = by_type.entry(task.task_type).or_insert_with(|| {
+            (
+                SlaTypeSummary {
+                    task_type: task.task_type,
+                    total: 0,
+                    completed: 0,
+                    met: 0,
+                    missed: 0,
+                    open: 0,
+                    overdue: 0,
+                    compliance_rate: None,
+                    average_minutes_to_complete: None,
+                },
+                0,
+            )
+        });
+
+        summary.total += 1;
+        if task.status.is_open() {
+            summary.open += 1;
+        }
+        if task.status == HousekeepingTaskStatus::Completed {
+            summary.completed += 1;
+            if let Some(completed_at) = task.completed_at {
+                *total_minutes += (completed_at - task.created_at).num_minutes();
+            }
+        }
+
+        match sla_status(task.status, task.due_at, task.completed_at, now) {
+            Some(SlaStatus::Met) => summary.met += 1,
+            Some(SlaStatus::Missed) => summary.missed += 1,
+            Some(SlaStatus::Overdue) => summary.overdue += 1,
+            Some(SlaStatus::OnTrack) | None => {}
+        }
+    }
+
+    by_type
+        .into_values()
+        .map(|(mut summary, total_minutes)| {
+            if summary.completed > 0 {
+                summary.compliance_rate =
+                    Some(summary.met as f64 / summary.completed as f64 * 100.0);
+                summary.average_minutes_to_complete =
+                    Some(total_minutes as f64 / summary.completed as f64);
+            }
+            summary
+        })
+        .collect()
+}
+
+/// Extra minutes counted against a cleaner for a room on a floor they have
+/// no other work on
+pub const FLOOR_CHANGE_MINUTES: i64 = 10;
+
+/// Floor of a room from its number: the digits before the last two
+/// (`"101"` is on floor 1, `"1205"` on floor 12); the default floor of a new room
+pub fn room_floor(number: &str) -> Option<i32> {
+    let digits: String = number.trim().chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.len() < 3 {
+        return None;
+    }
+    digits[..digits.len() - 2].parse().ok()
+}
+
+/// Estimated minutes of a task, from the size of the room
+pub fn estimated_minutes(task_type: HousekeepingTaskType, room_type: RoomType) -> i64 {
+    let full_clean = match room_type {
+        RoomType::Single => 30,
+        RoomType::Double => 45,
+        RoomType::Suite => 75,
+    };
+
+    match task_type {
+        HousekeepingTaskType::CheckoutClean => full_clean,
+        HousekeepingTaskType::DeepClean => full_clean * 3,
+        HousekeepingTaskType::Stayover => full_clean / 2,
+        HousekeepingTaskType::Turndown => 10,
+    }
+}
+
+/// A task waiting for a cleaner
+#[derive(Debug, Clone)]
+pub struct AssignableTask {
+    pub task_id: Uuid,
+    pub floor: Option<i32>,
+    pub minutes: i64,
+}
+
+/// A cleaner's load while a plan is built
+#[derive(Debug, Clone)]
+pub struct CleanerLoad {
+    pub cleaner_id: Uuid,
+    /// Estimated minutes of the cleaner's open tasks
+    pub minutes: i64,
+    /// Floors the cleaner has open tasks on
+    pub floors: Vec<i32>,
+}
+
+/// Spread tasks across cleaners: `(task_id, cleaner_id)` pairs
+///
+/// Tasks are taken in the order given (most urgent first). Each goes to the
+/// cleaner who would end up with the fewest minutes of work, counting
+/// [`FLOOR_CHANGE_MINUTES`] extra when the room is on a floor the cleaner has
+/// no other work on. Ties go to the cleaner with less work, then to the one
+/// listed first. `cleaners` is updated with the planned loads.
+pub fn plan_assignments(tasks: &[AssignableTask], cleaners: &mut [CleanerLoad]) -> Vec<(Uuid, Uuid)> {
+    let mut plan = Vec::new();
+
+    for task in tasks {
+        let cost = |cleaner: &CleanerLoad| {
+            let floor_change = match task.floor {
+                Some(floor) if !cleaner.floors.is_empty() && !cleaner.floors.contains(&floor) => {
+                    FLOOR_CHANGE_MINUTES
+                }
+                _ => 0,
+            };
+            (cleaner.minutes + task.minutes + floor_change, cleaner.minutes)
+        };
+
+        let Some(best) = cleaners
+            .iter_mut()
+            .enumerate()
+            .min_by_key(|(i, cleaner)| (cost(cleaner), *i))
+            .map(|(_, cleaner)| cleaner)
+        else {
+            break;
+        };
+
+        best.minutes += task.minutes;
+        if let Some(floor) = task.floor {
+            if !best.floors.contains(&floor) {
+                best.floors.push(floor);
+            }
+        }
+        plan.push((task.task_id, best.cleaner_id));
+    }
+
+    plan
+}
+
+/// Longest inspection checklist item, in characters
+pub const MAX_CHECKLIST_ITEM_LENGTH: usize = 200;
+
+/// Check an inspection result against the checklist and return the failed
+/// items as they appear on it
+///
+/// A pass has no failed items; a fail names failed items or has notes for
+/// the cleaner.
+pub fn validate_inspection(
+    checklist: &[String],
+    passed: bool,
+    failed_items: &[String],
+    notes: Option<&str>,
+) -> AppResult<Vec<String>> {
+    let mut failed: Vec<String> = Vec::new();
+    for item in failed_items {
+        let item = item.trim();
+        let listed = checklist
+            .iter()
+            .find(|c| c.eq_ignore_ascii_case(item))
+            .ok_or_else(|| {
+                AppError::ValidationError(format!("'{}' is not on the inspection checklist", item))
+            })?;
+        if !failed.contains(listed) {
+            failed.push(listed.clone());
+        }
+    }
+
+    let has_notes = notes.is_some_and(|n| !n.trim().is_empty());
+    if passed && !failed.is_empty() {
+        return Err(AppError::ValidationError(
+            "A passed inspection cannot have failed items".to_string(),
+        ));
+    }
+    if !passed && failed.is_empty() && !has_notes {
+        return Err(AppError::ValidationError(
+            "A failed inspection needs failed items or notes for the cleaner".to_string(),
+        ));
+    }
+
+    Ok(failed)
+}
+
+/// Notes on the task that redoes a clean which failed inspection
+pub fn rework_notes(failed_items: &[String], notes: Option<&str>) -> String {
+    let mut text = "Failed inspection".to_string();
+    if !failed_items.is_empty() {
+        text.push_str(": ");
+        text.push_str(&failed_items.join(", "));
+    }
+    if let Some(notes) = notes.map(str::trim).filter(|n| !n.is_empty()) {
+        text.push_str(". ");
+        text.push_str(notes);
+    }
+    text
+}
+
+/// Filters for the admin task list
+#[derive(Debug, Default)]
+pub struct HousekeepingTaskFilter {
+    pub status: Option<HousekeepingTaskStatus>,
+    pub task_type: Option<HousekeepingTaskType>,
+    pub room_id: Option<Uuid>,
+    pub cleaner_id: Option<Uuid>,
+}
+
+/// A task created by an admin
+#[derive(Debug)]
+pub struct CreateHousekeepingTask {
+    pub room_id: Uuid,
+    pub task_type: HousekeepingTaskType,
+    /// Defaults from the task type and today's arrivals
+    pub priority: Option<HousekeepingPriority>,
+    pub assigned_cleaner_id: Option<Uuid>,
+    pub notes: Option<String>,
+}
+
+/// A supervisor's verdict on a room waiting for inspection
+#[derive(Debug)]
+pub struct InspectRoom {
+    pub passed: bool,
+    /// Checklist items the room failed on
+    pub failed_items: Vec<String>,
+    /// For the cleaner when the room fails
+    pub notes: Option<String>,
+}
+
+/// Housekeeping service: the cleaning task queue, its SLAs and room inspections
+pub struct HousekeepingService {
+    pool: DbPool,
+}
+
+impl HousekeepingService {
+    /// Create a new HousekeepingService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Load the current housekeeping SLAs
+    pub fn get_policy(&self) -> AppResult<HousekeepingPolicy> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Self::load_policy(&mut conn).map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    fn load_policy(conn: &mut PgConnection) -> QueryResult<HousekeepingPolicy> {
+        let settings: HashMap<String, String> = system_settings::table
+            .filter(system_settings::key.like("housekeeping_%"))
+            .select((system_settings::key, system_settings::value))
+            .load::<(String, String)>(conn)?
+            .into_iter()
+            .collect();
+
+        Ok(HousekeepingPolicy::from_settings(&settings))
+    }
+
+    /// Validate and save the housekeeping SLAs
+    pub fn update_policy(&self, policy: HousekeepingPolicy) -> AppResult<HousekeepingPolicy> {
+        for task_type in HousekeepingTaskType::ALL {
+            if !(1..=MAX_SLA_MINUTES).contains(&policy.sla_minutes(task_type)) {
+                return Err(AppError::ValidationError(format!(
+                    "SLA for {} must be between 1 and {} minutes",
+                    task_type.as_str(),
+                    MAX_SLA_MINUTES
+                )));
+            }
+        }
+
+        let mut policy = policy;
+        policy.inspection_checklist = policy
+            .inspection_checklist
+            .iter()
+            .map(|item| item.trim().to_string())
+            .collect();
+        for (i, item) in policy.inspection_checklist.iter().enumerate() {
+            if item.is_empty() || item.chars().count() > MAX_CHECKLIST_ITEM_LENGTH {
+                return Err(AppError::ValidationError(format!(
+                    "Checklist items must be between 1 and {} characters",
+                    MAX_CHECKLIST_ITEM_LENGTH
+                )));
+            }
+            if policy.inspection_checklist[..i]
+                .iter()
+                .any(|other| other.eq_ignore_ascii_case(item))
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Checklist item '{}' is listed twice",
+                    item
+                )));
+            }
+        }
+        if policy.inspection_required && policy.inspection_checklist.is_empty() {
+            return Err(AppError::ValidationError(
+                "Inspections need at least one checklist item".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            for (key, value) in policy.to_settings() {
+                diesel::insert_into(system_settings::table)
+                    .values((
+                        system_settings::key.eq(key),
+                        system_settings::value.eq(&value),
+                        system_settings::updated_at.eq(Utc::now()),
+                    ))
+                    .on_conflict(system_settings::key)
+                    .do_update()
+                    .set((
+                        system_settings::value.eq(&value),
+                        system_settings::updated_at.eq(Utc::now()),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(policy)
+    }
+
+    /// List tasks, newest first
+    pub fn list_tasks(&self, filter: HousekeepingTaskFilter) -> AppResult<Vec<HousekeepingTaskView>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut query = housekeeping_tasks::table.into_boxed();
+        if let Some(status) = filter.status {
+            query = query.filter(housekeeping_tasks::status.eq(status));
+        }
+        if let Some(task_type) = filter.task_type {
+            query = query.filter(housekeeping_tasks::task_type.eq(task_type));
+        }
+        if let Some(room_id) = filter.room_id {
+            query = query.filter(housekeeping_tasks::room_id.eq(room_id));
+        }
+        if let Some(cleaner_id) = filter.cleaner_id {
+            query = query.filter(housekeeping_tasks::assigned_cleaner_id.eq(cleaner_id));
+        }
+
+        let tasks: Vec<HousekeepingTask> = query
+            .order(housekeeping_tasks::created_at.desc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Self::views_with_conn(&mut conn, tasks)
+    }
+
+    /// Open tasks a cleaner can work on: their own and unassigned ones, in
+    /// queue order
+    pub fn cleaner_queue(&self, cleaner_id: Uuid) -> AppResult<Vec<HousekeepingTaskView>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let tasks: Vec<HousekeepingTask> = housekeeping_tasks::table
+            .filter(housekeeping_tasks::status.eq_any(HousekeepingTaskStatus::OPEN))
+            .filter(
+                housekeeping_tasks::assigned_cleaner_id
+                    .eq(cleaner_id)
+                    .or(housekeeping_tasks::assigned_cleaner_id.is_null()),
+            )
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut views = Self::views_with_conn(&mut conn, tasks)?;
+        sort_queue(&mut views);
+        Ok(views)
+    }
+
+    /// Get a task by ID
+    pub fn get_task(&self, task_id: Uuid) -> AppResult<HousekeepingTaskView> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let task = Self::find_task_with_conn(&mut conn, task_id, false)?;
+        Self::views_with_conn(&mut conn, vec![task])?
+            .pop()
+            .ok_or_else(|| AppError::NotFound(format!("Task with ID '{}' not found", task_id)))
+    }
+
+    /// Create a task by hand
+    pub fn create_task(
+        &self,
+        request: CreateHousekeepingTask,
+        actor_id: Uuid,
+    ) -> AppResult<HousekeepingTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let room: Room = rooms::table
+                .find(request.room_id)
+                .first(conn)
+                .map_err(|_| {
+                    AppError::NotFound(format!("Room with ID '{}' not found", request.room_id))
+                })?;
+
+            if let Some(cleaner_id) = request.assigned_cleaner_id {
+                Self::ensure_active_cleaner_with_conn(conn, cleaner_id)?;
+            }
+
+            let policy = Self::load_policy(conn).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let task = Self::create_with_conn(
+                conn,
+                &policy,
+                &room,
+                None,
+                request.task_type,
+                request.priority,
+                request.assigned_cleaner_id,
+                request.notes,
+                Some(actor_id),
+            )?;
+
+            if task.task_type.cleans_room() && task.assigned_cleaner_id.is_some() {
+                Self::set_room_cleaner_with_conn(conn, room.id, task.assigned_cleaner_id)?;
+            }
+
+            Ok(task)
+        })
+    }
+
+    /// Change an open task's priority, assignee, notes or due time
+    pub fn update_task(
+        &self,
+        task_id: Uuid,
+        update: UpdateHousekeepingTask,
+    ) -> AppResult<HousekeepingTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let task = Self::find_task_with_conn(conn, task_id, true)?;
+            Self::ensure_open(&task)?;
+
+            if let Some(Some(cleaner_id)) = update.assigned_cleaner_id {
+                Self::ensure_active_cleaner_with_conn(conn, cleaner_id)?;
+            }
+
+            let updated: HousekeepingTask = diesel::update(housekeeping_tasks::table.find(task_id))
+                .set(&update)
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if updated.task_type.cleans_room() && update.assigned_cleaner_id.is_some() {
+                Self::set_room_cleaner_with_conn(conn, updated.room_id, updated.assigned_cleaner_id)?;
+            }
+
+            Ok(updated)
+        })
+    }
+
+    /// Cancel an open task
+    pub fn cancel_task(&self, task_id: Uuid) -> AppResult<HousekeepingTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let task = Self::find_task_with_conn(conn, task_id, true)?;
+            Self::ensure_open(&task)?;
+
+            let cancelled = Self::set_status_with_conn(conn, &task, HousekeepingTaskStatus::Cancelled, None)?;
+
+            // Nobody is cleaning the room any more
+            if task.status == HousekeepingTaskStatus::InProgress && task.task_type.cleans_room() {
+                Self::room_back_to_dirty_with_conn(conn, task.room_id)?;
+            }
+
+            Ok(cancelled)
+        })
+    }
+
+    /// Start a task, claiming it if it is unassigned
+    pub fn start_task(&self, task_id: Uuid, cleaner_id: Uuid) -> AppResult<HousekeepingTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| Self::start_with_conn(conn, task_id, cleaner_id))
+    }
+
+    /// Finish a started task
+    pub fn complete_task(
+        &self,
+        task_id: Uuid,
+        cleaner_id: Uuid,
+        notes: Option<String>,
+    ) -> AppResult<HousekeepingTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            Self::complete_with_conn(conn, task_id, cleaner_id, notes)
+        })
+    }
+
+    /// Put a started task back in the queue (e.g. the room needs rework)
+    pub fn release_task(
+        &self,
+        task_id: Uuid,
+        cleaner_id: Uuid,
+        notes: Option<String>,
+    ) -> AppResult<HousekeepingTask> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            Self::release_with_conn(conn, task_id, cleaner_id, notes)
+        })
+    }
+
+    /// Move a room through the cleaning flow by its status, acting on the
+    /// room's open cleaning task: Cleaning starts it, Available completes it
+    /// and Dirty puts it back in the queue
+    pub fn set_room_status(
+        &self,
+        room_id: Uuid,
+        cleaner_id: Uuid,
+        status: RoomStatus,
+    ) -> AppResult<Room> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let room: Room = rooms::table
+                .find(room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
+
+            if !matches!(room.status, RoomStatus::Dirty | RoomStatus::Cleaning) {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Cannot transition room from {:?} to {:?}",
+                    room.status, status
+                )));
+            }
+
+            // In-progress tasks first, then the oldest
+            let task: HousekeepingTask = match housekeeping_tasks::table
+                .filter(housekeeping_tasks::room_id.eq(room_id))
+                .filter(housekeeping_tasks::status.eq_any(HousekeepingTaskStatus::OPEN))
+                .filter(housekeeping_tasks::task_type.eq_any(Self::room_clean_types()))
+                .order((housekeeping_tasks::status.desc(), housekeeping_tasks::created_at.asc()))
+                .first(conn)
+                .optional()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            {
+                Some(task) => task,
+                None => Self::open_room_clean_with_conn(conn, room_id, None)?,
+            };
+
+            match status {
+                RoomStatus::Cleaning => {
+                    if task.status == HousekeepingTaskStatus::Pending {
+                        Self::start_with_conn(conn, task.id, cleaner_id)?;
+                    }
+                }
+                RoomStatus::Available => {
+                    if task.status == HousekeepingTaskStatus::Pending {
+                        Self::start_with_conn(conn, task.id, cleaner_id)?;
+                    }
+                    Self::complete_with_conn(conn, task.id, cleaner_id, None)?;
+                }
+                RoomStatus::Dirty => {
+                    if task.status == HousekeepingTaskStatus::InProgress {
+                        Self::release_with_conn(conn, task.id, cleaner_id, None)?;
+                    }
+                }
+                other => {
+                    return Err(AppError::InvalidStatusTransition(format!(
+                        "Cannot transition room from {:?} to {:?}",
+                        room.status, other
+                    )));
+                }
+            }
+
+            rooms::table
+                .find(room_id)
+                .first(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))
+        })
+    }
+
+    /// SLA figures of the tasks created between two dates (inclusive)
+    pub fn sla_report(&self, start: NaiveDate, end: NaiveDate) -> AppResult<Vec<SlaTypeSummary>> {
+        if start > end {
+            return Err(AppError::ValidationError(
+                "Start date must be on or before end date".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let from = start.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let until = (end + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        let tasks: Vec<HousekeepingTask> = housekeeping_tasks::table
+            .filter(housekeeping_tasks::created_at.ge(from))
+            .filter(housekeeping_tasks::created_at.lt(until))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(summarize_sla(&tasks, Utc::now()))
+    }
+
+    /// Pass or fail a room waiting for inspection
+    ///
+    /// A pass makes the room Available. A fail sends it back to Dirty with a
+    /// task to redo the clean, carrying the failed items and notes, for the
+    /// cleaner who did it.
+    pub fn inspect_room(
+        &self,
+        room_id: Uuid,
+        inspector_id: Uuid,
+        request: InspectRoom,
+    ) -> AppResult<RoomInspection> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let room: Room = rooms::table
+                .find(room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
+
+            if room.status != RoomStatus::PendingInspection {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Room {} is not waiting for inspection (status {:?})",
+                    room.number, room.status
+                )));
+            }
+
+            let policy = Self::load_policy(conn).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let notes = request.notes.filter(|n| !n.trim().is_empty());
+            let failed_items = validate_inspection(
+                &policy.inspection_checklist,
+                request.passed,
+                &request.failed_items,
+                notes.as_deref(),
+            )?;
+
+            let cleaned: Option<HousekeepingTask> = housekeeping_tasks::table
+                .filter(housekeeping_tasks::room_id.eq(room_id))
+                .filter(housekeeping_tasks::status.eq(HousekeepingTaskStatus::Completed))
+                .filter(housekeeping_tasks::task_type.eq_any(Self::room_clean_types()))
+                .order(housekeeping_tasks::completed_at.desc())
+                .first(conn)
+                .optional()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            let cleaner_id = cleaned
+                .as_ref()
+                .and_then(|t| t.assigned_cleaner_id)
+                .or(room.assigned_cleaner_id);
+
+            let rework_task_id = if request.passed {
+                diesel::update(rooms::table.find(room_id))
+                    .set((
+                        rooms::status.eq(RoomStatus::Available),
+                        rooms::assigned_cleaner_id.eq(None::<Uuid>),
+                    ))
+                    .execute(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                None
+            } else {
+                diesel::update(rooms::table.find(room_id))
+                    .set(rooms::status.eq(RoomStatus::Dirty))
+                    .execute(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+                // Back to the same cleaner while they are still on the team
+                let assignee = match cleaner_id {
+                    Some(id) if Self::ensure_active_cleaner_with_conn(conn, id).is_ok() => Some(id),
+                    _ => None,
+                };
+
+                let rework = Self::create_with_conn(
+                    conn,
+                    &policy,
+                    &room,
+                    cleaned.as_ref().and_then(|t| t.booking_id),
+                    cleaned
+                        .as_ref()
+                        .map(|t| t.task_type)
+                        .unwrap_or(HousekeepingTaskType::CheckoutClean),
+                    cleaned.as_ref().map(|t| t.priority),
+                    assignee,
+                    Some(rework_notes(&failed_items, notes.as_deref())),
+                    Some(inspector_id),
+                )?;
+                let rework = Self::auto_assign_with_conn(conn, &policy, &room, rework)?;
+                Self::set_room_cleaner_with_conn(conn, room_id, rework.assigned_cleaner_id)?;
+                Some(rework.id)
+            };
+
+            diesel::insert_into(room_inspections::table)
+                .values(&NewRoomInspection {
+                    room_id,
+                    housekeeping_task_id: cleaned.as_ref().map(|t| t.id),
+                    cleaner_id,
+                    inspector_id,
+                    passed: request.passed,
+                    checklist: policy.inspection_checklist.clone(),
+                    failed_items,
+                    notes,
+                    rework_task_id,
+                })
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))
+        })
+    }
+
+    /// Inspections, newest first
+    pub fn list_inspections(
+        &self,
+        room_id: Option<Uuid>,
+        cleaner_id: Option<Uuid>,
+        passed: Option<bool>,
+    ) -> AppResult<Vec<RoomInspection>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut query = room_inspections::table.into_boxed();
+        if let Some(room_id) = room_id {
+            query = query.filter(room_inspections::room_id.eq(room_id));
+        }
+        if let Some(cleaner_id) = cleaner_id {
+            query = query.filter(room_inspections::cleaner_id.eq(cleaner_id));
+        }
+        if let Some(passed) = passed {
+            query = query.filter(room_inspections::passed.eq(passed));
+        }
+
+        query
+            .order(room_inspections::created_at.desc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Make sure a room left dirty has a cleaning task (run inside the
+    /// transaction that made it dirty)
+    ///
+    /// Returns the room's open cleaning task, creating a checkout clean
+    /// assigned to the room's cleaner if there is none.
+    pub fn open_room_clean_with_conn(
+        conn: &mut PgConnection,
+        room_id: Uuid,
+        booking_id: Option<Uuid>,
+    ) -> AppResult<HousekeepingTask> {
+        let existing: Option<HousekeepingTask> = housekeeping_tasks::table
+            .filter(housekeeping_tasks::room_id.eq(room_id))
+            .filter(housekeeping_tasks::status.eq_any(HousekeepingTaskStatus::OPEN))
+            .filter(housekeeping_tasks::task_type.eq_any(Self::room_clean_types()))
+            .order(housekeeping_tasks::created_at.asc())
+            .first(conn)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if let Some(task) = existing {
+            return Ok(task);
+        }
+
+        let room: Room = rooms::table
+            .find(room_id)
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
+
+        let policy = Self::load_policy(conn).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let task = Self::create_with_conn(
+            conn,
+            &policy,
+            &room,
+            booking_id,
+            HousekeepingTaskType::CheckoutClean,
+            None,
+            room.assigned_cleaner_id,
+            None,
+            None,
+        )?;
+
+        Self::auto_assign_with_conn(conn, &policy, &room, task)
+    }
+
+    /// Give a new unassigned task to the best placed cleaner when
+    /// auto-assignment is on
+    fn auto_assign_with_conn(
+        conn: &mut PgConnection,
+        policy: &HousekeepingPolicy,
+        room: &Room,
+        task: HousekeepingTask,
+    ) -> AppResult<HousekeepingTask> {
+        if task.assigned_cleaner_id.is_some() || !policy.auto_assign {
+            return Ok(task);
+        }
+
+        let (_, mut loads) = Self::cleaner_loads_with_conn(conn)?;
+        let waiting = AssignableTask {
+            task_id: task.id,
+            floor: room.floor,
+            minutes: estimated_minutes(task.task_type, room.room_type),
+        };
+        match plan_assignments(&[waiting], &mut loads).first() {
+            Some(&(_, cleaner_id)) => Self::assign_with_conn(conn, &task, cleaner_id),
+            None => Ok(task),
+        }
+    }
+
+    /// Preview spreading unassigned open tasks across active cleaners
+    ///
+    /// The plan is not saved; admins can change it and send it to
+    /// [`Self::apply_assignments`].
+    pub fn preview_auto_assign(&self) -> AppResult<AutoAssignPlan> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let (cleaners, mut loads) = Self::cleaner_loads_with_conn(&mut conn)?;
+        let current: HashMap<Uuid, i64> = loads.iter().map(|l| (l.cleaner_id, l.minutes)).collect();
+
+        let unassigned: Vec<HousekeepingTask> = housekeeping_tasks::table
+            .filter(housekeeping_tasks::status.eq_any(HousekeepingTaskStatus::OPEN))
+            .filter(housekeeping_tasks::assigned_cleaner_id.is_null())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let mut queue = Self::views_with_conn(&mut conn, unassigned)?;
+        sort_queue(&mut queue);
+
+        let room_ids: Vec<Uuid> = queue.iter().map(|t| t.task.room_id).collect();
+        let room_details: HashMap<Uuid, (RoomType, Option<i32>)> = rooms::table
+            .filter(rooms::id.eq_any(&room_ids))
+            .select((rooms::id, rooms::room_type, rooms::floor))
+            .load::<(Uuid, RoomType, Option<i32>)>(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .map(|(id, room_type, floor)| (id, (room_type, floor)))
+            .collect();
+
+        let waiting: Vec<AssignableTask> = queue
+            .iter()
+            .map(|t| {
+                let (room_type, floor) = room_details[&t.task.room_id];
+                AssignableTask {
+                    task_id: t.task.id,
+                    floor,
+                    minutes: estimated_minutes(t.task.task_type, room_type),
+                }
+            })
+            .collect();
+        let plan = plan_assignments(&waiting, &mut loads);
+
+        let names: HashMap<Uuid, Option<String>> = cleaners
+            .iter()
+            .map(|c| (c.id, c.full_name.clone().or_else(|| c.username.clone())))
+            .collect();
+        let tasks: HashMap<Uuid, (&HousekeepingTaskView, &AssignableTask)> =
+            queue.iter().zip(&waiting).map(|(v, w)| (v.task.id, (v, w))).collect();
+
+        let assignments = plan
+            .into_iter()
+            .map(|(task_id, cleaner_id)| {
+                let (view, waiting) = tasks[&task_id];
+                ProposedAssignment {
+                    task_id,
+                    cleaner_id,
+                    room_id: view.task.room_id,
+                    room_number: view.room_number.clone(),
+                    room_type: room_details[&view.task.room_id].0,
+                    floor: waiting.floor,
+                    task_type: view.task.task_type,
+                    estimated_minutes: waiting.minutes,
+                    cleaner_name: names[&cleaner_id].clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let cleaners = loads
+            .iter()
+            .map(|load| CleanerWorkload {
+                cleaner_id: load.cleaner_id,
+                cleaner_name: names[&load.cleaner_id].clone(),
+                current_minutes: current[&load.cleaner_id],
+                planned_minutes: load.minutes,
+                planned_tasks: assignments
+                    .iter()
+                    .filter(|a| a.cleaner_id == load.cleaner_id)
+                    .count() as i64,
+            })
+            .collect();
+
+        Ok(AutoAssignPlan {
+            assignments,
+            cleaners,
+        })
+    }
+
+    /// Assign tasks to cleaners, e.g. an adjusted auto-assignment preview
+    ///
+    /// All or nothing: a task that is closed, or was given to another
+    /// cleaner since the preview, fails the whole batch.
+    pub fn apply_assignments(&self, assignments: Vec<(Uuid, Uuid)>) -> AppResult<Vec<HousekeepingTask>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let mut assigned = Vec::with_capacity(assignments.len());
+            for (task_id, cleaner_id) in assignments {
+                Self::ensure_active_cleaner_with_conn(conn, cleaner_id)?;
+
+                let task = Self::find_task_with_conn(conn, task_id, true)?;
+                Self::ensure_open(&task)?;
+                if task.assigned_cleaner_id.is_some_and(|current| current != cleaner_id) {
+                    return Err(AppError::Conflict(format!(
+                        "Task '{}' was assigned to another cleaner; refresh the preview",
+                        task_id
+                    )));
+                }
+
+                assigned.push(Self::assign_with_conn(conn, &task, cleaner_id)?);
+            }
+            Ok(assigned)
+        })
+    }
+
+    /// Active cleaners and the open work already assigned to them
+    fn cleaner_loads_with_conn(conn: &mut PgConnection) -> AppResult<(Vec<User>, Vec<CleanerLoad>)> {
+        let cleaners: Vec<User> = users::table
+            .filter(users::role.eq(UserRole::Cleaner))
+            .filter(users::deactivated_at.is_null())
+            .order(users::username.asc())
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let cleaner_ids: Vec<Uuid> = cleaners.iter().map(|c| c.id).collect();
+        let open: Vec<(HousekeepingTask, Room)> = housekeeping_tasks::table
+            .inner_join(rooms::table)
+            .filter(housekeeping_tasks::status.eq_any(HousekeepingTaskStatus::OPEN))
+            .filter(housekeeping_tasks::assigned_cleaner_id.eq_any(&cleaner_ids))
+            .select((HousekeepingTask::as_select(), Room::as_select()))
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut loads: Vec<CleanerLoad> = cleaners
+            .iter()
+            .map(|c| CleanerLoad {
+                cleaner_id: c.id,
+                minutes: 0,
+                floors: Vec::new(),
+            })
+            .collect();
+
+        for (task, room) in open {
+            let Some(load) = loads
+                .iter_mut()
+                .find(|l| Some(l.cleaner_id) == task.assigned_cleaner_id)
+            else {
+                continue;
+            };
+            load.minutes += estimated_minutes(task.task_type, room.room_type);
+            if let Some(floor) = room.floor {
+                if !load.floors.contains(&floor) {
+                    load.floors.push(floor);
+                }
+            }
+        }
+
+        Ok((cleaners, loads))
+    }
+
+    /// Give a task to a cleaner, and its room too for full cleans
+    fn assign_with_conn(
+        conn: &mut PgConnection,
+        task: &HousekeepingTask,
+        cleaner_id: Uuid,
+    ) -> AppResult<HousekeepingTask> {
+        let assigned: HousekeepingTask = diesel::update(housekeeping_tasks::table.find(task.id))
+            .set(housekeeping_tasks::assigned_cleaner_id.eq(Some(cleaner_id)))
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if assigned.task_type.cleans_room() {
+            Self::set_room_cleaner_with_conn(conn, assigned.room_id, Some(cleaner_id))?;
+        }
+
+        Ok(assigned)
+    }
+
+    /// Cancel a room's open cleaning tasks once it no longer needs cleaning
+    /// (a guest checked in, or staff set it available)
+    pub fn cancel_room_cleans_with_conn(conn: &mut PgConnection, room_id: Uuid) -> AppResult<()> {
+        diesel::update(
+            housekeeping_tasks::table
+                .filter(housekeeping_tasks::room_id.eq(room_id))
+                .filter(housekeeping_tasks::status.eq_any(HousekeepingTaskStatus::OPEN))
+                .filter(housekeeping_tasks::task_type.eq_any(Self::room_clean_types())),
+        )
+        .set(housekeeping_tasks::status.eq(HousekeepingTaskStatus::Cancelled))
+        .execute(conn)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn room_clean_types() -> Vec<HousekeepingTaskType> {
+        HousekeepingTaskType::ALL
+            .into_iter()
+            .filter(|t| t.cleans_room())
+            .collect()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_with_conn(
+        conn: &mut PgConnection,
+        policy: &HousekeepingPolicy,
+        room: &Room,
+        booking_id: Option<Uuid>,
+        task_type: HousekeepingTaskType,
+        priority: Option<HousekeepingPriority>,
+        assigned_cleaner_id: Option<Uuid>,
+        notes: Option<String>,
+        actor_id: Option<Uuid>,
+    ) -> AppResult<HousekeepingTask> {
+        let priority = match priority {
+            Some(priority) => priority,
+            None => default_priority(task_type, Self::arrival_today_with_conn(conn, room.id)?),
+        };
+
+        diesel::insert_into(housekeeping_tasks::table)
+            .values(&NewHousekeepingTask {
+                room_id: room.id,
+                booking_id,
+                task_type,
+                priority,
+                assigned_cleaner_id,
+                notes,
+                due_at: Utc::now() + Duration::minutes(policy.sla_minutes(task_type)),
+                created_by_user_id: actor_id,
+            })
+            .get_result(conn)
+            .map_err(|e| match e {
+                DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _) => {
+                    AppError::Conflict(format!(
+                        "Room {} already has an open {} task",
+                        room.number,
+                        task_type.as_str()
+                    ))
+                }
+                e => AppError::DatabaseError(e.to_string()),
+            })
+    }
+
+    fn start_with_conn(
+        conn: &mut PgConnection,
+        task_id: Uuid,
+        cleaner_id: Uuid,
+    ) -> AppResult<HousekeepingTask> {
+        let task = Self::find_task_with_conn(conn, task_id, true)?;
+        Self::ensure_assignee(&task, cleaner_id)?;
+        if task.status != HousekeepingTaskStatus::Pending {
+            return Err(AppError::InvalidStatusTransition(format!(
+                "Cannot start a task with status {:?}",
+                task.status
+            )));
+        }
+
+        if task.task_type.cleans_room() {
+            let room: Room = rooms::table
+                .find(task.room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            match room.status {
+                RoomStatus::Dirty | RoomStatus::Available | RoomStatus::Cleaning => {
+                    diesel::update(rooms::table.find(room.id))
+                        .set((
+                            rooms::status.eq(RoomStatus::Cleaning),
+                            rooms::assigned_cleaner_id.eq(Some(cleaner_id)),
+                        ))
+                        .execute(conn)
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                }
+                other => {
+                    return Err(AppError::InvalidStatusTransition(format!(
+                        "Cannot clean room {} while it is {:?}",
+                        room.number, other
+                    )));
+                }
+            }
+        }
+
+        diesel::update(housekeeping_tasks::table.find(task_id))
+            .set((
+                housekeeping_tasks::status.eq(HousekeepingTaskStatus::InProgress),
+                housekeeping_tasks::started_at.eq(Some(Utc::now())),
+                housekeeping_tasks::assigned_cleaner_id.eq(Some(cleaner_id)),
+            ))
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    fn complete_with_conn(
+        conn: &mut PgConnection,
+        task_id: Uuid,
+        cleaner_id: Uuid,
+        notes: Option<String>,
+    ) -> AppResult<HousekeepingTask> {
+        let task = Self::find_task_with_conn(conn, task_id, true)?;
+        Self::ensure_assignee(&task, cleaner_id)?;
+        if task.status != HousekeepingTaskStatus::InProgress {
+            return Err(AppError::InvalidStatusTransition(format!(
+                "Cannot complete a task with status {:?}; start it first",
+                task.status
+            )));
+        }
+
+        let completed = Self::set_status_with_conn(conn, &task, HousekeepingTaskStatus::Completed, notes)?;
+
+        if task.task_type.cleans_room() {
+            let other_open: i64 = housekeeping_tasks::table
+                .filter(housekeeping_tasks::room_id.eq(task.room_id))
+                .filter(housekeeping_tasks::id.ne(task.id))
+                .filter(housekeeping_tasks::status.eq_any(HousekeepingTaskStatus::OPEN))
+                .filter(housekeeping_tasks::task_type.eq_any(Self::room_clean_types()))
+                .count()
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if other_open == 0 {
+                let policy = Self::load_policy(conn).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                let room = rooms::table
+                    .find(task.room_id)
+                    .filter(rooms::status.eq_any([RoomStatus::Dirty, RoomStatus::Cleaning]));
+
+                // The cleaner stays on a room waiting for inspection, so a
+                // failed inspection goes back to them
+                if policy.inspection_required {
+                    diesel::update(room)
+                        .set(rooms::status.eq(RoomStatus::PendingInspection))
+                        .execute(conn)
+                } else {
+                    diesel::update(room)
+                        .set((
+                            rooms::status.eq(RoomStatus::Available),
+                            rooms::assigned_cleaner_id.eq(None::<Uuid>),
+                        ))
+                        .execute(conn)
+                }
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            }
+        }
+
+        Ok(completed)
+    }
+
+    fn release_with_conn(
+        conn: &mut PgConnection,
+        task_id: Uuid,
+        cleaner_id: Uuid,
+        notes: Option<String>,
+    ) -> AppResult<HousekeepingTask> {
+        let task = Self::find_task_with_conn(conn, task_id, true)?;
+        Self::ensure_assignee(&task, cleaner_id)?;
+        if task.status != HousekeepingTaskStatus::InProgress {
+            return Err(AppError::InvalidStatusTransition(format!(
+                "Cannot release a task with status {:?}",
+                task.status
+            )));
+        }
+
+        let released = Self::set_status_with_conn(conn, &task, HousekeepingTaskStatus::Pending, notes)?;
+
+        if task.task_type.cleans_room() {
+            Self::room_back_to_dirty_with_conn(conn, task.room_id)?;
+        }
+
+        Ok(released)
+    }
+
+    /// Set a task's status with the matching timestamps
+    fn set_status_with_conn(
+        conn: &mut PgConnection,
+        task: &HousekeepingTask,
+        status: HousekeepingTaskStatus,
+        notes: Option<String>,
+    ) -> AppResult<HousekeepingTask> {
+        let now = Utc::now();
+        let (started_at, completed_at) = match status {
+            HousekeepingTaskStatus::Pending => (None, None),
+            HousekeepingTaskStatus::InProgress => (Some(now), None),
+            HousekeepingTaskStatus::Completed => (task.started_at.or(Some(now)), Some(now)),
+            HousekeepingTaskStatus::Cancelled => (task.started_at, None),
+        };
+
+        diesel::update(housekeeping_tasks::table.find(task.id))
+            .set((
+                housekeeping_tasks::status.eq(status),
+                housekeeping_tasks::started_at.eq(started_at),
+                housekeeping_tasks::completed_at.eq(completed_at),
+                housekeeping_tasks::notes.eq(notes.or_else(|| task.notes.clone())),
+            ))
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// A room being cleaned goes back to Dirty when nobody is on it any more
+    fn room_back_to_dirty_with_conn(conn: &mut PgConnection, room_id: Uuid) -> AppResult<()> {
+        let in_progress: i64 = housekeeping_tasks::table
+            .filter(housekeeping_tasks::room_id.eq(room_id))
+            .filter(housekeeping_tasks::status.eq(HousekeepingTaskStatus::InProgress))
+            .filter(housekeeping_tasks::task_type.eq_any(Self::room_clean_types()))
+            .count()
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if in_progress == 0 {
+            diesel::update(
+                rooms::table
+                    .find(room_id)
+                    .filter(rooms::status.eq(RoomStatus::Cleaning)),
+            )
+            .set(rooms::status.eq(RoomStatus::Dirty))
+            .execute(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    fn set_room_cleaner_with_conn(
+        conn: &mut PgConnection,
+        room_id: Uuid,
+        cleaner_id: Option<Uuid>,
+    ) -> AppResult<()> {
+        diesel::update(rooms::table.find(room_id))
+            .set(rooms::assigned_cleaner_id.eq(cleaner_id))
+            .execute(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn find_task_with_conn(
+        conn: &mut PgConnection,
+        task_id: Uuid,
+        lock: bool,
+    ) -> AppResult<HousekeepingTask> {
+        let query = housekeeping_tasks::table.find(task_id);
+        let task = if lock {
+            query.for_update().first(conn).optional()
+        } else {
+            query.first(conn).optional()
+        };
+
+        task.map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound(format!("Task with ID '{}' not found", task_id)))
+    }
+
+    fn ensure_open(task: &HousekeepingTask) -> AppResult<()> {
+        if !task.status.is_open() {
+            return Err(AppError::InvalidStatusTransition(format!(
+                "Task is already {:?}",
+                task.status
+            )));
+        }
+        Ok(())
+    }
+
+    /// Cleaners work on their own tasks and unassigned ones
+    fn ensure_assignee(task: &HousekeepingTask, cleaner_id: Uuid) -> AppResult<()> {
+        match task.assigned_cleaner_id {
+            Some(assignee) if assignee != cleaner_id => Err(AppError::Forbidden(
+                "Task is assigned to another cleaner".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn ensure_active_cleaner_with_conn(conn: &mut PgConnection, user_id: Uuid) -> AppResult<()> {
+        let user: Option<User> = users::table
+            .find(user_id)
+            .first(conn)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match user {
+            Some(user) if user.role == UserRole::Cleaner && user.deactivated_at.is_none() => Ok(()),
+            _ => Err(AppError::ValidationError(format!(
+                "User '{}' is not an active cleaner",
+                user_id
+            ))),
+        }
+    }
+
+    fn arrival_today_with_conn(conn: &mut PgConnection, room_id: Uuid) -> AppResult<bool> {
+        let arrivals: i64 = bookings::table
+            .filter(bookings::room_id.eq(room_id))
+            .filter(bookings::status.eq(BookingStatus::Upcoming))
+            .filter(bookings::check_in_date.eq(Utc::now().date_naive()))
+            .count()
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(arrivals > 0)
+    }
+
+    fn views_with_conn(
+        conn: &mut PgConnection,
+        tasks: Vec<HousekeepingTask>,
+    ) -> AppResult<Vec<HousekeepingTaskView>> {
+        let room_ids: Vec<Uuid> = tasks.iter().map(|t| t.room_id).collect();
+
+        let rooms_by_id: HashMap<Uuid, Room> = rooms::table
+            .filter(rooms::id.eq_any(&room_ids))
+            .load::<Room>(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .map(|r| (r.id, r))
+            .collect();
+
+        let arriving: HashSet<Uuid> = bookings::table
+            .filter(bookings::room_id.eq_any(&room_ids))
+            .filter(bookings::status.eq(BookingStatus::Upcoming))
+            .filter(bookings::check_in_date.eq(Utc::now().date_naive()))
+            .select(bookings::room_id)
+            .load::<Uuid>(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .collect();
+
+        let now = Utc::now();
+        Ok(tasks
+            .into_iter()
+            .filter_map(|task| {
+                let room = rooms_by_id.get(&task.room_id)?;
+                Some(HousekeepingTaskView {
+                    room_number: room.number.clone(),
+                    room_status: room.status,
+                    arrival_today: arriving.contains(&task.room_id),
+                    sla_status: sla_status(task.status, task.due_at, task.completed_at, now),
+                    task,
+                })
+            })
+            .collect())
+    }
+}
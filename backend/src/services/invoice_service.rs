@@ -0,0 +1,762 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Datelike, NaiveDate, Utc};
+use diesel::connection::{AnsiTransactionManager, TransactionManager};
+use diesel::prelude::*;
+use pdf_writer::{Content, Name, Pdf, Rect, Ref, Str};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, FolioCharge, Invoice, NewInvoice, Payment, PaymentSummary, PaymentType, Room, TaxLine,
+};
+use crate::schema::{bookings, folio_charges, invoice_counters, invoices, payments, rooms};
+use crate::services::storage_service::{download_object, upload_object};
+use crate::services::PaymentService;
+
+/// MinIO bucket holding rendered invoices (private; served through the API)
+pub const INVOICE_BUCKET: &str = "invoices";
+
+/// Name printed at the top of every invoice
+pub const HOTEL_NAME: &str = "Pupinn Hotel";
+
+/// Invoice number for the `sequence`-th invoice of `year`, e.g. `INV-2025-000042`
+pub fn invoice_number(year: i32, sequence: i32) -> String {
+    format!("INV-{}-{:06}", year, sequence)
+}
+
+/// Format an amount in whole VND with thousands separators, e.g. `1,250,000`
+pub fn format_money(amount: &BigDecimal) -> String {
+    let rounded = amount.round(0).with_scale(0).to_string();
+    let (sign, digits) = match rounded.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", rounded.as_str()),
+    };
+
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    format!("{}{}", sign, grouped)
+}
+
+/// Format an exchange rate without trailing zeros, e.g. `25,400` or `1.085`
+pub fn format_rate(rate: &BigDecimal) -> String {
+    let fixed = rate.with_scale(6).to_string();
+    let (whole, fraction) = fixed.split_once('.').unwrap_or((fixed.as_str(), ""));
+    let whole = format_money(&whole.parse::<BigDecimal>().unwrap_or_default());
+    match fraction.trim_end_matches('0') {
+        "" => whole,
+        fraction => format!("{}.{}", whole, fraction),
+    }
+}
+
+/// Payment line text for an invoice, e.g. `Deposit (cash)`
+///
+/// Foreign currency payments also show what was paid and the rate used:
+/// `Payment (card, USD 120.00 at 25,400)`.
+pub fn describe_payment(payment: &Payment) -> String {
+    let kind = match payment.payment_type {
+        PaymentType::Deposit => "Deposit",
+        PaymentType::Partial => "Partial payment",
+        PaymentType::Full => "Payment",
+        PaymentType::Refund => "Refund",
+    };
+    let method = payment.payment_method.replace('_', " ");
+
+    match &payment.original_amount {
+        Some(original) => format!(
+            "{} ({}, {} {} at {})",
+            kind,
+            method,
+            payment.currency,
+            original.with_scale(2),
+            format_rate(&payment.exchange_rate)
+        ),
+        None => format!("{} ({})", kind, method),
+    }
+}
+
+/// Escape text for HTML element content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Encode text for the standard PDF fonts (WinAnsi)
+///
+/// Latin-1 characters are kept, other Vietnamese letters lose their
+/// diacritics (the built-in fonts cannot draw them) and anything else
+/// becomes `?`.
+pub fn pdf_text(text: &str) -> Vec<u8> {
+    const FOLDS: [(&str, char); 14] = [
+        ("àáảãạăằắẳẵặâầấẩẫậ", 'a'),
+        ("ÀÁẢÃẠĂẰẮẲẴẶÂẦẤẨẪẬ", 'A'),
+        ("èéẻẽẹêềếểễệ", 'e'),
+        ("ÈÉẺẼẸÊỀẾỂỄỆ", 'E'),
+        ("ìíỉĩị", 'i'),
+        ("ÌÍỈĨỊ", 'I'),
+        ("òóỏõọôồốổỗộơờớởỡợ", 'o'),
+        ("ÒÓỎÕỌÔỒỐỔỖỘƠỜỚỞỠỢ", 'O'),
+        ("ùúủũụưừứửữự", 'u'),
+        ("ÙÚỦŨỤƯỪỨỬỮỰ", 'U'),
+        ("ỳýỷỹỵ", 'y'),
+        ("ỲÝỶỸỴ", 'Y'),
+        ("đ", 'd'),
+        ("Đ", 'D'),
+    ];
+
+    text.chars()
+        .map(|c| {
+            if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) {
+                c as u32 as u8
+            } else if let Some((_, base)) = FOLDS.iter().find(|(group, _)| group.contains(c)) {
+                *base as u8
+            } else {
+                b'?'
+            }
+        })
+        .collect()
+}
+
+/// Download format of an invoice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InvoiceFormat {
+    Html,
+    Pdf,
+}
+
+impl InvoiceFormat {
+    /// MIME type of the rendered document
+    pub fn content_type(self) -> &'static str {
+        match self {
+            InvoiceFormat::Html => "text/html; charset=utf-8",
+            InvoiceFormat::Pdf => "application/pdf",
+        }
+    }
+
+    /// File extension of the rendered document
+    pub fn extension(self) -> &'static str {
+        match self {
+            InvoiceFormat::Html => "html",
+            InvoiceFormat::Pdf => "pdf",
+        }
+    }
+}
+
+/// Billed line of an invoice (amounts as entered, inclusive taxes included)
+#[derive(Debug, Clone, Serialize)]
+pub struct InvoiceLine {
+    pub description: String,
+    pub quantity: i32,
+    pub unit_price: BigDecimal,
+    pub amount: BigDecimal,
+}
+
+/// Payment or refund listed on an invoice
+#[derive(Debug, Clone, Serialize)]
+pub struct InvoicePayment {
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: BigDecimal,
+}
+
+/// Everything printed on an invoice
+#[derive(Debug, Clone, Serialize)]
+pub struct InvoiceDocument {
+    pub invoice_number: String,
+    pub issued_on: NaiveDate,
+    pub booking_reference: String,
+    pub guest_name: String,
+    pub room_number: String,
+    pub room_type: String,
+    pub check_in_date: NaiveDate,
+    pub check_out_date: NaiveDate,
+    pub lines: Vec<InvoiceLine>,
+    /// Sum of the billed lines
+    pub subtotal: BigDecimal,
+    pub taxes: Vec<TaxLine>,
+    pub net_total: BigDecimal,
+    pub tax_total: BigDecimal,
+    pub total_amount: BigDecimal,
+    pub payments: Vec<InvoicePayment>,
+    pub total_paid: BigDecimal,
+    pub balance_due: BigDecimal,
+}
+
+/// Collect the invoice contents of a booking
+pub fn build_invoice_document(
+    invoice_number: String,
+    issued_on: NaiveDate,
+    booking: &Booking,
+    room: &Room,
+    charges: &[FolioCharge],
+    payment_list: &[Payment],
+    summary: &PaymentSummary,
+) -> InvoiceDocument {
+    let nights = (booking.check_out_date - booking.check_in_date).num_days();
+    let mut lines = vec![InvoiceLine {
+        description: format!(
+            "Room {} ({:?}), {} night{}",
+            room.number,
+            room.room_type,
+            nights,
+            if nights == 1 { "" } else { "s" }
+        ),
+        quantity: 1,
+        unit_price: booking.price.clone(),
+        amount: booking.price.clone(),
+    }];
+
+    lines.extend(charges.iter().map(|charge| InvoiceLine {
+        description: charge.description.clone(),
+        quantity: charge.quantity,
+        unit_price: charge.unit_price.clone(),
+        amount: &charge.unit_price * BigDecimal::from(charge.quantity),
+    }));
+
+    let subtotal = lines.iter().map(|l| l.amount.clone()).sum();
+
+    let payments = payment_list
+        .iter()
+        .map(|payment| InvoicePayment {
+            date: payment.created_at.date_naive(),
+            description: describe_payment(payment),
+            amount: payment.amount.clone(),
+        })
+        .collect();
+
+    InvoiceDocument {
+        invoice_number,
+        issued_on,
+        booking_reference: booking.reference.clone(),
+        guest_name: booking.guest_name.clone(),
+        room_number: room.number.clone(),
+        room_type: format!("{:?}", room.room_type),
+        check_in_date: booking.check_in_date,
+        check_out_date: booking.check_out_date,
+        lines,
+        subtotal,
+        taxes: summary.taxes.clone(),
+        net_total: summary.net_total.clone(),
+        tax_total: summary.tax_total.clone(),
+        total_amount: summary.total_price.clone(),
+        payments,
+        total_paid: summary.total_paid.clone(),
+        balance_due: summary.remaining_balance.clone(),
+    }
+}
+
+/// Label of a tax line, e.g. `VAT 10% (included)`
+fn tax_label(tax: &TaxLine) -> String {
+    format!(
+        "{} {}%{}",
+        tax.name,
+        tax.rate.normalized(),
+        if tax.is_inclusive { " (included)" } else { "" }
+    )
+}
+
+/// Render an invoice as a standalone HTML page
+pub fn render_invoice_html(doc: &InvoiceDocument) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!("<title>Invoice {}</title>\n", escape_html(&doc.invoice_number)));
+    html.push_str(
+        "<style>\
+body{font-family:Helvetica,Arial,sans-serif;color:#222;max-width:760px;margin:32px auto;}\
+h1{margin:0;font-size:24px;}\
+table{width:100%;border-collapse:collapse;margin-top:16px;}\
+th,td{padding:6px 8px;border-bottom:1px solid #ddd;text-align:left;}\
+td.num,th.num{text-align:right;}\
+tr.total td{font-weight:bold;border-top:2px solid #222;}\
+.meta td{border:none;padding:2px 8px 2px 0;}\
+</style>\n</head>\n<body>\n",
+    );
+
+    html.push_str(&format!("<h1>{}</h1>\n", escape_html(HOTEL_NAME)));
+    html.push_str(&format!(
+        "<h2>Invoice {}</h2>\n",
+        escape_html(&doc.invoice_number)
+    ));
+
+    html.push_str("<table class=\"meta\">\n");
+    for (label, value) in [
+        ("Issued", doc.issued_on.to_string()),
+        ("Booking", doc.booking_reference.clone()),
+        ("Guest", doc.guest_name.clone()),
+        ("Room", format!("{} ({})", doc.room_number, doc.room_type)),
+        (
+            "Stay",
+            format!("{} to {}", doc.check_in_date, doc.check_out_date),
+        ),
+    ] {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td></tr>\n",
+            label,
+            escape_html(&value)
+        ));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str(
+        "<table>\n<tr><th>Description</th><th class=\"num\">Qty</th>\
+<th class=\"num\">Unit price</th><th class=\"num\">Amount</th></tr>\n",
+    );
+    for line in &doc.lines {
+        html.push_str(&format!(
+            "<tr><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>\n",
+            escape_html(&line.description),
+            line.quantity,
+            format_money(&line.unit_price),
+            format_money(&line.amount)
+        ));
+    }
+    html.push_str(&format!(
+        "<tr><td colspan=\"3\">Subtotal</td><td class=\"num\">{}</td></tr>\n",
+        format_money(&doc.subtotal)
+    ));
+    for tax in &doc.taxes {
+        html.push_str(&format!(
+            "<tr><td colspan=\"3\">{}</td><td class=\"num\">{}</td></tr>\n",
+            escape_html(&tax_label(tax)),
+            format_money(&tax.amount)
+        ));
+    }
+    html.push_str(&format!(
+        "<tr><td colspan=\"3\">Net amount</td><td class=\"num\">{}</td></tr>\n\
+<tr><td colspan=\"3\">Total tax</td><td class=\"num\">{}</td></tr>\n\
+<tr class=\"total\"><td colspan=\"3\">Total (VND)</td><td class=\"num\">{}</td></tr>\n</table>\n",
+        format_money(&doc.net_total),
+        format_money(&doc.tax_total),
+        format_money(&doc.total_amount)
+    ));
+
+    if !doc.payments.is_empty() {
+        html.push_str("<table>\n<tr><th>Date</th><th>Payment</th><th class=\"num\">Amount</th></tr>\n");
+        for payment in &doc.payments {
+            html.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td class=\"num\">{}</td></tr>\n",
+                payment.date,
+                escape_html(&payment.description),
+                format_money(&payment.amount)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str(&format!(
+        "<table>\n<tr><td>Total paid</td><td class=\"num\">{}</td></tr>\n\
+<tr class=\"total\"><td>Balance due (VND)</td><td class=\"num\">{}</td></tr>\n</table>\n",
+        format_money(&doc.total_paid),
+        format_money(&doc.balance_due)
+    ));
+
+    if doc.balance_due <= BigDecimal::zero() {
+        html.push_str("<p><strong>Paid in full. Thank you for staying with us.</strong></p>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Approximate width of Helvetica text in points
+fn text_width(text: &[u8], size: f32) -> f32 {
+    let units: u32 = text
+        .iter()
+        .map(|b| match b {
+            b',' | b'.' | b' ' => 278,
+            b'-' | b'(' | b')' => 333,
+            b'A'..=b'Z' => 667,
+            _ => 556,
+        })
+        .sum();
+    units as f32 * size / 1000.0
+}
+
+/// Simple top-down page layout on A4 with automatic page breaks
+struct PdfLayout {
+    pages: Vec<Content>,
+    current: Content,
+    y: f32,
+}
+
+impl PdfLayout {
+    const TOP: f32 = 800.0;
+    const BOTTOM: f32 = 50.0;
+    const LEFT: f32 = 50.0;
+    const RIGHT: f32 = 545.0;
+
+    fn new() -> Self {
+        Self {
+            pages: Vec::new(),
+            current: Content::new(),
+            y: Self::TOP,
+        }
+    }
+
+    /// Move down by `height`, starting a new page if it does not fit
+    fn advance(&mut self, height: f32) {
+        if self.y - height < Self::BOTTOM {
+            let page = std::mem::replace(&mut self.current, Content::new());
+            self.pages.push(page);
+            self.y = Self::TOP;
+        }
+        self.y -= height;
+    }
+
+    fn text(&mut self, x: f32, bold: bool, size: f32, text: &str) {
+        let font = if bold { Name(b"F2") } else { Name(b"F1") };
+        let y = self.y;
+        self.current
+            .begin_text()
+            .set_font(font, size)
+            .next_line(x, y)
+            .show(Str(&pdf_text(text)))
+            .end_text();
+    }
+
+    fn text_right(&mut self, right: f32, bold: bool, size: f32, text: &str) {
+        let width = text_width(&pdf_text(text), size);
+        self.text(right - width, bold, size, text);
+    }
+
+    fn rule(&mut self) {
+        let y = self.y - 4.0;
+        self.current
+            .set_line_width(0.5)
+            .move_to(Self::LEFT, y)
+            .line_to(Self::RIGHT, y)
+            .stroke();
+    }
+
+    /// Row with a label on the left and an amount on the right
+    fn amount_row(&mut self, label: &str, amount: &BigDecimal, bold: bool) {
+        self.advance(16.0);
+        self.text(Self::LEFT, bold, 10.0, label);
+        self.text_right(Self::RIGHT, bold, 10.0, &format_money(amount));
+    }
+
+    fn finish(mut self) -> Vec<Content> {
+        self.pages.push(self.current);
+        self.pages
+    }
+}
+
+/// Render an invoice as a PDF document
+pub fn render_invoice_pdf(doc: &InvoiceDocument) -> Vec<u8> {
+    let mut layout = PdfLayout::new();
+
+    layout.text(PdfLayout::LEFT, true, 20.0, HOTEL_NAME);
+    layout.advance(26.0);
+    layout.text(
+        PdfLayout::LEFT,
+        true,
+        14.0,
+        &format!("Invoice {}", doc.invoice_number),
+    );
+
+    for (label, value) in [
+        ("Issued", doc.issued_on.to_string()),
+        ("Booking", doc.booking_reference.clone()),
+        ("Guest", doc.guest_name.clone()),
+        ("Room", format!("{} ({})", doc.room_number, doc.room_type)),
+        (
+            "Stay",
+            format!("{} to {}", doc.check_in_date, doc.check_out_date),
+        ),
+    ] {
+        layout.advance(15.0);
+        layout.text(PdfLayout::LEFT, false, 10.0, label);
+        layout.text(PdfLayout::LEFT + 70.0, false, 10.0, &value);
+    }
+
+    layout.advance(30.0);
+    layout.text(PdfLayout::LEFT, true, 10.0, "Description");
+    layout.text_right(360.0, true, 10.0, "Qty");
+    layout.text_right(450.0, true, 10.0, "Unit price");
+    layout.text_right(PdfLayout::RIGHT, true, 10.0, "Amount");
+    layout.rule();
+
+    for line in &doc.lines {
+        let description: String = line.description.chars().take(50).collect();
+        layout.advance(16.0);
+        layout.text(PdfLayout::LEFT, false, 10.0, &description);
+        layout.text_right(360.0, false, 10.0, &line.quantity.to_string());
+        layout.text_right(450.0, false, 10.0, &format_money(&line.unit_price));
+        layout.text_right(PdfLayout::RIGHT, false, 10.0, &format_money(&line.amount));
+    }
+    layout.rule();
+
+    layout.amount_row("Subtotal", &doc.subtotal, false);
+    for tax in &doc.taxes {
+        layout.amount_row(&tax_label(tax), &tax.amount, false);
+    }
+    layout.amount_row("Net amount", &doc.net_total, false);
+    layout.amount_row("Total tax", &doc.tax_total, false);
+    layout.rule();
+    layout.amount_row("Total (VND)", &doc.total_amount, true);
+
+    if !doc.payments.is_empty() {
+        layout.advance(14.0);
+        for payment in &doc.payments {
+            layout.amount_row(
+                &format!("{}  {}", payment.date, payment.description),
+                &payment.amount,
+                false,
+            );
+        }
+    }
+
+    layout.advance(8.0);
+    layout.amount_row("Total paid", &doc.total_paid, false);
+    layout.rule();
+    layout.amount_row("Balance due (VND)", &doc.balance_due, true);
+
+    if doc.balance_due <= BigDecimal::zero() {
+        layout.advance(24.0);
+        layout.text(
+            PdfLayout::LEFT,
+            true,
+            10.0,
+            "Paid in full. Thank you for staying with us.",
+        );
+    }
+
+    let pages = layout.finish();
+
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let bold_font_id = Ref::new(4);
+    let page_ids: Vec<Ref> = (0..pages.len()).map(|i| Ref::new(5 + 2 * i as i32)).collect();
+
+    let mut pdf = Pdf::new();
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(pages.len() as i32);
+    pdf.type1_font(font_id)
+        .base_font(Name(b"Helvetica"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+    pdf.type1_font(bold_font_id)
+        .base_font(Name(b"Helvetica-Bold"))
+        .encoding_predefined(Name(b"WinAnsiEncoding"));
+
+    for (page_id, content) in page_ids.iter().zip(pages) {
+        let content_id = Ref::new(page_id.get() + 1);
+        let mut page = pdf.page(*page_id);
+        page.parent(page_tree_id)
+            .media_box(Rect::new(0.0, 0.0, 595.0, 842.0))
+            .contents(content_id);
+        page.resources()
+            .fonts()
+            .pair(Name(b"F1"), font_id)
+            .pair(Name(b"F2"), bold_font_id);
+        drop(page);
+        pdf.stream(content_id, &content.finish());
+    }
+
+    pdf.finish()
+}
+
+/// Invoice service: issuing and serving numbered invoices
+pub struct InvoiceService {
+    pool: DbPool,
+    s3_client: aws_sdk_s3::Client,
+}
+
+impl InvoiceService {
+    /// Create a new InvoiceService instance
+    pub fn new(pool: DbPool, s3_client: aws_sdk_s3::Client) -> Self {
+        Self { pool, s3_client }
+    }
+
+    /// Issue the next numbered invoice for a booking
+    ///
+    /// The number is taken, the row written and both documents uploaded in
+    /// one database transaction, so a failed upload leaves no gap in the
+    /// numbering.
+    pub async fn issue_invoice(&self, booking_id: Uuid, issued_by_user_id: Uuid) -> AppResult<Invoice> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        AnsiTransactionManager::begin_transaction(&mut *conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match self.issue_with_conn(&mut conn, booking_id, issued_by_user_id).await {
+            Ok(invoice) => {
+                AnsiTransactionManager::commit_transaction(&mut *conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                Ok(invoice)
+            }
+            Err(e) => {
+                if let Err(rollback_err) = AnsiTransactionManager::rollback_transaction(&mut *conn) {
+                    tracing::error!("Failed to roll back invoice transaction: {}", rollback_err);
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn issue_with_conn(
+        &self,
+        conn: &mut PgConnection,
+        booking_id: Uuid,
+        issued_by_user_id: Uuid,
+    ) -> AppResult<Invoice> {
+        let booking: Booking = bookings::table
+            .find(booking_id)
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))?;
+
+        let room: Room = rooms::table
+            .find(booking.room_id)
+            .first(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let charges: Vec<FolioCharge> = folio_charges::table
+            .filter(folio_charges::booking_id.eq(booking_id))
+            .filter(folio_charges::voided_at.is_null())
+            .order(folio_charges::created_at.asc())
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let payment_list: Vec<Payment> = payments::table
+            .filter(payments::booking_id.eq(booking_id))
+            .filter(payments::voided_at.is_null())
+            .order(payments::created_at.asc())
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let summary = PaymentService::summary_with_conn(conn, &booking)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Row-locked counter: concurrent issuers wait for this transaction
+        let today = Utc::now().date_naive();
+        let sequence: i32 = diesel::insert_into(invoice_counters::table)
+            .values((
+                invoice_counters::year.eq(today.year()),
+                invoice_counters::last_number.eq(1),
+            ))
+            .on_conflict(invoice_counters::year)
+            .do_update()
+            .set(invoice_counters::last_number.eq(invoice_counters::last_number + 1))
+            .returning(invoice_counters::last_number)
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let number = invoice_number(today.year(), sequence);
+
+        let document = build_invoice_document(
+            number.clone(),
+            today,
+            &booking,
+            &room,
+            &charges,
+            &payment_list,
+            &summary,
+        );
+        let html = render_invoice_html(&document);
+        let pdf = render_invoice_pdf(&document);
+
+        let html_key = format!("{}/{}.html", today.year(), number);
+        let pdf_key = format!("{}/{}.pdf", today.year(), number);
+
+        let invoice: Invoice = diesel::insert_into(invoices::table)
+            .values(&NewInvoice {
+                invoice_number: number,
+                booking_id,
+                guest_name: document.guest_name,
+                room_number: document.room_number,
+                check_in_date: document.check_in_date,
+                check_out_date: document.check_out_date,
+                net_total: document.net_total,
+                tax_total: document.tax_total,
+                total_amount: document.total_amount,
+                total_paid: document.total_paid,
+                balance_due: document.balance_due,
+                html_object_key: html_key.clone(),
+                pdf_object_key: pdf_key.clone(),
+                issued_by_user_id,
+            })
+            .get_result(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        for (key, data, format) in [
+            (html_key, html.into_bytes(), InvoiceFormat::Html),
+            (pdf_key, pdf, InvoiceFormat::Pdf),
+        ] {
+            upload_object(&self.s3_client, INVOICE_BUCKET, &key, data, format.content_type())
+                .await
+                .map_err(|e| AppError::InternalError(format!("Failed to store invoice: {}", e)))?;
+        }
+
+        Ok(invoice)
+    }
+
+    /// List the invoices of a booking, newest first
+    pub fn list_invoices(&self, booking_id: Uuid) -> AppResult<Vec<Invoice>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        invoices::table
+            .filter(invoices::booking_id.eq(booking_id))
+            .order(invoices::issued_at.desc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Fetch a stored invoice document of a booking
+    pub async fn download_invoice(
+        &self,
+        booking_id: Uuid,
+        invoice_id: Uuid,
+        format: InvoiceFormat,
+    ) -> AppResult<(Invoice, Vec<u8>)> {
+        let invoice: Invoice = {
+            let mut conn = self
+                .pool
+                .get()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            invoices::table
+                .filter(invoices::id.eq(invoice_id))
+                .filter(invoices::booking_id.eq(booking_id))
+                .first(&mut conn)
+                .map_err(|_| {
+                    AppError::NotFound(format!("Invoice with ID '{}' not found", invoice_id))
+                })?
+        };
+
+        let key = match format {
+            InvoiceFormat::Html => &invoice.html_object_key,
+            InvoiceFormat::Pdf => &invoice.pdf_object_key,
+        };
+
+        let data = download_object(&self.s3_client, INVOICE_BUCKET, key)
+            .await
+            .map_err(|e| AppError::InternalError(format!("Failed to load invoice: {}", e)))?;
+
+        Ok((invoice, data))
+    }
+}
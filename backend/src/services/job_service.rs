@@ -0,0 +1,209 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::config::{DirtyRoomSweepConfig, NoShowAction, NoShowJobConfig};
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{BookingStatus, JobRun, NewJobRun, RoomStatus};
+use crate::schema::{bookings, job_runs, rooms};
+use crate::services::NoShowService;
+
+/// Job name for CheckedIn -> Overstay detection
+pub const OVERSTAY_JOB: &str = "overstay_detection";
+/// Job name for the no-show policy on Upcoming bookings
+pub const NO_SHOW_JOB: &str = "no_show";
+/// Job name for the sweep flagging rooms left Dirty too long
+pub const DIRTY_ROOM_SWEEP_JOB: &str = "dirty_room_sweep";
+
+/// Maximum number of runs returned by `list_runs`
+pub const MAX_RUNS_LIMIT: i64 = 200;
+
+/// What a single job execution did
+#[derive(Debug, Clone)]
+pub struct JobOutcome {
+    /// Bookings or rooms the run changed or flagged
+    pub affected_ids: Vec<Uuid>,
+    pub summary: String,
+}
+
+/// Last check-in date that still counts as "expected to arrive".
+///
+/// Upcoming bookings with a check-in date before this cutoff are no-shows.
+pub fn no_show_cutoff(today: NaiveDate, grace_days: i64) -> NaiveDate {
+    today - Duration::days(grace_days.max(0))
+}
+
+/// Rooms that turned Dirty before this instant have been dirty too long
+pub fn dirty_room_cutoff(now: DateTime<Utc>, max_dirty_hours: i64) -> DateTime<Utc> {
+    now - Duration::hours(max_dirty_hours.max(1))
+}
+
+/// Service running booking/room lifecycle jobs and recording their runs
+pub struct JobService {
+    pool: DbPool,
+}
+
+impl JobService {
+    /// Create a new JobService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Move CheckedIn bookings past their check-out date to Overstay
+    pub fn run_overstay_detection(&self) -> AppResult<JobOutcome> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let today = Utc::now().date_naive();
+
+        let ids: Vec<Uuid> = diesel::update(
+            bookings::table
+                .filter(bookings::status.eq(BookingStatus::CheckedIn))
+                .filter(bookings::check_out_date.lt(today)),
+        )
+        .set(bookings::status.eq(BookingStatus::Overstay))
+        .returning(bookings::id)
+        .get_results(&mut conn)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let summary = format!("{} booking(s) marked as overstay", ids.len());
+        Ok(JobOutcome {
+            affected_ids: ids,
+            summary,
+        })
+    }
+
+    /// Apply the no-show policy to Upcoming bookings past the grace period
+    pub fn run_no_show(&self, config: &NoShowJobConfig) -> AppResult<JobOutcome> {
+        let no_show_service = NoShowService::new(self.pool.clone());
+        let policy = no_show_service.get_policy()?;
+        let cutoff = no_show_cutoff(Utc::now().date_naive(), policy.grace_days);
+
+        let (ids, verb) = match config.action {
+            NoShowAction::Report => (no_show_service.list_overdue(cutoff)?, "flagged"),
+            NoShowAction::Mark => (
+                no_show_service.mark_overdue(cutoff)?,
+                "marked as no-show",
+            ),
+        };
+
+        let summary = format!(
+            "{} booking(s) {} (check-in before {})",
+            ids.len(),
+            verb,
+            cutoff
+        );
+        Ok(JobOutcome {
+            affected_ids: ids,
+            summary,
+        })
+    }
+
+    /// Flag rooms that have stayed Dirty longer than the configured threshold
+    pub fn run_dirty_room_sweep(&self, config: &DirtyRoomSweepConfig) -> AppResult<JobOutcome> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let cutoff = dirty_room_cutoff(Utc::now(), config.max_dirty_hours);
+
+        let stale: Vec<(Uuid, String)> = rooms::table
+            .filter(rooms::status.eq(RoomStatus::Dirty))
+            .filter(rooms::status_changed_at.lt(cutoff))
+            .order(rooms::number.asc())
+            .select((rooms::id, rooms::number))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let summary = if stale.is_empty() {
+            format!("No rooms dirty for more than {}h", config.max_dirty_hours)
+        } else {
+            let numbers: Vec<&str> = stale.iter().map(|(_, n)| n.as_str()).collect();
+            tracing::warn!(
+                "Rooms dirty for more than {}h: {}",
+                config.max_dirty_hours,
+                numbers.join(", ")
+            );
+            format!(
+                "{} room(s) dirty for more than {}h: {}",
+                stale.len(),
+                config.max_dirty_hours,
+                numbers.join(", ")
+            )
+        };
+
+        Ok(JobOutcome {
+            affected_ids: stale.into_iter().map(|(id, _)| id).collect(),
+            summary,
+        })
+    }
+
+    /// Run a job and record the result, successful or not
+    pub fn execute<F>(&self, job_name: &str, job: F) -> AppResult<JobRun>
+    where
+        F: FnOnce(&Self) -> AppResult<JobOutcome>,
+    {
+        let started_at = Utc::now();
+        let result = job(self);
+        let finished_at = Utc::now();
+
+        let new_run = match result {
+            Ok(outcome) => NewJobRun {
+                job_name,
+                started_at,
+                finished_at,
+                success: true,
+                affected_count: outcome.affected_ids.len() as i32,
+                affected_ids: outcome.affected_ids,
+                summary: outcome.summary,
+                error_message: None,
+            },
+            Err(e) => {
+                tracing::error!("Job '{}' failed: {}", job_name, e);
+                NewJobRun {
+                    job_name,
+                    started_at,
+                    finished_at,
+                    success: false,
+                    affected_count: 0,
+                    affected_ids: Vec::new(),
+                    summary: "Run failed".to_string(),
+                    error_message: Some(e.to_string()),
+                }
+            }
+        };
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        diesel::insert_into(job_runs::table)
+            .values(&new_run)
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// List recent job runs, newest first
+    pub fn list_runs(&self, job_name: Option<&str>, limit: i64) -> AppResult<Vec<JobRun>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut query = job_runs::table.into_boxed();
+        if let Some(name) = job_name {
+            query = query.filter(job_runs::job_name.eq(name));
+        }
+
+        query
+            .order(job_runs::started_at.desc())
+            .limit(limit.clamp(1, MAX_RUNS_LIMIT))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+}
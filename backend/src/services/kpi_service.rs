@@ -0,0 +1,466 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, BookingRoomStay, BookingStatus, ForecastDay, ForecastFigures, ForecastReport,
+    ForecastTotals, KpiBreakdown, KpiPeriod, KpiPeriodReport, KpiReport, Kpis, RoomType, RoomTypeKpis,
+    SourceKpis,
+};
+use crate::schema::{booking_room_stays, bookings, rooms};
+use crate::services::booking_service::{nightly_revenue, room_segments};
+use crate::services::exchange_rate_service::BASE_CURRENCY;
+use crate::services::tax_service::{applicable_rules, apply_taxes, TaxTarget};
+use crate::services::TaxService;
+
+/// Longest date range a KPI report covers
+pub const MAX_KPI_RANGE_DAYS: i64 = 731;
+
+/// Longest look-ahead of an on-the-books report
+pub const MAX_FORECAST_DAYS: i64 = 365;
+
+/// Days back to the same weekday last year
+pub const LAST_YEAR_OFFSET_DAYS: i64 = 364;
+
+/// First day of the period `date` falls in
+pub fn period_start(date: NaiveDate, period: KpiPeriod) -> NaiveDate {
+    match period {
+        KpiPeriod::Day => date,
+        KpiPeriod::Week => date - Duration::days(date.weekday().num_days_from_monday() as i64),
+        KpiPeriod::Month => date.with_day(1).unwrap(),
+    }
+}
+
+/// Periods covering `[start, end]` as `(first day, last day)`, the first and
+/// last clipped to the range
+pub fn report_periods(start: NaiveDate, end: NaiveDate, period: KpiPeriod) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut periods = Vec::new();
+    let mut from = start;
+    while from <= end {
+        let next = match period {
+            KpiPeriod::Day => from + Duration::days(1),
+            KpiPeriod::Week => period_start(from, period) + Duration::days(7),
+            KpiPeriod::Month => period_start(from, period)
+                .checked_add_months(chrono::Months::new(1))
+                .unwrap(),
+        };
+        let to = (next - Duration::days(1)).min(end);
+        periods.push((from, to));
+        from = next;
+    }
+    periods
+}
+
+/// Counts a set of KPIs is worked out from
+#[derive(Debug, Clone, Default)]
+pub struct KpiTotals {
+    pub room_nights_sold: i64,
+    pub room_revenue: BigDecimal,
+    /// Bookings arriving in the range, whatever became of them
+    pub bookings: i64,
+    pub cancelled: i64,
+    /// Arrivals that were not cancelled or no-shows
+    pub arrivals: i64,
+    pub stay_nights: i64,
+    pub lead_days: i64,
+}
+
+/// KPIs from their counts and the room nights available
+pub fn compute_kpis(totals: &KpiTotals, available_room_nights: i64) -> Kpis {
+    let per = |count: i64| -> Option<BigDecimal> {
+        (count > 0).then(|| (&totals.room_revenue / BigDecimal::from(count)).round(2))
+    };
+    let average = |sum: i64, count: i64| -> Option<f64> { (count > 0).then(|| sum as f64 / count as f64) };
+    let percentage = |part: i64, whole: i64| -> f64 {
+        if whole > 0 {
+            part as f64 / whole as f64 * 100.0
+        } else {
+            0.0
+        }
+    };
+
+    Kpis {
+        available_room_nights,
+        room_nights_sold: totals.room_nights_sold,
+        room_revenue: totals.room_revenue.clone(),
+        occupancy_rate: percentage(totals.room_nights_sold, available_room_nights),
+        adr: per(totals.room_nights_sold),
+        revpar: per(available_room_nights),
+        arrivals: totals.arrivals,
+        average_length_of_stay: average(totals.stay_nights, totals.arrivals),
+        average_lead_time_days: average(totals.lead_days, totals.arrivals),
+        cancelled_count: totals.cancelled,
+        cancellation_rate: percentage(totals.cancelled, totals.bookings),
+    }
+}
+
+/// Room nights and revenue with the occupancy they make of `room_count`
+/// rooms over `days` days
+pub fn forecast_figures(room_nights: i64, revenue: BigDecimal, room_count: i64, days: i64) -> ForecastFigures {
+    let capacity = room_count * days;
+    ForecastFigures {
+        room_nights,
+        revenue,
+        occupancy_rate: if capacity > 0 {
+            room_nights as f64 / capacity as f64 * 100.0
+        } else {
+            0.0
+        },
+    }
+}
+
+/// Pickup forecast of a stay date: `(room nights, revenue)`
+///
+/// What is on the books plus what was picked up last year with the same
+/// notice left. Nights are capped at the rooms there are, and the pickup
+/// revenue is cut in the same proportion.
+pub fn pickup_forecast(
+    on_the_books: (i64, &BigDecimal),
+    pickup: (i64, &BigDecimal),
+    room_count: i64,
+) -> (i64, BigDecimal) {
+    let (booked_nights, booked_revenue) = on_the_books;
+    let (pickup_nights, pickup_revenue) = pickup;
+
+    let room_left = (room_count - booked_nights).max(0);
+    if pickup_nights <= room_left {
+        return (booked_nights + pickup_nights, booked_revenue + pickup_revenue);
+    }
+
+    let kept = (pickup_revenue * BigDecimal::from(room_left) / BigDecimal::from(pickup_nights)).round(2);
+    (booked_nights + room_left, booked_revenue + kept)
+}
+
+/// Totals of the whole hotel, each room type and each source
+#[derive(Debug, Default)]
+struct KpiBuckets {
+    hotel: KpiTotals,
+    by_room_type: HashMap<RoomType, KpiTotals>,
+    by_source: BTreeMap<String, KpiTotals>,
+}
+
+impl KpiBuckets {
+    fn add(&mut self, room_type: Option<RoomType>, source: &str, update: impl Fn(&mut KpiTotals)) {
+        update(&mut self.hotel);
+        if let Some(room_type) = room_type {
+            update(self.by_room_type.entry(room_type).or_default());
+        }
+        update(self.by_source.entry(source.to_string()).or_default());
+    }
+
+    fn breakdown(&self, rooms_by_type: &[(RoomType, i64)], nights: i64) -> KpiBreakdown {
+        let room_count: i64 = rooms_by_type.iter().map(|(_, count)| count).sum();
+        let hotel_nights = room_count * nights;
+        let empty = KpiTotals::default();
+
+        KpiBreakdown {
+            kpis: compute_kpis(&self.hotel, hotel_nights),
+            by_room_type: rooms_by_type
+                .iter()
+                .map(|(room_type, count)| RoomTypeKpis {
+                    room_type: *room_type,
+                    kpis: compute_kpis(self.by_room_type.get(room_type).unwrap_or(&empty), count * nights),
+                })
+                .collect(),
+            by_source: self
+                .by_source
+                .iter()
+                .map(|(source, totals)| SourceKpis {
+                    source: source.clone(),
+                    kpis: compute_kpis(totals, hotel_nights),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// KPI service: hotel-level performance indicators
+pub struct KpiService {
+    pool: DbPool,
+}
+
+impl KpiService {
+    /// Create a new KpiService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// KPIs for `[start_date, end_date]`, overall and per period
+    pub fn get_kpis(
+        &self,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        group_by: KpiPeriod,
+    ) -> AppResult<KpiReport> {
+        if end_date < start_date {
+            return Err(AppError::ValidationError(
+                "End date must not be before start date".to_string(),
+            ));
+        }
+        if (end_date - start_date).num_days() >= MAX_KPI_RANGE_DAYS {
+            return Err(AppError::ValidationError(format!(
+                "KPI reports cover at most {} days",
+                MAX_KPI_RANGE_DAYS
+            )));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let room_types: HashMap<Uuid, RoomType> = rooms::table
+            .select((rooms::id, rooms::room_type))
+            .load::<(Uuid, RoomType)>(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .collect();
+        let rooms_by_type: Vec<(RoomType, i64)> = [RoomType::Single, RoomType::Double, RoomType::Suite]
+            .into_iter()
+            .map(|t| (t, room_types.values().filter(|rt| **rt == t).count() as i64))
+            .filter(|(_, count)| *count > 0)
+            .collect();
+
+        // Stays touching the range, which includes every arrival in it
+        let range_bookings: Vec<Booking> = bookings::table
+            .filter(bookings::check_in_date.le(end_date))
+            .filter(bookings::check_out_date.gt(start_date))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let booking_ids: Vec<Uuid> = range_bookings.iter().map(|b| b.id).collect();
+        let stays: Vec<BookingRoomStay> = booking_room_stays::table
+            .filter(booking_room_stays::booking_id.eq_any(&booking_ids))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let rules = TaxService::active_rules_with_conn(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let in_range = |date: NaiveDate| date >= start_date && date <= end_date;
+        let mut overall = KpiBuckets::default();
+        let mut periods: BTreeMap<NaiveDate, KpiBuckets> = BTreeMap::new();
+
+        for booking in &range_bookings {
+            let source = booking.creation_source.as_str();
+            let cancelled = booking.status == BookingStatus::Cancelled;
+            let stayed = !matches!(booking.status, BookingStatus::Cancelled | BookingStatus::NoShow);
+
+            if stayed {
+                let booking_rules = applicable_rules(&rules, TaxTarget::Room, booking.check_in_date);
+                for (night, room_id, amount) in nightly_revenue(booking, &stays) {
+                    if !in_range(night) {
+                        continue;
+                    }
+                    let net = apply_taxes(&amount, &booking_rules).net;
+                    let add_night = |t: &mut KpiTotals| {
+                        t.room_nights_sold += 1;
+                        t.room_revenue += &net;
+                    };
+                    let room_type = room_types.get(&room_id).copied();
+                    overall.add(room_type, source, add_night);
+                    periods
+                        .entry(period_start(night, group_by))
+                        .or_default()
+                        .add(room_type, source, add_night);
+                }
+            }
+
+            if in_range(booking.check_in_date) {
+                let first_room = room_segments(booking, &stays)[0].0;
+                let nights = (booking.check_out_date - booking.check_in_date).num_days();
+                let lead_days = (booking.check_in_date - booking.created_at.date_naive()).num_days().max(0);
+                let add_arrival = |t: &mut KpiTotals| {
+                    t.bookings += 1;
+                    if cancelled {
+                        t.cancelled += 1;
+                    } else if stayed {
+                        t.arrivals += 1;
+                        t.stay_nights += nights;
+                        t.lead_days += lead_days;
+                    }
+                };
+                let room_type = room_types.get(&first_room).copied();
+                overall.add(room_type, source, add_arrival);
+                periods
+                    .entry(period_start(booking.check_in_date, group_by))
+                    .or_default()
+                    .add(room_type, source, add_arrival);
+            }
+        }
+
+        let empty = KpiBuckets::default();
+        let period_reports = report_periods(start_date, end_date, group_by)
+            .into_iter()
+            .map(|(from, to)| {
+                let buckets = periods.get(&period_start(from, group_by)).unwrap_or(&empty);
+                KpiPeriodReport {
+                    period_start: from,
+                    period_end: to,
+                    breakdown: buckets.breakdown(&rooms_by_type, (to - from).num_days() + 1),
+                }
+            })
+            .collect();
+
+        Ok(KpiReport {
+            start_date,
+            end_date,
+            group_by,
+            currency: BASE_CURRENCY,
+            overall: overall.breakdown(&rooms_by_type, (end_date - start_date).num_days() + 1),
+            periods: period_reports,
+        })
+    }
+
+    /// Revenue and occupancy on the books for the `days` days from today
+    ///
+    /// On the books is what upcoming and in-house bookings have committed.
+    /// Each date is compared with the same weekday last year, and forecast
+    /// by adding last year's pickup: the nights of that date booked with no
+    /// more notice than is left now.
+    pub fn get_forecast(&self, days: i64) -> AppResult<ForecastReport> {
+        if !(1..=MAX_FORECAST_DAYS).contains(&days) {
+            return Err(AppError::ValidationError(format!(
+                "Days must be between 1 and {}",
+                MAX_FORECAST_DAYS
+            )));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let today = Utc::now().date_naive();
+        let end_date = today + Duration::days(days - 1);
+        let offset = Duration::days(LAST_YEAR_OFFSET_DAYS);
+
+        let room_count: i64 = rooms::table
+            .count()
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let booked = Self::stays_between_with_conn(
+            &mut conn,
+            &[BookingStatus::Upcoming, BookingStatus::CheckedIn],
+            today,
+            end_date,
+        )?;
+        let last_year = Self::stays_between_with_conn(
+            &mut conn,
+            &[BookingStatus::CheckedIn, BookingStatus::CheckedOut, BookingStatus::Overstay],
+            today - offset,
+            end_date - offset,
+        )?;
+
+        let rules = TaxService::active_rules_with_conn(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // (room nights, net revenue) per stay date
+        let mut on_the_books: BTreeMap<NaiveDate, (i64, BigDecimal)> = BTreeMap::new();
+        let mut last_year_stayed: BTreeMap<NaiveDate, (i64, BigDecimal)> = BTreeMap::new();
+        let mut last_year_pickup: BTreeMap<NaiveDate, (i64, BigDecimal)> = BTreeMap::new();
+        let add = |totals: &mut BTreeMap<NaiveDate, (i64, BigDecimal)>, date: NaiveDate, net: &BigDecimal| {
+            let entry = totals.entry(date).or_default();
+            entry.0 += 1;
+            entry.1 += net;
+        };
+
+        let (bookings, stays) = &booked;
+        for booking in bookings {
+            let booking_rules = applicable_rules(&rules, TaxTarget::Room, booking.check_in_date);
+            for (night, _, amount) in nightly_revenue(booking, stays) {
+                if night >= today && night <= end_date {
+                    add(&mut on_the_books, night, &apply_taxes(&amount, &booking_rules).net);
+                }
+            }
+        }
+
+        let (bookings, stays) = &last_year;
+        for booking in bookings {
+            let booking_rules = applicable_rules(&rules, TaxTarget::Room, booking.check_in_date);
+            let booked_on = booking.created_at.date_naive();
+            for (night, _, amount) in nightly_revenue(booking, stays) {
+                let date = night + offset;
+                if date < today || date > end_date {
+                    continue;
+                }
+                let net = apply_taxes(&amount, &booking_rules).net;
+                add(&mut last_year_stayed, date, &net);
+
+                // Booked with no more notice than is left before `date` now
+                if booked_on >= night - (date - today) {
+                    add(&mut last_year_pickup, date, &net);
+                }
+            }
+        }
+
+        let none = (0, BigDecimal::from(0));
+        let mut totals = [(0, BigDecimal::from(0)), (0, BigDecimal::from(0)), (0, BigDecimal::from(0))];
+        let daily: Vec<ForecastDay> = (0..days)
+            .map(|i| {
+                let date = today + Duration::days(i);
+                let booked = on_the_books.get(&date).unwrap_or(&none);
+                let stayed = last_year_stayed.get(&date).unwrap_or(&none);
+                let pickup = last_year_pickup.get(&date).unwrap_or(&none);
+                let forecast = pickup_forecast((booked.0, &booked.1), (pickup.0, &pickup.1), room_count);
+
+                for (total, (nights, revenue)) in totals.iter_mut().zip([booked, stayed, &forecast]) {
+                    total.0 += nights;
+                    total.1 += revenue;
+                }
+
+                ForecastDay {
+                    date,
+                    last_year_date: date - offset,
+                    on_the_books: forecast_figures(booked.0, booked.1.clone(), room_count, 1),
+                    last_year: forecast_figures(stayed.0, stayed.1.clone(), room_count, 1),
+                    last_year_pickup: forecast_figures(pickup.0, pickup.1.clone(), room_count, 1),
+                    forecast: forecast_figures(forecast.0, forecast.1, room_count, 1),
+                }
+            })
+            .collect();
+
+        let [booked, stayed, forecast] = totals;
+        Ok(ForecastReport {
+            start_date: today,
+            end_date,
+            room_count,
+            currency: BASE_CURRENCY,
+            totals: ForecastTotals {
+                on_the_books: forecast_figures(booked.0, booked.1, room_count, days),
+                last_year: forecast_figures(stayed.0, stayed.1, room_count, days),
+                forecast: forecast_figures(forecast.0, forecast.1, room_count, days),
+            },
+            daily,
+        })
+    }
+
+    /// Bookings in `statuses` staying any night in `[start, end]`, with
+    /// their room moves
+    fn stays_between_with_conn(
+        conn: &mut PgConnection,
+        statuses: &[BookingStatus],
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> AppResult<(Vec<Booking>, Vec<BookingRoomStay>)> {
+        let found: Vec<Booking> = bookings::table
+            .filter(bookings::status.eq_any(statuses))
+            .filter(bookings::check_in_date.le(end))
+            .filter(bookings::check_out_date.gt(start))
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let booking_ids: Vec<Uuid> = found.iter().map(|b| b.id).collect();
+        let stays: Vec<BookingRoomStay> = booking_room_stays::table
+            .filter(booking_room_stays::booking_id.eq_any(&booking_ids))
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok((found, stays))
+    }
+}
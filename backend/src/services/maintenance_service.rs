@@ -0,0 +1,459 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDate, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    MaintenanceOrder, MaintenanceOrderView, MaintenancePriority, MaintenanceStatus,
+    NewMaintenanceOrder, Room, RoomStatus, UpdateMaintenanceOrder, User, UserRole,
+};
+use crate::schema::{maintenance_orders, rooms, users};
+use crate::services::BookingService;
+
+/// Longest expected out-of-order period, to catch mistyped years
+pub const MAX_ORDER_DAYS: i64 = 365;
+
+/// Check an order's expected dates: in order, not entirely in the past and
+/// at most [`MAX_ORDER_DAYS`] long
+pub fn validate_order_dates(start_date: NaiveDate, end_date: NaiveDate, today: NaiveDate) -> AppResult<()> {
+    if end_date < start_date {
+        return Err(AppError::ValidationError(
+            "End date must be on or after start date".to_string(),
+        ));
+    }
+    if end_date < today {
+        return Err(AppError::ValidationError(
+            "End date cannot be in the past".to_string(),
+        ));
+    }
+    if (end_date - start_date).num_days() + 1 > MAX_ORDER_DAYS {
+        return Err(AppError::ValidationError(format!(
+            "A work order can cover at most {} days",
+            MAX_ORDER_DAYS
+        )));
+    }
+    Ok(())
+}
+
+/// Filters for the work order list
+#[derive(Debug, Default)]
+pub struct MaintenanceOrderFilter {
+    pub room_id: Option<Uuid>,
+    pub status: Option<MaintenanceStatus>,
+    pub assigned_to_user_id: Option<Uuid>,
+    /// Orders covering any date from `from` to `to` (inclusive)
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+/// A work order reported by staff
+#[derive(Debug)]
+pub struct CreateMaintenanceOrder {
+    pub room_id: Uuid,
+    pub description: String,
+    pub priority: Option<MaintenancePriority>,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub assigned_to_user_id: Option<Uuid>,
+}
+
+/// Maintenance service: work orders taking rooms out of order
+pub struct MaintenanceService {
+    pool: DbPool,
+}
+
+impl MaintenanceService {
+    /// Create a new MaintenanceService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List work orders, soonest first
+    pub fn list_orders(&self, filter: MaintenanceOrderFilter) -> AppResult<Vec<MaintenanceOrderView>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut query = maintenance_orders::table.into_boxed();
+        if let Some(room_id) = filter.room_id {
+            query = query.filter(maintenance_orders::room_id.eq(room_id));
+        }
+        if let Some(status) = filter.status {
+            query = query.filter(maintenance_orders::status.eq(status));
+        }
+        if let Some(user_id) = filter.assigned_to_user_id {
+            query = query.filter(maintenance_orders::assigned_to_user_id.eq(user_id));
+        }
+        if let Some(from) = filter.from {
+            query = query.filter(maintenance_orders::end_date.ge(from));
+        }
+        if let Some(to) = filter.to {
+            query = query.filter(maintenance_orders::start_date.le(to));
+        }
+
+        let orders: Vec<MaintenanceOrder> = query
+            .order((maintenance_orders::start_date.asc(), maintenance_orders::created_at.asc()))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Self::views_with_conn(&mut conn, orders)
+    }
+
+    /// Get a work order by ID
+    pub fn get_order(&self, order_id: Uuid) -> AppResult<MaintenanceOrderView> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let order = Self::find_order_with_conn(&mut conn, order_id, false)?;
+        Self::views_with_conn(&mut conn, vec![order])?
+            .pop()
+            .ok_or_else(|| AppError::NotFound(format!("Work order with ID '{}' not found", order_id)))
+    }
+
+    /// Report a work order; its dates must be free of bookings
+    pub fn create_order(
+        &self,
+        request: CreateMaintenanceOrder,
+        actor_id: Uuid,
+    ) -> AppResult<MaintenanceOrder> {
+        let description = request.description.trim().to_string();
+        if description.is_empty() {
+            return Err(AppError::ValidationError(
+                "Description is required".to_string(),
+            ));
+        }
+        validate_order_dates(request.start_date, request.end_date, Utc::now().date_naive())?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let room = Self::lock_room_with_conn(conn, request.room_id)?;
+            if let Some(user_id) = request.assigned_to_user_id {
+                Self::ensure_active_staff_with_conn(conn, user_id)?;
+            }
+            Self::ensure_dates_free_with_conn(conn, &room, request.start_date, request.end_date, None)?;
+
+            diesel::insert_into(maintenance_orders::table)
+                .values(&NewMaintenanceOrder {
+                    room_id: room.id,
+                    description,
+                    priority: request.priority.unwrap_or(MaintenancePriority::Normal),
+                    start_date: request.start_date,
+                    end_date: request.end_date,
+                    assigned_to_user_id: request.assigned_to_user_id,
+                    created_by_user_id: Some(actor_id),
+                })
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))
+        })
+    }
+
+    /// Change an open order's description, priority, dates or assignee
+    pub fn update_order(
+        &self,
+        order_id: Uuid,
+        update: UpdateMaintenanceOrder,
+    ) -> AppResult<MaintenanceOrder> {
+        let mut update = update;
+        if let Some(description) = update.description.as_mut() {
+            *description = description.trim().to_string();
+            if description.is_empty() {
+                return Err(AppError::ValidationError(
+                    "Description is required".to_string(),
+                ));
+            }
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let order = Self::find_order_with_conn(conn, order_id, true)?;
+            Self::ensure_open(&order)?;
+
+            let start_date = update.start_date.unwrap_or(order.start_date);
+            let end_date = update.end_date.unwrap_or(order.end_date);
+            if update.start_date.is_some() || update.end_date.is_some() {
+                validate_order_dates(start_date, end_date, Utc::now().date_naive())?;
+                let room = Self::lock_room_with_conn(conn, order.room_id)?;
+                Self::ensure_dates_free_with_conn(conn, &room, start_date, end_date, Some(order.id))?;
+            }
+            if let Some(Some(user_id)) = update.assigned_to_user_id {
+                Self::ensure_active_staff_with_conn(conn, user_id)?;
+            }
+
+            diesel::update(maintenance_orders::table.find(order_id))
+                .set(&update)
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))
+        })
+    }
+
+    /// Start work on an order, putting the room in maintenance
+    pub fn start_order(&self, order_id: Uuid) -> AppResult<MaintenanceOrder> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let order = Self::find_order_with_conn(conn, order_id, true)?;
+            if order.status != MaintenanceStatus::Open {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Cannot start a work order with status {:?}",
+                    order.status
+                )));
+            }
+
+            let room = Self::lock_room_with_conn(conn, order.room_id)?;
+            if !room.status.can_transition_to(RoomStatus::Maintenance) {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Room {} is {:?}; work can start once it is Available",
+                    room.number, room.status
+                )));
+            }
+            diesel::update(rooms::table.find(room.id))
+                .set((
+                    rooms::status.eq(RoomStatus::Maintenance),
+                    rooms::assigned_cleaner_id.eq(None::<Uuid>),
+                ))
+                .execute(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            diesel::update(maintenance_orders::table.find(order_id))
+                .set((
+                    maintenance_orders::status.eq(MaintenanceStatus::InProgress),
+                    maintenance_orders::started_at.eq(Some(Utc::now())),
+                ))
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))
+        })
+    }
+
+    /// Close a fixed order with its resolution; the room becomes Available
+    /// once no other work is in progress on it
+    pub fn close_order(&self, order_id: Uuid, resolution: String) -> AppResult<MaintenanceOrder> {
+        let resolution = resolution.trim().to_string();
+        if resolution.is_empty() {
+            return Err(AppError::ValidationError(
+                "Resolution is required to close a work order".to_string(),
+            ));
+        }
+
+        self.finish_order(order_id, MaintenanceStatus::Closed, Some(resolution))
+    }
+
+    /// Cancel an order that is no longer needed
+    pub fn cancel_order(&self, order_id: Uuid, reason: Option<String>) -> AppResult<MaintenanceOrder> {
+        let reason = reason.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+        self.finish_order(order_id, MaintenanceStatus::Cancelled, reason)
+    }
+
+    /// Open orders blocking any night from `check_in_date` to
+    /// `check_out_date` (check-out day excluded)
+    pub fn blocking_orders_with_conn(
+        conn: &mut PgConnection,
+        room_id: Uuid,
+        check_in_date: NaiveDate,
+        check_out_date: NaiveDate,
+    ) -> QueryResult<Vec<MaintenanceOrder>> {
+        maintenance_orders::table
+            .filter(maintenance_orders::room_id.eq(room_id))
+            .filter(maintenance_orders::status.eq_any(MaintenanceStatus::OPEN))
+            .filter(maintenance_orders::start_date.lt(check_out_date))
+            .filter(maintenance_orders::end_date.ge(check_in_date))
+            .order(maintenance_orders::start_date.asc())
+            .load(conn)
+    }
+
+    fn finish_order(
+        &self,
+        order_id: Uuid,
+        status: MaintenanceStatus,
+        resolution: Option<String>,
+    ) -> AppResult<MaintenanceOrder> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let order = Self::find_order_with_conn(conn, order_id, true)?;
+            Self::ensure_open(&order)?;
+
+            let finished: MaintenanceOrder = diesel::update(maintenance_orders::table.find(order_id))
+                .set((
+                    maintenance_orders::status.eq(status),
+                    maintenance_orders::resolution.eq(resolution),
+                    maintenance_orders::closed_at.eq(Some(Utc::now())),
+                ))
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            let still_in_progress: i64 = maintenance_orders::table
+                .filter(maintenance_orders::room_id.eq(order.room_id))
+                .filter(maintenance_orders::status.eq(MaintenanceStatus::InProgress))
+                .count()
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if still_in_progress == 0 {
+                diesel::update(
+                    rooms::table
+                        .find(order.room_id)
+                        .filter(rooms::status.eq(RoomStatus::Maintenance)),
+                )
+                .set(rooms::status.eq(RoomStatus::Available))
+                .execute(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+            }
+
+            Ok(finished)
+        })
+    }
+
+    fn lock_room_with_conn(conn: &mut PgConnection, room_id: Uuid) -> AppResult<Room> {
+        rooms::table
+            .find(room_id)
+            .for_update()
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))
+    }
+
+    /// The dates must be free of bookings (guests have to be moved first)
+    /// and of other open orders
+    fn ensure_dates_free_with_conn(
+        conn: &mut PgConnection,
+        room: &Room,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        exclude_order_id: Option<Uuid>,
+    ) -> AppResult<()> {
+        let bookings = BookingService::overlapping_bookings_with_conn(
+            conn,
+            room.id,
+            start_date,
+            end_date + Duration::days(1),
+            None,
+        )
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if !bookings.is_empty() {
+            let references: Vec<&str> = bookings.iter().map(|b| b.reference.as_str()).collect();
+            return Err(AppError::Conflict(format!(
+                "Room {} is booked on these dates ({}); move the bookings first",
+                room.number,
+                references.join(", ")
+            )));
+        }
+
+        let mut overlapping = maintenance_orders::table
+            .filter(maintenance_orders::room_id.eq(room.id))
+            .filter(maintenance_orders::status.eq_any(MaintenanceStatus::OPEN))
+            .filter(maintenance_orders::start_date.le(end_date))
+            .filter(maintenance_orders::end_date.ge(start_date))
+            .into_boxed();
+        if let Some(order_id) = exclude_order_id {
+            overlapping = overlapping.filter(maintenance_orders::id.ne(order_id));
+        }
+        let overlapping: Option<MaintenanceOrder> = overlapping
+            .first(conn)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match overlapping {
+            Some(other) => Err(AppError::Conflict(format!(
+                "Room {} already has an open work order from {} to {}",
+                room.number, other.start_date, other.end_date
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    fn find_order_with_conn(
+        conn: &mut PgConnection,
+        order_id: Uuid,
+        lock: bool,
+    ) -> AppResult<MaintenanceOrder> {
+        let query = maintenance_orders::table.find(order_id);
+        let order = if lock {
+            query.for_update().first(conn).optional()
+        } else {
+            query.first(conn).optional()
+        };
+
+        order
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .ok_or_else(|| AppError::NotFound(format!("Work order with ID '{}' not found", order_id)))
+    }
+
+    fn ensure_open(order: &MaintenanceOrder) -> AppResult<()> {
+        if !order.status.is_open() {
+            return Err(AppError::InvalidStatusTransition(format!(
+                "Work order is already {:?}",
+                order.status
+            )));
+        }
+        Ok(())
+    }
+
+    fn ensure_active_staff_with_conn(conn: &mut PgConnection, user_id: Uuid) -> AppResult<()> {
+        let user: Option<User> = users::table
+            .find(user_id)
+            .first(conn)
+            .optional()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match user {
+            Some(user)
+                if matches!(
+                    user.role,
+                    UserRole::Admin | UserRole::Receptionist | UserRole::Cleaner
+                ) && user.deactivated_at.is_none() =>
+            {
+                Ok(())
+            }
+            _ => Err(AppError::ValidationError(format!(
+                "User '{}' is not an active staff member",
+                user_id
+            ))),
+        }
+    }
+
+    fn views_with_conn(
+        conn: &mut PgConnection,
+        orders: Vec<MaintenanceOrder>,
+    ) -> AppResult<Vec<MaintenanceOrderView>> {
+        let room_ids: Vec<Uuid> = orders.iter().map(|o| o.room_id).collect();
+        let rooms_by_id: HashMap<Uuid, Room> = rooms::table
+            .filter(rooms::id.eq_any(&room_ids))
+            .load::<Room>(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .map(|r| (r.id, r))
+            .collect();
+
+        Ok(orders
+            .into_iter()
+            .filter_map(|order| {
+                let room = rooms_by_id.get(&order.room_id)?;
+                Some(MaintenanceOrderView {
+                    room_number: room.number.clone(),
+                    room_status: room.status,
+                    order,
+                })
+            })
+            .collect())
+    }
+}
@@ -1,8 +1,24 @@
 pub mod auth_service;
 pub mod booking_service;
+pub mod cancellation_service;
+pub mod cashier_shift_service;
+pub mod exchange_rate_service;
+pub mod export_service;
+pub mod folio_service;
+pub mod invoice_service;
+pub mod guest_credit_service;
 pub mod guest_service;
+pub mod housekeeping_service;
+pub mod job_service;
+pub mod kpi_service;
+pub mod maintenance_service;
+pub mod no_show_service;
+pub mod payment_gateway;
+pub mod payment_intent_service;
 pub mod payment_service;
+pub mod pricing_service;
 pub mod room_service;
+pub mod tax_service;
 pub mod inventory_service;
 pub mod storage_service;
 pub mod ai_service;
@@ -12,7 +28,21 @@ pub use auth_service::{
     GuestRegisterRequest, LoginRequest,
 };
 pub use booking_service::{BookingService, RoomFinancials};
+pub use cancellation_service::CancellationService;
+pub use cashier_shift_service::CashierShiftService;
+pub use exchange_rate_service::ExchangeRateService;
+pub use folio_service::FolioService;
+pub use invoice_service::InvoiceService;
+pub use guest_credit_service::GuestCreditService;
 pub use guest_service::GuestService;
+pub use housekeeping_service::HousekeepingService;
+pub use job_service::JobService;
+pub use kpi_service::KpiService;
+pub use maintenance_service::MaintenanceService;
+pub use no_show_service::NoShowService;
+pub use payment_intent_service::PaymentIntentService;
 pub use payment_service::PaymentService;
+pub use pricing_service::PricingService;
 pub use room_service::RoomService;
+pub use tax_service::TaxService;
 pub use inventory_service::InventoryService;
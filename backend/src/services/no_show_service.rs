@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{Booking, BookingStatus, NoShowPolicy, Room};
+use crate::schema::{bookings, rooms, system_settings};
+use crate::services::BookingService;
+
+/// Upper bound for the grace period, to catch typos like 100 instead of 1
+pub const MAX_NO_SHOW_GRACE_DAYS: i64 = 30;
+
+/// Service applying the hotel's no-show policy to bookings
+pub struct NoShowService {
+    pool: DbPool,
+}
+
+impl NoShowService {
+    /// Create a new NoShowService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Load the current no-show policy
+    pub fn get_policy(&self) -> AppResult<NoShowPolicy> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Self::load_policy(&mut conn).map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    fn load_policy(conn: &mut PgConnection) -> QueryResult<NoShowPolicy> {
+        let settings: HashMap<String, String> = system_settings::table
+            .filter(system_settings::key.like("no_show_%"))
+            .select((system_settings::key, system_settings::value))
+            .load::<(String, String)>(conn)?
+            .into_iter()
+            .collect();
+
+        Ok(NoShowPolicy::from_settings(&settings))
+    }
+
+    /// Validate and save the no-show policy
+    pub fn update_policy(&self, policy: NoShowPolicy) -> AppResult<NoShowPolicy> {
+        if !(0..=MAX_NO_SHOW_GRACE_DAYS).contains(&policy.grace_days) {
+            return Err(AppError::ValidationError(format!(
+                "Grace period must be between 0 and {} days",
+                MAX_NO_SHOW_GRACE_DAYS
+            )));
+        }
+
+        if policy.fee_amount < BigDecimal::zero() {
+            return Err(AppError::ValidationError(
+                "No-show fee amount cannot be negative".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            for (key, value) in policy.to_settings() {
+                diesel::insert_into(system_settings::table)
+                    .values((
+                        system_settings::key.eq(key),
+                        system_settings::value.eq(&value),
+                        system_settings::updated_at.eq(Utc::now()),
+                    ))
+                    .on_conflict(system_settings::key)
+                    .do_update()
+                    .set((
+                        system_settings::value.eq(&value),
+                        system_settings::updated_at.eq(Utc::now()),
+                    ))
+                    .execute(conn)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(policy)
+    }
+
+    /// Mark an Upcoming booking as a no-show, leaving the policy's fee owed
+    pub fn mark_no_show(&self, booking_id: Uuid) -> AppResult<Booking> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let policy = Self::load_policy(conn)?;
+            Self::mark_with_conn(conn, booking_id, &policy)
+        })
+    }
+
+    /// Mark every Upcoming booking that should have arrived before `cutoff`
+    ///
+    /// Each booking is handled in its own transaction so that one failure does
+    /// not hold back the rest. Returns the ids of the bookings marked.
+    pub fn mark_overdue(&self, cutoff: NaiveDate) -> AppResult<Vec<Uuid>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let policy = Self::load_policy(&mut conn).map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let overdue = Self::overdue_ids(&mut conn, cutoff)?;
+
+        let mut marked = Vec::with_capacity(overdue.len());
+        for booking_id in overdue {
+            match conn.transaction::<_, AppError, _>(|conn| {
+                Self::mark_with_conn(conn, booking_id, &policy)
+            }) {
+                Ok(_) => marked.push(booking_id),
+                Err(e) => tracing::warn!("Could not mark booking {} as no-show: {}", booking_id, e),
+            }
+        }
+
+        Ok(marked)
+    }
+
+    /// Ids of Upcoming bookings with a check-in date before `cutoff`
+    ///
+    /// Bookings whose no-show was undone are left out, as staff have already
+    /// decided the guest is still coming.
+    pub fn list_overdue(&self, cutoff: NaiveDate) -> AppResult<Vec<Uuid>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Self::overdue_ids(&mut conn, cutoff)
+    }
+
+    fn overdue_ids(conn: &mut PgConnection, cutoff: NaiveDate) -> AppResult<Vec<Uuid>> {
+        bookings::table
+            .filter(bookings::status.eq(BookingStatus::Upcoming))
+            .filter(bookings::check_in_date.lt(cutoff))
+            .filter(bookings::no_show_undone_at.is_null())
+            .order(bookings::check_in_date.asc())
+            .select(bookings::id)
+            .load(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    fn mark_with_conn(
+        conn: &mut PgConnection,
+        booking_id: Uuid,
+        policy: &NoShowPolicy,
+    ) -> AppResult<Booking> {
+        let booking: Booking = bookings::table
+            .find(booking_id)
+            .for_update()
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("Booking '{}' not found", booking_id)))?;
+
+        if booking.status != BookingStatus::Upcoming {
+            return Err(AppError::InvalidStatusTransition(format!(
+                "Only upcoming bookings can be marked as no-show (status is {:?})",
+                booking.status
+            )));
+        }
+
+        let today = Utc::now().date_naive();
+        if booking.check_in_date > today {
+            return Err(AppError::ValidationError(format!(
+                "Booking cannot be a no-show before its check-in date ({})",
+                booking.check_in_date
+            )));
+        }
+
+        let nights = (booking.check_out_date - booking.check_in_date).num_days();
+        let fee = policy.fee_for(&booking.price, nights);
+
+        // The fee replaces the stay price as the amount the guest owes; with
+        // deposits already taken counting towards it, the payment summary
+        // shows the rest as the remaining balance until staff collect it.
+        // The current price is saved so an undo can restore it; the first
+        // quote in `original_price` is left alone.
+        diesel::update(bookings::table.find(booking_id))
+            .set((
+                bookings::status.eq(BookingStatus::NoShow),
+                bookings::price.eq(fee),
+                bookings::price_before_no_show.eq(Some(booking.price.clone())),
+            ))
+            .get_result(conn)
+            .map_err(AppError::from)
+    }
+
+    /// Undo a no-show: restore the booking to Upcoming at the price it had
+    ///
+    /// The price saved in `price_before_no_show` when the no-show was marked
+    /// comes back, and the room must still be free for the booked dates. The
+    /// undo is recorded so the no-show job does not mark the booking again.
+    pub fn undo_no_show(&self, booking_id: Uuid) -> AppResult<Booking> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let booking: Booking = bookings::table
+                .find(booking_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Booking '{}' not found", booking_id)))?;
+
+            if booking.status != BookingStatus::NoShow {
+                return Err(AppError::InvalidStatusTransition(format!(
+                    "Booking is not marked as no-show (status is {:?})",
+                    booking.status
+                )));
+            }
+
+            let today = Utc::now().date_naive();
+            if booking.check_out_date <= today {
+                return Err(AppError::ValidationError(
+                    "The booked stay has already ended".to_string(),
+                ));
+            }
+
+            let room: Room = rooms::table
+                .find(booking.room_id)
+                .for_update()
+                .first(conn)
+                .map_err(|_| AppError::NotFound(format!("Room '{}' not found", booking.room_id)))?;
+
+            let available = BookingService::check_availability_with_conn(
+                conn,
+                room.id,
+                booking.check_in_date,
+                booking.check_out_date,
+                Some(booking_id),
+            )
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if !available {
+                return Err(AppError::RoomUnavailable(format!(
+                    "Room {} has been booked by someone else for these dates",
+                    room.number
+                )));
+            }
+
+            let price = booking.price_before_no_show.clone().ok_or_else(|| {
+                AppError::Conflict("The price before the no-show was not recorded".to_string())
+            })?;
+
+            diesel::update(bookings::table.find(booking_id))
+                .set((
+                    bookings::status.eq(BookingStatus::Upcoming),
+                    bookings::price.eq(price),
+                    bookings::price_before_no_show.eq(None::<BigDecimal>),
+                    bookings::no_show_undone_at.eq(Some(Utc::now())),
+                ))
+                .get_result(conn)
+                .map_err(AppError::from)
+        })
+    }
+}
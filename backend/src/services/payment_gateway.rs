@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use bigdecimal::BigDecimal;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::errors::{AppError, AppResult};
+
+/// What the hotel asks a gateway to collect
+#[derive(Debug, Clone)]
+pub struct GatewayIntentRequest {
+    /// Our payment intent ID, passed along so the gateway can echo it back
+    pub intent_id: Uuid,
+    pub booking_reference: String,
+    pub amount: BigDecimal,
+    pub currency: String,
+    pub description: String,
+}
+
+/// Intent as accepted by a gateway
+#[derive(Debug, Clone)]
+pub struct GatewayIntent {
+    /// Gateway's own ID for the intent, quoted in its webhooks
+    pub provider_reference: String,
+    /// Where the guest completes the payment
+    pub checkout_url: Option<String>,
+}
+
+/// Outcome reported by a gateway webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GatewayEventType {
+    #[serde(rename = "payment.succeeded")]
+    PaymentSucceeded,
+    #[serde(rename = "payment.failed")]
+    PaymentFailed,
+}
+
+impl GatewayEventType {
+    /// Event type as stored in `payment_webhook_events`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GatewayEventType::PaymentSucceeded => "payment.succeeded",
+            GatewayEventType::PaymentFailed => "payment.failed",
+        }
+    }
+}
+
+/// Verified webhook event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GatewayEvent {
+    /// Unique per event; redeliveries reuse it
+    #[serde(rename = "id")]
+    pub event_id: String,
+    #[serde(rename = "type")]
+    pub event_type: GatewayEventType,
+    #[serde(rename = "reference")]
+    pub provider_reference: String,
+    pub amount: BigDecimal,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_reason: Option<String>,
+}
+
+/// Online payment provider
+///
+/// Implementations create intents on the provider's side and turn its
+/// webhook callbacks into verified events. Everything else (recording
+/// payments, idempotency) is handled by `PaymentIntentService`.
+#[async_trait]
+pub trait PaymentGateway: Send + Sync {
+    /// Provider name, stored on intents and used in the webhook URL
+    fn name(&self) -> &'static str;
+
+    /// Register an intent with the provider
+    async fn create_intent(&self, request: &GatewayIntentRequest) -> AppResult<GatewayIntent>;
+
+    /// Check a webhook's signature and parse its event
+    fn parse_webhook(&self, headers: &HeaderMap, body: &[u8]) -> AppResult<GatewayEvent>;
+}
+
+/// Header carrying the mock gateway's webhook signature
+pub const MOCK_SIGNATURE_HEADER: &str = "x-mock-signature";
+
+/// Local stand-in for a payment provider, for development and tests
+///
+/// Intents are accepted immediately. Webhooks are JSON `GatewayEvent`s
+/// signed with a hex HMAC-SHA256 of the raw body in `X-Mock-Signature`.
+pub struct MockGateway {
+    webhook_secret: String,
+}
+
+impl MockGateway {
+    /// Create a mock gateway verifying webhooks with `webhook_secret`
+    pub fn new(webhook_secret: impl Into<String>) -> Self {
+        Self {
+            webhook_secret: webhook_secret.into(),
+        }
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        Hmac::<Sha256>::new_from_slice(self.webhook_secret.as_bytes())
+            .expect("HMAC accepts keys of any length")
+    }
+
+    /// Signature the mock gateway sends with a webhook body
+    #[allow(dead_code)]
+    pub fn sign(&self, body: &[u8]) -> String {
+        let mut mac = self.mac();
+        mac.update(body);
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for MockGateway {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn create_intent(&self, request: &GatewayIntentRequest) -> AppResult<GatewayIntent> {
+        let provider_reference = format!("mock_{}", request.intent_id.simple());
+        tracing::info!(
+            "Mock gateway accepted {} {} for booking {} ({}) as {}",
+            request.amount,
+            request.currency,
+            request.booking_reference,
+            request.description,
+            provider_reference
+        );
+        Ok(GatewayIntent {
+            checkout_url: Some(format!("mock://checkout/{}", provider_reference)),
+            provider_reference,
+        })
+    }
+
+    fn parse_webhook(&self, headers: &HeaderMap, body: &[u8]) -> AppResult<GatewayEvent> {
+        let signature = headers
+            .get(MOCK_SIGNATURE_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| hex::decode(value.trim()).ok())
+            .ok_or_else(|| AppError::Unauthorized("Missing webhook signature".to_string()))?;
+
+        let mut mac = self.mac();
+        mac.update(body);
+        mac.verify_slice(&signature)
+            .map_err(|_| AppError::Unauthorized("Invalid webhook signature".to_string()))?;
+
+        serde_json::from_slice(body)
+            .map_err(|e| AppError::BadRequest(format!("Invalid webhook payload: {}", e)))
+    }
+}
+
+/// Build the configured payment gateway
+///
+/// The mock gateway confirms payments nobody collected, so it is refused
+/// unless `allow_mock` is set.
+pub fn build_gateway(
+    provider: &str,
+    webhook_secret: &str,
+    allow_mock: bool,
+) -> Result<Arc<dyn PaymentGateway>, String> {
+    match provider.trim().to_ascii_lowercase().as_str() {
+        "mock" if allow_mock => Ok(Arc::new(MockGateway::new(webhook_secret))),
+        "mock" => Err(
+            "the mock payment provider is for development and tests only; set PAYMENT_ALLOW_MOCK=true to use it"
+                .to_string(),
+        ),
+        other => Err(format!("unknown payment provider '{}'", other)),
+    }
+}
@@ -0,0 +1,362 @@
+use std::sync::Arc;
+
+use axum::http::HeaderMap;
+use bigdecimal::{BigDecimal, One, Zero};
+use diesel::dsl::sum;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    Booking, BookingStatus, NewPayment, NewPaymentIntent, NewPaymentWebhookEvent, PaymentIntent,
+    PaymentIntentStatus, PaymentType,
+};
+use crate::schema::{bookings, payment_intents, payment_webhook_events};
+use crate::services::exchange_rate_service::BASE_CURRENCY;
+use crate::services::payment_gateway::{GatewayEventType, GatewayIntentRequest, PaymentGateway};
+use crate::services::PaymentService;
+
+/// Payment method recorded for payments confirmed by a gateway
+pub const ONLINE_PAYMENT_METHOD: &str = "online";
+
+/// Currency sent to payment gateways
+pub const GATEWAY_CURRENCY: &str = BASE_CURRENCY;
+
+/// Payment type for an online payment when the payer did not pick one
+///
+/// Settling the whole remaining balance is a full payment, anything less a
+/// partial one.
+pub fn default_payment_type(amount: &BigDecimal, remaining_balance: &BigDecimal) -> PaymentType {
+    if amount >= remaining_balance {
+        PaymentType::Full
+    } else {
+        PaymentType::Partial
+    }
+}
+
+/// Check an online payment amount against what is still owed
+pub fn validate_intent_amount(amount: &BigDecimal, remaining_balance: &BigDecimal) -> AppResult<()> {
+    if *amount <= BigDecimal::zero() {
+        return Err(AppError::ValidationError(
+            "Payment amount must be positive".to_string(),
+        ));
+    }
+
+    if amount > remaining_balance {
+        return Err(AppError::ValidationError(format!(
+            "Payment amount {} exceeds the remaining balance of {}",
+            amount, remaining_balance
+        )));
+    }
+
+    Ok(())
+}
+
+/// What a webhook delivery did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookOutcome {
+    /// The event changed the intent (and recorded a payment on success)
+    Processed,
+    /// The event ID was seen before; nothing was done
+    Duplicate,
+    /// The intent was already settled; the event was recorded but ignored
+    Ignored,
+}
+
+/// Payment intent service: online payments through the configured gateway
+pub struct PaymentIntentService {
+    pool: DbPool,
+    gateway: Arc<dyn PaymentGateway>,
+}
+
+impl PaymentIntentService {
+    /// Create a new PaymentIntentService instance
+    pub fn new(pool: DbPool, gateway: Arc<dyn PaymentGateway>) -> Self {
+        Self { pool, gateway }
+    }
+
+    /// Start an online payment for a booking
+    ///
+    /// `amount` defaults to the remaining balance less what pending intents
+    /// already ask for, and cannot exceed it. The intent is stored
+    /// before the gateway is called, so a gateway failure leaves a `failed`
+    /// intent with the reason rather than nothing.
+    pub async fn create_intent(
+        &self,
+        booking_id: Uuid,
+        amount: Option<BigDecimal>,
+        payment_type: Option<PaymentType>,
+        created_by_user_id: Uuid,
+    ) -> AppResult<PaymentIntent> {
+        let (booking, intent) = {
+            let mut conn = self
+                .pool
+                .get()
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            // The booking is locked so two intents started at once cannot
+            // both claim the same balance
+            conn.transaction::<_, AppError, _>(|conn| {
+                let booking: Booking = bookings::table
+                    .find(booking_id)
+                    .for_update()
+                    .first(conn)
+                    .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))?;
+
+                if matches!(booking.status, BookingStatus::Cancelled | BookingStatus::NoShow) {
+                    return Err(AppError::InvalidStatusTransition(format!(
+                        "Cannot take payments for a booking with status {:?}.",
+                        booking.status
+                    )));
+                }
+
+                if payment_type == Some(PaymentType::Refund) {
+                    return Err(AppError::ValidationError(
+                        "Refunds cannot be paid online".to_string(),
+                    ));
+                }
+
+                // Money already requested by pending intents is not owed twice
+                let summary = PaymentService::summary_with_conn(conn, &booking)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                let pending = Self::pending_total_with_conn(conn, booking_id)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                let unrequested = (&summary.remaining_balance - &pending).max(BigDecimal::zero());
+
+                let amount = amount.unwrap_or_else(|| unrequested.clone());
+                if pending > BigDecimal::zero() && amount > unrequested {
+                    return Err(AppError::ValidationError(format!(
+                        "Payment amount {} exceeds the {} not already requested by pending online payments",
+                        amount, unrequested
+                    )));
+                }
+                validate_intent_amount(&amount, &unrequested)?;
+                let payment_type =
+                    payment_type.unwrap_or_else(|| default_payment_type(&amount, &unrequested));
+
+                let intent: PaymentIntent = diesel::insert_into(payment_intents::table)
+                    .values(&NewPaymentIntent {
+                        booking_id,
+                        provider: self.gateway.name().to_string(),
+                        amount,
+                        payment_type,
+                        created_by_user_id,
+                    })
+                    .get_result(conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+                Ok((booking, intent))
+            })?
+        };
+
+        let request = GatewayIntentRequest {
+            intent_id: intent.id,
+            booking_reference: booking.reference.clone(),
+            amount: intent.amount.clone(),
+            currency: GATEWAY_CURRENCY.to_string(),
+            description: format!("Booking {}", booking.reference),
+        };
+        let result = self.gateway.create_intent(&request).await;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match result {
+            Ok(accepted) => diesel::update(payment_intents::table.find(intent.id))
+                .set((
+                    payment_intents::provider_reference.eq(accepted.provider_reference),
+                    payment_intents::checkout_url.eq(accepted.checkout_url),
+                ))
+                .get_result(&mut conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string())),
+            Err(e) => {
+                tracing::warn!(
+                    "Payment gateway '{}' rejected intent {}: {}",
+                    self.gateway.name(),
+                    intent.id,
+                    e
+                );
+                diesel::update(payment_intents::table.find(intent.id))
+                    .set((
+                        payment_intents::status.eq(PaymentIntentStatus::Failed),
+                        payment_intents::failure_reason.eq(e.to_string()),
+                    ))
+                    .execute(&mut conn)
+                    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+                Err(e)
+            }
+        }
+    }
+
+    /// List the payment intents of a booking, newest first
+    pub fn list_intents(&self, booking_id: Uuid) -> AppResult<Vec<PaymentIntent>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        payment_intents::table
+            .filter(payment_intents::booking_id.eq(booking_id))
+            .order(payment_intents::created_at.desc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Total amount of the booking's intents still waiting for the gateway
+    fn pending_total_with_conn(conn: &mut PgConnection, booking_id: Uuid) -> QueryResult<BigDecimal> {
+        let total: Option<BigDecimal> = payment_intents::table
+            .filter(payment_intents::booking_id.eq(booking_id))
+            .filter(payment_intents::status.eq(PaymentIntentStatus::Pending))
+            .select(sum(payment_intents::amount))
+            .first(conn)?;
+
+        Ok(total.unwrap_or_else(BigDecimal::zero))
+    }
+
+    /// Cancel the still-pending intents of a booking (run inside a transaction)
+    pub fn cancel_pending_with_conn(conn: &mut PgConnection, booking_id: Uuid) -> QueryResult<usize> {
+        diesel::update(
+            payment_intents::table
+                .filter(payment_intents::booking_id.eq(booking_id))
+                .filter(payment_intents::status.eq(PaymentIntentStatus::Pending)),
+        )
+        .set(payment_intents::status.eq(PaymentIntentStatus::Cancelled))
+        .execute(conn)
+    }
+
+    /// Apply a webhook delivery from a gateway
+    ///
+    /// Each event ID is applied at most once, and an intent records at most
+    /// one payment however many success events arrive for it.
+    pub fn handle_webhook(
+        &self,
+        provider: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> AppResult<WebhookOutcome> {
+        if provider != self.gateway.name() {
+            return Err(AppError::NotFound(format!(
+                "Payment provider '{}' is not configured",
+                provider
+            )));
+        }
+
+        let event = self.gateway.parse_webhook(headers, body)?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            let inserted = diesel::insert_into(payment_webhook_events::table)
+                .values(&NewPaymentWebhookEvent {
+                    provider: provider.to_string(),
+                    event_id: event.event_id.clone(),
+                    event_type: event.event_type.as_str().to_string(),
+                    payload: String::from_utf8_lossy(body).into_owned(),
+                })
+                .on_conflict((payment_webhook_events::provider, payment_webhook_events::event_id))
+                .do_nothing()
+                .execute(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if inserted == 0 {
+                return Ok(WebhookOutcome::Duplicate);
+            }
+
+            let intent: PaymentIntent = payment_intents::table
+                .filter(payment_intents::provider.eq(provider))
+                .filter(payment_intents::provider_reference.eq(&event.provider_reference))
+                .for_update()
+                .first(conn)
+                .map_err(|_| {
+                    AppError::NotFound(format!(
+                        "Payment intent '{}' not found",
+                        event.provider_reference
+                    ))
+                })?;
+
+            diesel::update(
+                payment_webhook_events::table
+                    .filter(payment_webhook_events::provider.eq(provider))
+                    .filter(payment_webhook_events::event_id.eq(&event.event_id)),
+            )
+            .set(payment_webhook_events::payment_intent_id.eq(intent.id))
+            .execute(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            match event.event_type {
+                GatewayEventType::PaymentSucceeded => {
+                    if intent.status == PaymentIntentStatus::Succeeded {
+                        return Ok(WebhookOutcome::Ignored);
+                    }
+
+                    if event.amount != intent.amount {
+                        return Err(AppError::ValidationError(format!(
+                            "Webhook amount {} does not match intent amount {}",
+                            event.amount, intent.amount
+                        )));
+                    }
+
+                    if intent.status == PaymentIntentStatus::Cancelled {
+                        tracing::warn!(
+                            "Payment received for cancelled intent {} (booking {}); a refund may be due",
+                            intent.id,
+                            intent.booking_id
+                        );
+                    }
+
+                    let payment = PaymentService::create_payment_with_conn(
+                        conn,
+                        NewPayment {
+                            booking_id: intent.booking_id,
+                            amount: intent.amount.clone(),
+                            payment_type: intent.payment_type,
+                            payment_method: ONLINE_PAYMENT_METHOD.to_string(),
+                            notes: Some(format!("{} {}", provider, event.provider_reference)),
+                            created_by_user_id: intent.created_by_user_id,
+                            currency: BASE_CURRENCY.to_string(),
+                            exchange_rate: BigDecimal::one(),
+                            original_amount: None,
+                        },
+                    )?;
+
+                    diesel::update(payment_intents::table.find(intent.id))
+                        .set((
+                            payment_intents::status.eq(PaymentIntentStatus::Succeeded),
+                            payment_intents::payment_id.eq(payment.id),
+                            payment_intents::failure_reason.eq(None::<String>),
+                        ))
+                        .execute(conn)
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+                    Ok(WebhookOutcome::Processed)
+                }
+                GatewayEventType::PaymentFailed => {
+                    if intent.status != PaymentIntentStatus::Pending {
+                        return Ok(WebhookOutcome::Ignored);
+                    }
+
+                    diesel::update(payment_intents::table.find(intent.id))
+                        .set((
+                            payment_intents::status.eq(PaymentIntentStatus::Failed),
+                            payment_intents::failure_reason.eq(event
+                                .failure_reason
+                                .clone()
+                                .unwrap_or_else(|| "Declined by the payment provider".to_string())),
+                        ))
+                        .execute(conn)
+                        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+                    Ok(WebhookOutcome::Processed)
+                }
+            }
+        })
+    }
+}
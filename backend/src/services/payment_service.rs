@@ -1,14 +1,84 @@
 use diesel::prelude::*;
 use diesel::dsl::{count, sum};
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, Zero};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::Deserialize;
 use uuid::Uuid;
 
 use crate::db::DbPool;
 use crate::errors::{AppError, AppResult};
 use crate::models::{
-    Booking, Payment, PaymentSummary, PaymentType, NewPayment, UpdatePayment,
+    Booking, CurrencyTotal, GroupPaymentSummary, Payment, PaymentLedgerAction, PaymentLedgerEntry,
+    PaymentSummary, PaymentType, NewPayment, NewPaymentLedgerEntry, UpdatePayment,
 };
-use crate::schema::{bookings, payments};
+use crate::schema::{booking_groups, bookings, payment_ledger_entries, payments};
+use crate::services::tax_service::merge_tax_lines;
+use crate::services::exchange_rate_service::{convert_to_base, normalize_currency, BASE_CURRENCY};
+use crate::services::guest_credit_service::GUEST_CREDIT_PAYMENT_METHOD;
+use crate::services::{CashierShiftService, ExchangeRateService, FolioService, TaxService};
+
+/// Accepted payment methods (`online` is used for payments confirmed by a
+/// gateway, `guest_credit` for money moved to or from a guest's credit)
+pub const PAYMENT_METHODS: [&str; 6] = ["cash", "card", "bank_transfer", "online", "guest_credit", "other"];
+
+/// Check a payment's amount, sign and method
+///
+/// Refunds are negative, everything else positive, and nothing is zero.
+pub fn validate_payment(amount: &BigDecimal, payment_type: PaymentType, payment_method: &str) -> AppResult<()> {
+    if amount.is_zero() {
+        return Err(AppError::ValidationError(
+            "Payment amount cannot be zero".to_string(),
+        ));
+    }
+
+    if payment_type == PaymentType::Refund && *amount > BigDecimal::zero() {
+        return Err(AppError::ValidationError(
+            "Refund amount must be negative".to_string(),
+        ));
+    }
+
+    if payment_type != PaymentType::Refund && *amount < BigDecimal::zero() {
+        return Err(AppError::ValidationError(
+            "Payment amount must be positive (use refund type for negative amounts)".to_string(),
+        ));
+    }
+
+    if !PAYMENT_METHODS.contains(&payment_method) {
+        return Err(AppError::ValidationError(
+            format!("Invalid payment method. Must be one of: {}", PAYMENT_METHODS.join(", "))
+        ));
+    }
+
+    Ok(())
+}
+
+/// Amount paid beyond what is due (zero unless the booking is overpaid)
+pub fn overpaid_amount(total_due: &BigDecimal, total_paid: &BigDecimal) -> BigDecimal {
+    (total_paid - total_due).max(BigDecimal::zero())
+}
+
+/// Trimmed reason for voiding or adjusting a payment; one is required
+pub fn require_reason(reason: &str) -> AppResult<String> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return Err(AppError::ValidationError(
+            "A reason is required to void or adjust a payment".to_string(),
+        ));
+    }
+    Ok(reason.to_string())
+}
+
+/// Payment request payload
+#[derive(Debug, Deserialize)]
+pub struct CreatePaymentRequest {
+    /// In `currency`
+    pub amount: BigDecimal,
+    /// Defaults to the base currency
+    pub currency: Option<String>,
+    pub payment_type: PaymentType,
+    pub payment_method: String,
+    pub notes: Option<String>,
+}
 
 /// Payment service for managing payment transactions
 pub struct PaymentService {
@@ -22,15 +92,26 @@ impl PaymentService {
     }
 
     /// Create a new payment
+    ///
+    /// A payment in a foreign currency is converted to the base currency at
+    /// today's rate; the currency, rate and original amount are kept with it.
     pub fn create_payment(
         &self,
         booking_id: Uuid,
-        amount: BigDecimal,
-        payment_type: PaymentType,
-        payment_method: String,
-        notes: Option<String>,
+        request: CreatePaymentRequest,
         created_by_user_id: Uuid,
     ) -> AppResult<Payment> {
+        if request.payment_method == GUEST_CREDIT_PAYMENT_METHOD {
+            return Err(AppError::ValidationError(
+                "Guest credit is moved with the credit-overpayment and apply-credit endpoints".to_string(),
+            ));
+        }
+
+        let currency = match request.currency.as_deref() {
+            Some(code) => normalize_currency(code)?,
+            None => BASE_CURRENCY.to_string(),
+        };
+
         let mut conn = self
             .pool
             .get()
@@ -42,51 +123,120 @@ impl PaymentService {
             .first(&mut conn)
             .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))?;
 
-        // Validate amount
-        if amount == BigDecimal::from(0) {
-            return Err(AppError::ValidationError(
-                "Payment amount cannot be zero".to_string(),
-            ));
-        }
-
-        // Validate refund amount (must be negative)
-        if payment_type == PaymentType::Refund && amount > BigDecimal::from(0) {
-            return Err(AppError::ValidationError(
-                "Refund amount must be negative".to_string(),
-            ));
-        }
-
-        // Validate non-refund amount (must be positive)
-        if payment_type != PaymentType::Refund && amount < BigDecimal::from(0) {
-            return Err(AppError::ValidationError(
-                "Payment amount must be positive (use refund type for negative amounts)".to_string(),
-            ));
-        }
+        conn.transaction::<_, AppError, _>(|conn| {
+            let exchange_rate =
+                ExchangeRateService::rate_on_with_conn(conn, &currency, Utc::now().date_naive())?;
+            let (amount, original_amount) = if currency == BASE_CURRENCY {
+                (request.amount, None)
+            } else {
+                (convert_to_base(&request.amount, &exchange_rate), Some(request.amount))
+            };
+
+            Self::create_payment_with_conn(
+                conn,
+                NewPayment {
+                    booking_id,
+                    amount,
+                    payment_type: request.payment_type,
+                    payment_method: request.payment_method,
+                    notes: request.notes,
+                    created_by_user_id,
+                    currency,
+                    exchange_rate,
+                    original_amount,
+                },
+            )
+        })
+    }
 
-        // Validate payment method
-        let valid_methods = vec!["cash", "card", "bank_transfer", "other"];
-        if !valid_methods.contains(&payment_method.as_str()) {
-            return Err(AppError::ValidationError(
-                format!("Invalid payment method. Must be one of: {}", valid_methods.join(", "))
-            ));
-        }
+    /// Validate and insert a payment using an existing connection
+    /// (usable inside another service's transaction)
+    pub fn create_payment_with_conn(
+        conn: &mut PgConnection,
+        new_payment: NewPayment,
+    ) -> AppResult<Payment> {
+        validate_payment(
+            &new_payment.amount,
+            new_payment.payment_type,
+            &new_payment.payment_method,
+        )?;
 
-        let new_payment = NewPayment {
-            booking_id,
-            amount,
-            payment_type,
-            payment_method,
-            notes,
-            created_by_user_id,
-        };
+        let shift_id = CashierShiftService::open_shift_id_with_conn(conn, new_payment.created_by_user_id)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        let payment = Self::insert_payment_with_conn(conn, &new_payment, None, shift_id)?;
+
+        Self::record_ledger_with_conn(
+            conn,
+            &payment,
+            PaymentLedgerAction::Created,
+            payment.amount.clone(),
+            None,
+            None,
+            payment.created_by_user_id,
+        )?;
+
+        Ok(payment)
+    }
 
+    /// Insert a payment attached to a cashier shift (or none)
+    fn insert_payment_with_conn(
+        conn: &mut PgConnection,
+        new_payment: &NewPayment,
+        replaces_payment_id: Option<Uuid>,
+        shift_id: Option<Uuid>,
+    ) -> AppResult<Payment> {
         diesel::insert_into(payments::table)
-            .values(&new_payment)
-            .get_result(&mut conn)
+            .values((
+                new_payment,
+                payments::replaces_payment_id.eq(replaces_payment_id),
+                payments::shift_id.eq(shift_id),
+            ))
+            .get_result(conn)
             .map_err(|e| AppError::DatabaseError(e.to_string()))
     }
 
-    /// Get all payments for a booking
+    /// Append a ledger entry describing `payment` after a change
+    fn record_ledger_with_conn(
+        conn: &mut PgConnection,
+        payment: &Payment,
+        action: PaymentLedgerAction,
+        amount_delta: BigDecimal,
+        previous_payment_id: Option<Uuid>,
+        reason: Option<String>,
+        actor_user_id: Uuid,
+    ) -> AppResult<()> {
+        diesel::insert_into(payment_ledger_entries::table)
+            .values(&NewPaymentLedgerEntry {
+                payment_id: payment.id,
+                booking_id: payment.booking_id,
+                action,
+                amount_delta,
+                amount: payment.amount.clone(),
+                payment_type: payment.payment_type,
+                payment_method: payment.payment_method.clone(),
+                previous_payment_id,
+                reason,
+                actor_user_id,
+            })
+            .execute(conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Total of all payments (refunds are negative) recorded for a booking,
+    /// leaving out voided ones
+    pub fn total_paid_with_conn(conn: &mut PgConnection, booking_id: Uuid) -> QueryResult<BigDecimal> {
+        let total: Option<BigDecimal> = payments::table
+            .filter(payments::booking_id.eq(booking_id))
+            .filter(payments::voided_at.is_null())
+            .select(sum(payments::amount))
+            .first(conn)?;
+
+        Ok(total.unwrap_or_else(BigDecimal::zero))
+    }
+
+    /// Get all payments for a booking (voided ones included)
     pub fn get_payments_by_booking(&self, booking_id: Uuid) -> AppResult<Vec<Payment>> {
         let mut conn = self
             .pool
@@ -121,80 +271,208 @@ impl PaymentService {
             .map_err(|_| AppError::NotFound(format!("Payment with ID '{}' not found", payment_id)))
     }
 
-    /// Update a payment
-    pub fn update_payment(
+    /// Correct a payment: the original is voided and a replacement with
+    /// the corrected values is recorded in its place
+    ///
+    /// The replacement keeps the original's recorder and cashier shift, so
+    /// the drawer that took the money still reports it; the adjuster is
+    /// recorded in the ledger.
+    pub fn adjust_payment(
         &self,
         payment_id: Uuid,
         update: UpdatePayment,
+        reason: &str,
+        actor_id: Uuid,
     ) -> AppResult<Payment> {
+        let reason = require_reason(reason)?;
+
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        // Get existing payment
-        let existing: Payment = payments::table
-            .find(payment_id)
-            .first(&mut conn)
-            .map_err(|_| AppError::NotFound(format!("Payment with ID '{}' not found", payment_id)))?;
-
-        // Validate amount if provided
-        if let Some(ref amount) = update.amount {
-            if *amount == BigDecimal::from(0) {
+        conn.transaction::<_, AppError, _>(|conn| {
+            let existing = Self::lock_active_payment(conn, payment_id)?;
+
+            // A corrected amount is in the payment's own currency and is
+            // converted at the rate the original was taken at
+            let (amount, original_amount) = match (update.amount, &existing.original_amount) {
+                (Some(amount), Some(_)) => {
+                    (convert_to_base(&amount, &existing.exchange_rate), Some(amount))
+                }
+                (Some(amount), None) => (amount, None),
+                (None, _) => (existing.amount.clone(), existing.original_amount.clone()),
+            };
+
+            let replacement = NewPayment {
+                booking_id: existing.booking_id,
+                amount,
+                payment_type: update.payment_type.unwrap_or(existing.payment_type),
+                payment_method: update
+                    .payment_method
+                    .unwrap_or_else(|| existing.payment_method.clone()),
+                notes: update.notes.unwrap_or_else(|| existing.notes.clone()),
+                created_by_user_id: existing.created_by_user_id,
+                currency: existing.currency.clone(),
+                exchange_rate: existing.exchange_rate.clone(),
+                original_amount,
+            };
+
+            if replacement.payment_method == GUEST_CREDIT_PAYMENT_METHOD {
                 return Err(AppError::ValidationError(
-                    "Payment amount cannot be zero".to_string(),
+                    "A payment cannot be adjusted into guest credit".to_string(),
                 ));
             }
 
-            // Validate refund amount
-            let payment_type = update.payment_type.unwrap_or(existing.payment_type);
-            if payment_type == PaymentType::Refund && *amount > BigDecimal::from(0) {
+            if replacement.amount == existing.amount
+                && replacement.original_amount == existing.original_amount
+                && replacement.payment_type == existing.payment_type
+                && replacement.payment_method == existing.payment_method
+                && replacement.notes == existing.notes
+            {
                 return Err(AppError::ValidationError(
-                    "Refund amount must be negative".to_string(),
+                    "The adjustment does not change the payment".to_string(),
                 ));
             }
 
-            if payment_type != PaymentType::Refund && *amount < BigDecimal::from(0) {
-                return Err(AppError::ValidationError(
-                    "Payment amount must be positive".to_string(),
-                ));
-            }
+            validate_payment(
+                &replacement.amount,
+                replacement.payment_type,
+                &replacement.payment_method,
+            )?;
+
+            Self::mark_voided_with_conn(conn, payment_id, &format!("Adjusted: {}", reason), actor_id)?;
+
+            let payment = Self::insert_payment_with_conn(
+                conn,
+                &replacement,
+                Some(existing.id),
+                existing.shift_id,
+            )?;
+
+            Self::record_ledger_with_conn(
+                conn,
+                &payment,
+                PaymentLedgerAction::Adjusted,
+                &payment.amount - &existing.amount,
+                Some(existing.id),
+                Some(reason),
+                actor_id,
+            )?;
+
+            Ok(payment)
+        })
+    }
+
+    /// Void a payment; the row is kept but no longer counts as paid
+    pub fn void_payment(&self, payment_id: Uuid, reason: &str, actor_id: Uuid) -> AppResult<Payment> {
+        let reason = require_reason(reason)?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, AppError, _>(|conn| {
+            Self::lock_active_payment(conn, payment_id)?;
+            let payment = Self::mark_voided_with_conn(conn, payment_id, &reason, actor_id)?;
+
+            Self::record_ledger_with_conn(
+                conn,
+                &payment,
+                PaymentLedgerAction::Voided,
+                -payment.amount.clone(),
+                None,
+                Some(reason),
+                actor_id,
+            )?;
+
+            Ok(payment)
+        })
+    }
+
+    /// Lock a payment for a change, refusing voided ones
+    fn lock_active_payment(conn: &mut PgConnection, payment_id: Uuid) -> AppResult<Payment> {
+        let payment: Payment = payments::table
+            .find(payment_id)
+            .for_update()
+            .first(conn)
+            .map_err(|_| AppError::NotFound(format!("Payment with ID '{}' not found", payment_id)))?;
+
+        if payment.voided_at.is_some() {
+            return Err(AppError::Conflict(format!(
+                "Payment '{}' has already been voided",
+                payment_id
+            )));
         }
 
-        // Validate payment method if provided
-        if let Some(ref method) = update.payment_method {
-            let valid_methods = vec!["cash", "card", "bank_transfer", "other"];
-            if !valid_methods.contains(&method.as_str()) {
-                return Err(AppError::ValidationError(
-                    format!("Invalid payment method. Must be one of: {}", valid_methods.join(", "))
-                ));
-            }
+        // The guest's credit balance moved with it and would no longer match
+        if payment.payment_method == GUEST_CREDIT_PAYMENT_METHOD {
+            return Err(AppError::Conflict(
+                "Guest credit transfers cannot be voided or adjusted".to_string(),
+            ));
         }
 
+        Ok(payment)
+    }
+
+    fn mark_voided_with_conn(
+        conn: &mut PgConnection,
+        payment_id: Uuid,
+        reason: &str,
+        actor_id: Uuid,
+    ) -> AppResult<Payment> {
         diesel::update(payments::table.find(payment_id))
-            .set(&update)
-            .get_result(&mut conn)
+            .set((
+                payments::voided_at.eq(Utc::now()),
+                payments::voided_by_user_id.eq(actor_id),
+                payments::void_reason.eq(reason),
+            ))
+            .get_result(conn)
             .map_err(|e| AppError::DatabaseError(e.to_string()))
     }
 
-    /// Delete a payment
-    pub fn delete_payment(&self, payment_id: Uuid) -> AppResult<()> {
+    /// Ledger of a booking's payments, oldest first
+    pub fn get_ledger_by_booking(&self, booking_id: Uuid) -> AppResult<Vec<PaymentLedgerEntry>> {
         let mut conn = self
             .pool
             .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        // Verify payment exists
-        payments::table
-            .find(payment_id)
-            .first::<Payment>(&mut conn)
-            .map_err(|_| AppError::NotFound(format!("Payment with ID '{}' not found", payment_id)))?;
+        bookings::table
+            .find(booking_id)
+            .first::<Booking>(&mut conn)
+            .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))?;
 
-        diesel::delete(payments::table.find(payment_id))
-            .execute(&mut conn)
+        payment_ledger_entries::table
+            .filter(payment_ledger_entries::booking_id.eq(booking_id))
+            .order(payment_ledger_entries::created_at.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Ledger entries recorded between two dates (inclusive), oldest first
+    pub fn list_ledger(&self, start_date: NaiveDate, end_date: NaiveDate) -> AppResult<Vec<PaymentLedgerEntry>> {
+        if end_date < start_date {
+            return Err(AppError::ValidationError(
+                "End date must not be before start date".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        Ok(())
+        let from = start_date.and_hms_opt(0, 0, 0).unwrap().and_utc();
+        let to = (end_date + Duration::days(1)).and_hms_opt(0, 0, 0).unwrap().and_utc();
+
+        payment_ledger_entries::table
+            .filter(payment_ledger_entries::created_at.ge(from))
+            .filter(payment_ledger_entries::created_at.lt(to))
+            .order(payment_ledger_entries::created_at.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
     }
 
     /// Calculate payment summary for a booking
@@ -210,29 +488,131 @@ impl PaymentService {
             .first(&mut conn)
             .map_err(|_| AppError::NotFound(format!("Booking with ID '{}' not found", booking_id)))?;
 
-        // Calculate total paid
-        let total_paid: Option<BigDecimal> = payments::table
-            .filter(payments::booking_id.eq(booking_id))
-            .select(sum(payments::amount))
-            .first(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Self::summary_with_conn(&mut conn, &booking)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Payment summary of a booking using an existing connection
+    ///
+    /// The amount due is the room price with its exclusive taxes plus all
+    /// folio charges.
+    pub fn summary_with_conn(conn: &mut PgConnection, booking: &Booking) -> QueryResult<PaymentSummary> {
+        let total_paid = Self::total_paid_with_conn(conn, booking.id)?;
 
-        // Get payment count
         let payment_count: i64 = payments::table
-            .filter(payments::booking_id.eq(booking_id))
+            .filter(payments::booking_id.eq(booking.id))
+            .filter(payments::voided_at.is_null())
             .select(count(payments::id))
-            .first(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
-
-        let total_paid = total_paid.unwrap_or_else(|| BigDecimal::from(0));
-        let remaining_balance = &booking.price - &total_paid;
+            .first(conn)?;
+
+        let by_currency: Vec<(String, i64, Option<BigDecimal>, Option<BigDecimal>)> = payments::table
+            .filter(payments::booking_id.eq(booking.id))
+            .filter(payments::voided_at.is_null())
+            .group_by(payments::currency)
+            .select((
+                payments::currency,
+                count(payments::id),
+                sum(payments::original_amount),
+                sum(payments::amount),
+            ))
+            .order(payments::currency.asc())
+            .load(conn)?;
+
+        let paid_by_currency = by_currency
+            .into_iter()
+            .map(|(currency, payment_count, original, base)| {
+                let base_amount = base.unwrap_or_else(BigDecimal::zero);
+                CurrencyTotal {
+                    // Base currency payments have no separate original amount
+                    amount: original.unwrap_or_else(|| base_amount.clone()),
+                    currency,
+                    payment_count,
+                    base_amount,
+                }
+            })
+            .collect();
+
+        let room = TaxService::room_breakdown_with_conn(conn, &booking.price, booking.check_in_date)?;
+        let charges = FolioService::charges_totals_with_conn(conn, booking.id)?;
+
+        let total_price = &room.gross + &charges.gross;
+        let remaining_balance = &total_price - &total_paid;
+        let overpaid_amount = overpaid_amount(&total_price, &total_paid);
 
         Ok(PaymentSummary {
-            booking_id,
-            total_price: booking.price,
+            booking_id: booking.id,
+            room_price: booking.price.clone(),
+            room_tax: room.tax.clone(),
+            charges_total: charges.gross,
+            net_total: room.net + charges.net,
+            tax_total: room.tax + charges.tax,
+            taxes: merge_tax_lines(room.lines.into_iter().chain(charges.lines)),
+            total_price,
             total_paid,
             remaining_balance,
+            overpaid_amount,
+            payment_count,
+            currency: BASE_CURRENCY.to_string(),
+            paid_by_currency,
+        })
+    }
+
+    /// Calculate one combined payment summary for all bookings of a group
+    ///
+    /// Cancelled and no-show bookings count at their current price, which is
+    /// what the hotel kept (zero for a free cancellation), so the penalty is
+    /// owed rather than shown as an overpayment.
+    pub fn get_group_payment_summary(&self, group_id: Uuid) -> AppResult<GroupPaymentSummary> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let exists: bool = diesel::select(diesel::dsl::exists(booking_groups::table.find(group_id)))
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "Booking group with ID '{}' not found",
+                group_id
+            )));
+        }
+
+        let members: Vec<Uuid> = bookings::table
+            .filter(bookings::group_id.eq(group_id))
+            .order(bookings::check_in_date.asc())
+            .select(bookings::id)
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut total_price = BigDecimal::zero();
+        let mut net_total = BigDecimal::zero();
+        let mut tax_total = BigDecimal::zero();
+        let mut total_paid = BigDecimal::zero();
+        let mut payment_count = 0;
+        let mut summaries = Vec::with_capacity(members.len());
+
+        for booking_id in members {
+            let summary = self.get_payment_summary(booking_id)?;
+            total_price += &summary.total_price;
+            net_total += &summary.net_total;
+            tax_total += &summary.tax_total;
+            total_paid += &summary.total_paid;
+            payment_count += summary.payment_count;
+            summaries.push(summary);
+        }
+
+        Ok(GroupPaymentSummary {
+            group_id,
+            remaining_balance: &total_price - &total_paid,
+            net_total,
+            tax_total,
+            total_price,
+            total_paid,
             payment_count,
+            currency: BASE_CURRENCY.to_string(),
+            bookings: summaries,
         })
     }
 
@@ -253,6 +633,7 @@ impl PaymentService {
 
         let total: Option<BigDecimal> = payments::table
             .filter(payments::booking_id.eq_any(booking_ids))
+            .filter(payments::voided_at.is_null())
             .select(sum(payments::amount))
             .first(&mut conn)
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -0,0 +1,541 @@
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use diesel::pg::upsert::excluded;
+use diesel::prelude::*;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{
+    NewRateOverride, NewRatePlan, RateOverride, RatePlan, RatePlanWithOverrides, Room, RoomType,
+    UpdateRatePlan,
+};
+use crate::schema::{cancellation_policies, rate_overrides, rate_plans, rooms};
+
+/// Pricing service resolving nightly rates from rate plans and date overrides
+pub struct PricingService {
+    pool: DbPool,
+}
+
+/// Where the price of a single night came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateSource {
+    /// A per-date override on the winning rate plan
+    Override,
+    /// The plan's weekend rate (Friday and Saturday nights)
+    Weekend,
+    /// The plan's base rate
+    Base,
+    /// No plan covered the night; the room's list price was used
+    RoomPrice,
+}
+
+/// Price of a single night of a stay
+#[derive(Debug, Clone, Serialize)]
+pub struct NightlyRate {
+    pub date: NaiveDate,
+    pub price: BigDecimal,
+    pub rate_plan_id: Option<Uuid>,
+    pub source: RateSource,
+}
+
+/// Full price quote for a stay in a room
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceQuote {
+    pub room_id: Uuid,
+    pub check_in_date: NaiveDate,
+    pub check_out_date: NaiveDate,
+    pub nights: i64,
+    pub total_price: BigDecimal,
+    /// Longest minimum-stay rule among the nights of the stay
+    pub min_stay_nights: i32,
+    /// Plan that priced the arrival night
+    pub rate_plan_id: Option<Uuid>,
+    pub nightly_rates: Vec<NightlyRate>,
+}
+
+impl PriceQuote {
+    /// Check whether the stay satisfies the minimum-stay rules
+    pub fn meets_min_stay(&self) -> bool {
+        self.nights >= i64::from(self.min_stay_nights)
+    }
+
+    /// Reject stays shorter than the minimum-stay rules allow
+    pub fn enforce_min_stay(&self) -> AppResult<()> {
+        if !self.meets_min_stay() {
+            return Err(AppError::ValidationError(format!(
+                "Minimum stay for these dates is {} nights",
+                self.min_stay_nights
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Friday and Saturday nights are priced at the weekend rate
+pub fn is_weekend_night(date: NaiveDate) -> bool {
+    matches!(date.weekday(), Weekday::Fri | Weekday::Sat)
+}
+
+/// Check if a rate plan covers the given room on the given night
+pub fn plan_applies(plan: &RatePlan, room: &Room, date: NaiveDate) -> bool {
+    let targets_room = match (plan.room_id, plan.room_type) {
+        (Some(room_id), _) => room_id == room.id,
+        (None, Some(room_type)) => room_type == room.room_type,
+        (None, None) => false,
+    };
+
+    plan.is_active
+        && targets_room
+        && plan.start_date.map_or(true, |start| date >= start)
+        && plan.end_date.map_or(true, |end| date <= end)
+}
+
+/// Pick the plan that prices a night.
+///
+/// Room-level plans beat room-type plans; then higher `priority` wins; then
+/// seasonal plans beat open-ended ones; the newest plan breaks remaining ties.
+pub fn select_plan<'a>(plans: &'a [RatePlan], room: &Room, date: NaiveDate) -> Option<&'a RatePlan> {
+    plans
+        .iter()
+        .filter(|plan| plan_applies(plan, room, date))
+        .max_by_key(|plan| {
+            (
+                plan.room_id.is_some(),
+                plan.priority,
+                plan.start_date.is_some() || plan.end_date.is_some(),
+                plan.created_at,
+            )
+        })
+}
+
+/// Price a stay night by night.
+///
+/// Each night uses the override on the winning plan if there is one, else the
+/// plan's weekend or base rate, else the room's list price.
+pub fn price_stay(
+    room: &Room,
+    plans: &[RatePlan],
+    overrides: &[RateOverride],
+    check_in_date: NaiveDate,
+    check_out_date: NaiveDate,
+) -> PriceQuote {
+    let nights = (check_out_date - check_in_date).num_days().max(0);
+    let mut nightly_rates = Vec::with_capacity(nights as usize);
+    let mut total_price = BigDecimal::from(0);
+    let mut min_stay_nights = 1;
+
+    for offset in 0..nights {
+        let date = check_in_date + Duration::days(offset);
+
+        let rate = match select_plan(plans, room, date) {
+            Some(plan) => {
+                let override_rate = overrides
+                    .iter()
+                    .find(|o| o.rate_plan_id == plan.id && o.stay_date == date);
+
+                let night_min_stay = override_rate
+                    .and_then(|o| o.min_stay_nights)
+                    .unwrap_or(plan.min_stay_nights);
+                min_stay_nights = min_stay_nights.max(night_min_stay);
+
+                let (price, source) = match (override_rate, &plan.weekend_rate) {
+                    (Some(o), _) => (o.price.clone(), RateSource::Override),
+                    (None, Some(weekend)) if is_weekend_night(date) => {
+                        (weekend.clone(), RateSource::Weekend)
+                    }
+                    _ => (plan.base_rate.clone(), RateSource::Base),
+                };
+
+                NightlyRate {
+                    date,
+                    price,
+                    rate_plan_id: Some(plan.id),
+                    source,
+                }
+            }
+            None => NightlyRate {
+                date,
+                price: room.price.clone(),
+                rate_plan_id: None,
+                source: RateSource::RoomPrice,
+            },
+        };
+
+        total_price += &rate.price;
+        nightly_rates.push(rate);
+    }
+
+    PriceQuote {
+        room_id: room.id,
+        check_in_date,
+        check_out_date,
+        nights,
+        total_price,
+        min_stay_nights,
+        rate_plan_id: nightly_rates.first().and_then(|r| r.rate_plan_id),
+        nightly_rates,
+    }
+}
+
+/// Validate the fields shared by new and updated rate plans
+fn validate_rate_plan_fields(
+    name: &str,
+    base_rate: &BigDecimal,
+    weekend_rate: Option<&BigDecimal>,
+    start_date: Option<NaiveDate>,
+    end_date: Option<NaiveDate>,
+    min_stay_nights: i32,
+) -> AppResult<()> {
+    let zero = BigDecimal::from(0);
+
+    if name.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Rate plan name is required".to_string(),
+        ));
+    }
+
+    if name.len() > 100 {
+        return Err(AppError::ValidationError(
+            "Rate plan name must be 100 characters or less".to_string(),
+        ));
+    }
+
+    if *base_rate < zero || weekend_rate.is_some_and(|rate| *rate < zero) {
+        return Err(AppError::ValidationError(
+            "Rates cannot be negative".to_string(),
+        ));
+    }
+
+    if let (Some(start), Some(end)) = (start_date, end_date) {
+        if end < start {
+            return Err(AppError::ValidationError(
+                "Season end date must be on or after the start date".to_string(),
+            ));
+        }
+    }
+
+    if min_stay_nights < 1 {
+        return Err(AppError::ValidationError(
+            "Minimum stay must be at least 1 night".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Reject references to cancellation policies that do not exist
+fn ensure_cancellation_policy_exists(conn: &mut PgConnection, policy_id: Uuid) -> AppResult<()> {
+    let exists: bool = diesel::select(diesel::dsl::exists(
+        cancellation_policies::table.find(policy_id),
+    ))
+    .get_result(conn)
+    .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+    if !exists {
+        return Err(AppError::NotFound(format!(
+            "Cancellation policy with ID '{}' not found",
+            policy_id
+        )));
+    }
+
+    Ok(())
+}
+
+impl PricingService {
+    /// Create a new PricingService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// Quote a stay using an existing connection (usable inside transactions)
+    pub fn quote_with_conn(
+        conn: &mut PgConnection,
+        room: &Room,
+        check_in_date: NaiveDate,
+        check_out_date: NaiveDate,
+    ) -> QueryResult<PriceQuote> {
+        let plans: Vec<RatePlan> = rate_plans::table
+            .filter(rate_plans::is_active.eq(true))
+            .filter(
+                rate_plans::room_id
+                    .eq(room.id)
+                    .or(rate_plans::room_type.eq(room.room_type)),
+            )
+            .filter(
+                rate_plans::start_date
+                    .is_null()
+                    .or(rate_plans::start_date.lt(check_out_date)),
+            )
+            .filter(
+                rate_plans::end_date
+                    .is_null()
+                    .or(rate_plans::end_date.ge(check_in_date)),
+            )
+            .load(conn)?;
+
+        let plan_ids: Vec<Uuid> = plans.iter().map(|p| p.id).collect();
+        let overrides: Vec<RateOverride> = rate_overrides::table
+            .filter(rate_overrides::rate_plan_id.eq_any(&plan_ids))
+            .filter(rate_overrides::stay_date.ge(check_in_date))
+            .filter(rate_overrides::stay_date.lt(check_out_date))
+            .load(conn)?;
+
+        Ok(price_stay(room, &plans, &overrides, check_in_date, check_out_date))
+    }
+
+    /// Quote a stay in a room
+    pub fn quote(
+        &self,
+        room_id: Uuid,
+        check_in_date: NaiveDate,
+        check_out_date: NaiveDate,
+    ) -> AppResult<PriceQuote> {
+        if check_out_date <= check_in_date {
+            return Err(AppError::ValidationError(
+                "Check-out date must be after check-in date".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let room: Room = rooms::table
+            .find(room_id)
+            .first(&mut conn)
+            .map_err(|_| AppError::NotFound(format!("Room with ID '{}' not found", room_id)))?;
+
+        Self::quote_with_conn(&mut conn, &room, check_in_date, check_out_date)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// List rate plans, optionally filtered by room type or room
+    pub fn list_rate_plans(
+        &self,
+        room_type_filter: Option<RoomType>,
+        room_id_filter: Option<Uuid>,
+    ) -> AppResult<Vec<RatePlan>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let mut query = rate_plans::table.into_boxed();
+
+        if let Some(room_type) = room_type_filter {
+            query = query.filter(rate_plans::room_type.eq(room_type));
+        }
+
+        if let Some(room_id) = room_id_filter {
+            query = query.filter(rate_plans::room_id.eq(room_id));
+        }
+
+        query
+            .order((rate_plans::priority.desc(), rate_plans::name.asc()))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Get a rate plan with its date overrides
+    pub fn get_rate_plan(&self, plan_id: Uuid) -> AppResult<RatePlanWithOverrides> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let rate_plan: RatePlan = rate_plans::table
+            .find(plan_id)
+            .first(&mut conn)
+            .map_err(|_| AppError::NotFound(format!("Rate plan with ID '{}' not found", plan_id)))?;
+
+        let overrides: Vec<RateOverride> = rate_overrides::table
+            .filter(rate_overrides::rate_plan_id.eq(plan_id))
+            .order(rate_overrides::stay_date.asc())
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(RatePlanWithOverrides {
+            rate_plan,
+            overrides,
+        })
+    }
+
+    /// Create a new rate plan
+    pub fn create_rate_plan(&self, new_plan: NewRatePlan) -> AppResult<RatePlan> {
+        validate_rate_plan_fields(
+            &new_plan.name,
+            &new_plan.base_rate,
+            new_plan.weekend_rate.as_ref(),
+            new_plan.start_date,
+            new_plan.end_date,
+            new_plan.min_stay_nights,
+        )?;
+
+        if new_plan.room_type.is_some() == new_plan.room_id.is_some() {
+            return Err(AppError::ValidationError(
+                "A rate plan must target either a room type or a room".to_string(),
+            ));
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if let Some(room_id) = new_plan.room_id {
+            let room_exists: bool = diesel::select(diesel::dsl::exists(rooms::table.find(room_id)))
+                .get_result(&mut conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if !room_exists {
+                return Err(AppError::NotFound(format!(
+                    "Room with ID '{}' not found",
+                    room_id
+                )));
+            }
+        }
+
+        if let Some(policy_id) = new_plan.cancellation_policy_id {
+            ensure_cancellation_policy_exists(&mut conn, policy_id)?;
+        }
+
+        diesel::insert_into(rate_plans::table)
+            .values(&new_plan)
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Update a rate plan
+    pub fn update_rate_plan(&self, plan_id: Uuid, update: UpdateRatePlan) -> AppResult<RatePlan> {
+        let current = self.get_rate_plan(plan_id)?.rate_plan;
+
+        let weekend_rate = match &update.weekend_rate {
+            Some(rate) => rate.as_ref(),
+            None => current.weekend_rate.as_ref(),
+        };
+
+        validate_rate_plan_fields(
+            update.name.as_deref().unwrap_or(&current.name),
+            update.base_rate.as_ref().unwrap_or(&current.base_rate),
+            weekend_rate,
+            update.start_date.unwrap_or(current.start_date),
+            update.end_date.unwrap_or(current.end_date),
+            update.min_stay_nights.unwrap_or(current.min_stay_nights),
+        )?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if let Some(Some(policy_id)) = update.cancellation_policy_id {
+            ensure_cancellation_policy_exists(&mut conn, policy_id)?;
+        }
+
+        diesel::update(rate_plans::table.find(plan_id))
+            .set(&update)
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Delete a rate plan and its overrides
+    pub fn delete_rate_plan(&self, plan_id: Uuid) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let deleted = diesel::delete(rate_plans::table.find(plan_id))
+            .execute(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!(
+                "Rate plan with ID '{}' not found",
+                plan_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Create or replace date overrides for a rate plan
+    pub fn upsert_overrides(
+        &self,
+        plan_id: Uuid,
+        overrides: Vec<NewRateOverride>,
+    ) -> AppResult<Vec<RateOverride>> {
+        let zero = BigDecimal::from(0);
+        for o in &overrides {
+            if o.price < zero {
+                return Err(AppError::ValidationError(format!(
+                    "Override price for {} cannot be negative",
+                    o.stay_date
+                )));
+            }
+            if o.min_stay_nights.is_some_and(|n| n < 1) {
+                return Err(AppError::ValidationError(format!(
+                    "Minimum stay for {} must be at least 1 night",
+                    o.stay_date
+                )));
+            }
+        }
+
+        // Ensure the plan exists before touching overrides
+        self.get_rate_plan(plan_id)?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            let mut saved = Vec::with_capacity(overrides.len());
+            for o in &overrides {
+                let row: RateOverride = diesel::insert_into(rate_overrides::table)
+                    .values(o)
+                    .on_conflict((rate_overrides::rate_plan_id, rate_overrides::stay_date))
+                    .do_update()
+                    // Explicit columns so a missing minimum stay clears the old one
+                    .set((
+                        rate_overrides::price.eq(excluded(rate_overrides::price)),
+                        rate_overrides::min_stay_nights
+                            .eq(excluded(rate_overrides::min_stay_nights)),
+                    ))
+                    .get_result(conn)?;
+                saved.push(row);
+            }
+            Ok(saved)
+        })
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Remove the override for a single date of a rate plan
+    pub fn delete_override(&self, plan_id: Uuid, stay_date: NaiveDate) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let deleted = diesel::delete(
+            rate_overrides::table
+                .filter(rate_overrides::rate_plan_id.eq(plan_id))
+                .filter(rate_overrides::stay_date.eq(stay_date)),
+        )
+        .execute(&mut conn)
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!(
+                "No override on {} for rate plan '{}'",
+                stay_date, plan_id
+            )));
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,147 @@
 use diesel::prelude::*;
 use bigdecimal::BigDecimal;
+use serde::Deserialize;
 use std::str::FromStr;
 use uuid::Uuid;
 
 use crate::db::DbPool;
 use crate::errors::{AppError, AppResult};
-use crate::models::{NewRoom, Room, RoomStatus, RoomType, UpdateRoom};
-use crate::schema::rooms;
+use crate::models::{BookingStatus, NewRoom, Room, RoomStatus, RoomType, UpdateRoom};
+use crate::schema::{bookings, rooms};
+use crate::services::housekeeping_service::room_floor;
+use crate::services::HousekeepingService;
+
+/// Most guests any room can be set to sleep
+pub const MAX_OCCUPANCY: i32 = 20;
+
+/// Most amenities a room can list
+pub const MAX_AMENITIES: usize = 30;
+
+/// Longest amenity name
+pub const MAX_AMENITY_LENGTH: usize = 50;
+
+/// Longest bed configuration, e.g. "1 king"
+pub const MAX_BED_CONFIGURATION_LENGTH: usize = 50;
+
+/// Longest view name, e.g. "sea"
+pub const MAX_VIEW_LENGTH: usize = 30;
+
+/// Filters for listing rooms; every given filter must match
+#[derive(Debug, Clone, Default)]
+pub struct RoomFilter {
+    pub status: Option<RoomStatus>,
+    pub room_type: Option<RoomType>,
+    pub floor: Option<i32>,
+    /// Rooms sleeping at least this many guests
+    pub guests: Option<i32>,
+    pub bed_configuration: Option<String>,
+    pub view: Option<String>,
+    /// Rooms having all of these amenities
+    pub amenities: Vec<String>,
+    pub smoking: Option<bool>,
+    pub wheelchair_accessible: Option<bool>,
+}
+
+impl RoomFilter {
+    /// Filter on room type only
+    pub fn by_type(room_type: Option<RoomType>) -> Self {
+        Self {
+            room_type,
+            ..Default::default()
+        }
+    }
+}
+
+/// Descriptive attributes of a new room
+///
+/// The floor defaults to the one in the room number and the capacity to the
+/// room type's.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RoomAttributes {
+    pub floor: Option<i32>,
+    pub max_occupancy: Option<i32>,
+    pub bed_configuration: Option<String>,
+    pub view: Option<String>,
+    #[serde(default)]
+    pub amenities: Vec<String>,
+    #[serde(default)]
+    pub smoking: bool,
+    #[serde(default)]
+    pub wheelchair_accessible: bool,
+}
+
+/// Changes to a room's attributes; `None` keeps the current value and
+/// `Some(None)` clears a nullable one
+#[derive(Debug, Clone, Default)]
+pub struct RoomAttributesUpdate {
+    pub floor: Option<Option<i32>>,
+    pub max_occupancy: Option<i32>,
+    pub bed_configuration: Option<Option<String>>,
+    pub view: Option<Option<String>>,
+    pub amenities: Option<Vec<String>>,
+    pub smoking: Option<bool>,
+    pub wheelchair_accessible: Option<bool>,
+}
+
+/// Trim and lowercase amenity names, dropping blanks and duplicates
+pub fn normalize_amenities(amenities: &[String]) -> AppResult<Vec<String>> {
+    let mut normalized: Vec<String> = Vec::with_capacity(amenities.len());
+    for amenity in amenities {
+        let amenity = amenity.trim().to_lowercase();
+        if amenity.is_empty() || normalized.contains(&amenity) {
+            continue;
+        }
+        if amenity.chars().count() > MAX_AMENITY_LENGTH {
+            return Err(AppError::ValidationError(format!(
+                "Amenity names must be {} characters or less",
+                MAX_AMENITY_LENGTH
+            )));
+        }
+        normalized.push(amenity);
+    }
+
+    if normalized.len() > MAX_AMENITIES {
+        return Err(AppError::ValidationError(format!(
+            "A room can list at most {} amenities",
+            MAX_AMENITIES
+        )));
+    }
+
+    Ok(normalized)
+}
+
+/// Check a room capacity is between 1 and [`MAX_OCCUPANCY`]
+pub fn validate_max_occupancy(max_occupancy: i32) -> AppResult<()> {
+    if !(1..=MAX_OCCUPANCY).contains(&max_occupancy) {
+        return Err(AppError::ValidationError(format!(
+            "Max occupancy must be between 1 and {}",
+            MAX_OCCUPANCY
+        )));
+    }
+    Ok(())
+}
+
+/// Trim and lowercase a free-text attribute; blank clears it
+fn normalize_label(value: Option<&str>, field: &str, max_length: usize) -> AppResult<Option<String>> {
+    let value = value.map(|v| v.trim().to_lowercase()).filter(|v| !v.is_empty());
+    if let Some(v) = &value {
+        if v.chars().count() > max_length {
+            return Err(AppError::ValidationError(format!(
+                "{} must be {} characters or less",
+                field, max_length
+            )));
+        }
+    }
+    Ok(value)
+}
+
+fn normalize_bed_configuration(value: Option<&str>) -> AppResult<Option<String>> {
+    normalize_label(value, "Bed configuration", MAX_BED_CONFIGURATION_LENGTH)
+}
+
+fn normalize_view(value: Option<&str>) -> AppResult<Option<String>> {
+    normalize_label(value, "View", MAX_VIEW_LENGTH)
+}
 
 /// Room service for managing hotel rooms
 pub struct RoomService {
@@ -20,7 +155,20 @@ impl RoomService {
     }
 
     /// Create a new room
-    pub fn create_room(&self, number: &str, room_type: RoomType) -> AppResult<Room> {
+    pub fn create_room(
+        &self,
+        number: &str,
+        room_type: RoomType,
+        attributes: RoomAttributes,
+    ) -> AppResult<Room> {
+        let max_occupancy = attributes
+            .max_occupancy
+            .unwrap_or_else(|| room_type.default_max_occupancy());
+        validate_max_occupancy(max_occupancy)?;
+        let amenities = normalize_amenities(&attributes.amenities)?;
+        let bed_configuration = normalize_bed_configuration(attributes.bed_configuration.as_deref())?;
+        let view = normalize_view(attributes.view.as_deref())?;
+
         let mut conn = self
             .pool
             .get()
@@ -48,7 +196,18 @@ impl RoomService {
             RoomType::Suite => BigDecimal::from_str("2500000").unwrap(),
         };
 
-        let new_room = NewRoom { number, room_type, price };
+        let new_room = NewRoom {
+            number,
+            room_type,
+            price,
+            floor: attributes.floor.or_else(|| room_floor(number)),
+            max_occupancy,
+            bed_configuration,
+            view,
+            amenities,
+            smoking: attributes.smoking,
+            wheelchair_accessible: attributes.wheelchair_accessible,
+        };
 
         diesel::insert_into(rooms::table)
             .values(&new_room)
@@ -83,12 +242,8 @@ impl RoomService {
             .map_err(|_| AppError::NotFound(format!("Room '{}' not found", number)))
     }
 
-    /// List all rooms with optional filters
-    pub fn list_rooms(
-        &self,
-        status_filter: Option<RoomStatus>,
-        type_filter: Option<RoomType>,
-    ) -> AppResult<Vec<Room>> {
+    /// List rooms matching every given filter
+    pub fn list_rooms(&self, filter: &RoomFilter) -> AppResult<Vec<Room>> {
         let mut conn = self
             .pool
             .get()
@@ -96,14 +251,43 @@ impl RoomService {
 
         let mut query = rooms::table.into_boxed();
 
-        if let Some(status) = status_filter {
+        if let Some(status) = filter.status {
             query = query.filter(rooms::status.eq(status));
         }
 
-        if let Some(room_type) = type_filter {
+        if let Some(room_type) = filter.room_type {
             query = query.filter(rooms::room_type.eq(room_type));
         }
 
+        if let Some(floor) = filter.floor {
+            query = query.filter(rooms::floor.eq(floor));
+        }
+
+        if let Some(guests) = filter.guests {
+            query = query.filter(rooms::max_occupancy.ge(guests));
+        }
+
+        if let Some(bed_configuration) = normalize_bed_configuration(filter.bed_configuration.as_deref())? {
+            query = query.filter(rooms::bed_configuration.eq(bed_configuration));
+        }
+
+        if let Some(view) = normalize_view(filter.view.as_deref())? {
+            query = query.filter(rooms::view.eq(view));
+        }
+
+        let amenities = normalize_amenities(&filter.amenities)?;
+        if !amenities.is_empty() {
+            query = query.filter(rooms::amenities.contains(amenities));
+        }
+
+        if let Some(smoking) = filter.smoking {
+            query = query.filter(rooms::smoking.eq(smoking));
+        }
+
+        if let Some(wheelchair_accessible) = filter.wheelchair_accessible {
+            query = query.filter(rooms::wheelchair_accessible.eq(wheelchair_accessible));
+        }
+
         query
             .order(rooms::number.asc())
             .load(&mut conn)
@@ -117,7 +301,25 @@ impl RoomService {
         room_type: Option<RoomType>,
         status: Option<RoomStatus>,
         assigned_cleaner_id: Option<Option<Uuid>>,
+        attributes: RoomAttributesUpdate,
     ) -> AppResult<Room> {
+        if let Some(max_occupancy) = attributes.max_occupancy {
+            validate_max_occupancy(max_occupancy)?;
+        }
+        let amenities = attributes
+            .amenities
+            .as_deref()
+            .map(normalize_amenities)
+            .transpose()?;
+        let bed_configuration = attributes
+            .bed_configuration
+            .map(|b| normalize_bed_configuration(b.as_deref()))
+            .transpose()?;
+        let view = attributes
+            .view
+            .map(|v| normalize_view(v.as_deref()))
+            .transpose()?;
+
         let mut conn = self
             .pool
             .get()
@@ -153,11 +355,41 @@ impl RoomService {
             }
         }
 
+        // Upcoming and in-house bookings must still fit a smaller room
+        if let Some(max_occupancy) = attributes.max_occupancy.filter(|m| *m < current.max_occupancy) {
+            let too_large: Vec<String> = bookings::table
+                .filter(bookings::room_id.eq(room_id))
+                .filter(bookings::status.eq_any([
+                    BookingStatus::Upcoming,
+                    BookingStatus::CheckedIn,
+                    BookingStatus::Overstay,
+                ]))
+                .filter(bookings::guest_count.gt(max_occupancy))
+                .select(bookings::reference)
+                .load(&mut conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            if !too_large.is_empty() {
+                return Err(AppError::Conflict(format!(
+                    "Bookings {} have more than {} guests in this room",
+                    too_large.join(", "),
+                    max_occupancy
+                )));
+            }
+        }
+
         let mut update = UpdateRoom {
             room_type,
             status,
             price: None,
             assigned_cleaner_id,
+            floor: attributes.floor,
+            max_occupancy: attributes.max_occupancy,
+            bed_configuration,
+            view,
+            amenities,
+            smoking: attributes.smoking,
+            wheelchair_accessible: attributes.wheelchair_accessible,
         };
         
         // Auto-clear assignment if becoming available
@@ -165,10 +397,25 @@ impl RoomService {
             update.assigned_cleaner_id = Some(None);
         }
 
-        diesel::update(rooms::table.find(room_id))
-            .set(&update)
-            .get_result(&mut conn)
-            .map_err(|e| AppError::DatabaseError(e.to_string()))
+        conn.transaction::<_, AppError, _>(|conn| {
+            let room: Room = diesel::update(rooms::table.find(room_id))
+                .set(&update)
+                .get_result(conn)
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            // Keep the housekeeping queue in step with the room
+            match status {
+                Some(RoomStatus::Dirty) if current.status != RoomStatus::Dirty => {
+                    HousekeepingService::open_room_clean_with_conn(conn, room_id, None)?;
+                }
+                Some(RoomStatus::Available) => {
+                    HousekeepingService::cancel_room_cleans_with_conn(conn, room_id)?;
+                }
+                _ => {}
+            }
+
+            Ok(room)
+        })
     }
 
     /// Update room status (internal use for check-in/out)
@@ -196,10 +443,8 @@ impl RoomService {
         }
         
         let mut update = UpdateRoom {
-            room_type: None,
             status: Some(status),
-            price: None,
-            assigned_cleaner_id: None,
+            ..Default::default()
         };
         
         // Auto-clear assignment if becoming available
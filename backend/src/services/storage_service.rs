@@ -7,7 +7,28 @@ pub async fn upload_image(
     file_name: &str,
     data: Vec<u8>
 ) -> Result<String, Box<dyn std::error::Error>> {
-    tracing::info!("Starting upload to MinIO: bucket={}, file={}, size={} bytes", bucket, file_name, data.len());
+    upload_object(client, bucket, file_name, data, "image/jpeg").await?;
+
+    let minio_url = env::var("MINIO_URL")
+        .map_err(|_| {
+            tracing::error!("MINIO_URL environment variable not set");
+            "MINIO_URL environment variable must be set"
+        })?;
+    
+    let result_url = format!("{}/{}/{}", minio_url, bucket, file_name);
+    tracing::info!("Upload complete, returning URL: {}", result_url);
+    Ok(result_url)
+}
+
+/// Store an object in MinIO, creating the bucket on first use
+pub async fn upload_object(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+    data: Vec<u8>,
+    content_type: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    tracing::info!("Starting upload to MinIO: bucket={}, file={}, size={} bytes", bucket, key, data.len());
     
     // Check if bucket exists, create if not
     match client.head_bucket().bucket(bucket).send().await {
@@ -34,27 +55,44 @@ pub async fn upload_image(
     match client
         .put_object()
         .bucket(bucket)
-        .key(file_name)
+        .key(key)
         .body(data.into())
-        .content_type("image/jpeg")
+        .content_type(content_type)
         .send()
         .await {
         Ok(_) => {
-            tracing::info!("Successfully uploaded object to MinIO: {}/{}", bucket, file_name);
+            tracing::info!("Successfully uploaded object to MinIO: {}/{}", bucket, key);
+            Ok(())
         }
         Err(e) => {
             tracing::error!("Failed to upload object to MinIO: {:?}", e);
-            return Err(format!("Failed to upload to MinIO: {}", e).into());
+            Err(format!("Failed to upload to MinIO: {}", e).into())
         }
     }
-    
-    let minio_url = env::var("MINIO_URL")
-        .map_err(|_| {
-            tracing::error!("MINIO_URL environment variable not set");
-            "MINIO_URL environment variable must be set"
+}
+
+/// Read an object back from MinIO
+pub async fn download_object(
+    client: &Client,
+    bucket: &str,
+    key: &str,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let object = client
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to fetch object {}/{} from MinIO: {:?}", bucket, key, e);
+            format!("Failed to fetch from MinIO: {}", e)
         })?;
-    
-    let result_url = format!("{}/{}/{}", minio_url, bucket, file_name);
-    tracing::info!("Upload complete, returning URL: {}", result_url);
-    Ok(result_url)
-}
\ No newline at end of file
+
+    let data = object
+        .body
+        .collect()
+        .await
+        .map_err(|e| format!("Failed to read object from MinIO: {}", e))?;
+
+    Ok(data.into_bytes().to_vec())
+}
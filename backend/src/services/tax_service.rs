@@ -0,0 +1,294 @@
+use bigdecimal::{BigDecimal, Zero};
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use crate::db::DbPool;
+use crate::errors::{AppError, AppResult};
+use crate::models::{ChargeCategory, NewTaxRule, TaxBreakdown, TaxLine, TaxRule, UpdateTaxRule};
+use crate::schema::tax_rules;
+
+/// What a tax rule is applied to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxTarget {
+    /// Room price of a stay
+    Room,
+    /// Folio charge of the given category
+    Charge(ChargeCategory),
+}
+
+/// Whether `rule` taxes `target` on `date`
+///
+/// Stays are dated by their check-in date, folio charges by the day they are
+/// posted.
+pub fn rule_applies(rule: &TaxRule, target: TaxTarget, date: NaiveDate) -> bool {
+    if !rule.is_active {
+        return false;
+    }
+
+    if rule.effective_from.map(|from| date < from).unwrap_or(false)
+        || rule.effective_to.map(|to| date > to).unwrap_or(false)
+    {
+        return false;
+    }
+
+    match target {
+        TaxTarget::Room => rule.applies_to_room,
+        TaxTarget::Charge(category) => rule.charge_categories.contains(&category),
+    }
+}
+
+/// Rules from `rules` that tax `target` on `date`, in application order
+pub fn applicable_rules(rules: &[TaxRule], target: TaxTarget, date: NaiveDate) -> Vec<&TaxRule> {
+    let mut applicable: Vec<&TaxRule> = rules
+        .iter()
+        .filter(|r| rule_applies(r, target, date))
+        .collect();
+    applicable.sort_by(|a, b| a.sort_order.cmp(&b.sort_order).then_with(|| a.name.cmp(&b.name)));
+    applicable
+}
+
+/// Split `amount` into net, tax and gross under `rules`
+///
+/// Every rule is a percentage of the net amount. Inclusive rules are already
+/// part of `amount`, so the net is backed out of it; exclusive rules are added
+/// on top. Tax lines are rounded to whole currency units.
+pub fn apply_taxes(amount: &BigDecimal, rules: &[&TaxRule]) -> TaxBreakdown {
+    let hundred = BigDecimal::from(100);
+    let inclusive_rate: BigDecimal = rules
+        .iter()
+        .filter(|r| r.is_inclusive)
+        .map(|r| r.rate.clone())
+        .sum();
+    let base = amount * &hundred / (&hundred + inclusive_rate);
+
+    let lines: Vec<TaxLine> = rules
+        .iter()
+        .map(|rule| TaxLine {
+            tax_rule_id: Some(rule.id),
+            name: rule.name.clone(),
+            rate: rule.rate.clone(),
+            is_inclusive: rule.is_inclusive,
+            amount: (&base * &rule.rate / &hundred).round(0),
+        })
+        .collect();
+
+    let mut net = amount.clone();
+    let mut gross = amount.clone();
+    let mut tax = BigDecimal::zero();
+    for line in &lines {
+        if line.is_inclusive {
+            net -= &line.amount;
+        } else {
+            gross += &line.amount;
+        }
+        tax += &line.amount;
+    }
+
+    TaxBreakdown { net, tax, gross, lines }
+}
+
+/// Combine tax lines of the same rule and rate into one line each
+pub fn merge_tax_lines(lines: impl IntoIterator<Item = TaxLine>) -> Vec<TaxLine> {
+    let mut merged: Vec<TaxLine> = Vec::new();
+    for line in lines {
+        match merged.iter_mut().find(|m| {
+            m.tax_rule_id == line.tax_rule_id
+                && m.name == line.name
+                && m.rate == line.rate
+                && m.is_inclusive == line.is_inclusive
+        }) {
+            Some(existing) => existing.amount += &line.amount,
+            None => merged.push(line),
+        }
+    }
+    merged
+}
+
+/// Validate the fields shared by new and updated rules
+fn validate_rule_fields(
+    name: &str,
+    rate: &BigDecimal,
+    applies_to_room: bool,
+    charge_categories: &[ChargeCategory],
+    effective_from: Option<NaiveDate>,
+    effective_to: Option<NaiveDate>,
+) -> AppResult<()> {
+    if name.trim().is_empty() {
+        return Err(AppError::ValidationError(
+            "Tax rule name is required".to_string(),
+        ));
+    }
+
+    if name.len() > 100 {
+        return Err(AppError::ValidationError(
+            "Tax rule name must be 100 characters or less".to_string(),
+        ));
+    }
+
+    let max_rate = BigDecimal::from(100);
+    if *rate < BigDecimal::zero() || rate > &max_rate {
+        return Err(AppError::ValidationError(
+            "Tax rate must be between 0 and 100".to_string(),
+        ));
+    }
+
+    if !applies_to_room && charge_categories.is_empty() {
+        return Err(AppError::ValidationError(
+            "Tax rule must apply to the room price or at least one charge category".to_string(),
+        ));
+    }
+
+    if let (Some(from), Some(to)) = (effective_from, effective_to) {
+        if from > to {
+            return Err(AppError::ValidationError(
+                "effective_from must be on or before effective_to".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Tax service: tax and service-charge rules
+pub struct TaxService {
+    pool: DbPool,
+}
+
+impl TaxService {
+    /// Create a new TaxService instance
+    pub fn new(pool: DbPool) -> Self {
+        Self { pool }
+    }
+
+    /// List all tax rules in application order
+    pub fn list_rules(&self) -> AppResult<Vec<TaxRule>> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        tax_rules::table
+            .order((tax_rules::sort_order.asc(), tax_rules::name.asc()))
+            .load(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Get a tax rule by ID
+    pub fn get_rule(&self, rule_id: Uuid) -> AppResult<TaxRule> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        tax_rules::table
+            .find(rule_id)
+            .first(&mut conn)
+            .map_err(|_| AppError::NotFound(format!("Tax rule with ID '{}' not found", rule_id)))
+    }
+
+    /// Create a tax rule
+    pub fn create_rule(&self, new_rule: NewTaxRule) -> AppResult<TaxRule> {
+        validate_rule_fields(
+            &new_rule.name,
+            &new_rule.rate,
+            new_rule.applies_to_room,
+            &new_rule.charge_categories,
+            new_rule.effective_from,
+            new_rule.effective_to,
+        )?;
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        diesel::insert_into(tax_rules::table)
+            .values(&new_rule)
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Update a tax rule
+    ///
+    /// Room taxes are worked out from the current rules, so changing a rate
+    /// also changes the bills of stays it covers. To change a rate from a
+    /// given date, end the old rule with `effective_to` and add a new one.
+    pub fn update_rule(&self, rule_id: Uuid, update: UpdateTaxRule) -> AppResult<TaxRule> {
+        let current = self.get_rule(rule_id)?;
+
+        validate_rule_fields(
+            update.name.as_deref().unwrap_or(&current.name),
+            update.rate.as_ref().unwrap_or(&current.rate),
+            update.applies_to_room.unwrap_or(current.applies_to_room),
+            update.charge_categories.as_deref().unwrap_or(&current.charge_categories),
+            update.effective_from.unwrap_or(current.effective_from),
+            update.effective_to.unwrap_or(current.effective_to),
+        )?;
+
+        let has_changes = update.name.is_some()
+            || update.rate.is_some()
+            || update.is_inclusive.is_some()
+            || update.applies_to_room.is_some()
+            || update.charge_categories.is_some()
+            || update.effective_from.is_some()
+            || update.effective_to.is_some()
+            || update.sort_order.is_some()
+            || update.is_active.is_some();
+        if !has_changes {
+            return Ok(current);
+        }
+
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        diesel::update(tax_rules::table.find(rule_id))
+            .set(&update)
+            .get_result(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    /// Delete a tax rule (tax lines already posted on folio charges are kept)
+    pub fn delete_rule(&self, rule_id: Uuid) -> AppResult<()> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let deleted = diesel::delete(tax_rules::table.find(rule_id))
+            .execute(&mut conn)
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if deleted == 0 {
+            return Err(AppError::NotFound(format!(
+                "Tax rule with ID '{}' not found",
+                rule_id
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// All active rules using an existing connection
+    pub fn active_rules_with_conn(conn: &mut PgConnection) -> QueryResult<Vec<TaxRule>> {
+        tax_rules::table
+            .filter(tax_rules::is_active.eq(true))
+            .order((tax_rules::sort_order.asc(), tax_rules::name.asc()))
+            .load(conn)
+    }
+
+    /// Tax breakdown of a room price for a stay checking in on `check_in_date`
+    pub fn room_breakdown_with_conn(
+        conn: &mut PgConnection,
+        price: &BigDecimal,
+        check_in_date: NaiveDate,
+    ) -> QueryResult<TaxBreakdown> {
+        let rules = Self::active_rules_with_conn(conn)?;
+        Ok(apply_taxes(
+            price,
+            &applicable_rules(&rules, TaxTarget::Room, check_in_date),
+        ))
+    }
+}
@@ -1,4 +1,5 @@
+pub mod serde_helpers;
 pub mod validation;
 
+pub use serde_helpers::*;
 pub use validation::*;
-
@@ -0,0 +1,13 @@
+use serde::{Deserialize, Deserializer};
+
+/// Deserialize a nullable PATCH field so an explicit `null` becomes `Some(None)`
+///
+/// Use with `#[serde(default, deserialize_with = "...")]` so an omitted field
+/// stays `None` (no change) while `null` clears the value.
+pub fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -4,6 +4,8 @@
 //! từ file gốc nhưng chuyển chúng thành các kiểm tra thuần logic (không cần DB),
 //! bằng cách dùng helper ngày và kiểm tra enum / hàm thuần.
 
+mod common;
+
 use chrono::{NaiveDate, Utc};
 use regex::Regex;
 
@@ -431,4 +433,332 @@ mod checkout_room_status_tests {
             "Occupied rooms must be allowed to transition to Dirty on checkout"
         );
     }
-}
\ No newline at end of file
+}
+// ============================================================================
+// GROUP BOOKING REQUESTS
+// ============================================================================
+
+mod group_booking_request_tests {
+    use hotel_management_backend::services::booking_service::CreateGroupBookingRequest;
+
+    #[test]
+    fn group_request_rooms_inherit_group_dates_unless_overridden() {
+        let request: CreateGroupBookingRequest = serde_json::from_value(serde_json::json!({
+            "name": "Wedding party",
+            "contact_name": "Nguyen Van A",
+            "check_in_date": "2026-12-20",
+            "check_out_date": "2026-12-22",
+            "rooms": [
+                { "room_id": "10000000-0000-0000-0000-000000000101" },
+                {
+                    "room_id": "10000000-0000-0000-0000-000000000102",
+                    "guest_name": "Tran Thi B",
+                    "check_in_date": "2026-12-19"
+                }
+            ]
+        }))
+        .expect("group request should deserialize");
+
+        assert_eq!(request.rooms.len(), 2);
+        assert!(request.rooms[0].check_in_date.is_none(), "First room uses the group dates");
+        assert!(request.rooms[0].guest_name.is_none(), "First room defaults to the contact");
+        assert_eq!(
+            request.rooms[1].check_in_date.map(|d| d.to_string()),
+            Some("2026-12-19".to_string()),
+            "Early arrival overrides the group check-in date"
+        );
+        assert!(request.rooms[1].check_out_date.is_none());
+    }
+
+    #[test]
+    fn group_request_requires_room_list() {
+        let result = serde_json::from_value::<CreateGroupBookingRequest>(serde_json::json!({
+            "name": "Tour",
+            "contact_name": "Le C",
+            "check_in_date": "2026-12-20",
+            "check_out_date": "2026-12-22"
+        }));
+
+        assert!(result.is_err(), "A group request without rooms should be rejected");
+    }
+}
+
+// ============================================================================
+// DOUBLE-BOOKING CONSTRAINT (requires a database)
+// ============================================================================
+//
+// These tests talk to a real PostgreSQL instance with all migrations applied.
+// Point TEST_DATABASE_URL at a disposable database to run them; they are
+// skipped otherwise. Each test creates its own room and removes it afterwards.
+
+mod overlap_constraint_tests {
+    use super::*;
+
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    use bigdecimal::BigDecimal;
+    use diesel::prelude::*;
+    use uuid::Uuid;
+
+    use hotel_management_backend::db::DbPool;
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::{Booking, NewBooking};
+    use hotel_management_backend::schema::bookings;
+    use hotel_management_backend::services::BookingService;
+
+    use crate::common::{
+        create_test_room, create_test_staff, remove_test_room, remove_test_staff, test_pool,
+    };
+
+    fn insert_raw(pool: &DbPool, room_id: Uuid, check_in: NaiveDate, check_out: NaiveDate) -> Result<Booking, AppError> {
+        let mut conn = pool.get().unwrap();
+        let reference = format!("BK-TEST-{}", &Uuid::new_v4().simple().to_string()[..6]);
+        diesel::insert_into(bookings::table)
+            .values(&NewBooking {
+                reference: &reference,
+                guest_name: "Raw Insert",
+                room_id,
+                check_in_date: check_in,
+                check_out_date: check_out,
+                created_by_user_id: None,
+                creation_source: "staff",
+                price: BigDecimal::from(1_000_000),
+                rate_plan_id: None,
+                group_id: None,
+                guest_count: 1,
+            })
+            .get_result(&mut conn)
+            .map_err(AppError::from)
+    }
+
+    #[test]
+    fn concurrent_bookings_for_same_room_allow_only_one() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "T");
+
+        let attempts = 8;
+        let barrier = Arc::new(Barrier::new(attempts));
+        let check_in = days_from_now(400);
+        let check_out = days_from_now(403);
+
+        let handles: Vec<_> = (0..attempts)
+            .map(|i| {
+                let service = BookingService::new(pool.clone());
+                let barrier = Arc::clone(&barrier);
+                let room_id = room.id;
+                thread::spawn(move || {
+                    barrier.wait();
+                    service.create_booking(&format!("Guest {}", i), room_id, check_in, check_out, 1, None)
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        remove_test_room(&pool, room.id);
+
+        let created = results.iter().filter(|r| r.is_ok()).count();
+        let rejected = results
+            .iter()
+            .filter(|r| matches!(r, Err(AppError::RoomUnavailable(_))))
+            .count();
+
+        assert_eq!(created, 1, "Exactly one concurrent booking should succeed: {:?}", results);
+        assert_eq!(rejected, attempts - 1, "All other attempts should be RoomUnavailable");
+    }
+
+    #[test]
+    fn database_rejects_overlap_that_skips_availability_check() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "T");
+
+        let first = insert_raw(&pool, room.id, days_from_now(410), days_from_now(413));
+        let overlapping = insert_raw(&pool, room.id, days_from_now(412), days_from_now(414));
+        let back_to_back = insert_raw(&pool, room.id, days_from_now(413), days_from_now(415));
+        remove_test_room(&pool, room.id);
+
+        assert!(first.is_ok());
+        assert!(
+            matches!(overlapping, Err(AppError::RoomUnavailable(_))),
+            "Overlap should map to RoomUnavailable, got {:?}",
+            overlapping
+        );
+        assert!(back_to_back.is_ok(), "Check-out day may be the next guest's check-in day");
+    }
+
+    #[test]
+    fn cancelled_booking_does_not_block_the_dates() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "T");
+        let service = BookingService::new(pool.clone());
+
+        let staff = create_test_staff(&pool, "overlap");
+
+        let first = insert_raw(&pool, room.id, days_from_now(420), days_from_now(422)).unwrap();
+        service.cancel(first.id, staff).unwrap();
+        let rebooked = insert_raw(&pool, room.id, days_from_now(420), days_from_now(422));
+        remove_test_room(&pool, room.id);
+        remove_test_staff(&pool, staff);
+
+        assert!(rebooked.is_ok(), "Cancelled stays should free the room: {:?}", rebooked);
+    }
+}
+
+// ============================================================================
+// GROUP CHECK-IN (requires a database)
+// ============================================================================
+
+mod group_check_in_db_tests {
+    use diesel::prelude::*;
+    use uuid::Uuid;
+
+    use hotel_management_backend::db::DbPool;
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::BookingStatus;
+    use hotel_management_backend::schema::{booking_groups, bookings};
+    use hotel_management_backend::services::booking_service::{
+        CreateGroupBookingRequest, GroupRoomRequest,
+    };
+    use hotel_management_backend::services::BookingService;
+
+    use crate::common::{
+        create_test_room, create_test_staff, days_from_now, money, remove_test_room,
+        remove_test_staff, test_pool,
+    };
+
+    fn room_request(room_id: Uuid) -> GroupRoomRequest {
+        GroupRoomRequest {
+            room_id,
+            guest_name: None,
+            check_in_date: None,
+            check_out_date: None,
+            price: None,
+            guest_count: None,
+        }
+    }
+
+    fn group_request(rooms: Vec<GroupRoomRequest>) -> CreateGroupBookingRequest {
+        CreateGroupBookingRequest {
+            name: "Conference".to_string(),
+            contact_name: "Pham Van D".to_string(),
+            notes: None,
+            check_in_date: days_from_now(0),
+            check_out_date: days_from_now(3),
+            rooms,
+        }
+    }
+
+    fn remove_group(pool: &DbPool, group_id: Uuid) {
+        let mut conn = pool.get().unwrap();
+        diesel::delete(bookings::table.filter(bookings::group_id.eq(group_id)))
+            .execute(&mut conn)
+            .unwrap();
+        diesel::delete(booking_groups::table.find(group_id))
+            .execute(&mut conn)
+            .unwrap();
+    }
+
+    #[test]
+    fn group_check_in_leaves_later_arrivals_upcoming() {
+        let Some(pool) = test_pool() else { return };
+        let today_room = create_test_room(&pool, "G");
+        let later_room = create_test_room(&pool, "G");
+        let staff = create_test_staff(&pool, "group");
+        let service = BookingService::new(pool.clone());
+
+        let group = service
+            .create_group_booking(
+                &group_request(vec![
+                    room_request(today_room.id),
+                    GroupRoomRequest {
+                        check_in_date: Some(days_from_now(1)),
+                        ..room_request(later_room.id)
+                    },
+                ]),
+                Some(staff),
+            )
+            .unwrap();
+        let checked_in = service.check_in_group(group.group.id);
+        remove_group(&pool, group.group.id);
+        remove_test_room(&pool, today_room.id);
+        remove_test_room(&pool, later_room.id);
+        remove_test_staff(&pool, staff);
+
+        let checked_in = checked_in.unwrap();
+        let status_of = |room_id: Uuid| {
+            checked_in
+                .bookings
+                .iter()
+                .find(|b| b.booking.room_id == room_id)
+                .map(|b| b.booking.status)
+                .unwrap()
+        };
+        assert_eq!(group.group.created_by_user_id, Some(staff));
+        assert_eq!(status_of(today_room.id), BookingStatus::CheckedIn);
+        assert_eq!(status_of(later_room.id), BookingStatus::Upcoming);
+    }
+
+    #[test]
+    fn group_room_price_cannot_be_negative() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "G");
+        let service = BookingService::new(pool.clone());
+
+        let result = service.create_group_booking(
+            &group_request(vec![GroupRoomRequest {
+                price: Some(money(-1)),
+                ..room_request(room.id)
+            }]),
+            None,
+        );
+        remove_test_room(&pool, room.id);
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
+
+// ============================================================================
+// CHECK-OUT WITH AN UNPAID BALANCE (DATABASE)
+// ============================================================================
+
+mod unpaid_check_out_db_tests {
+    use diesel::prelude::*;
+
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::BookingStatus;
+    use hotel_management_backend::schema::bookings;
+    use hotel_management_backend::services::BookingService;
+
+    use crate::common::{
+        create_test_room, create_test_staff, days_from_now, insert_test_booking, money,
+        remove_test_room, remove_test_staff, test_pool,
+    };
+
+    #[test]
+    fn overriding_an_unpaid_balance_records_who_did_it() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "U");
+        let staff = create_test_staff(&pool, "checkout");
+        let bookings = BookingService::new(pool.clone());
+
+        let booking = insert_test_booking(&pool, room.id, days_from_now(-1), days_from_now(0), 1_000_000);
+        diesel::update(bookings::table.find(booking.id))
+            .set(bookings::status.eq(BookingStatus::CheckedIn))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+        let refused = bookings.check_out(booking.id, false, false, staff);
+        let overridden = bookings.check_out(booking.id, false, true, staff);
+        remove_test_room(&pool, room.id);
+        remove_test_staff(&pool, staff);
+
+        assert!(
+            matches!(refused, Err(AppError::UnpaidBalance(_))),
+            "Check-out with money owed should be refused, got {:?}",
+            refused
+        );
+        let overridden = overridden.unwrap();
+        assert_eq!(overridden.status, BookingStatus::CheckedOut);
+        assert_eq!(overridden.unpaid_check_out_by_user_id, Some(staff));
+        assert!(overridden.unpaid_check_out_balance.unwrap() >= money(1_000_000));
+    }
+}
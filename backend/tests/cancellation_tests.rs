@@ -0,0 +1,291 @@
+//! Tests for cancellation penalties and refunds. The database tests run only
+//! when TEST_DATABASE_URL is set.
+
+mod common;
+
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use uuid::Uuid;
+
+use hotel_management_backend::models::{CancellationPenaltyType, CancellationPolicy};
+use hotel_management_backend::services::cancellation_service::{
+    cancellation_penalty, refund_due, split_refund,
+};
+
+use common::{date, money};
+
+fn policy(free_days: i32, penalty_type: CancellationPenaltyType, value: i64) -> CancellationPolicy {
+    CancellationPolicy {
+        id: Uuid::new_v4(),
+        name: "Standard".to_string(),
+        free_cancellation_days: free_days,
+        penalty_type,
+        penalty_value: money(value),
+        room_type: None,
+        is_active: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+// ============================================================================
+// FREE CANCELLATION
+// ============================================================================
+
+mod free_cancellation_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_policy_is_free() {
+        let penalty = cancellation_penalty(None, &money(3_000_000), date(2026, 5, 10), date(2026, 5, 13), date(2026, 5, 10));
+
+        assert_eq!(penalty, money(0));
+    }
+
+    #[test]
+    fn test_cancelling_on_the_deadline_is_free() {
+        let p = policy(3, CancellationPenaltyType::Percent, 50);
+
+        // Exactly 3 days before check-in
+        let penalty = cancellation_penalty(Some(&p), &money(3_000_000), date(2026, 5, 10), date(2026, 5, 13), date(2026, 5, 7));
+
+        assert_eq!(penalty, money(0));
+    }
+
+    #[test]
+    fn test_inactive_policy_is_ignored() {
+        let mut p = policy(3, CancellationPenaltyType::Percent, 50);
+        p.is_active = false;
+
+        let penalty = cancellation_penalty(Some(&p), &money(3_000_000), date(2026, 5, 10), date(2026, 5, 13), date(2026, 5, 9));
+
+        assert_eq!(penalty, money(0));
+    }
+}
+
+// ============================================================================
+// LATE CANCELLATION PENALTIES
+// ============================================================================
+
+mod penalty_tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_penalty_inside_window() {
+        let p = policy(3, CancellationPenaltyType::Percent, 50);
+
+        let penalty = cancellation_penalty(Some(&p), &money(3_000_000), date(2026, 5, 10), date(2026, 5, 13), date(2026, 5, 8));
+
+        assert_eq!(penalty, money(1_500_000));
+    }
+
+    #[test]
+    fn test_nights_penalty_uses_average_nightly_rate() {
+        let p = policy(1, CancellationPenaltyType::Nights, 1);
+
+        // 3 nights for 3,300,000 -> one night is 1,100,000
+        let penalty = cancellation_penalty(Some(&p), &money(3_300_000), date(2026, 5, 10), date(2026, 5, 13), date(2026, 5, 10));
+
+        assert_eq!(penalty, money(1_100_000));
+    }
+
+    #[test]
+    fn test_nights_penalty_is_capped_at_stay_length() {
+        let p = policy(7, CancellationPenaltyType::Nights, 5);
+
+        let penalty = cancellation_penalty(Some(&p), &money(2_000_000), date(2026, 5, 10), date(2026, 5, 12), date(2026, 5, 9));
+
+        assert_eq!(penalty, money(2_000_000));
+    }
+
+    #[test]
+    fn test_penalty_after_check_in_date_still_applies() {
+        let p = policy(2, CancellationPenaltyType::Percent, 100);
+
+        let penalty = cancellation_penalty(Some(&p), &money(1_000_000), date(2026, 5, 10), date(2026, 5, 11), date(2026, 5, 12));
+
+        assert_eq!(penalty, money(1_000_000));
+    }
+}
+
+// ============================================================================
+// REFUNDS
+// ============================================================================
+
+mod refund_tests {
+    use super::*;
+
+    #[test]
+    fn test_refund_is_payments_minus_penalty() {
+        assert_eq!(refund_due(&money(2_000_000), &money(500_000)), money(1_500_000));
+    }
+
+    #[test]
+    fn test_no_refund_when_paid_less_than_penalty() {
+        assert_eq!(refund_due(&money(300_000), &money(500_000)), money(0));
+    }
+
+    #[test]
+    fn test_full_refund_when_free() {
+        assert_eq!(refund_due(&money(1_000_000), &money(0)), money(1_000_000));
+    }
+}
+
+// ============================================================================
+// SPLITTING REFUNDS BY PAYMENT METHOD
+// ============================================================================
+
+mod split_refund_tests {
+    use super::*;
+
+    fn paid(methods: &[(&str, i64)]) -> Vec<(String, BigDecimal)> {
+        methods.iter().map(|(m, a)| (m.to_string(), money(*a))).collect()
+    }
+
+    #[test]
+    fn test_refund_is_shared_in_proportion_to_what_was_paid() {
+        let shares = split_refund(&money(1_000_000), &paid(&[("card", 1_500_000), ("guest_credit", 500_000)]));
+
+        assert_eq!(shares, paid(&[("card", 750_000), ("guest_credit", 250_000)]));
+    }
+
+    #[test]
+    fn test_credit_never_gets_back_more_than_was_paid_from_it() {
+        let shares = split_refund(&money(2_000_000), &paid(&[("card", 1_500_000), ("guest_credit", 500_000)]));
+
+        assert_eq!(shares, paid(&[("card", 1_500_000), ("guest_credit", 500_000)]));
+    }
+
+    #[test]
+    fn test_fully_refunded_methods_are_left_out() {
+        let shares = split_refund(&money(400_000), &paid(&[("card", 0), ("cash", 400_000)]));
+
+        assert_eq!(shares, paid(&[("cash", 400_000)]));
+    }
+
+    #[test]
+    fn test_rounding_leftover_goes_to_the_first_method() {
+        let shares = split_refund(&money(100), &paid(&[("card", 100), ("cash", 100), ("transfer", 100)]));
+        let amount = |s: &str| s.parse::<BigDecimal>().unwrap();
+
+        assert_eq!(
+            shares,
+            vec![
+                ("card".to_string(), amount("33.34")),
+                ("cash".to_string(), amount("33.33")),
+                ("transfer".to_string(), amount("33.33")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nothing_paid_refunds_as_other() {
+        assert_eq!(split_refund(&money(500), &[]), paid(&[("other", 500)]));
+    }
+}
+
+// ============================================================================
+// CANCELLING A PAID BOOKING (DATABASE)
+// ============================================================================
+
+mod cancel_refund_db_tests {
+    use super::*;
+
+    use hotel_management_backend::models::PaymentType;
+    use hotel_management_backend::services::payment_service::CreatePaymentRequest;
+    use hotel_management_backend::services::{BookingService, CashierShiftService, PaymentService};
+
+    use common::{create_test_room, create_test_staff, days_from_now, test_pool};
+
+    // Payments are append-only, so the booking, payments and shift stay behind
+
+    #[test]
+    fn refund_is_made_by_the_cancelling_staff_on_their_shift() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "C");
+        let staff = create_test_staff(&pool, "cancel");
+        let shift = CashierShiftService::new(pool.clone())
+            .open_shift(staff, money(500_000), None)
+            .unwrap();
+        let bookings = BookingService::new(pool.clone());
+        let payments = PaymentService::new(pool.clone());
+
+        // Far enough ahead that any policy lets the guest cancel for free
+        let booking = bookings
+            .create_booking("Refund Guest", room.id, days_from_now(500), days_from_now(502), 1, Some(money(2_000_000)))
+            .unwrap();
+        payments
+            .create_payment(
+                booking.id,
+                CreatePaymentRequest {
+                    amount: money(2_000_000),
+                    currency: None,
+                    payment_type: PaymentType::Deposit,
+                    payment_method: "cash".to_string(),
+                    notes: None,
+                },
+                staff,
+            )
+            .unwrap();
+
+        let cancelled = bookings.cancel(booking.id, staff).unwrap();
+        let [refund_id] = cancelled.cancellation.refund_payment_ids[..] else {
+            panic!("expected one refund, got {:?}", cancelled.cancellation.refund_payment_ids);
+        };
+        let refund = payments.get_payment_by_id(refund_id).unwrap();
+
+        assert_eq!(cancelled.cancellation.refund_amount, money(2_000_000));
+        assert_eq!(refund.amount, money(-2_000_000));
+        assert_eq!(refund.payment_method, "cash");
+        assert_eq!(refund.created_by_user_id, staff);
+        assert_eq!(refund.shift_id, Some(shift.id));
+    }
+
+    #[test]
+    fn refund_goes_back_to_each_method_in_proportion() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "C");
+        let staff = create_test_staff(&pool, "split");
+        let bookings = BookingService::new(pool.clone());
+        let payments = PaymentService::new(pool.clone());
+
+        let booking = bookings
+            .create_booking("Split Guest", room.id, days_from_now(500), days_from_now(502), 1, Some(money(2_000_000)))
+            .unwrap();
+        for (amount, method) in [(1_500_000, "card"), (500_000, "cash")] {
+            payments
+                .create_payment(
+                    booking.id,
+                    CreatePaymentRequest {
+                        amount: money(amount),
+                        currency: None,
+                        payment_type: PaymentType::Deposit,
+                        payment_method: method.to_string(),
+                        notes: None,
+                    },
+                    staff,
+                )
+                .unwrap();
+        }
+
+        let cancelled = bookings.cancel(booking.id, staff).unwrap();
+        let mut refunds: Vec<(String, BigDecimal)> = cancelled
+            .cancellation
+            .refund_payment_ids
+            .iter()
+            .map(|id| {
+                let refund = payments.get_payment_by_id(*id).unwrap();
+                (refund.payment_method, refund.amount)
+            })
+            .collect();
+        refunds.sort();
+
+        assert_eq!(
+            refunds,
+            vec![
+                ("card".to_string(), money(-1_500_000)),
+                ("cash".to_string(), money(-500_000)),
+            ]
+        );
+    }
+}
@@ -0,0 +1,229 @@
+//! Tests for cashier shift totals and drawer reconciliation
+//!
+//! The adjustment tests need a database and run only when TEST_DATABASE_URL is set.
+
+mod common;
+
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use uuid::Uuid;
+
+use hotel_management_backend::errors::AppError;
+use hotel_management_backend::models::{Payment, PaymentType};
+use hotel_management_backend::services::cashier_shift_service::{
+    reconcile_drawer, summarize_shift_payments, validate_cash_amount,
+};
+
+use common::money;
+
+fn payment(amount: i64, method: &str) -> Payment {
+    let now = Utc::now();
+    Payment {
+        id: Uuid::new_v4(),
+        booking_id: Uuid::new_v4(),
+        amount: money(amount),
+        payment_type: if amount < 0 { PaymentType::Refund } else { PaymentType::Partial },
+        payment_method: method.to_string(),
+        notes: None,
+        created_by_user_id: Uuid::new_v4(),
+        created_at: now,
+        updated_at: now,
+        voided_at: None,
+        voided_by_user_id: None,
+        void_reason: None,
+        replaces_payment_id: None,
+        shift_id: Some(Uuid::new_v4()),
+        currency: "VND".to_string(),
+        exchange_rate: BigDecimal::from(1),
+        original_amount: None,
+    }
+}
+
+fn foreign(amount: i64, currency: &str, rate: i64, method: &str) -> Payment {
+    let mut payment = payment(amount * rate, method);
+    payment.currency = currency.to_string();
+    payment.exchange_rate = money(rate);
+    payment.original_amount = Some(money(amount));
+    payment
+}
+
+fn voided(mut payment: Payment) -> Payment {
+    payment.voided_at = Some(Utc::now());
+    payment.voided_by_user_id = Some(Uuid::new_v4());
+    payment.void_reason = Some("Duplicate entry".to_string());
+    payment
+}
+
+// ============================================================================
+// SHIFT TOTALS
+// ============================================================================
+
+mod summary_tests {
+    use super::*;
+
+    #[test]
+    fn test_totals_per_method() {
+        let payments = vec![
+            payment(300_000, "cash"),
+            payment(200_000, "card"),
+            payment(150_000, "cash"),
+        ];
+
+        let (totals, net_cash) = summarize_shift_payments(&payments);
+
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].payment_method, "card");
+        assert_eq!(totals[0].payment_count, 1);
+        assert_eq!(totals[1].payment_method, "cash");
+        assert_eq!(totals[1].payment_count, 2);
+        assert_eq!(totals[1].total, money(450_000));
+        assert_eq!(net_cash, money(450_000));
+    }
+
+    #[test]
+    fn test_cash_refunds_reduce_net_cash() {
+        let payments = vec![payment(500_000, "cash"), payment(-120_000, "cash")];
+
+        let (_, net_cash) = summarize_shift_payments(&payments);
+
+        assert_eq!(net_cash, money(380_000));
+    }
+
+    #[test]
+    fn test_voided_payments_are_left_out() {
+        let payments = vec![payment(300_000, "cash"), voided(payment(100_000, "cash"))];
+
+        let (totals, net_cash) = summarize_shift_payments(&payments);
+
+        assert_eq!(totals[0].payment_count, 1);
+        assert_eq!(net_cash, money(300_000));
+    }
+
+    #[test]
+    fn test_foreign_cash_is_listed_but_not_in_net_cash() {
+        let payments = vec![payment(300_000, "cash"), foreign(100, "USD", 25_000, "cash")];
+
+        let (totals, net_cash) = summarize_shift_payments(&payments);
+
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].currency, "USD");
+        assert_eq!(totals[0].total, money(100));
+        assert_eq!(totals[0].base_total, money(2_500_000));
+        assert_eq!(totals[1].currency, "VND");
+        assert_eq!(net_cash, money(300_000));
+    }
+
+    #[test]
+    fn test_no_cash_taken() {
+        let (totals, net_cash) = summarize_shift_payments(&[payment(200_000, "card")]);
+
+        assert_eq!(totals.len(), 1);
+        assert_eq!(net_cash, money(0));
+    }
+}
+
+// ============================================================================
+// DRAWER RECONCILIATION
+// ============================================================================
+
+mod reconciliation_tests {
+    use super::*;
+
+    #[test]
+    fn test_balanced_drawer() {
+        let (expected, over_short) = reconcile_drawer(&money(500_000), &money(300_000), &money(800_000));
+        assert_eq!(expected, money(800_000));
+        assert_eq!(over_short, money(0));
+    }
+
+    #[test]
+    fn test_short_drawer_is_negative() {
+        let (_, over_short) = reconcile_drawer(&money(500_000), &money(300_000), &money(790_000));
+        assert_eq!(over_short, money(-10_000));
+    }
+
+    #[test]
+    fn test_over_drawer_is_positive() {
+        let (_, over_short) = reconcile_drawer(&money(500_000), &money(300_000), &money(805_000));
+        assert_eq!(over_short, money(5_000));
+    }
+
+    #[test]
+    fn test_negative_amounts_are_rejected() {
+        assert!(matches!(
+            validate_cash_amount(&money(-1), "Opening float"),
+            Err(AppError::ValidationError(_))
+        ));
+        assert!(validate_cash_amount(&money(0), "Counted cash").is_ok());
+    }
+}
+
+// ============================================================================
+// ADJUSTING A SHIFT'S PAYMENT (DATABASE)
+// ============================================================================
+
+mod adjustment_db_tests {
+    use super::*;
+
+    use hotel_management_backend::models::{PaymentLedgerAction, UpdatePayment};
+    use hotel_management_backend::services::payment_service::CreatePaymentRequest;
+    use hotel_management_backend::services::{CashierShiftService, PaymentService};
+
+    use common::{create_test_room, create_test_staff, days_from_now, insert_test_booking, test_pool};
+
+    // Payments are append-only, so the booking, payments and shifts stay behind
+
+    #[test]
+    fn adjustment_stays_in_the_drawer_that_took_the_payment() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "S");
+        let cashier = create_test_staff(&pool, "cashier");
+        let manager = create_test_staff(&pool, "manager");
+        let shifts = CashierShiftService::new(pool.clone());
+        let cashier_shift = shifts.open_shift(cashier, money(500_000), None).unwrap();
+        shifts.open_shift(manager, money(0), None).unwrap();
+        let payments = PaymentService::new(pool.clone());
+
+        let booking =
+            insert_test_booking(&pool, room.id, days_from_now(20), days_from_now(22), 2_000_000);
+        let original = payments
+            .create_payment(
+                booking.id,
+                CreatePaymentRequest {
+                    amount: money(1_000_000),
+                    currency: None,
+                    payment_type: PaymentType::Deposit,
+                    payment_method: "cash".to_string(),
+                    notes: None,
+                },
+                cashier,
+            )
+            .unwrap();
+
+        let replacement = payments
+            .adjust_payment(
+                original.id,
+                UpdatePayment {
+                    amount: Some(money(800_000)),
+                    payment_type: None,
+                    payment_method: None,
+                    notes: None,
+                },
+                "Miscounted",
+                manager,
+            )
+            .unwrap();
+
+        assert_eq!(replacement.shift_id, Some(cashier_shift.id));
+        assert_eq!(replacement.created_by_user_id, cashier);
+        assert_eq!(shifts.current_shift(cashier).unwrap().net_cash, money(800_000));
+        assert_eq!(shifts.current_shift(manager).unwrap().net_cash, money(0));
+
+        let ledger = payments.get_ledger_by_booking(booking.id).unwrap();
+        let adjusted = ledger
+            .iter()
+            .find(|entry| entry.action == PaymentLedgerAction::Adjusted)
+            .unwrap();
+        assert_eq!(adjusted.actor_user_id, manager);
+    }
+}
@@ -49,6 +49,14 @@ mod cleaner_status_permission_tests {
         );
     }
 
+    #[test]
+    fn cleaner_cannot_skip_to_pending_inspection() {
+        assert!(
+            !RoomStatus::PendingInspection.is_allowed_for_role(UserRole::Cleaner),
+            "Finishing a clean decides whether the room waits for inspection"
+        );
+    }
+
     #[test]
     fn cleaner_can_set_cleaning_flow_statuses() {
         assert!(
@@ -0,0 +1,223 @@
+//! Shared fixtures for the integration tests
+//!
+//! In-memory model factories for the DB-free tests, plus a pool and row
+//! helpers for the database tests, which run only when TEST_DATABASE_URL is
+//! set. Each test binary uses a different subset, hence the `dead_code` allow.
+
+#![allow(dead_code)]
+
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDate, Utc};
+use diesel::prelude::*;
+use uuid::Uuid;
+
+use hotel_management_backend::db::{create_pool, DbPool};
+use hotel_management_backend::models::{
+    Booking, BookingRoomStay, BookingStatus, NewBooking, NewRoom, NewUser, Room, RoomStatus,
+    RoomType, UserRole,
+};
+use hotel_management_backend::schema::{bookings, rooms, users};
+
+// ============================================================================
+// VALUES
+// ============================================================================
+
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(y, m, d).unwrap()
+}
+
+pub fn days_from_now(days: i64) -> NaiveDate {
+    Utc::now().date_naive() + chrono::Duration::days(days)
+}
+
+pub fn money(amount: i64) -> BigDecimal {
+    BigDecimal::from(amount)
+}
+
+/// Decimal from a string, for rates and fractional amounts
+pub fn rate(value: &str) -> BigDecimal {
+    BigDecimal::from_str(value).unwrap()
+}
+
+// ============================================================================
+// IN-MEMORY MODELS
+// ============================================================================
+
+/// Checked-out staff booking for one guest
+pub fn booking(room_id: Uuid, check_in: NaiveDate, check_out: NaiveDate, price: i64) -> Booking {
+    Booking {
+        id: Uuid::new_v4(),
+        reference: "BK-20260501-TEST".to_string(),
+        guest_name: "Test Guest".to_string(),
+        room_id,
+        check_in_date: check_in,
+        check_out_date: check_out,
+        status: BookingStatus::CheckedOut,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        created_by_user_id: None,
+        creation_source: "staff".to_string(),
+        price: money(price),
+        rate_plan_id: None,
+        group_id: None,
+        original_price: None,
+        guest_count: 1,
+        status_changed_at: Utc::now(),
+        unpaid_check_out_by_user_id: None,
+        unpaid_check_out_balance: None,
+        price_before_no_show: None,
+        no_show_undone_at: None,
+    }
+}
+
+pub fn stay(booking: &Booking, room_id: Uuid, start: NaiveDate, end: NaiveDate) -> BookingRoomStay {
+    BookingRoomStay {
+        id: Uuid::new_v4(),
+        booking_id: booking.id,
+        room_id,
+        start_date: start,
+        end_date: end,
+        reason: None,
+        created_at: Utc::now(),
+    }
+}
+
+/// 3-night stay: one night in `old`, then moved to `new` for two nights
+pub fn moved_booking(old: Uuid, new: Uuid) -> (Booking, Vec<BookingRoomStay>) {
+    let b = booking(new, date(2026, 5, 1), date(2026, 5, 4), 3_000_000);
+    let stays = vec![
+        stay(&b, new, date(2026, 5, 2), date(2026, 5, 4)),
+        stay(&b, old, date(2026, 5, 1), date(2026, 5, 2)),
+    ];
+    (b, stays)
+}
+
+/// Available room with the type's default capacity and no other attributes
+pub fn room(room_type: RoomType, price: i64) -> Room {
+    Room {
+        id: Uuid::new_v4(),
+        number: "101".to_string(),
+        room_type,
+        status: RoomStatus::Available,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        price: money(price),
+        assigned_cleaner_id: None,
+        status_changed_at: Utc::now(),
+        floor: Some(1),
+        max_occupancy: room_type.default_max_occupancy(),
+        bed_configuration: None,
+        view: None,
+        amenities: Vec::new(),
+        smoking: false,
+        wheelchair_accessible: false,
+    }
+}
+
+// ============================================================================
+// DATABASE
+// ============================================================================
+
+pub fn test_pool() -> Option<DbPool> {
+    match std::env::var("TEST_DATABASE_URL") {
+        Ok(url) => Some(create_pool(&url)),
+        Err(_) => {
+            eprintln!("TEST_DATABASE_URL not set, skipping database test");
+            None
+        }
+    }
+}
+
+/// Short random suffix for unique room numbers, usernames and references
+pub fn unique_suffix() -> String {
+    Uuid::new_v4().simple().to_string()[..8].to_string()
+}
+
+/// Single room priced 1,000,000 whose number starts with `prefix`
+pub fn create_test_room(pool: &DbPool, prefix: &str) -> Room {
+    let mut conn = pool.get().unwrap();
+    let number = format!("{}{}", prefix, unique_suffix());
+    diesel::insert_into(rooms::table)
+        .values(&NewRoom {
+            number: &number,
+            room_type: RoomType::Single,
+            price: money(1_000_000),
+            floor: None,
+            max_occupancy: 2,
+            bed_configuration: None,
+            view: None,
+            amenities: Vec::new(),
+            smoking: false,
+            wheelchair_accessible: false,
+        })
+        .get_result(&mut conn)
+        .unwrap()
+}
+
+/// Receptionist whose username starts with `prefix`
+pub fn create_test_staff(pool: &DbPool, prefix: &str) -> Uuid {
+    let mut conn = pool.get().unwrap();
+    let username = format!("{}-{}", prefix, unique_suffix());
+    diesel::insert_into(users::table)
+        .values(&NewUser {
+            username: Some(&username),
+            password_hash: "not-a-hash",
+            role: UserRole::Receptionist,
+            email: None,
+            full_name: None,
+            phone: None,
+            id_number: None,
+        })
+        .returning(users::id)
+        .get_result(&mut conn)
+        .unwrap()
+}
+
+/// Upcoming staff booking inserted directly, so the dates may lie in the past
+pub fn insert_test_booking(
+    pool: &DbPool,
+    room_id: Uuid,
+    check_in: NaiveDate,
+    check_out: NaiveDate,
+    price: i64,
+) -> Booking {
+    let mut conn = pool.get().unwrap();
+    let reference = format!("BK-TEST-{}", &unique_suffix()[..6]);
+    diesel::insert_into(bookings::table)
+        .values(&NewBooking {
+            reference: &reference,
+            guest_name: "Test Guest",
+            room_id,
+            check_in_date: check_in,
+            check_out_date: check_out,
+            created_by_user_id: None,
+            creation_source: "staff",
+            price: money(price),
+            rate_plan_id: None,
+            group_id: None,
+            guest_count: 1,
+        })
+        .get_result(&mut conn)
+        .unwrap()
+}
+
+/// Delete a test room and its bookings
+pub fn remove_test_room(pool: &DbPool, room_id: Uuid) {
+    let mut conn = pool.get().unwrap();
+    diesel::delete(bookings::table.filter(bookings::room_id.eq(room_id)))
+        .execute(&mut conn)
+        .unwrap();
+    diesel::delete(rooms::table.find(room_id))
+        .execute(&mut conn)
+        .unwrap();
+}
+
+/// Delete a test user that recorded no payments
+pub fn remove_test_staff(pool: &DbPool, user_id: Uuid) {
+    let mut conn = pool.get().unwrap();
+    diesel::delete(users::table.find(user_id))
+        .execute(&mut conn)
+        .unwrap();
+}
@@ -0,0 +1,117 @@
+//! Unit tests for currency codes, conversion and receipt lines (DB-free)
+
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+use chrono::Utc;
+use uuid::Uuid;
+
+use hotel_management_backend::errors::AppError;
+use hotel_management_backend::models::{Payment, PaymentType};
+use hotel_management_backend::services::exchange_rate_service::{convert_to_base, normalize_currency};
+use hotel_management_backend::services::invoice_service::{describe_payment, format_rate};
+
+fn dec(value: &str) -> BigDecimal {
+    BigDecimal::from_str(value).unwrap()
+}
+
+fn payment(amount: &str, method: &str) -> Payment {
+    let now = Utc::now();
+    Payment {
+        id: Uuid::new_v4(),
+        booking_id: Uuid::new_v4(),
+        amount: dec(amount),
+        payment_type: PaymentType::Full,
+        payment_method: method.to_string(),
+        notes: None,
+        created_by_user_id: Uuid::new_v4(),
+        created_at: now,
+        updated_at: now,
+        voided_at: None,
+        voided_by_user_id: None,
+        void_reason: None,
+        replaces_payment_id: None,
+        shift_id: None,
+        currency: "VND".to_string(),
+        exchange_rate: dec("1"),
+        original_amount: None,
+    }
+}
+
+// ============================================================================
+// CURRENCY CODES
+// ============================================================================
+
+mod currency_code_tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_are_upper_cased() {
+        assert_eq!(normalize_currency(" usd ").unwrap(), "USD");
+        assert_eq!(normalize_currency("EUR").unwrap(), "EUR");
+    }
+
+    #[test]
+    fn test_invalid_codes_are_rejected() {
+        for code in ["", "US", "USDT", "U$D", "12A"] {
+            assert!(
+                matches!(normalize_currency(code), Err(AppError::ValidationError(_))),
+                "{} should be rejected",
+                code
+            );
+        }
+    }
+}
+
+// ============================================================================
+// CONVERSION
+// ============================================================================
+
+mod conversion_tests {
+    use super::*;
+
+    #[test]
+    fn test_converts_at_rate() {
+        assert_eq!(convert_to_base(&dec("120"), &dec("25400")), dec("3048000"));
+    }
+
+    #[test]
+    fn test_rounds_to_whole_base_units() {
+        assert_eq!(convert_to_base(&dec("10.55"), &dec("27350.5")), dec("288548"));
+    }
+
+    #[test]
+    fn test_refunds_stay_negative() {
+        assert_eq!(convert_to_base(&dec("-50"), &dec("25400")), dec("-1270000"));
+    }
+}
+
+// ============================================================================
+// RECEIPT LINES
+// ============================================================================
+
+mod receipt_tests {
+    use super::*;
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(&dec("25400.000000")), "25,400");
+        assert_eq!(format_rate(&dec("27350.5")), "27,350.5");
+        assert_eq!(format_rate(&dec("1.085")), "1.085");
+    }
+
+    #[test]
+    fn test_base_currency_payment() {
+        assert_eq!(describe_payment(&payment("500000", "bank_transfer")), "Payment (bank transfer)");
+    }
+
+    #[test]
+    fn test_foreign_payment_shows_original_currency() {
+        let mut paid = payment("3048000", "card");
+        paid.currency = "USD".to_string();
+        paid.exchange_rate = dec("25400");
+        paid.original_amount = Some(dec("120"));
+
+        assert_eq!(describe_payment(&paid), "Payment (card, USD 120.00 at 25,400)");
+    }
+}
@@ -0,0 +1,117 @@
+//! Unit tests for export cells and CSV output (DB-free)
+
+use bigdecimal::BigDecimal;
+use chrono::{NaiveDate, TimeZone, Utc};
+
+use hotel_management_backend::models::{BookingStatus, RoomType};
+use hotel_management_backend::services::export_service::{
+    csv_rows, formula_safe, ExportCell, ExportFormat,
+};
+
+fn text(s: &str) -> ExportCell {
+    ExportCell::Text(s.to_string())
+}
+
+// ============================================================================
+// CELLS
+// ============================================================================
+
+mod cell_tests {
+    use super::*;
+
+    #[test]
+    fn test_enums_are_spelled_as_in_the_api() {
+        assert_eq!(ExportCell::label(BookingStatus::CheckedOut), text("checked_out"));
+        assert_eq!(ExportCell::label(RoomType::Suite), text("suite"));
+    }
+
+    #[test]
+    fn test_dates_and_timestamps() {
+        assert_eq!(ExportCell::date(NaiveDate::from_ymd_opt(2026, 10, 5).unwrap()), text("2026-10-05"));
+        let at = Utc.with_ymd_and_hms(2026, 10, 5, 14, 30, 0).unwrap();
+        assert_eq!(ExportCell::timestamp(at), text("2026-10-05 14:30:00"));
+    }
+
+    #[test]
+    fn test_missing_text_is_empty() {
+        assert_eq!(ExportCell::text(None::<String>), ExportCell::Empty);
+        assert_eq!(ExportCell::Empty.to_text(), "");
+    }
+
+    #[test]
+    fn test_numbers_keep_their_precision() {
+        let amount: BigDecimal = "1234567.89".parse().unwrap();
+        assert_eq!(ExportCell::Number(amount).to_text(), "1234567.89");
+    }
+
+    #[test]
+    fn test_formats() {
+        assert_eq!(ExportFormat::Csv.extension(), "csv");
+        assert!(ExportFormat::Xlsx.content_type().contains("spreadsheetml"));
+    }
+}
+
+// ============================================================================
+// CSV
+// ============================================================================
+
+mod csv_tests {
+    use super::*;
+
+    #[test]
+    fn test_rows_are_comma_separated_lines() {
+        let rows = vec![
+            vec![text("BK-1"), ExportCell::Integer(2), ExportCell::Number(BigDecimal::from(500_000))],
+            vec![text("BK-2"), ExportCell::Empty, ExportCell::Number(BigDecimal::from(-100))],
+        ];
+
+        let csv = String::from_utf8(csv_rows(&rows).unwrap()).unwrap();
+
+        assert_eq!(csv, "BK-1,2,500000\nBK-2,,-100\n");
+    }
+
+    #[test]
+    fn test_commas_and_quotes_are_escaped() {
+        let rows = vec![vec![text("Pham, Bao-Huy"), text("said \"hi\"")]];
+
+        let csv = String::from_utf8(csv_rows(&rows).unwrap()).unwrap();
+
+        assert_eq!(csv, "\"Pham, Bao-Huy\",\"said \"\"hi\"\"\"\n");
+    }
+
+    #[test]
+    fn test_no_rows_is_empty() {
+        assert!(csv_rows(&[]).unwrap().is_empty());
+    }
+}
+
+// ============================================================================
+// FORMULA INJECTION
+// ============================================================================
+
+mod formula_tests {
+    use super::*;
+
+    #[test]
+    fn test_formula_starts_are_escaped() {
+        for risky in ["=HYPERLINK(\"x\")", "+1", "-2+3", "@SUM(A1)", "\tcmd", "\rcmd"] {
+            assert_eq!(formula_safe(risky), format!("'{}", risky));
+        }
+    }
+
+    #[test]
+    fn test_plain_text_is_unchanged() {
+        assert_eq!(formula_safe("Nguyen Van A"), "Nguyen Van A");
+        assert_eq!(formula_safe("Room 1=2"), "Room 1=2");
+        assert_eq!(formula_safe(""), "");
+    }
+
+    #[test]
+    fn test_csv_escapes_text_but_not_numbers() {
+        let rows = vec![vec![text("=1+1"), ExportCell::Number(BigDecimal::from(-100))]];
+
+        let csv = String::from_utf8(csv_rows(&rows).unwrap()).unwrap();
+
+        assert_eq!(csv, "'=1+1,-100\n");
+    }
+}
@@ -0,0 +1,142 @@
+//! Tests for folio charge amounts and voiding charges
+//!
+//! The voiding tests need a database and run only when TEST_DATABASE_URL is set.
+
+mod common;
+
+use hotel_management_backend::models::ChargeCategory;
+use hotel_management_backend::services::folio_service::charge_amounts;
+
+use common::{money, rate};
+
+// ============================================================================
+// CHARGE AMOUNTS
+// ============================================================================
+
+mod charge_amount_tests {
+    use super::*;
+
+    #[test]
+    fn test_untaxed_charge() {
+        let (tax, total) = charge_amounts(1, &money(50_000), &rate("0"));
+        assert_eq!(tax, money(0));
+        assert_eq!(total, money(50_000));
+    }
+
+    #[test]
+    fn test_quantity_multiplies_unit_price() {
+        let (tax, total) = charge_amounts(3, &money(25_000), &rate("0"));
+        assert_eq!(tax, money(0));
+        assert_eq!(total, money(75_000));
+    }
+
+    #[test]
+    fn test_tax_applies_to_line_net() {
+        let (tax, total) = charge_amounts(2, &money(100_000), &rate("10"));
+        assert_eq!(tax, money(20_000));
+        assert_eq!(total, money(220_000));
+    }
+
+    #[test]
+    fn test_fractional_tax_rounds_to_whole_units() {
+        // 33,333 * 8.5% = 2,833.305
+        let (tax, total) = charge_amounts(1, &money(33_333), &rate("8.5"));
+        assert_eq!(tax, money(2_833));
+        assert_eq!(total, money(36_166));
+    }
+
+    #[test]
+    fn test_free_item_has_no_tax() {
+        let (tax, total) = charge_amounts(4, &money(0), &rate("10"));
+        assert_eq!(tax, money(0));
+        assert_eq!(total, money(0));
+    }
+}
+
+// ============================================================================
+// CATEGORIES
+// ============================================================================
+
+mod category_tests {
+    use super::*;
+
+    #[test]
+    fn test_categories_use_snake_case() {
+        let json = serde_json::to_string(&ChargeCategory::FoodBeverage).unwrap();
+        assert_eq!(json, "\"food_beverage\"");
+
+        let parsed: ChargeCategory = serde_json::from_str("\"late_checkout\"").unwrap();
+        assert_eq!(parsed, ChargeCategory::LateCheckout);
+    }
+}
+
+// ============================================================================
+// VOIDING CHARGES (DATABASE)
+// ============================================================================
+
+mod void_charge_db_tests {
+    use super::*;
+
+    use diesel::prelude::*;
+
+    use hotel_management_backend::db::DbPool;
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::BookingStatus;
+    use hotel_management_backend::schema::bookings;
+    use hotel_management_backend::services::folio_service::CreateFolioChargeRequest;
+    use hotel_management_backend::services::FolioService;
+    use uuid::Uuid;
+
+    use common::{
+        create_test_room, create_test_staff, days_from_now, insert_test_booking, remove_test_room,
+        remove_test_staff, test_pool,
+    };
+
+    fn set_status(pool: &DbPool, booking_id: Uuid, status: BookingStatus) {
+        diesel::update(bookings::table.find(booking_id))
+            .set(bookings::status.eq(status))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+    }
+
+    fn minibar(unit_price: &str) -> CreateFolioChargeRequest {
+        CreateFolioChargeRequest {
+            category: ChargeCategory::Minibar,
+            description: "Water".to_string(),
+            quantity: Some(2),
+            unit_price: rate(unit_price),
+            tax_rate: Some(money(0)),
+        }
+    }
+
+    #[test]
+    fn voided_charge_is_kept_but_no_longer_owed() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "F");
+        let staff = create_test_staff(&pool, "folio");
+        let folio = FolioService::new(pool.clone());
+
+        let booking = insert_test_booking(&pool, room.id, days_from_now(-1), days_from_now(1), 1_000_000);
+        set_status(&pool, booking.id, BookingStatus::CheckedIn);
+        let fractional = folio.add_charge(booking.id, minibar("25000.50"), staff);
+        let charge = folio.add_charge(booking.id, minibar("25000"), staff).unwrap();
+        let without_reason = folio.void_charge(booking.id, charge.charge.id, "  ", staff);
+        let voided = folio.void_charge(booking.id, charge.charge.id, "Posted to the wrong room", staff);
+        let voided_again = folio.void_charge(booking.id, charge.charge.id, "Again", staff);
+        let after_void = folio.get_folio(booking.id).unwrap();
+        set_status(&pool, booking.id, BookingStatus::CheckedOut);
+        let after_check_out = folio.add_charge(booking.id, minibar("25000"), staff);
+        remove_test_room(&pool, room.id);
+        remove_test_staff(&pool, staff);
+
+        assert!(matches!(fractional, Err(AppError::ValidationError(_))));
+        assert!(matches!(without_reason, Err(AppError::ValidationError(_))));
+        let voided = voided.unwrap();
+        assert_eq!(voided.voided_by_user_id, Some(staff));
+        assert_eq!(voided.void_reason.as_deref(), Some("Posted to the wrong room"));
+        assert!(matches!(voided_again, Err(AppError::Conflict(_))));
+        assert_eq!(after_void.charges.len(), 1);
+        assert_eq!(after_void.summary.charges_total, money(0));
+        assert!(matches!(after_check_out, Err(AppError::InvalidStatusTransition(_))));
+    }
+}
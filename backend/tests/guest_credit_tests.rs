@@ -0,0 +1,86 @@
+//! Unit tests for overpayment detection and applying guest credit (DB-free)
+
+mod common;
+
+use hotel_management_backend::errors::AppError;
+use hotel_management_backend::services::guest_credit_service::credit_to_apply;
+use hotel_management_backend::services::payment_service::overpaid_amount;
+
+use common::money;
+
+// ============================================================================
+// OVERPAYMENT
+// ============================================================================
+
+mod overpayment_tests {
+    use super::*;
+
+    #[test]
+    fn test_paid_past_total_is_overpaid() {
+        assert_eq!(overpaid_amount(&money(1_000_000), &money(1_200_000)), money(200_000));
+    }
+
+    #[test]
+    fn test_exact_payment_is_not_overpaid() {
+        assert_eq!(overpaid_amount(&money(1_000_000), &money(1_000_000)), money(0));
+    }
+
+    #[test]
+    fn test_partial_payment_is_not_overpaid() {
+        assert_eq!(overpaid_amount(&money(1_000_000), &money(400_000)), money(0));
+    }
+}
+
+// ============================================================================
+// APPLYING CREDIT
+// ============================================================================
+
+mod apply_credit_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_uses_whole_balance_when_it_fits() {
+        let amount = credit_to_apply(None, &money(200_000), &money(500_000)).unwrap();
+        assert_eq!(amount, money(200_000));
+    }
+
+    #[test]
+    fn test_default_is_capped_at_remaining_balance() {
+        let amount = credit_to_apply(None, &money(800_000), &money(500_000)).unwrap();
+        assert_eq!(amount, money(500_000));
+    }
+
+    #[test]
+    fn test_explicit_amount_within_limits() {
+        let amount = credit_to_apply(Some(&money(100_000)), &money(200_000), &money(500_000)).unwrap();
+        assert_eq!(amount, money(100_000));
+    }
+
+    #[test]
+    fn test_amount_over_balance_is_rejected() {
+        assert!(matches!(
+            credit_to_apply(Some(&money(300_000)), &money(200_000), &money(500_000)),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_amount_over_remaining_balance_is_rejected() {
+        assert!(matches!(
+            credit_to_apply(Some(&money(300_000)), &money(800_000), &money(200_000)),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_non_positive_amount_is_rejected() {
+        assert!(credit_to_apply(Some(&money(0)), &money(200_000), &money(500_000)).is_err());
+        assert!(credit_to_apply(Some(&money(-1)), &money(200_000), &money(500_000)).is_err());
+    }
+
+    #[test]
+    fn test_no_credit_is_rejected() {
+        assert!(credit_to_apply(None, &money(0), &money(500_000)).is_err());
+        assert!(credit_to_apply(None, &money(200_000), &money(0)).is_err());
+    }
+}
@@ -0,0 +1,400 @@
+//! Unit tests for housekeeping task priorities, queue order, SLAs,
+//! auto-assignment and inspections (DB-free)
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use uuid::Uuid;
+
+use hotel_management_backend::models::{
+    HousekeepingPolicy, HousekeepingPriority, HousekeepingTask, HousekeepingTaskStatus,
+    HousekeepingTaskType, HousekeepingTaskView, RoomStatus, RoomType, SlaStatus,
+    HOUSEKEEPING_INSPECTION_CHECKLIST_KEY, HOUSEKEEPING_SLA_CHECKOUT_CLEAN_KEY,
+    HOUSEKEEPING_SLA_TURNDOWN_KEY,
+};
+use hotel_management_backend::services::housekeeping_service::{
+    default_priority, estimated_minutes, plan_assignments, rework_notes, room_floor, sla_status,
+    sort_queue, summarize_sla, validate_inspection, AssignableTask, CleanerLoad,
+};
+
+fn at(hour: u32, minute: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 3, 10, hour, minute, 0).unwrap()
+}
+
+fn task(
+    task_type: HousekeepingTaskType,
+    status: HousekeepingTaskStatus,
+    created_at: DateTime<Utc>,
+    due_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+) -> HousekeepingTask {
+    HousekeepingTask {
+        id: Uuid::new_v4(),
+        room_id: Uuid::new_v4(),
+        booking_id: None,
+        task_type,
+        status,
+        priority: HousekeepingPriority::Normal,
+        assigned_cleaner_id: None,
+        notes: None,
+        due_at,
+        started_at: completed_at.map(|_| created_at),
+        completed_at,
+        created_by_user_id: None,
+        created_at,
+        updated_at: created_at,
+    }
+}
+
+fn view(room: &str, priority: HousekeepingPriority, due_at: DateTime<Utc>, arrival_today: bool) -> HousekeepingTaskView {
+    let mut task = task(
+        HousekeepingTaskType::CheckoutClean,
+        HousekeepingTaskStatus::Pending,
+        at(9, 0),
+        due_at,
+        None,
+    );
+    task.priority = priority;
+    HousekeepingTaskView {
+        task,
+        room_number: room.to_string(),
+        room_status: RoomStatus::Dirty,
+        arrival_today,
+        sla_status: Some(SlaStatus::OnTrack),
+    }
+}
+
+// ============================================================================
+// PRIORITY AND QUEUE ORDER
+// ============================================================================
+
+mod queue_tests {
+    use super::*;
+
+    #[test]
+    fn test_same_day_arrival_is_high_priority() {
+        assert_eq!(
+            default_priority(HousekeepingTaskType::CheckoutClean, true),
+            HousekeepingPriority::High
+        );
+        assert_eq!(
+            default_priority(HousekeepingTaskType::DeepClean, true),
+            HousekeepingPriority::High
+        );
+    }
+
+    #[test]
+    fn test_default_priority_by_type() {
+        assert_eq!(
+            default_priority(HousekeepingTaskType::CheckoutClean, false),
+            HousekeepingPriority::Normal
+        );
+        assert_eq!(
+            default_priority(HousekeepingTaskType::Turndown, false),
+            HousekeepingPriority::Normal
+        );
+        assert_eq!(
+            default_priority(HousekeepingTaskType::DeepClean, false),
+            HousekeepingPriority::Low
+        );
+    }
+
+    #[test]
+    fn test_queue_puts_arrivals_first_then_priority_then_due_time() {
+        let mut queue = vec![
+            view("101", HousekeepingPriority::High, at(12, 0), false),
+            view("102", HousekeepingPriority::Normal, at(10, 0), false),
+            view("103", HousekeepingPriority::Low, at(11, 0), true),
+            view("104", HousekeepingPriority::High, at(11, 0), false),
+        ];
+        sort_queue(&mut queue);
+
+        let rooms: Vec<&str> = queue.iter().map(|t| t.room_number.as_str()).collect();
+        assert_eq!(rooms, vec!["103", "104", "101", "102"]);
+    }
+
+    #[test]
+    fn test_only_full_cleans_move_the_room() {
+        assert!(HousekeepingTaskType::CheckoutClean.cleans_room());
+        assert!(HousekeepingTaskType::DeepClean.cleans_room());
+        assert!(!HousekeepingTaskType::Stayover.cleans_room());
+        assert!(!HousekeepingTaskType::Turndown.cleans_room());
+    }
+}
+
+// ============================================================================
+// SLA TRACKING
+// ============================================================================
+
+mod sla_tests {
+    use super::*;
+
+    #[test]
+    fn test_open_task_on_track_until_due() {
+        let status = sla_status(HousekeepingTaskStatus::InProgress, at(11, 0), None, at(11, 0));
+        assert_eq!(status, Some(SlaStatus::OnTrack));
+    }
+
+    #[test]
+    fn test_open_task_overdue_after_due() {
+        let status = sla_status(HousekeepingTaskStatus::Pending, at(11, 0), None, at(11, 1));
+        assert_eq!(status, Some(SlaStatus::Overdue));
+    }
+
+    #[test]
+    fn test_completed_task_met_or_missed() {
+        let met = sla_status(HousekeepingTaskStatus::Completed, at(11, 0), Some(at(10, 45)), at(15, 0));
+        let missed = sla_status(HousekeepingTaskStatus::Completed, at(11, 0), Some(at(11, 30)), at(15, 0));
+        assert_eq!(met, Some(SlaStatus::Met));
+        assert_eq!(missed, Some(SlaStatus::Missed));
+    }
+
+    #[test]
+    fn test_cancelled_task_has_no_sla() {
+        let status = sla_status(HousekeepingTaskStatus::Cancelled, at(11, 0), None, at(15, 0));
+        assert_eq!(status, None);
+    }
+
+    #[test]
+    fn test_summary_per_type() {
+        let clean = HousekeepingTaskType::CheckoutClean;
+        let tasks = vec![
+            task(clean, HousekeepingTaskStatus::Completed, at(9, 0), at(10, 30), Some(at(10, 0))),
+            task(clean, HousekeepingTaskStatus::Completed, at(9, 0), at(10, 30), Some(at(11, 0))),
+            task(clean, HousekeepingTaskStatus::Pending, at(9, 0), at(10, 30), None),
+            task(clean, HousekeepingTaskStatus::Cancelled, at(9, 0), at(10, 30), None),
+            task(HousekeepingTaskType::Turndown, HousekeepingTaskStatus::InProgress, at(18, 0), at(18, 30), None),
+        ];
+
+        let summary = summarize_sla(&tasks, at(12, 0));
+        assert_eq!(summary.len(), 2);
+
+        let checkout = &summary[0];
+        assert_eq!(checkout.task_type, clean);
+        assert_eq!(checkout.total, 4);
+        assert_eq!(checkout.completed, 2);
+        assert_eq!((checkout.met, checkout.missed), (1, 1));
+        assert_eq!((checkout.open, checkout.overdue), (1, 1));
+        assert_eq!(checkout.compliance_rate, Some(50.0));
+        // 60 and 120 minutes from creation
+        assert_eq!(checkout.average_minutes_to_complete, Some(90.0));
+
+        let turndown = &summary[1];
+        assert_eq!((turndown.open, turndown.overdue), (1, 0));
+        assert_eq!(turndown.compliance_rate, None);
+        assert_eq!(turndown.average_minutes_to_complete, None);
+    }
+
+    #[test]
+    fn test_summary_of_nothing_is_empty() {
+        assert!(summarize_sla(&[], at(12, 0)).is_empty());
+    }
+}
+
+// ============================================================================
+// SLA SETTINGS
+// ============================================================================
+
+mod policy_tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_defaults_for_missing_or_invalid_settings() {
+        let settings = HashMap::from([
+            (HOUSEKEEPING_SLA_CHECKOUT_CLEAN_KEY.to_string(), "120".to_string()),
+            (HOUSEKEEPING_SLA_TURNDOWN_KEY.to_string(), "soon".to_string()),
+        ]);
+        let policy = HousekeepingPolicy::from_settings(&settings);
+        let defaults = HousekeepingPolicy::default();
+
+        assert_eq!(policy.sla_minutes(HousekeepingTaskType::CheckoutClean), 120);
+        assert_eq!(policy.turndown_sla_minutes, defaults.turndown_sla_minutes);
+        assert_eq!(policy.stayover_sla_minutes, defaults.stayover_sla_minutes);
+    }
+
+    #[test]
+    fn test_policy_round_trips_through_settings() {
+        let policy = HousekeepingPolicy {
+            checkout_clean_sla_minutes: 60,
+            stayover_sla_minutes: 20,
+            deep_clean_sla_minutes: 300,
+            turndown_sla_minutes: 15,
+            auto_assign: true,
+            inspection_required: true,
+            inspection_checklist: vec!["Bed made".to_string(), "Towels replaced".to_string()],
+        };
+        let settings: HashMap<String, String> = policy
+            .to_settings()
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+
+        assert_eq!(HousekeepingPolicy::from_settings(&settings), policy);
+    }
+
+    #[test]
+    fn test_checklist_falls_back_to_default_when_not_json() {
+        let settings = HashMap::from([(
+            HOUSEKEEPING_INSPECTION_CHECKLIST_KEY.to_string(),
+            "Bed made, Towels".to_string(),
+        )]);
+        let policy = HousekeepingPolicy::from_settings(&settings);
+
+        assert!(!policy.inspection_required);
+        assert_eq!(policy.inspection_checklist, HousekeepingPolicy::default().inspection_checklist);
+    }
+
+    #[test]
+    fn test_due_time_follows_type_sla() {
+        let policy = HousekeepingPolicy::default();
+        let created = at(9, 0);
+        let due = created + Duration::minutes(policy.sla_minutes(HousekeepingTaskType::DeepClean));
+        assert_eq!(due, at(13, 0));
+    }
+}
+
+// ============================================================================
+// AUTO-ASSIGNMENT
+// ============================================================================
+
+mod auto_assign_tests {
+    use super::*;
+
+    fn waiting(floor: i32, minutes: i64) -> AssignableTask {
+        AssignableTask {
+            task_id: Uuid::new_v4(),
+            floor: Some(floor),
+            minutes,
+        }
+    }
+
+    fn cleaner(minutes: i64, floors: Vec<i32>) -> CleanerLoad {
+        CleanerLoad {
+            cleaner_id: Uuid::new_v4(),
+            minutes,
+            floors,
+        }
+    }
+
+    #[test]
+    fn test_room_floor_from_number() {
+        assert_eq!(room_floor("101"), Some(1));
+        assert_eq!(room_floor("1205"), Some(12));
+        assert_eq!(room_floor("305A"), Some(3));
+        assert_eq!(room_floor("12"), None);
+        assert_eq!(room_floor("PH1"), None);
+    }
+
+    #[test]
+    fn test_estimated_minutes_by_type_and_room() {
+        assert_eq!(estimated_minutes(HousekeepingTaskType::CheckoutClean, RoomType::Single), 30);
+        assert_eq!(estimated_minutes(HousekeepingTaskType::CheckoutClean, RoomType::Suite), 75);
+        assert_eq!(estimated_minutes(HousekeepingTaskType::DeepClean, RoomType::Double), 135);
+        assert_eq!(estimated_minutes(HousekeepingTaskType::Stayover, RoomType::Double), 22);
+        assert_eq!(estimated_minutes(HousekeepingTaskType::Turndown, RoomType::Suite), 10);
+    }
+
+    #[test]
+    fn test_plan_balances_minutes() {
+        let tasks = vec![waiting(1, 75), waiting(1, 30), waiting(1, 30)];
+        let mut cleaners = vec![cleaner(0, vec![]), cleaner(0, vec![])];
+        let plan = plan_assignments(&tasks, &mut cleaners);
+
+        let (a, b) = (cleaners[0].cleaner_id, cleaners[1].cleaner_id);
+        assert_eq!(
+            plan,
+            vec![(tasks[0].task_id, a), (tasks[1].task_id, b), (tasks[2].task_id, b)]
+        );
+        assert_eq!((cleaners[0].minutes, cleaners[1].minutes), (75, 60));
+    }
+
+    #[test]
+    fn test_plan_counts_existing_work() {
+        let tasks = vec![waiting(2, 30)];
+        let mut cleaners = vec![cleaner(90, vec![2]), cleaner(20, vec![2])];
+        let plan = plan_assignments(&tasks, &mut cleaners);
+
+        assert_eq!(plan, vec![(tasks[0].task_id, cleaners[1].cleaner_id)]);
+        assert_eq!(cleaners[1].minutes, 50);
+    }
+
+    #[test]
+    fn test_plan_prefers_cleaner_on_same_floor() {
+        // Equal loads: the cleaner already on floor 3 avoids the floor change
+        let tasks = vec![waiting(3, 30)];
+        let mut cleaners = vec![cleaner(45, vec![2]), cleaner(45, vec![3])];
+        let plan = plan_assignments(&tasks, &mut cleaners);
+        assert_eq!(plan, vec![(tasks[0].task_id, cleaners[1].cleaner_id)]);
+
+        // A much lighter load still wins over staying on the floor
+        let mut cleaners = vec![cleaner(0, vec![2]), cleaner(45, vec![3])];
+        let plan = plan_assignments(&tasks, &mut cleaners);
+        assert_eq!(plan, vec![(tasks[0].task_id, cleaners[0].cleaner_id)]);
+        assert_eq!(cleaners[0].floors, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_plan_without_cleaners_is_empty() {
+        assert!(plan_assignments(&[waiting(1, 30)], &mut []).is_empty());
+    }
+}
+
+// ============================================================================
+// INSPECTIONS
+// ============================================================================
+
+mod inspection_tests {
+    use super::*;
+
+    fn checklist() -> Vec<String> {
+        vec!["Bed made".to_string(), "Bathroom cleaned".to_string(), "Bins emptied".to_string()]
+    }
+
+    #[test]
+    fn test_pass_without_failed_items() {
+        let failed = validate_inspection(&checklist(), true, &[], None).unwrap();
+        assert!(failed.is_empty());
+    }
+
+    #[test]
+    fn test_pass_with_failed_items_is_rejected() {
+        let result = validate_inspection(&checklist(), true, &["Bed made".to_string()], None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_fail_needs_items_or_notes() {
+        assert!(validate_inspection(&checklist(), false, &[], None).is_err());
+        assert!(validate_inspection(&checklist(), false, &[], Some("  ")).is_err());
+        assert!(validate_inspection(&checklist(), false, &[], Some("Stain on carpet")).is_ok());
+    }
+
+    #[test]
+    fn test_failed_items_must_be_on_checklist() {
+        let result = validate_inspection(&checklist(), false, &["Minibar stocked".to_string()], None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_failed_items_take_checklist_wording_once() {
+        let failed = validate_inspection(
+            &checklist(),
+            false,
+            &["bins emptied ".to_string(), "Bed made".to_string(), "BED MADE".to_string()],
+            None,
+        )
+        .unwrap();
+        assert_eq!(failed, vec!["Bins emptied".to_string(), "Bed made".to_string()]);
+    }
+
+    #[test]
+    fn test_rework_notes_list_items_then_notes() {
+        let items = vec!["Bed made".to_string(), "Bins emptied".to_string()];
+        assert_eq!(
+            rework_notes(&items, Some("Hair on pillow ")),
+            "Failed inspection: Bed made, Bins emptied. Hair on pillow"
+        );
+        assert_eq!(rework_notes(&items, None), "Failed inspection: Bed made, Bins emptied");
+        assert_eq!(rework_notes(&[], Some("Smells of smoke")), "Failed inspection. Smells of smoke");
+    }
+}
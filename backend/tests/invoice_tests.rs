@@ -0,0 +1,164 @@
+//! Unit tests for invoice numbering and rendering (DB-free)
+
+mod common;
+
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+
+use hotel_management_backend::models::TaxLine;
+use hotel_management_backend::services::invoice_service::{
+    escape_html, format_money, invoice_number, pdf_text, render_invoice_html, render_invoice_pdf,
+    InvoiceDocument, InvoiceLine, InvoicePayment,
+};
+
+use common::{date, money};
+
+fn sample_document(total_paid: i64) -> InvoiceDocument {
+    InvoiceDocument {
+        invoice_number: invoice_number(2025, 7),
+        issued_on: date(2025, 12, 18),
+        booking_reference: "BK-20251215-ABCD".to_string(),
+        guest_name: "Nguyễn Văn <Đức>".to_string(),
+        room_number: "301".to_string(),
+        room_type: "Suite".to_string(),
+        check_in_date: date(2025, 12, 15),
+        check_out_date: date(2025, 12, 18),
+        lines: vec![
+            InvoiceLine {
+                description: "Room 301 (Suite), 3 nights".to_string(),
+                quantity: 1,
+                unit_price: money(7_500_000),
+                amount: money(7_500_000),
+            },
+            InvoiceLine {
+                description: "Minibar".to_string(),
+                quantity: 2,
+                unit_price: money(50_000),
+                amount: money(100_000),
+            },
+        ],
+        subtotal: money(7_600_000),
+        taxes: vec![TaxLine {
+            tax_rule_id: None,
+            name: "VAT".to_string(),
+            rate: BigDecimal::from_str("10.00").unwrap(),
+            is_inclusive: false,
+            amount: money(760_000),
+        }],
+        net_total: money(7_600_000),
+        tax_total: money(760_000),
+        total_amount: money(8_360_000),
+        payments: vec![InvoicePayment {
+            date: date(2025, 12, 15),
+            description: "Deposit (cash)".to_string(),
+            amount: money(total_paid),
+        }],
+        total_paid: money(total_paid),
+        balance_due: money(8_360_000 - total_paid),
+    }
+}
+
+// ============================================================================
+// NUMBERING AND FORMATTING
+// ============================================================================
+
+mod formatting_tests {
+    use super::*;
+
+    #[test]
+    fn test_invoice_number_is_zero_padded_per_year() {
+        assert_eq!(invoice_number(2025, 1), "INV-2025-000001");
+        assert_eq!(invoice_number(2026, 123_456), "INV-2026-123456");
+    }
+
+    #[test]
+    fn test_format_money_groups_thousands() {
+        assert_eq!(format_money(&money(0)), "0");
+        assert_eq!(format_money(&money(950)), "950");
+        assert_eq!(format_money(&money(1_250_000)), "1,250,000");
+        assert_eq!(format_money(&money(-500_000)), "-500,000");
+    }
+
+    #[test]
+    fn test_format_money_rounds_to_whole_dong() {
+        let amount = BigDecimal::from_str("1234.60").unwrap();
+        assert_eq!(format_money(&amount), "1,235");
+    }
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<b>Tom & \"Jerry\"</b>"),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn test_pdf_text_folds_vietnamese_diacritics() {
+        assert_eq!(pdf_text("Nguyễn Văn Đức"), b"Nguyen Van Duc".to_vec());
+    }
+
+    #[test]
+    fn test_pdf_text_keeps_latin1_and_replaces_others() {
+        assert_eq!(pdf_text("Café"), vec![b'C', b'a', b'f', 0xE9]);
+        assert_eq!(pdf_text("東京"), b"??".to_vec());
+    }
+}
+
+// ============================================================================
+// RENDERING
+// ============================================================================
+
+mod rendering_tests {
+    use super::*;
+
+    #[test]
+    fn test_html_contains_number_lines_and_totals() {
+        let html = render_invoice_html(&sample_document(2_000_000));
+
+        assert!(html.contains("INV-2025-000007"));
+        assert!(html.contains("BK-20251215-ABCD"));
+        assert!(html.contains("Minibar"));
+        assert!(html.contains("VAT 10%"));
+        assert!(html.contains("8,360,000"));
+        assert!(html.contains("6,360,000"));
+        assert!(!html.contains("Paid in full"));
+    }
+
+    #[test]
+    fn test_html_escapes_guest_name() {
+        let html = render_invoice_html(&sample_document(0));
+        assert!(html.contains("Nguyễn Văn &lt;Đức&gt;"));
+        assert!(!html.contains("<Đức>"));
+    }
+
+    #[test]
+    fn test_html_marks_settled_invoice() {
+        let html = render_invoice_html(&sample_document(8_360_000));
+        assert!(html.contains("Paid in full"));
+    }
+
+    #[test]
+    fn test_pdf_is_a_pdf_document() {
+        let pdf = render_invoice_pdf(&sample_document(2_000_000));
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert!(pdf.windows(9).any(|w| w == b"Helvetica"));
+    }
+
+    #[test]
+    fn test_pdf_breaks_long_invoices_into_pages() {
+        let mut doc = sample_document(0);
+        let line = doc.lines[1].clone();
+        doc.lines = vec![line; 120];
+
+        let pdf = String::from_utf8_lossy(&render_invoice_pdf(&doc)).to_string();
+        let count: u32 = pdf
+            .split("/Count ")
+            .nth(1)
+            .and_then(|rest| rest.split(|c: char| !c.is_ascii_digit()).next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        assert!(count > 1);
+    }
+}
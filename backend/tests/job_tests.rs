@@ -0,0 +1,101 @@
+//! Unit tests for background lifecycle jobs (DB-free)
+//!
+//! Covers the cutoff calculations used by the no-show and dirty room jobs
+//! and the job configuration defaults.
+
+mod common;
+
+use chrono::{TimeZone, Utc};
+
+use hotel_management_backend::config::{JobsConfig, NoShowAction};
+use hotel_management_backend::services::job_service::{dirty_room_cutoff, no_show_cutoff};
+
+use common::date;
+
+// ============================================================================
+// NO-SHOW CUTOFF
+// ============================================================================
+
+mod no_show_cutoff_tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_grace_flags_bookings_from_yesterday() {
+        // check_in_date < cutoff => no-show
+        let cutoff = no_show_cutoff(date(2026, 3, 10), 0);
+
+        assert_eq!(cutoff, date(2026, 3, 10));
+        assert!(date(2026, 3, 9) < cutoff, "Yesterday's arrival is a no-show");
+        assert!(date(2026, 3, 10) >= cutoff, "Today's arrival can still check in");
+    }
+
+    #[test]
+    fn test_grace_days_push_the_cutoff_back() {
+        let cutoff = no_show_cutoff(date(2026, 3, 10), 2);
+
+        assert_eq!(cutoff, date(2026, 3, 8));
+    }
+
+    #[test]
+    fn test_negative_grace_is_treated_as_zero() {
+        assert_eq!(no_show_cutoff(date(2026, 3, 10), -3), date(2026, 3, 10));
+    }
+}
+
+// ============================================================================
+// DIRTY ROOM CUTOFF
+// ============================================================================
+
+mod dirty_room_cutoff_tests {
+    use super::*;
+
+    #[test]
+    fn test_cutoff_is_threshold_hours_before_now() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            dirty_room_cutoff(now, 6),
+            Utc.with_ymd_and_hms(2026, 3, 10, 6, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_threshold_is_at_least_one_hour() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+
+        assert_eq!(
+            dirty_room_cutoff(now, 0),
+            Utc.with_ymd_and_hms(2026, 3, 10, 11, 0, 0).unwrap()
+        );
+    }
+}
+
+// ============================================================================
+// JOB CONFIGURATION
+// ============================================================================
+
+mod job_config_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_show_action_parses_case_insensitively() {
+        assert_eq!("report".parse::<NoShowAction>(), Ok(NoShowAction::Report));
+        assert_eq!(" Mark ".parse::<NoShowAction>(), Ok(NoShowAction::Mark));
+        assert!("delete".parse::<NoShowAction>().is_err());
+    }
+
+    #[test]
+    fn test_defaults_enable_all_jobs_and_mark_no_shows() {
+        let config = JobsConfig::default();
+
+        assert!(config.enabled);
+        assert!(config.overstay.enabled);
+        assert!(config.no_show.schedule.enabled);
+        assert!(config.dirty_room_sweep.schedule.enabled);
+        assert_eq!(
+            config.no_show.action,
+            NoShowAction::Mark,
+            "Overdue arrivals are marked so they stop blocking the room"
+        );
+    }
+}
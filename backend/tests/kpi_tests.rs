@@ -0,0 +1,170 @@
+//! Unit tests for hotel KPIs, report periods and the pickup forecast (DB-free)
+
+mod common;
+
+use hotel_management_backend::models::KpiPeriod;
+use hotel_management_backend::services::kpi_service::{
+    compute_kpis, forecast_figures, period_start, pickup_forecast, report_periods, KpiTotals,
+};
+
+use common::{date, money};
+
+// ============================================================================
+// KPI FORMULAS
+// ============================================================================
+
+mod kpi_tests {
+    use super::*;
+
+    fn totals() -> KpiTotals {
+        KpiTotals {
+            room_nights_sold: 15,
+            room_revenue: money(15_000_000),
+            bookings: 6,
+            cancelled: 1,
+            arrivals: 4,
+            stay_nights: 10,
+            lead_days: 30,
+        }
+    }
+
+    #[test]
+    fn test_adr_is_revenue_per_night_sold() {
+        let kpis = compute_kpis(&totals(), 30);
+        assert_eq!(kpis.adr, Some(money(1_000_000)));
+    }
+
+    #[test]
+    fn test_revpar_is_revenue_per_available_night() {
+        let kpis = compute_kpis(&totals(), 30);
+        assert_eq!(kpis.revpar, Some(money(500_000)));
+    }
+
+    #[test]
+    fn test_occupancy_is_nights_sold_over_available() {
+        let kpis = compute_kpis(&totals(), 30);
+        assert_eq!(kpis.occupancy_rate, 50.0);
+    }
+
+    #[test]
+    fn test_stay_length_and_lead_time_are_per_arrival() {
+        let kpis = compute_kpis(&totals(), 30);
+        assert_eq!(kpis.average_length_of_stay, Some(2.5));
+        assert_eq!(kpis.average_lead_time_days, Some(7.5));
+    }
+
+    #[test]
+    fn test_cancellation_rate_is_over_all_bookings() {
+        let kpis = compute_kpis(&totals(), 30);
+        assert!((kpis.cancellation_rate - 100.0 / 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_empty_range_has_no_averages() {
+        let kpis = compute_kpis(&KpiTotals::default(), 0);
+        assert_eq!(kpis.adr, None);
+        assert_eq!(kpis.revpar, None);
+        assert_eq!(kpis.average_length_of_stay, None);
+        assert_eq!(kpis.occupancy_rate, 0.0);
+        assert_eq!(kpis.cancellation_rate, 0.0);
+    }
+}
+
+// ============================================================================
+// PERIODS
+// ============================================================================
+
+mod period_tests {
+    use super::*;
+
+    #[test]
+    fn test_week_starts_on_monday() {
+        // 2026-10-15 is a Thursday
+        assert_eq!(period_start(date(2026, 10, 15), KpiPeriod::Week), date(2026, 10, 12));
+        assert_eq!(period_start(date(2026, 10, 12), KpiPeriod::Week), date(2026, 10, 12));
+    }
+
+    #[test]
+    fn test_month_starts_on_the_first() {
+        assert_eq!(period_start(date(2026, 10, 15), KpiPeriod::Month), date(2026, 10, 1));
+    }
+
+    #[test]
+    fn test_daily_periods() {
+        let periods = report_periods(date(2026, 10, 1), date(2026, 10, 3), KpiPeriod::Day);
+        assert_eq!(periods.len(), 3);
+        assert_eq!(periods[2], (date(2026, 10, 3), date(2026, 10, 3)));
+    }
+
+    #[test]
+    fn test_weekly_periods_are_clipped_to_the_range() {
+        let periods = report_periods(date(2026, 10, 15), date(2026, 10, 27), KpiPeriod::Week);
+        assert_eq!(
+            periods,
+            vec![
+                (date(2026, 10, 15), date(2026, 10, 18)),
+                (date(2026, 10, 19), date(2026, 10, 25)),
+                (date(2026, 10, 26), date(2026, 10, 27)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_periods_cross_the_year() {
+        let periods = report_periods(date(2026, 12, 10), date(2027, 2, 5), KpiPeriod::Month);
+        assert_eq!(
+            periods,
+            vec![
+                (date(2026, 12, 10), date(2026, 12, 31)),
+                (date(2027, 1, 1), date(2027, 1, 31)),
+                (date(2027, 2, 1), date(2027, 2, 5)),
+            ]
+        );
+    }
+}
+
+// ============================================================================
+// PICKUP FORECAST
+// ============================================================================
+
+mod forecast_tests {
+    use super::*;
+
+    #[test]
+    fn test_forecast_adds_last_year_pickup() {
+        let forecast = pickup_forecast((4, &money(4_000_000)), (3, &money(2_700_000)), 10);
+        assert_eq!(forecast, (7, money(6_700_000)));
+    }
+
+    #[test]
+    fn test_forecast_without_pickup_is_what_is_on_the_books() {
+        let forecast = pickup_forecast((4, &money(4_000_000)), (0, &money(0)), 10);
+        assert_eq!(forecast, (4, money(4_000_000)));
+    }
+
+    #[test]
+    fn test_forecast_caps_nights_at_room_count() {
+        // Only 2 of the 4 picked up nights fit, so half the pickup revenue is kept
+        let forecast = pickup_forecast((8, &money(8_000_000)), (4, &money(3_000_000)), 10);
+        assert_eq!(forecast, (10, money(9_500_000)));
+    }
+
+    #[test]
+    fn test_forecast_when_already_full() {
+        let forecast = pickup_forecast((10, &money(10_000_000)), (2, &money(2_000_000)), 10);
+        assert_eq!(forecast, (10, money(10_000_000)));
+    }
+
+    #[test]
+    fn test_figures_occupancy_over_range() {
+        let figures = forecast_figures(15, money(15_000_000), 10, 3);
+        assert_eq!(figures.room_nights, 15);
+        assert!((figures.occupancy_rate - 50.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_figures_without_rooms() {
+        let figures = forecast_figures(0, money(0), 0, 30);
+        assert_eq!(figures.occupancy_rate, 0.0);
+    }
+}
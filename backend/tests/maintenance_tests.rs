@@ -0,0 +1,319 @@
+//! Tests for maintenance work orders and the dates they block. The database
+//! tests run only when TEST_DATABASE_URL is set.
+
+mod common;
+
+use chrono::{NaiveDate, Utc};
+use uuid::Uuid;
+
+use hotel_management_backend::models::{
+    MaintenanceOrder, MaintenancePriority, MaintenanceStatus,
+};
+use hotel_management_backend::services::maintenance_service::{validate_order_dates, MAX_ORDER_DAYS};
+
+use common::days_from_now;
+
+fn date(day: u32) -> NaiveDate {
+    common::date(2026, 6, day)
+}
+
+fn order(status: MaintenanceStatus, start_date: NaiveDate, end_date: NaiveDate) -> MaintenanceOrder {
+    let now = Utc::now();
+    MaintenanceOrder {
+        id: Uuid::new_v4(),
+        room_id: Uuid::new_v4(),
+        description: "Leaking shower".to_string(),
+        status,
+        priority: MaintenancePriority::Normal,
+        start_date,
+        end_date,
+        assigned_to_user_id: None,
+        resolution: None,
+        started_at: None,
+        closed_at: None,
+        created_by_user_id: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+// ============================================================================
+// BLOCKED DATES
+// ============================================================================
+
+mod blocked_dates_tests {
+    use super::*;
+
+    #[test]
+    fn test_order_blocks_the_nights_it_covers() {
+        // Out of order on the 10th, 11th and 12th
+        let order = order(MaintenanceStatus::Open, date(10), date(12));
+
+        assert!(order.blocks(date(8), date(11)), "Stay over the first day");
+        assert!(order.blocks(date(12), date(14)), "Stay starting on the last day");
+        assert!(order.blocks(date(11), date(12)), "One night inside");
+        assert!(order.blocks(date(5), date(20)), "Stay around the whole order");
+    }
+
+    #[test]
+    fn test_order_leaves_surrounding_dates_free() {
+        let order = order(MaintenanceStatus::InProgress, date(10), date(12));
+
+        assert!(!order.blocks(date(7), date(10)), "Check-out on the first day");
+        assert!(!order.blocks(date(13), date(15)), "Check-in the day after");
+    }
+
+    #[test]
+    fn test_finished_orders_block_nothing() {
+        assert!(!order(MaintenanceStatus::Closed, date(10), date(12)).blocks(date(10), date(12)));
+        assert!(!order(MaintenanceStatus::Cancelled, date(10), date(12)).blocks(date(10), date(12)));
+    }
+
+    #[test]
+    fn test_open_statuses() {
+        assert!(MaintenanceStatus::Open.is_open());
+        assert!(MaintenanceStatus::InProgress.is_open());
+        assert!(!MaintenanceStatus::Closed.is_open());
+        assert!(!MaintenanceStatus::Cancelled.is_open());
+    }
+
+    #[test]
+    fn test_priority_order_and_serialization() {
+        assert!(MaintenancePriority::Urgent > MaintenancePriority::High);
+        assert!(MaintenancePriority::Low < MaintenancePriority::Normal);
+        assert_eq!(
+            serde_json::to_string(&MaintenanceStatus::InProgress).unwrap(),
+            "\"in_progress\""
+        );
+    }
+}
+
+// ============================================================================
+// DATE VALIDATION
+// ============================================================================
+
+mod date_validation_tests {
+    use super::*;
+
+    #[test]
+    fn test_single_day_order_is_valid() {
+        assert!(validate_order_dates(date(10), date(10), date(1)).is_ok());
+    }
+
+    #[test]
+    fn test_end_before_start_is_rejected() {
+        assert!(validate_order_dates(date(12), date(10), date(1)).is_err());
+    }
+
+    #[test]
+    fn test_order_in_the_past_is_rejected() {
+        assert!(validate_order_dates(date(1), date(5), date(6)).is_err());
+        // Started earlier but still running
+        assert!(validate_order_dates(date(1), date(6), date(6)).is_ok());
+    }
+
+    #[test]
+    fn test_order_length_is_capped() {
+        let start = date(1);
+        let last_allowed = start + chrono::Duration::days(MAX_ORDER_DAYS - 1);
+        assert!(validate_order_dates(start, last_allowed, start).is_ok());
+        assert!(validate_order_dates(start, last_allowed + chrono::Duration::days(1), start).is_err());
+    }
+}
+
+// ============================================================================
+// AVAILABILITY AND ROOM STATUS (DATABASE)
+// ============================================================================
+
+mod maintenance_db_tests {
+    use super::*;
+
+    use diesel::prelude::*;
+
+    use hotel_management_backend::db::DbPool;
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::{BookingStatus, RoomStatus};
+    use hotel_management_backend::schema::{bookings, maintenance_orders, rooms};
+    use hotel_management_backend::services::booking_service::{CreateGroupBookingRequest, GroupRoomRequest};
+    use hotel_management_backend::services::maintenance_service::CreateMaintenanceOrder;
+    use hotel_management_backend::services::{BookingService, MaintenanceService};
+
+    use crate::common::{
+        create_test_room, create_test_staff, insert_test_booking, remove_test_room,
+        remove_test_staff, test_pool,
+    };
+
+    fn clean_up(pool: &DbPool, room_id: Uuid, user_id: Uuid) {
+        let mut conn = pool.get().unwrap();
+        diesel::delete(maintenance_orders::table.filter(maintenance_orders::room_id.eq(room_id)))
+            .execute(&mut conn)
+            .unwrap();
+        remove_test_room(pool, room_id);
+        remove_test_staff(pool, user_id);
+    }
+
+    fn room_status(pool: &DbPool, room_id: Uuid) -> RoomStatus {
+        let mut conn = pool.get().unwrap();
+        rooms::table
+            .find(room_id)
+            .select(rooms::status)
+            .first(&mut conn)
+            .unwrap()
+    }
+
+    fn new_order(room_id: Uuid, start_date: NaiveDate, end_date: NaiveDate) -> CreateMaintenanceOrder {
+        CreateMaintenanceOrder {
+            room_id,
+            description: "Replace air conditioner".to_string(),
+            priority: Some(MaintenancePriority::High),
+            start_date,
+            end_date,
+            assigned_to_user_id: None,
+        }
+    }
+
+    #[test]
+    fn work_order_blocks_only_its_dates() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "M");
+        let staff = create_test_staff(&pool, "maint");
+        let maintenance = MaintenanceService::new(pool.clone());
+        let bookings = BookingService::new(pool.clone());
+
+        let created = maintenance.create_order(new_order(room.id, days_from_now(420), days_from_now(422)), staff);
+        let before = bookings.check_availability(room.id, days_from_now(418), days_from_now(420), None);
+        let during = bookings.check_availability(room.id, days_from_now(421), days_from_now(424), None);
+        let after = bookings.check_availability(room.id, days_from_now(423), days_from_now(425), None);
+        let booked = bookings.create_booking("Blocked Guest", room.id, days_from_now(422), days_from_now(423), 1, None);
+        clean_up(&pool, room.id, staff);
+
+        assert!(created.is_ok(), "Order should be created: {:?}", created);
+        assert!(before.unwrap(), "Check-out on the first out-of-order day is fine");
+        assert!(!during.unwrap(), "Nights inside the order are blocked");
+        assert!(after.unwrap(), "The day after the order is free");
+        assert!(
+            matches!(booked, Err(AppError::RoomUnavailable(_))),
+            "Booking inside the order should be RoomUnavailable, got {:?}",
+            booked
+        );
+    }
+
+    #[test]
+    fn work_order_cannot_cover_existing_bookings() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "M");
+        let staff = create_test_staff(&pool, "maint");
+        let maintenance = MaintenanceService::new(pool.clone());
+        let bookings = BookingService::new(pool.clone());
+
+        let booking = bookings.create_booking("Booked Guest", room.id, days_from_now(430), days_from_now(432), 1, None);
+        let overlapping = maintenance.create_order(new_order(room.id, days_from_now(431), days_from_now(433)), staff);
+        let after_checkout = maintenance.create_order(new_order(room.id, days_from_now(432), days_from_now(433)), staff);
+        clean_up(&pool, room.id, staff);
+
+        assert!(booking.is_ok());
+        assert!(
+            matches!(overlapping, Err(AppError::Conflict(_))),
+            "Order over a booking should conflict, got {:?}",
+            overlapping
+        );
+        assert!(after_checkout.is_ok(), "Work can start on the check-out day");
+    }
+
+    #[test]
+    fn stay_cannot_be_extended_into_a_work_order() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "M");
+        let staff = create_test_staff(&pool, "maint");
+        let maintenance = MaintenanceService::new(pool.clone());
+        let bookings = BookingService::new(pool.clone());
+
+        let booking = insert_test_booking(&pool, room.id, days_from_now(-1), days_from_now(1), 1_000_000);
+        diesel::update(bookings::table.find(booking.id))
+            .set(bookings::status.eq(BookingStatus::CheckedIn))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+        let order = maintenance.create_order(new_order(room.id, days_from_now(2), days_from_now(3)), staff);
+        let into_order = bookings.extend_stay(booking.id, days_from_now(3), staff, None);
+        let up_to_order = bookings.extend_stay(booking.id, days_from_now(2), staff, None);
+        clean_up(&pool, room.id, staff);
+
+        assert!(order.is_ok(), "Order should be created: {:?}", order);
+        assert!(
+            matches!(into_order, Err(AppError::RoomUnavailable(_))),
+            "Extension into the order should be RoomUnavailable, got {:?}",
+            into_order
+        );
+        assert_eq!(up_to_order.unwrap().check_out_date, days_from_now(2));
+    }
+
+    #[test]
+    fn guest_and_group_bookings_cannot_take_work_order_dates() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "M");
+        let staff = create_test_staff(&pool, "maint");
+        let maintenance = MaintenanceService::new(pool.clone());
+        let bookings = BookingService::new(pool.clone());
+
+        let order = maintenance.create_order(new_order(room.id, days_from_now(440), days_from_now(441)), staff);
+        let guest_booking =
+            bookings.create_guest_booking(staff, "Guest", room.id, days_from_now(439), days_from_now(441), 1);
+        let group_booking = bookings.create_group_booking(
+            &CreateGroupBookingRequest {
+                name: "Conference".to_string(),
+                contact_name: "Organiser".to_string(),
+                notes: None,
+                check_in_date: days_from_now(441),
+                check_out_date: days_from_now(443),
+                rooms: vec![GroupRoomRequest {
+                    room_id: room.id,
+                    guest_name: None,
+                    check_in_date: None,
+                    check_out_date: None,
+                    price: None,
+                    guest_count: None,
+                }],
+            },
+            Some(staff),
+        );
+        clean_up(&pool, room.id, staff);
+
+        assert!(order.is_ok(), "Order should be created: {:?}", order);
+        assert!(
+            matches!(guest_booking, Err(AppError::RoomUnavailable(_))),
+            "Guest booking inside the order should be RoomUnavailable, got {:?}",
+            guest_booking
+        );
+        assert!(
+            matches!(group_booking, Err(AppError::RoomUnavailable(_))),
+            "Group booking inside the order should be RoomUnavailable, got {:?}",
+            group_booking
+        );
+    }
+
+    #[test]
+    fn closing_the_order_makes_the_room_available() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "M");
+        let staff = create_test_staff(&pool, "maint");
+        let maintenance = MaintenanceService::new(pool.clone());
+
+        let order = maintenance
+            .create_order(new_order(room.id, days_from_now(0), days_from_now(2)), staff)
+            .unwrap();
+        let started = maintenance.start_order(order.id);
+        let status_during = room_status(&pool, room.id);
+        let close_without_resolution = maintenance.close_order(order.id, "  ".to_string());
+        let closed = maintenance.close_order(order.id, "Compressor replaced".to_string());
+        let status_after = room_status(&pool, room.id);
+        clean_up(&pool, room.id, staff);
+
+        assert!(started.is_ok(), "Order should start: {:?}", started);
+        assert_eq!(status_during, RoomStatus::Maintenance);
+        assert!(matches!(close_without_resolution, Err(AppError::ValidationError(_))));
+        let closed = closed.unwrap();
+        assert_eq!(closed.status, MaintenanceStatus::Closed);
+        assert_eq!(closed.resolution.as_deref(), Some("Compressor replaced"));
+        assert_eq!(status_after, RoomStatus::Available);
+    }
+}
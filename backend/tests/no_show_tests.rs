@@ -0,0 +1,200 @@
+//! Tests for the no-show status and policy. The database tests run only when
+//! TEST_DATABASE_URL is set.
+
+mod common;
+
+use std::collections::HashMap;
+
+use hotel_management_backend::models::{BookingStatus, NoShowFeeType, NoShowPolicy};
+
+use common::money;
+
+fn policy(fee_type: NoShowFeeType) -> NoShowPolicy {
+    NoShowPolicy {
+        fee_enabled: true,
+        fee_type,
+        fee_amount: money(300_000),
+        ..NoShowPolicy::default()
+    }
+}
+
+// ============================================================================
+// STATUS TRANSITIONS
+// ============================================================================
+
+mod no_show_status_tests {
+    use super::*;
+
+    #[test]
+    fn test_upcoming_can_become_no_show_and_back() {
+        assert!(BookingStatus::Upcoming.can_transition_to(BookingStatus::NoShow));
+        assert!(BookingStatus::NoShow.can_transition_to(BookingStatus::Upcoming));
+    }
+
+    #[test]
+    fn test_in_house_and_finished_bookings_cannot_be_no_shows() {
+        assert!(!BookingStatus::CheckedIn.can_transition_to(BookingStatus::NoShow));
+        assert!(!BookingStatus::CheckedOut.can_transition_to(BookingStatus::NoShow));
+        assert!(!BookingStatus::Cancelled.can_transition_to(BookingStatus::NoShow));
+    }
+
+    #[test]
+    fn test_no_show_cannot_check_in_directly() {
+        assert!(!BookingStatus::NoShow.can_transition_to(BookingStatus::CheckedIn));
+    }
+
+    #[test]
+    fn test_no_show_releases_the_room() {
+        assert!(!BookingStatus::NoShow.blocks_availability());
+        assert!(!BookingStatus::NoShow.is_active());
+    }
+}
+
+// ============================================================================
+// NO-SHOW FEE
+// ============================================================================
+
+mod no_show_fee_tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_fee_is_zero() {
+        let policy = NoShowPolicy::default();
+
+        assert_eq!(policy.fee_for(&money(3_000_000), 3), money(0));
+    }
+
+    #[test]
+    fn test_first_night_fee_is_average_nightly_rate() {
+        let fee = policy(NoShowFeeType::FirstNight).fee_for(&money(3_000_000), 3);
+
+        assert_eq!(fee, money(1_000_000));
+    }
+
+    #[test]
+    fn test_full_stay_fee_is_booking_price() {
+        let fee = policy(NoShowFeeType::FullStay).fee_for(&money(3_000_000), 3);
+
+        assert_eq!(fee, money(3_000_000));
+    }
+
+    #[test]
+    fn test_fixed_fee_ignores_booking_price() {
+        let fee = policy(NoShowFeeType::Fixed).fee_for(&money(3_000_000), 3);
+
+        assert_eq!(fee, money(300_000));
+    }
+}
+
+// ============================================================================
+// POLICY SETTINGS
+// ============================================================================
+
+mod no_show_policy_settings_tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_round_trips_through_settings() {
+        let original = NoShowPolicy {
+            grace_days: 2,
+            ..policy(NoShowFeeType::Fixed)
+        };
+
+        let settings: HashMap<String, String> = original
+            .to_settings()
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+
+        assert_eq!(NoShowPolicy::from_settings(&settings), original);
+    }
+
+    #[test]
+    fn test_missing_or_invalid_settings_fall_back_to_defaults() {
+        let mut settings = HashMap::new();
+        settings.insert("no_show_grace_days".to_string(), "soon".to_string());
+        settings.insert("no_show_fee_type".to_string(), "everything".to_string());
+
+        assert_eq!(NoShowPolicy::from_settings(&settings), NoShowPolicy::default());
+    }
+}
+
+// ============================================================================
+// MARKING AND UNDOING (DATABASE)
+// ============================================================================
+
+mod no_show_db_tests {
+    use super::*;
+
+    use diesel::prelude::*;
+    use hotel_management_backend::config::{JobSchedule, NoShowAction, NoShowJobConfig};
+    use hotel_management_backend::models::Booking;
+    use hotel_management_backend::schema::bookings;
+    use hotel_management_backend::services::{JobService, NoShowService, PaymentService};
+
+    use common::{create_test_room, days_from_now, insert_test_booking, remove_test_room, test_pool};
+
+    #[test]
+    fn fee_is_owed_and_undo_restores_the_agreed_price() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "N");
+        // A negotiated price the rate calendar would not quote, after an
+        // earlier stay change from the first quote
+        let booking = insert_test_booking(&pool, room.id, days_from_now(-1), days_from_now(2), 2_345_000);
+        diesel::update(bookings::table.find(booking.id))
+            .set(bookings::original_price.eq(Some(money(1_500_000))))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+        let no_shows = NoShowService::new(pool.clone());
+        let payments = PaymentService::new(pool.clone());
+        let job_config = NoShowJobConfig {
+            schedule: JobSchedule {
+                enabled: true,
+                interval_secs: 3600,
+            },
+            action: NoShowAction::Mark,
+        };
+
+        let saved_policy = no_shows.get_policy().unwrap();
+        no_shows
+            .update_policy(NoShowPolicy {
+                grace_days: 0,
+                fee_enabled: true,
+                fee_type: NoShowFeeType::Fixed,
+                fee_amount: money(300_000),
+            })
+            .unwrap();
+        let marked = no_shows.mark_no_show(booking.id);
+        let summary = payments.get_payment_summary(booking.id);
+        let fee_payments = payments.get_payments_by_booking(booking.id);
+        let restored = no_shows.undo_no_show(booking.id);
+        // The check-in date is still past the cutoff; the job must leave the
+        // booking alone once staff have undone its no-show
+        let job_run = JobService::new(pool.clone()).run_no_show(&job_config);
+        let after_job = bookings::table
+            .find(booking.id)
+            .first::<Booking>(&mut pool.get().unwrap());
+        no_shows.update_policy(saved_policy).unwrap();
+        remove_test_room(&pool, room.id);
+
+        let marked = marked.unwrap();
+        let summary = summary.unwrap();
+        assert_eq!(marked.status, BookingStatus::NoShow);
+        assert_eq!(marked.price, money(300_000));
+        assert_eq!(marked.price_before_no_show, Some(money(2_345_000)));
+        assert_eq!(marked.original_price, Some(money(1_500_000)));
+        assert!(fee_payments.unwrap().is_empty(), "No money was collected for the fee");
+        assert_eq!(summary.total_paid, money(0));
+        assert_eq!(summary.remaining_balance, summary.total_price);
+
+        let restored = restored.unwrap();
+        assert_eq!(restored.status, BookingStatus::Upcoming);
+        assert_eq!(restored.price, money(2_345_000));
+        assert_eq!(restored.price_before_no_show, None);
+        assert_eq!(restored.original_price, Some(money(1_500_000)));
+        assert!(restored.no_show_undone_at.is_some());
+
+        assert!(!job_run.unwrap().affected_ids.contains(&booking.id));
+        assert_eq!(after_job.unwrap().status, BookingStatus::Upcoming);
+    }
+}
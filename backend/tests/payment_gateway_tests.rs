@@ -0,0 +1,273 @@
+//! Tests for the mock payment gateway, online payment rules and webhook handling
+//!
+//! The webhook tests need a database and run only when TEST_DATABASE_URL is set.
+
+mod common;
+
+use axum::http::{HeaderMap, HeaderValue};
+use uuid::Uuid;
+
+use hotel_management_backend::errors::AppError;
+use hotel_management_backend::models::PaymentType;
+use hotel_management_backend::services::payment_gateway::{
+    build_gateway, GatewayEventType, GatewayIntentRequest, MockGateway, PaymentGateway,
+    MOCK_SIGNATURE_HEADER,
+};
+use hotel_management_backend::services::payment_intent_service::{
+    default_payment_type, validate_intent_amount,
+};
+
+use common::money;
+
+fn signed_headers(gateway: &MockGateway, body: &[u8]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        MOCK_SIGNATURE_HEADER,
+        HeaderValue::from_str(&gateway.sign(body)).unwrap(),
+    );
+    headers
+}
+
+const SUCCEEDED: &[u8] =
+    br#"{"id":"evt_1","type":"payment.succeeded","reference":"mock_abc","amount":"500000"}"#;
+
+// ============================================================================
+// MOCK GATEWAY
+// ============================================================================
+
+mod mock_gateway_tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_create_intent_returns_reference_and_checkout_url() {
+        let gateway = MockGateway::new("secret");
+        let intent_id = Uuid::new_v4();
+
+        let intent = gateway
+            .create_intent(&GatewayIntentRequest {
+                intent_id,
+                booking_reference: "BK-20251215-ABCD".to_string(),
+                amount: money(500_000),
+                currency: "VND".to_string(),
+                description: "Booking BK-20251215-ABCD".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(intent.provider_reference, format!("mock_{}", intent_id.simple()));
+        assert!(intent.checkout_url.unwrap().ends_with(&intent.provider_reference));
+    }
+
+    #[test]
+    fn test_signed_webhook_is_parsed() {
+        let gateway = MockGateway::new("secret");
+
+        let event = gateway
+            .parse_webhook(&signed_headers(&gateway, SUCCEEDED), SUCCEEDED)
+            .unwrap();
+
+        assert_eq!(event.event_id, "evt_1");
+        assert_eq!(event.event_type, GatewayEventType::PaymentSucceeded);
+        assert_eq!(event.provider_reference, "mock_abc");
+        assert_eq!(event.amount, money(500_000));
+        assert_eq!(event.failure_reason, None);
+    }
+
+    #[test]
+    fn test_failed_event_carries_reason() {
+        let gateway = MockGateway::new("secret");
+        let body = br#"{"id":"evt_2","type":"payment.failed","reference":"mock_abc","amount":"500000","failure_reason":"Card declined"}"#;
+
+        let event = gateway.parse_webhook(&signed_headers(&gateway, body), body).unwrap();
+
+        assert_eq!(event.event_type, GatewayEventType::PaymentFailed);
+        assert_eq!(event.failure_reason.as_deref(), Some("Card declined"));
+    }
+
+    #[test]
+    fn test_missing_signature_is_rejected() {
+        let gateway = MockGateway::new("secret");
+        let result = gateway.parse_webhook(&HeaderMap::new(), SUCCEEDED);
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_tampered_body_is_rejected() {
+        let gateway = MockGateway::new("secret");
+        let headers = signed_headers(&gateway, SUCCEEDED);
+        let tampered =
+            br#"{"id":"evt_1","type":"payment.succeeded","reference":"mock_abc","amount":"900000"}"#;
+
+        let result = gateway.parse_webhook(&headers, tampered);
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_signature_with_other_secret_is_rejected() {
+        let gateway = MockGateway::new("secret");
+        let other = MockGateway::new("other-secret");
+
+        let result = gateway.parse_webhook(&signed_headers(&other, SUCCEEDED), SUCCEEDED);
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_signed_garbage_is_a_bad_request() {
+        let gateway = MockGateway::new("secret");
+        let body = b"not json";
+
+        let result = gateway.parse_webhook(&signed_headers(&gateway, body), body);
+        assert!(matches!(result, Err(AppError::BadRequest(_))));
+    }
+
+    #[test]
+    fn test_build_gateway() {
+        assert_eq!(build_gateway("Mock", "secret", true).unwrap().name(), "mock");
+        assert!(build_gateway("paypal", "secret", true).is_err());
+    }
+
+    #[test]
+    fn test_mock_gateway_needs_opt_in() {
+        assert!(build_gateway("mock", "secret", false).is_err());
+    }
+}
+
+// ============================================================================
+// ONLINE PAYMENT RULES
+// ============================================================================
+
+mod intent_rule_tests {
+    use super::*;
+
+    #[test]
+    fn test_paying_the_balance_is_a_full_payment() {
+        assert_eq!(
+            default_payment_type(&money(1_000_000), &money(1_000_000)),
+            PaymentType::Full
+        );
+    }
+
+    #[test]
+    fn test_paying_less_is_a_partial_payment() {
+        assert_eq!(
+            default_payment_type(&money(400_000), &money(1_000_000)),
+            PaymentType::Partial
+        );
+    }
+
+    #[test]
+    fn test_amount_must_be_positive() {
+        assert!(validate_intent_amount(&money(0), &money(1_000_000)).is_err());
+        assert!(validate_intent_amount(&money(-1), &money(1_000_000)).is_err());
+    }
+
+    #[test]
+    fn test_amount_cannot_exceed_balance() {
+        assert!(validate_intent_amount(&money(1_000_001), &money(1_000_000)).is_err());
+        assert!(validate_intent_amount(&money(1_000_000), &money(1_000_000)).is_ok());
+    }
+
+    #[test]
+    fn test_settled_booking_cannot_take_payments() {
+        assert!(validate_intent_amount(&money(1), &money(0)).is_err());
+    }
+}
+
+// ============================================================================
+// WEBHOOK HANDLING (DATABASE)
+// ============================================================================
+
+mod webhook_db_tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    use hotel_management_backend::models::PaymentIntentStatus;
+    use hotel_management_backend::services::payment_intent_service::{
+        PaymentIntentService, WebhookOutcome,
+    };
+    use hotel_management_backend::services::PaymentService;
+
+    use common::{create_test_room, create_test_staff, days_from_now, insert_test_booking, test_pool};
+
+    // Payments are append-only, so the booking, intent and payment stay behind
+
+    #[tokio::test]
+    async fn signed_success_records_one_payment() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "W");
+        let staff = create_test_staff(&pool, "webhook");
+        let booking =
+            insert_test_booking(&pool, room.id, days_from_now(10), days_from_now(12), 1_000_000);
+
+        let gateway = Arc::new(MockGateway::new("webhook-secret"));
+        let service = PaymentIntentService::new(pool.clone(), gateway.clone());
+        let intent = service
+            .create_intent(booking.id, Some(money(400_000)), None, staff)
+            .await
+            .unwrap();
+
+        let body = format!(
+            r#"{{"id":"evt_{}","type":"payment.succeeded","reference":"{}","amount":"400000"}}"#,
+            intent.id.simple(),
+            intent.provider_reference.as_deref().unwrap()
+        );
+        let body = body.as_bytes();
+
+        let forged = MockGateway::new("guessed-secret");
+        let result = service.handle_webhook("mock", &signed_headers(&forged, body), body);
+        assert!(matches!(result, Err(AppError::Unauthorized(_))));
+
+        let headers = signed_headers(&gateway, body);
+        assert_eq!(
+            service.handle_webhook("mock", &headers, body).unwrap(),
+            WebhookOutcome::Processed
+        );
+        assert_eq!(
+            service.handle_webhook("mock", &headers, body).unwrap(),
+            WebhookOutcome::Duplicate
+        );
+
+        let intents = service.list_intents(booking.id).unwrap();
+        assert_eq!(intents[0].status, PaymentIntentStatus::Succeeded);
+
+        let summary = PaymentService::new(pool.clone())
+            .get_payment_summary(booking.id)
+            .unwrap();
+        assert_eq!(summary.total_paid, money(400_000));
+        assert_eq!(summary.payment_count, 1);
+    }
+
+    #[tokio::test]
+    async fn pending_intents_are_not_requested_twice() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "W");
+        let staff = create_test_staff(&pool, "intents");
+        let booking =
+            insert_test_booking(&pool, room.id, days_from_now(10), days_from_now(12), 1_000_000);
+
+        let service = PaymentIntentService::new(pool.clone(), Arc::new(MockGateway::new("webhook-secret")));
+        let owed = PaymentService::new(pool.clone())
+            .get_payment_summary(booking.id)
+            .unwrap()
+            .remaining_balance;
+
+        let first = service
+            .create_intent(booking.id, Some(money(400_000)), None, staff)
+            .await
+            .unwrap();
+        let rest = service.create_intent(booking.id, None, None, staff).await.unwrap();
+        let too_much = service
+            .create_intent(booking.id, Some(money(1)), None, staff)
+            .await;
+
+        assert_eq!(first.payment_type, PaymentType::Partial);
+        assert_eq!(rest.amount, owed - money(400_000));
+        assert_eq!(rest.payment_type, PaymentType::Full);
+        assert!(
+            matches!(too_much, Err(AppError::ValidationError(_))),
+            "Nothing is left to request, got {:?}",
+            too_much
+        );
+    }
+}
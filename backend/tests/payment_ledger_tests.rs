@@ -0,0 +1,70 @@
+//! Unit tests for payment validation and void/adjust reasons (DB-free)
+
+mod common;
+
+use hotel_management_backend::errors::AppError;
+use hotel_management_backend::models::PaymentType;
+use hotel_management_backend::services::payment_service::{require_reason, validate_payment};
+
+use common::money;
+
+// ============================================================================
+// PAYMENT VALIDATION
+// ============================================================================
+
+mod validation_tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_payment() {
+        assert!(validate_payment(&money(500_000), PaymentType::Deposit, "cash").is_ok());
+        assert!(validate_payment(&money(-500_000), PaymentType::Refund, "card").is_ok());
+    }
+
+    #[test]
+    fn test_zero_amount_is_rejected() {
+        assert!(matches!(
+            validate_payment(&money(0), PaymentType::Full, "cash"),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_refund_must_be_negative() {
+        assert!(validate_payment(&money(100), PaymentType::Refund, "cash").is_err());
+    }
+
+    #[test]
+    fn test_payment_must_be_positive() {
+        assert!(validate_payment(&money(-100), PaymentType::Partial, "cash").is_err());
+    }
+
+    #[test]
+    fn test_unknown_method_is_rejected() {
+        assert!(validate_payment(&money(100), PaymentType::Full, "crypto").is_err());
+    }
+
+    #[test]
+    fn test_online_method_is_accepted() {
+        assert!(validate_payment(&money(100), PaymentType::Full, "online").is_ok());
+    }
+}
+
+// ============================================================================
+// VOID AND ADJUST REASONS
+// ============================================================================
+
+mod reason_tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_is_trimmed() {
+        assert_eq!(require_reason("  Duplicate entry \n").unwrap(), "Duplicate entry");
+    }
+
+    #[test]
+    fn test_blank_reason_is_rejected() {
+        assert!(matches!(require_reason(""), Err(AppError::ValidationError(_))));
+        assert!(matches!(require_reason("   "), Err(AppError::ValidationError(_))));
+    }
+}
@@ -0,0 +1,309 @@
+//! Unit tests for the pricing engine (DB-free)
+//!
+//! Exercises `price_stay` and plan selection with in-memory rooms, rate plans
+//! and overrides.
+
+mod common;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use uuid::Uuid;
+
+use hotel_management_backend::models::{RateOverride, RatePlan, RoomType};
+use hotel_management_backend::services::pricing_service::{
+    is_weekend_night, price_stay, select_plan, RateSource,
+};
+
+use common::{date, money, room};
+
+fn type_plan(room_type: RoomType, base_rate: i64) -> RatePlan {
+    RatePlan {
+        id: Uuid::new_v4(),
+        name: "Standard".to_string(),
+        room_type: Some(room_type),
+        room_id: None,
+        base_rate: money(base_rate),
+        weekend_rate: None,
+        start_date: None,
+        end_date: None,
+        min_stay_nights: 1,
+        priority: 0,
+        is_active: true,
+        created_at: Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap(),
+        updated_at: Utc::now(),
+        cancellation_policy_id: None,
+    }
+}
+
+fn override_for(plan: &RatePlan, stay_date: NaiveDate, price: i64, min_stay: Option<i32>) -> RateOverride {
+    RateOverride {
+        id: Uuid::new_v4(),
+        rate_plan_id: plan.id,
+        stay_date,
+        price: money(price),
+        min_stay_nights: min_stay,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+// ============================================================================
+// FALLBACK TO ROOM PRICE
+// ============================================================================
+
+mod room_price_fallback_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_plans_uses_room_price_per_night() {
+        let room = room(RoomType::Double, 1_500_000);
+
+        // Thu 2026-03-05 -> Sun 2026-03-08: 3 nights
+        let quote = price_stay(&room, &[], &[], date(2026, 3, 5), date(2026, 3, 8));
+
+        assert_eq!(quote.nights, 3);
+        assert_eq!(quote.total_price, money(4_500_000));
+        assert_eq!(quote.rate_plan_id, None);
+        assert!(quote.nightly_rates.iter().all(|n| n.source == RateSource::RoomPrice));
+    }
+
+    #[test]
+    fn test_plan_for_other_room_type_is_ignored() {
+        let room = room(RoomType::Single, 1_000_000);
+        let plan = type_plan(RoomType::Suite, 3_000_000);
+
+        let quote = price_stay(&room, &[plan], &[], date(2026, 3, 2), date(2026, 3, 3));
+
+        assert_eq!(quote.total_price, money(1_000_000));
+    }
+
+    #[test]
+    fn test_inactive_plan_is_ignored() {
+        let room = room(RoomType::Single, 1_000_000);
+        let mut plan = type_plan(RoomType::Single, 800_000);
+        plan.is_active = false;
+
+        let quote = price_stay(&room, &[plan], &[], date(2026, 3, 2), date(2026, 3, 3));
+
+        assert_eq!(quote.total_price, money(1_000_000));
+    }
+}
+
+// ============================================================================
+// WEEKEND AND SEASONAL RATES
+// ============================================================================
+
+mod weekend_and_season_tests {
+    use super::*;
+
+    #[test]
+    fn test_friday_and_saturday_are_weekend_nights() {
+        assert!(is_weekend_night(date(2026, 3, 6))); // Friday
+        assert!(is_weekend_night(date(2026, 3, 7))); // Saturday
+        assert!(!is_weekend_night(date(2026, 3, 8))); // Sunday
+        assert!(!is_weekend_night(date(2026, 3, 5))); // Thursday
+    }
+
+    #[test]
+    fn test_weekend_rate_applies_on_friday_and_saturday_nights() {
+        let room = room(RoomType::Double, 1_500_000);
+        let mut plan = type_plan(RoomType::Double, 1_400_000);
+        plan.weekend_rate = Some(money(1_800_000));
+
+        // Thu, Fri, Sat nights
+        let quote = price_stay(&room, &[plan], &[], date(2026, 3, 5), date(2026, 3, 8));
+
+        assert_eq!(quote.total_price, money(1_400_000 + 1_800_000 + 1_800_000));
+        assert_eq!(quote.nightly_rates[0].source, RateSource::Base);
+        assert_eq!(quote.nightly_rates[1].source, RateSource::Weekend);
+        assert_eq!(quote.nightly_rates[2].source, RateSource::Weekend);
+    }
+
+    #[test]
+    fn test_seasonal_plan_beats_open_ended_plan_inside_window() {
+        let room = room(RoomType::Single, 1_000_000);
+        let standard = type_plan(RoomType::Single, 1_000_000);
+        let mut summer = type_plan(RoomType::Single, 1_300_000);
+        summer.start_date = Some(date(2026, 6, 1));
+        summer.end_date = Some(date(2026, 6, 30));
+
+        // May 31 (standard) + June 1 (summer)
+        let quote = price_stay(
+            &room,
+            &[standard.clone(), summer.clone()],
+            &[],
+            date(2026, 5, 31),
+            date(2026, 6, 2),
+        );
+
+        assert_eq!(quote.total_price, money(2_300_000));
+        assert_eq!(quote.nightly_rates[0].rate_plan_id, Some(standard.id));
+        assert_eq!(quote.nightly_rates[1].rate_plan_id, Some(summer.id));
+        assert_eq!(quote.rate_plan_id, Some(standard.id));
+    }
+
+    #[test]
+    fn test_season_end_date_is_inclusive() {
+        let room = room(RoomType::Single, 1_000_000);
+        let mut plan = type_plan(RoomType::Single, 700_000);
+        plan.start_date = Some(date(2026, 6, 1));
+        plan.end_date = Some(date(2026, 6, 30));
+
+        assert!(select_plan(std::slice::from_ref(&plan), &room, date(2026, 6, 30)).is_some());
+        assert!(select_plan(std::slice::from_ref(&plan), &room, date(2026, 7, 1)).is_none());
+    }
+}
+
+// ============================================================================
+// PLAN PRECEDENCE AND OVERRIDES
+// ============================================================================
+
+mod precedence_tests {
+    use super::*;
+
+    #[test]
+    fn test_room_plan_beats_room_type_plan() {
+        let room = room(RoomType::Suite, 2_500_000);
+        let mut type_level = type_plan(RoomType::Suite, 2_500_000);
+        type_level.priority = 10;
+        let mut room_level = type_plan(RoomType::Suite, 3_000_000);
+        room_level.room_type = None;
+        room_level.room_id = Some(room.id);
+
+        let plans = [type_level, room_level.clone()];
+        let picked = select_plan(&plans, &room, date(2026, 3, 2));
+
+        assert_eq!(picked.map(|p| p.id), Some(room_level.id));
+    }
+
+    #[test]
+    fn test_higher_priority_wins_at_same_level() {
+        let room = room(RoomType::Double, 1_500_000);
+        let low = type_plan(RoomType::Double, 1_500_000);
+        let mut high = type_plan(RoomType::Double, 1_200_000);
+        high.priority = 5;
+
+        let plans = [low, high.clone()];
+        let picked = select_plan(&plans, &room, date(2026, 3, 2));
+
+        assert_eq!(picked.map(|p| p.id), Some(high.id));
+    }
+
+    #[test]
+    fn test_override_replaces_rate_for_that_night_only() {
+        let room = room(RoomType::Double, 1_500_000);
+        let mut plan = type_plan(RoomType::Double, 1_500_000);
+        plan.weekend_rate = Some(money(1_800_000));
+        // Tet holiday on a Saturday night
+        let holiday = override_for(&plan, date(2026, 2, 14), 3_000_000, None);
+
+        let quote = price_stay(&room, &[plan], &[holiday], date(2026, 2, 13), date(2026, 2, 16));
+
+        assert_eq!(quote.total_price, money(1_800_000 + 3_000_000 + 1_500_000));
+        assert_eq!(quote.nightly_rates[1].source, RateSource::Override);
+    }
+
+    #[test]
+    fn test_override_on_losing_plan_is_not_used() {
+        let room = room(RoomType::Double, 1_500_000);
+        let low = type_plan(RoomType::Double, 1_500_000);
+        let mut high = type_plan(RoomType::Double, 1_400_000);
+        high.priority = 1;
+        let ignored = override_for(&low, date(2026, 3, 2), 9_000_000, None);
+
+        let quote = price_stay(&room, &[low, high], &[ignored], date(2026, 3, 2), date(2026, 3, 3));
+
+        assert_eq!(quote.total_price, money(1_400_000));
+    }
+}
+
+// ============================================================================
+// MINIMUM STAY
+// ============================================================================
+
+mod min_stay_tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_min_stay_is_enforced() {
+        let room = room(RoomType::Suite, 2_500_000);
+        let mut plan = type_plan(RoomType::Suite, 2_500_000);
+        plan.min_stay_nights = 2;
+
+        let one_night = price_stay(&room, std::slice::from_ref(&plan), &[], date(2026, 3, 2), date(2026, 3, 3));
+        let two_nights = price_stay(&room, &[plan], &[], date(2026, 3, 2), date(2026, 3, 4));
+
+        assert!(!one_night.meets_min_stay());
+        assert!(one_night.enforce_min_stay().is_err());
+        assert!(two_nights.meets_min_stay());
+    }
+
+    #[test]
+    fn test_longest_rule_across_stay_applies() {
+        let room = room(RoomType::Double, 1_500_000);
+        let plan = type_plan(RoomType::Double, 1_500_000);
+        let event = override_for(&plan, date(2026, 3, 4), 2_000_000, Some(4));
+
+        let quote = price_stay(&room, &[plan], &[event], date(2026, 3, 2), date(2026, 3, 5));
+
+        assert_eq!(quote.min_stay_nights, 4);
+        assert!(!quote.meets_min_stay());
+    }
+
+    #[test]
+    fn test_no_plans_means_no_min_stay() {
+        let room = room(RoomType::Single, 1_000_000);
+
+        let quote = price_stay(&room, &[], &[], date(2026, 3, 2), date(2026, 3, 3));
+
+        assert_eq!(quote.min_stay_nights, 1);
+        assert!(quote.meets_min_stay());
+    }
+}
+
+// ============================================================================
+// DATE OVERRIDES (DATABASE)
+// ============================================================================
+
+mod override_db_tests {
+    use super::*;
+
+    use hotel_management_backend::models::{NewRateOverride, NewRatePlan};
+    use hotel_management_backend::services::PricingService;
+
+    use common::test_pool;
+
+    #[test]
+    fn replacing_an_override_can_clear_its_min_stay() {
+        let Some(pool) = test_pool() else { return };
+        let pricing = PricingService::new(pool);
+        let plan = pricing
+            .create_rate_plan(NewRatePlan {
+                name: format!("Override test {}", common::unique_suffix()),
+                room_type: Some(RoomType::Suite),
+                room_id: None,
+                base_rate: money(3_000_000),
+                weekend_rate: None,
+                start_date: None,
+                end_date: None,
+                min_stay_nights: 1,
+                priority: 0,
+                is_active: false,
+                cancellation_policy_id: None,
+            })
+            .unwrap();
+        let event = |price, min_stay_nights| NewRateOverride {
+            rate_plan_id: plan.id,
+            stay_date: date(2026, 12, 31),
+            price: money(price),
+            min_stay_nights,
+        };
+
+        pricing.upsert_overrides(plan.id, vec![event(4_000_000, Some(3))]).unwrap();
+        let replaced = pricing.upsert_overrides(plan.id, vec![event(3_500_000, None)]).unwrap();
+        pricing.delete_rate_plan(plan.id).unwrap();
+
+        assert_eq!(replaced.len(), 1);
+        assert_eq!(replaced[0].price, money(3_500_000));
+        assert_eq!(replaced[0].min_stay_nights, None);
+    }
+}
@@ -0,0 +1,218 @@
+//! Tests for cash and accrual revenue recognition
+//!
+//! The kept-penalty tests need a database and run only when TEST_DATABASE_URL is set.
+
+mod common;
+
+use bigdecimal::BigDecimal;
+use uuid::Uuid;
+
+use hotel_management_backend::models::TaxBreakdown;
+use hotel_management_backend::services::booking_service::{
+    nightly_revenue, room_payment_share, split_collected, RevenueBasis,
+};
+
+use common::{booking, date, money, moved_booking};
+
+// ============================================================================
+// BASIS SELECTION
+// ============================================================================
+
+mod basis_tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_to_checkout() {
+        assert_eq!(RevenueBasis::resolve(None, None), RevenueBasis::Checkout);
+        assert_eq!(RevenueBasis::resolve(None, Some(false)), RevenueBasis::Checkout);
+    }
+
+    #[test]
+    fn test_use_payments_means_cash() {
+        assert_eq!(RevenueBasis::resolve(None, Some(true)), RevenueBasis::Cash);
+    }
+
+    #[test]
+    fn test_explicit_basis_wins() {
+        assert_eq!(
+            RevenueBasis::resolve(Some(RevenueBasis::Accrual), Some(true)),
+            RevenueBasis::Accrual
+        );
+    }
+}
+
+// ============================================================================
+// ACCRUAL
+// ============================================================================
+
+mod accrual_tests {
+    use super::*;
+
+    #[test]
+    fn test_price_is_spread_over_nights() {
+        let room = Uuid::new_v4();
+        let b = booking(room, date(2026, 5, 1), date(2026, 5, 4), 3_000_000);
+
+        let nights = nightly_revenue(&b, &[]);
+
+        assert_eq!(nights.len(), 3);
+        assert_eq!(nights[0], (date(2026, 5, 1), room, money(1_000_000)));
+        assert_eq!(nights[2].0, date(2026, 5, 3));
+    }
+
+    #[test]
+    fn test_last_night_takes_rounding_remainder() {
+        let b = booking(Uuid::new_v4(), date(2026, 5, 1), date(2026, 5, 4), 1_000_000);
+
+        let nights = nightly_revenue(&b, &[]);
+        let total: BigDecimal = nights.iter().map(|(_, _, amount)| amount.clone()).sum();
+
+        assert_eq!(nights[0].2, "333333.33".parse::<BigDecimal>().unwrap());
+        assert_eq!(nights[2].2, "333333.34".parse::<BigDecimal>().unwrap());
+        assert_eq!(total, money(1_000_000));
+    }
+
+    #[test]
+    fn test_nights_follow_room_moves() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let (b, stays) = moved_booking(old, new);
+
+        let rooms: Vec<Uuid> = nightly_revenue(&b, &stays).into_iter().map(|(_, r, _)| r).collect();
+
+        assert_eq!(rooms, vec![old, new, new]);
+    }
+
+    #[test]
+    fn test_zero_night_booking_earns_nothing() {
+        let b = booking(Uuid::new_v4(), date(2026, 5, 1), date(2026, 5, 1), 500_000);
+        assert!(nightly_revenue(&b, &[]).is_empty());
+    }
+}
+
+// ============================================================================
+// CASH
+// ============================================================================
+
+mod cash_tests {
+    use super::*;
+
+    #[test]
+    fn test_payment_goes_to_the_only_room() {
+        let room = Uuid::new_v4();
+        let b = booking(room, date(2026, 5, 1), date(2026, 5, 4), 3_000_000);
+
+        assert_eq!(room_payment_share(&money(500_000), &b, &[], room), money(500_000));
+        assert_eq!(room_payment_share(&money(500_000), &b, &[], Uuid::new_v4()), money(0));
+    }
+
+    #[test]
+    fn test_payment_is_split_like_the_price_after_a_move() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let (b, stays) = moved_booking(old, new);
+
+        assert_eq!(room_payment_share(&money(900_000), &b, &stays, old), money(300_000));
+        assert_eq!(room_payment_share(&money(900_000), &b, &stays, new), money(600_000));
+    }
+
+    #[test]
+    fn test_refund_is_negative() {
+        let room = Uuid::new_v4();
+        let b = booking(room, date(2026, 5, 1), date(2026, 5, 4), 3_000_000);
+
+        assert_eq!(room_payment_share(&money(-200_000), &b, &[], room), money(-200_000));
+    }
+
+    #[test]
+    fn test_collected_cash_is_split_in_the_booking_tax_proportion() {
+        let booking_taxes = TaxBreakdown {
+            net: money(1_000_000),
+            tax: money(100_000),
+            gross: money(1_100_000),
+            lines: Vec::new(),
+        };
+
+        let split = split_collected(&money(550_000), &booking_taxes);
+
+        assert_eq!(split.gross, money(550_000));
+        assert_eq!(split.tax, money(50_000));
+        assert_eq!(split.net, money(500_000));
+    }
+
+    #[test]
+    fn test_untaxed_booking_has_no_tax_in_collected_cash() {
+        let booking_taxes = TaxBreakdown {
+            net: money(0),
+            tax: money(0),
+            gross: money(0),
+            lines: Vec::new(),
+        };
+
+        let split = split_collected(&money(300_000), &booking_taxes);
+
+        assert_eq!(split.tax, money(0));
+        assert_eq!(split.net, money(300_000));
+    }
+}
+
+// ============================================================================
+// KEPT PENALTIES AND FEES (DATABASE)
+// ============================================================================
+
+mod kept_revenue_db_tests {
+    use super::*;
+
+    use chrono::Utc;
+    use diesel::prelude::*;
+
+    use hotel_management_backend::db::DbPool;
+    use hotel_management_backend::models::BookingStatus;
+    use hotel_management_backend::schema::bookings;
+    use hotel_management_backend::services::tax_service::{applicable_rules, apply_taxes, TaxTarget};
+    use hotel_management_backend::services::{BookingService, TaxService};
+
+    use common::{create_test_room, days_from_now, insert_test_booking, remove_test_room, test_pool};
+
+    fn close(pool: &DbPool, booking_id: Uuid, status: BookingStatus, price: i64) {
+        diesel::update(bookings::table.find(booking_id))
+            .set((bookings::status.eq(status), bookings::price.eq(money(price))))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+    }
+
+    #[test]
+    fn cancellation_penalty_is_earned_on_the_day_of_cancellation() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "R");
+        let bookings = BookingService::new(pool.clone());
+        let today = Utc::now().date_naive();
+
+        let cancelled = insert_test_booking(&pool, room.id, days_from_now(30), days_from_now(32), 2_000_000);
+        close(&pool, cancelled.id, BookingStatus::Cancelled, 200_000);
+        let no_fee = insert_test_booking(&pool, room.id, days_from_now(40), days_from_now(41), 1_000_000);
+        close(&pool, no_fee.id, BookingStatus::NoShow, 0);
+
+        let rules = TaxService::active_rules_with_conn(&mut pool.get().unwrap()).unwrap();
+        let expected = apply_taxes(
+            &money(200_000),
+            &applicable_rules(&rules, TaxTarget::Room, cancelled.check_in_date),
+        );
+        let on_the_day = bookings
+            .get_revenue_time_series(Some(room.id), Some(today), Some(today), RevenueBasis::Accrual)
+            .unwrap();
+        let day_before = bookings
+            .get_revenue_time_series(
+                Some(room.id),
+                Some(days_from_now(-1)),
+                Some(days_from_now(-1)),
+                RevenueBasis::Accrual,
+            )
+            .unwrap();
+        remove_test_room(&pool, room.id);
+
+        assert_eq!(on_the_day.len(), 1);
+        assert_eq!(on_the_day[0].0, today);
+        assert_eq!(on_the_day[0].1.gross, expected.gross);
+        assert_eq!(on_the_day[0].1.net, expected.net);
+        assert!(day_before.is_empty());
+    }
+}
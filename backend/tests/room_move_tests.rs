@@ -0,0 +1,112 @@
+//! Unit tests for room moves: nights per room and revenue split (DB-free)
+
+mod common;
+
+use uuid::Uuid;
+
+use hotel_management_backend::services::booking_service::{
+    nights_in_room, room_revenue_share, room_segments,
+};
+
+use common::{booking, date, money, moved_booking, stay};
+
+// ============================================================================
+// STAY SEGMENTS
+// ============================================================================
+
+mod segment_tests {
+    use super::*;
+
+    #[test]
+    fn test_unmoved_booking_is_one_segment_in_its_room() {
+        let room = Uuid::new_v4();
+        let b = booking(room, date(2026, 5, 1), date(2026, 5, 4), 3_000_000);
+
+        assert_eq!(room_segments(&b, &[]), vec![(room, date(2026, 5, 1), date(2026, 5, 4))]);
+        assert_eq!(nights_in_room(&b, &[], room, None), 3);
+    }
+
+    #[test]
+    fn test_moved_booking_segments_are_in_date_order() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let (b, stays) = moved_booking(old, new);
+
+        let segments = room_segments(&b, &stays);
+
+        assert_eq!(segments[0].0, old);
+        assert_eq!(segments[1].0, new);
+    }
+
+    #[test]
+    fn test_nights_per_room_after_move() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let (b, stays) = moved_booking(old, new);
+
+        assert_eq!(nights_in_room(&b, &stays, old, None), 1);
+        assert_eq!(nights_in_room(&b, &stays, new, None), 2);
+        assert_eq!(nights_in_room(&b, &stays, Uuid::new_v4(), None), 0);
+    }
+
+    #[test]
+    fn test_nights_are_clipped_to_window() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let (b, stays) = moved_booking(old, new);
+
+        let window = Some((date(2026, 5, 3), date(2026, 5, 31)));
+
+        assert_eq!(nights_in_room(&b, &stays, old, window), 0);
+        assert_eq!(nights_in_room(&b, &stays, new, window), 1);
+    }
+}
+
+// ============================================================================
+// REVENUE SPLIT
+// ============================================================================
+
+mod revenue_split_tests {
+    use super::*;
+
+    #[test]
+    fn test_unmoved_booking_keeps_full_price() {
+        let room = Uuid::new_v4();
+        let b = booking(room, date(2026, 5, 1), date(2026, 5, 4), 3_000_000);
+
+        assert_eq!(room_revenue_share(&b, &[], room), money(3_000_000));
+        assert_eq!(room_revenue_share(&b, &[], Uuid::new_v4()), money(0));
+    }
+
+    #[test]
+    fn test_revenue_split_by_nights() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let (b, stays) = moved_booking(old, new);
+
+        assert_eq!(room_revenue_share(&b, &stays, old), money(1_000_000));
+        assert_eq!(room_revenue_share(&b, &stays, new), money(2_000_000));
+    }
+
+    #[test]
+    fn test_shares_add_up_to_price_despite_rounding() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let (mut b, stays) = moved_booking(old, new);
+        b.price = money(1_000_000);
+
+        let total = room_revenue_share(&b, &stays, old) + room_revenue_share(&b, &stays, new);
+
+        assert_eq!(total, money(1_000_000));
+    }
+
+    #[test]
+    fn test_moving_back_credits_both_stays_to_same_room() {
+        let (a, c) = (Uuid::new_v4(), Uuid::new_v4());
+        let b = booking(a, date(2026, 5, 1), date(2026, 5, 5), 4_000_000);
+        let stays = vec![
+            stay(&b, a, date(2026, 5, 1), date(2026, 5, 2)),
+            stay(&b, c, date(2026, 5, 2), date(2026, 5, 3)),
+            stay(&b, a, date(2026, 5, 3), date(2026, 5, 5)),
+        ];
+
+        assert_eq!(nights_in_room(&b, &stays, a, None), 3);
+        assert_eq!(room_revenue_share(&b, &stays, a), money(3_000_000));
+        assert_eq!(room_revenue_share(&b, &stays, c), money(1_000_000));
+    }
+}
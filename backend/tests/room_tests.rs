@@ -2,6 +2,8 @@
 //!
 //! These tests verify room-related business logic including status transitions.
 
+mod common;
+
 mod room_status_transition_tests {
     use hotel_management_backend::models::RoomStatus;
 
@@ -105,6 +107,30 @@ mod room_status_transition_tests {
             "Cleaning room should be able to transition back to dirty for rework"
         );
     }
+
+    #[test]
+    fn test_inspection_workflow_transitions() {
+        assert!(
+            RoomStatus::Cleaning.can_transition_to(RoomStatus::PendingInspection),
+            "Cleaning room should wait for inspection when inspections are on"
+        );
+        assert!(
+            RoomStatus::PendingInspection.can_transition_to(RoomStatus::Available),
+            "Passed inspection should make the room available"
+        );
+        assert!(
+            RoomStatus::PendingInspection.can_transition_to(RoomStatus::Dirty),
+            "Failed inspection should send the room back to dirty"
+        );
+        assert!(
+            !RoomStatus::Dirty.can_transition_to(RoomStatus::PendingInspection),
+            "Only a cleaned room can wait for inspection"
+        );
+        assert!(
+            !RoomStatus::PendingInspection.can_transition_to(RoomStatus::Maintenance),
+            "Room waiting for inspection cannot go straight to maintenance"
+        );
+    }
 }
 
 mod room_type_tests {
@@ -161,6 +187,10 @@ mod room_status_serialization_tests {
         assert_eq!(maintenance_json, "\"maintenance\"");
         assert_eq!(dirty_json, "\"dirty\"");
         assert_eq!(cleaning_json, "\"cleaning\"");
+        assert_eq!(
+            serde_json::to_string(&RoomStatus::PendingInspection).unwrap(),
+            "\"pending_inspection\""
+        );
     }
 
     #[test]
@@ -177,4 +207,265 @@ mod room_status_serialization_tests {
         assert_eq!(dirty, RoomStatus::Dirty);
         assert_eq!(cleaning, RoomStatus::Cleaning);
     }
-}
\ No newline at end of file
+}
+mod room_attribute_tests {
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::RoomType;
+    use hotel_management_backend::services::room_service::{
+        normalize_amenities, validate_max_occupancy, MAX_AMENITIES, MAX_OCCUPANCY,
+    };
+
+    fn names(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_amenities_are_trimmed_lowercased_and_deduplicated() {
+        let amenities = normalize_amenities(&names(&[" Balcony", "bathtub", "", "BALCONY ", "Mini Bar"])).unwrap();
+        assert_eq!(amenities, names(&["balcony", "bathtub", "mini bar"]));
+    }
+
+    #[test]
+    fn test_too_many_amenities_are_rejected() {
+        let amenities: Vec<String> = (0..=MAX_AMENITIES).map(|i| format!("amenity {}", i)).collect();
+        assert!(matches!(
+            normalize_amenities(&amenities),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_max_occupancy_range() {
+        assert!(validate_max_occupancy(1).is_ok());
+        assert!(validate_max_occupancy(MAX_OCCUPANCY).is_ok());
+        assert!(validate_max_occupancy(0).is_err());
+        assert!(validate_max_occupancy(MAX_OCCUPANCY + 1).is_err());
+    }
+
+    #[test]
+    fn test_default_capacity_by_room_type() {
+        assert_eq!(RoomType::Single.default_max_occupancy(), 2);
+        assert_eq!(RoomType::Double.default_max_occupancy(), 4);
+        assert_eq!(RoomType::Suite.default_max_occupancy(), 6);
+    }
+}
+
+mod guest_capacity_tests {
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::{Room, RoomType};
+    use hotel_management_backend::services::booking_service::validate_guest_count;
+
+    use crate::common;
+
+    fn room(max_occupancy: i32) -> Room {
+        Room {
+            max_occupancy,
+            ..common::room(RoomType::Double, 1_500_000)
+        }
+    }
+
+    #[test]
+    fn test_guests_up_to_capacity_fit() {
+        assert!(validate_guest_count(&room(3), 1).is_ok());
+        assert!(validate_guest_count(&room(3), 3).is_ok());
+    }
+
+    #[test]
+    fn test_guests_over_capacity_are_rejected() {
+        assert!(matches!(
+            validate_guest_count(&room(3), 4),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_booking_needs_a_guest() {
+        assert!(validate_guest_count(&room(3), 0).is_err());
+        assert!(validate_guest_count(&room(3), -1).is_err());
+    }
+}
+
+mod room_attribute_db_tests {
+    use chrono::Utc;
+    use diesel::prelude::*;
+    use uuid::Uuid;
+
+    use hotel_management_backend::db::DbPool;
+    use hotel_management_backend::errors::AppError;
+    use hotel_management_backend::models::{Room, RoomType};
+    use hotel_management_backend::schema::{bookings, rooms};
+    use hotel_management_backend::services::room_service::{
+        RoomAttributes, RoomAttributesUpdate, RoomFilter,
+    };
+    use hotel_management_backend::services::{BookingService, RoomService};
+
+    use crate::common::{test_pool, unique_suffix};
+
+    fn unique_view() -> String {
+        format!("v{}", unique_suffix())
+    }
+
+    /// Six-digit room number starting with `prefix`
+    fn unique_number(prefix: char) -> String {
+        format!("{}{:05}", prefix, Uuid::new_v4().as_u128() % 100_000)
+    }
+
+    fn create_room(pool: &DbPool, number: &str, view: &str, attributes: RoomAttributes) -> Room {
+        RoomService::new(pool.clone())
+            .create_room(
+                number,
+                RoomType::Double,
+                RoomAttributes {
+                    view: Some(view.to_string()),
+                    ..attributes
+                },
+            )
+            .unwrap()
+    }
+
+    fn remove_rooms(pool: &DbPool, room_ids: &[Uuid]) {
+        let mut conn = pool.get().unwrap();
+        diesel::delete(bookings::table.filter(bookings::room_id.eq_any(room_ids)))
+            .execute(&mut conn)
+            .unwrap();
+        diesel::delete(rooms::table.filter(rooms::id.eq_any(room_ids)))
+            .execute(&mut conn)
+            .unwrap();
+    }
+
+    #[test]
+    fn new_rooms_default_floor_and_capacity() {
+        let Some(pool) = test_pool() else { return };
+        let view = unique_view();
+        let number = unique_number('8');
+        let room = create_room(
+            &pool,
+            &number,
+            &view,
+            RoomAttributes {
+                amenities: vec!["Balcony".to_string(), "balcony".to_string()],
+                ..Default::default()
+            },
+        );
+        remove_rooms(&pool, &[room.id]);
+
+        // Floor from the digits before the last two
+        assert_eq!(room.floor, number[..4].parse().ok());
+        assert_eq!(room.max_occupancy, RoomType::Double.default_max_occupancy());
+        assert_eq!(room.view.as_deref(), Some(view.as_str()));
+        assert_eq!(room.amenities, vec!["balcony".to_string()]);
+        assert!(!room.smoking);
+    }
+
+    #[test]
+    fn list_rooms_filters_on_attributes() {
+        let Some(pool) = test_pool() else { return };
+        let view = unique_view();
+        let accessible = create_room(
+            &pool,
+            &unique_number('F'),
+            &view,
+            RoomAttributes {
+                floor: Some(3),
+                max_occupancy: Some(2),
+                amenities: vec!["bathtub".to_string(), "balcony".to_string()],
+                wheelchair_accessible: true,
+                ..Default::default()
+            },
+        );
+        let large = create_room(
+            &pool,
+            &unique_number('F'),
+            &view,
+            RoomAttributes {
+                floor: Some(4),
+                max_occupancy: Some(5),
+                amenities: vec!["balcony".to_string()],
+                smoking: true,
+                ..Default::default()
+            },
+        );
+
+        let service = RoomService::new(pool.clone());
+        let list = |filter: RoomFilter| -> Vec<Uuid> {
+            service
+                .list_rooms(&RoomFilter {
+                    view: Some(view.to_uppercase()),
+                    ..filter
+                })
+                .unwrap()
+                .into_iter()
+                .map(|r| r.id)
+                .collect()
+        };
+        let by_view = list(RoomFilter::default());
+        let for_four = list(RoomFilter { guests: Some(4), ..Default::default() });
+        let with_bathtub = list(RoomFilter {
+            amenities: vec!["Bathtub".to_string(), "balcony".to_string()],
+            ..Default::default()
+        });
+        let on_floor_four = list(RoomFilter { floor: Some(4), ..Default::default() });
+        let non_smoking_accessible = list(RoomFilter {
+            smoking: Some(false),
+            wheelchair_accessible: Some(true),
+            ..Default::default()
+        });
+        remove_rooms(&pool, &[accessible.id, large.id]);
+
+        assert_eq!(by_view.len(), 2);
+        assert_eq!(for_four, vec![large.id]);
+        assert_eq!(with_bathtub, vec![accessible.id]);
+        assert_eq!(on_floor_four, vec![large.id]);
+        assert_eq!(non_smoking_accessible, vec![accessible.id]);
+    }
+
+    #[test]
+    fn bookings_are_checked_against_capacity() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_room(
+            &pool,
+            &unique_number('C'),
+            &unique_view(),
+            RoomAttributes {
+                max_occupancy: Some(3),
+                ..Default::default()
+            },
+        );
+        let bookings = BookingService::new(pool.clone());
+        let rooms = RoomService::new(pool.clone());
+        let check_in = Utc::now().date_naive() + chrono::Duration::days(500);
+        let check_out = check_in + chrono::Duration::days(2);
+
+        let too_many = bookings.create_booking("Big Family", room.id, check_in, check_out, 4, None);
+        let booked = bookings.create_booking("Small Family", room.id, check_in, check_out, 3, None);
+        let shrink = rooms.update_room(
+            room.id,
+            None,
+            None,
+            None,
+            RoomAttributesUpdate {
+                max_occupancy: Some(2),
+                ..Default::default()
+            },
+        );
+        let booking_id = booked.as_ref().map(|b| b.id).ok();
+        let fewer_guests = booking_id.map(|id| bookings.set_guest_count(id, 2));
+        let shrink_after = rooms.update_room(
+            room.id,
+            None,
+            None,
+            None,
+            RoomAttributesUpdate {
+                max_occupancy: Some(2),
+                ..Default::default()
+            },
+        );
+        remove_rooms(&pool, &[room.id]);
+
+        assert!(matches!(too_many, Err(AppError::ValidationError(_))), "got {:?}", too_many);
+        assert_eq!(booked.unwrap().guest_count, 3);
+        assert!(matches!(shrink, Err(AppError::Conflict(_))), "got {:?}", shrink);
+        assert_eq!(fewer_guests.unwrap().unwrap().guest_count, 2);
+        assert_eq!(shrink_after.unwrap().max_occupancy, 2);
+    }
+}
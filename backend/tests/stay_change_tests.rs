@@ -0,0 +1,165 @@
+//! Tests for stay extensions and early departures
+//!
+//! The check-out tests need a database and run only when TEST_DATABASE_URL is set.
+
+mod common;
+
+use uuid::Uuid;
+
+use hotel_management_backend::errors::AppError;
+use hotel_management_backend::models::{Booking, BookingStatus, StayChangeType};
+use hotel_management_backend::services::booking_service::{stay_change_price, validate_stay_change};
+
+use common::{date, money};
+
+/// 3-night stay, 2026-05-01 to 2026-05-04
+fn booking(status: BookingStatus) -> Booking {
+    Booking {
+        status,
+        ..common::booking(Uuid::new_v4(), date(2026, 5, 1), date(2026, 5, 4), 3_000_000)
+    }
+}
+
+// ============================================================================
+// VALIDATION
+// ============================================================================
+
+mod validation_tests {
+    use super::*;
+
+    #[test]
+    fn test_later_date_is_an_extension() {
+        let b = booking(BookingStatus::CheckedIn);
+
+        let change = validate_stay_change(&b, date(2026, 5, 6), date(2026, 5, 2));
+
+        assert_eq!(change.unwrap(), StayChangeType::Extend);
+    }
+
+    #[test]
+    fn test_earlier_date_is_a_shortening() {
+        let b = booking(BookingStatus::CheckedIn);
+
+        let change = validate_stay_change(&b, date(2026, 5, 3), date(2026, 5, 2));
+
+        assert_eq!(change.unwrap(), StayChangeType::Shorten);
+    }
+
+    #[test]
+    fn test_in_house_stay_cannot_end_in_the_past() {
+        let b = booking(BookingStatus::CheckedIn);
+
+        let result = validate_stay_change(&b, date(2026, 5, 2), date(2026, 5, 3));
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_upcoming_stay_can_be_shortened_before_arrival() {
+        let b = booking(BookingStatus::Upcoming);
+
+        let change = validate_stay_change(&b, date(2026, 5, 2), date(2026, 4, 20));
+
+        assert_eq!(change.unwrap(), StayChangeType::Shorten);
+    }
+
+    #[test]
+    fn test_check_out_must_stay_after_check_in() {
+        let b = booking(BookingStatus::Upcoming);
+
+        let result = validate_stay_change(&b, date(2026, 5, 1), date(2026, 4, 20));
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_unchanged_date_is_rejected() {
+        let b = booking(BookingStatus::CheckedIn);
+
+        let result = validate_stay_change(&b, date(2026, 5, 4), date(2026, 5, 2));
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn test_finished_bookings_cannot_change() {
+        for status in [BookingStatus::CheckedOut, BookingStatus::Cancelled, BookingStatus::NoShow] {
+            let b = booking(status);
+
+            let result = validate_stay_change(&b, date(2026, 5, 6), date(2026, 5, 2));
+
+            assert!(matches!(result, Err(AppError::InvalidStatusTransition(_))));
+        }
+    }
+}
+
+// ============================================================================
+// RE-PRICING
+// ============================================================================
+
+mod pricing_tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_adds_new_nights_to_agreed_price() {
+        let price = stay_change_price(&money(2_700_000), &money(2_000_000), StayChangeType::Extend);
+
+        assert_eq!(price, money(4_700_000));
+    }
+
+    #[test]
+    fn test_shortening_removes_dropped_nights() {
+        let price = stay_change_price(&money(3_000_000), &money(1_000_000), StayChangeType::Shorten);
+
+        assert_eq!(price, money(2_000_000));
+    }
+
+    #[test]
+    fn test_shortening_never_goes_below_zero() {
+        let price = stay_change_price(&money(1_000_000), &money(1_500_000), StayChangeType::Shorten);
+
+        assert_eq!(price, money(0));
+    }
+}
+
+// ============================================================================
+// DEPARTURE AT CHECK-OUT (DATABASE)
+// ============================================================================
+
+mod check_out_db_tests {
+    use super::*;
+
+    use diesel::prelude::*;
+
+    use hotel_management_backend::schema::bookings;
+    use hotel_management_backend::services::BookingService;
+
+    use common::{
+        create_test_room, create_test_staff, days_from_now, insert_test_booking, remove_test_room,
+        remove_test_staff, test_pool,
+    };
+
+    #[test]
+    fn early_departure_is_recorded_as_made_by_the_checking_out_staff() {
+        let Some(pool) = test_pool() else { return };
+        let room = create_test_room(&pool, "D");
+        let staff = create_test_staff(&pool, "depart");
+        let bookings = BookingService::new(pool.clone());
+
+        let booking = insert_test_booking(&pool, room.id, days_from_now(-1), days_from_now(2), 3_000_000);
+        diesel::update(bookings::table.find(booking.id))
+            .set(bookings::status.eq(BookingStatus::CheckedIn))
+            .execute(&mut pool.get().unwrap())
+            .unwrap();
+        let checked_out = bookings.check_out(booking.id, true, true, staff);
+        let changes = bookings.list_stay_changes(booking.id);
+        remove_test_room(&pool, room.id);
+        remove_test_staff(&pool, staff);
+
+        assert_eq!(checked_out.unwrap().check_out_date, days_from_now(0));
+        let changes = changes.unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].change_type, StayChangeType::Shorten);
+        assert_eq!(changes[0].changed_by_user_id, staff);
+    }
+}
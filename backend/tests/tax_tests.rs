@@ -0,0 +1,210 @@
+//! Unit tests for the tax and service-charge engine (DB-free)
+
+mod common;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use hotel_management_backend::models::{ChargeCategory, TaxLine, TaxRule};
+use hotel_management_backend::services::tax_service::{
+    applicable_rules, apply_taxes, merge_tax_lines, rule_applies, TaxTarget,
+};
+
+use common::{date, money, rate};
+
+fn rule(name: &str, percent: &str, is_inclusive: bool) -> TaxRule {
+    TaxRule {
+        id: Uuid::new_v4(),
+        name: name.to_string(),
+        rate: rate(percent),
+        is_inclusive,
+        applies_to_room: true,
+        charge_categories: vec![ChargeCategory::FoodBeverage],
+        effective_from: None,
+        effective_to: None,
+        sort_order: 0,
+        is_active: true,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+// ============================================================================
+// APPLYING RULES
+// ============================================================================
+
+mod apply_taxes_tests {
+    use super::*;
+
+    #[test]
+    fn test_no_rules_means_no_tax() {
+        let breakdown = apply_taxes(&money(1_000_000), &[]);
+        assert_eq!(breakdown.net, money(1_000_000));
+        assert_eq!(breakdown.tax, money(0));
+        assert_eq!(breakdown.gross, money(1_000_000));
+        assert!(breakdown.lines.is_empty());
+    }
+
+    #[test]
+    fn test_exclusive_rules_are_added_on_top() {
+        let service = rule("Service charge", "5", false);
+        let vat = rule("VAT", "10", false);
+        let breakdown = apply_taxes(&money(1_000_000), &[&service, &vat]);
+
+        assert_eq!(breakdown.net, money(1_000_000));
+        assert_eq!(breakdown.lines[0].amount, money(50_000));
+        assert_eq!(breakdown.lines[1].amount, money(100_000));
+        assert_eq!(breakdown.tax, money(150_000));
+        assert_eq!(breakdown.gross, money(1_150_000));
+    }
+
+    #[test]
+    fn test_inclusive_rules_are_backed_out_of_the_price() {
+        let vat = rule("VAT", "10", true);
+        let breakdown = apply_taxes(&money(1_100_000), &[&vat]);
+
+        assert_eq!(breakdown.net, money(1_000_000));
+        assert_eq!(breakdown.tax, money(100_000));
+        assert_eq!(breakdown.gross, money(1_100_000));
+    }
+
+    #[test]
+    fn test_mixed_inclusive_and_exclusive() {
+        // Price includes 10% VAT; a 5% service charge is added on the net
+        let vat = rule("VAT", "10", true);
+        let service = rule("Service charge", "5", false);
+        let breakdown = apply_taxes(&money(1_100_000), &[&vat, &service]);
+
+        assert_eq!(breakdown.net, money(1_000_000));
+        assert_eq!(breakdown.tax, money(150_000));
+        assert_eq!(breakdown.gross, money(1_150_000));
+    }
+
+    #[test]
+    fn test_inclusive_split_always_adds_up() {
+        // 1,000,000 / 1.15 does not divide evenly
+        let vat = rule("VAT", "10", true);
+        let service = rule("Service charge", "5", true);
+        let breakdown = apply_taxes(&money(1_000_000), &[&vat, &service]);
+
+        assert_eq!(breakdown.lines[0].amount, money(86_957));
+        assert_eq!(breakdown.lines[1].amount, money(43_478));
+        assert_eq!(&breakdown.net + &breakdown.tax, money(1_000_000));
+        assert_eq!(breakdown.gross, money(1_000_000));
+    }
+
+    #[test]
+    fn test_lines_keep_rule_details() {
+        let vat = rule("VAT", "8", false);
+        let breakdown = apply_taxes(&money(250_000), &[&vat]);
+
+        let line = &breakdown.lines[0];
+        assert_eq!(line.tax_rule_id, Some(vat.id));
+        assert_eq!(line.name, "VAT");
+        assert_eq!(line.rate, rate("8"));
+        assert!(!line.is_inclusive);
+        assert_eq!(line.amount, money(20_000));
+    }
+}
+
+// ============================================================================
+// RULE SELECTION
+// ============================================================================
+
+mod rule_selection_tests {
+    use super::*;
+
+    #[test]
+    fn test_inactive_rule_never_applies() {
+        let mut vat = rule("VAT", "10", false);
+        vat.is_active = false;
+        assert!(!rule_applies(&vat, TaxTarget::Room, date(2025, 6, 1)));
+    }
+
+    #[test]
+    fn test_room_flag() {
+        let mut vat = rule("VAT", "10", false);
+        assert!(rule_applies(&vat, TaxTarget::Room, date(2025, 6, 1)));
+
+        vat.applies_to_room = false;
+        assert!(!rule_applies(&vat, TaxTarget::Room, date(2025, 6, 1)));
+    }
+
+    #[test]
+    fn test_charge_categories() {
+        let vat = rule("VAT", "10", false);
+        assert!(rule_applies(
+            &vat,
+            TaxTarget::Charge(ChargeCategory::FoodBeverage),
+            date(2025, 6, 1)
+        ));
+        assert!(!rule_applies(
+            &vat,
+            TaxTarget::Charge(ChargeCategory::Damage),
+            date(2025, 6, 1)
+        ));
+    }
+
+    #[test]
+    fn test_effective_dates_are_inclusive() {
+        let mut vat = rule("VAT", "8", false);
+        vat.effective_from = Some(date(2025, 1, 1));
+        vat.effective_to = Some(date(2025, 6, 30));
+
+        assert!(!rule_applies(&vat, TaxTarget::Room, date(2024, 12, 31)));
+        assert!(rule_applies(&vat, TaxTarget::Room, date(2025, 1, 1)));
+        assert!(rule_applies(&vat, TaxTarget::Room, date(2025, 6, 30)));
+        assert!(!rule_applies(&vat, TaxTarget::Room, date(2025, 7, 1)));
+    }
+
+    #[test]
+    fn test_applicable_rules_follow_sort_order() {
+        let mut vat = rule("VAT", "10", false);
+        vat.sort_order = 2;
+        let mut service = rule("Service charge", "5", false);
+        service.sort_order = 1;
+        let mut damage_only = rule("Damage levy", "3", false);
+        damage_only.applies_to_room = false;
+        damage_only.charge_categories = vec![ChargeCategory::Damage];
+
+        let rules = vec![vat, service, damage_only];
+        let names: Vec<&str> = applicable_rules(&rules, TaxTarget::Room, date(2025, 6, 1))
+            .iter()
+            .map(|r| r.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Service charge", "VAT"]);
+    }
+}
+
+// ============================================================================
+// MERGING LINES
+// ============================================================================
+
+mod merge_tests {
+    use super::*;
+
+    fn line(rule_id: Option<Uuid>, name: &str, amount: i64) -> TaxLine {
+        TaxLine {
+            tax_rule_id: rule_id,
+            name: name.to_string(),
+            rate: rate("10"),
+            is_inclusive: false,
+            amount: money(amount),
+        }
+    }
+
+    #[test]
+    fn test_same_rule_lines_are_summed() {
+        let vat = Some(Uuid::new_v4());
+        let merged = merge_tax_lines(vec![
+            line(vat, "VAT", 100_000),
+            line(None, "Tax", 5_000),
+            line(vat, "VAT", 20_000),
+        ]);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].name, "VAT");
+        assert_eq!(merged[0].amount, money(120_000));
+        assert_eq!(merged[1].amount, money(5_000));
+    }
+}
@@ -71,9 +71,10 @@ mod admin_room_management {
             BookingStatus::CheckedOut,
             BookingStatus::Cancelled,
             BookingStatus::Overstay,
+            BookingStatus::NoShow,
         ];
 
-        assert_eq!(statuses.len(), 5, "Should have 5 booking statuses");
+        assert_eq!(statuses.len(), 6, "Should have 6 booking statuses");
     }
 }
 
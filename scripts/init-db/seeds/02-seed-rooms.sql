@@ -10,32 +10,53 @@ END $$;
 -- Using ON CONFLICT to make idempotent
 -- Insert sample rooms with various types and statuses (includes `price` in VND)
 -- Using ON CONFLICT to make idempotent
-INSERT INTO rooms (id, number, room_type, status, created_at, updated_at, price)
+INSERT INTO rooms (id, number, room_type, status, created_at, updated_at, price,
+                   floor, max_occupancy, bed_configuration, view, amenities, smoking, wheelchair_accessible)
 VALUES 
   -- Floor 1: Single rooms
-  ('10000000-0000-0000-0000-000000000101'::uuid, '101', 'single', 'available', NOW(), NOW(), 1000000),
-  ('10000000-0000-0000-0000-000000000102'::uuid, '102', 'single', 'available', NOW(), NOW(), 1000000),
-  ('10000000-0000-0000-0000-000000000103'::uuid, '103', 'single', 'occupied', NOW(), NOW(), 1000000),
+  ('10000000-0000-0000-0000-000000000101'::uuid, '101', 'single', 'available', NOW(), NOW(), 1000000,
+   1, 2, '1 queen', 'city', '{wifi,desk}', false, false),
+  ('10000000-0000-0000-0000-000000000102'::uuid, '102', 'single', 'available', NOW(), NOW(), 1000000,
+   1, 2, '1 queen', 'garden', '{wifi,desk}', false, true),
+  ('10000000-0000-0000-0000-000000000103'::uuid, '103', 'single', 'occupied', NOW(), NOW(), 1000000,
+   1, 1, '1 single', 'city', '{wifi}', true, false),
   
   -- Floor 2: Double rooms
-  ('10000000-0000-0000-0000-000000000201'::uuid, '201', 'double', 'available', NOW(), NOW(), 1500000),
-  ('10000000-0000-0000-0000-000000000202'::uuid, '202', 'double', 'available', NOW(), NOW(), 1500000),
-  ('10000000-0000-0000-0000-000000000203'::uuid, '203', 'double', 'maintenance', NOW(), NOW(), 1500000),
-  ('10000000-0000-0000-0000-000000000204'::uuid, '204', 'double', 'occupied', NOW(), NOW(), 1500000),
+  ('10000000-0000-0000-0000-000000000201'::uuid, '201', 'double', 'available', NOW(), NOW(), 1500000,
+   2, 4, '2 double', 'city', '{wifi,minibar}', false, false),
+  ('10000000-0000-0000-0000-000000000202'::uuid, '202', 'double', 'available', NOW(), NOW(), 1500000,
+   2, 3, '1 king', 'pool', '{wifi,minibar,balcony}', false, false),
+  ('10000000-0000-0000-0000-000000000203'::uuid, '203', 'double', 'maintenance', NOW(), NOW(), 1500000,
+   2, 4, '2 double', 'garden', '{wifi,minibar}', false, true),
+  ('10000000-0000-0000-0000-000000000204'::uuid, '204', 'double', 'occupied', NOW(), NOW(), 1500000,
+   2, 2, '1 king', 'city', '{wifi,minibar}', true, false),
   
   -- Floor 3: Suites
-  ('10000000-0000-0000-0000-000000000301'::uuid, '301', 'suite', 'available', NOW(), NOW(), 2500000),
-  ('10000000-0000-0000-0000-000000000302'::uuid, '302', 'suite', 'available', NOW(), NOW(), 2500000),
-  ('10000000-0000-0000-0000-000000000303'::uuid, '303', 'suite', 'occupied', NOW(), NOW(), 2500000),
+  ('10000000-0000-0000-0000-000000000301'::uuid, '301', 'suite', 'available', NOW(), NOW(), 2500000,
+   3, 6, '1 king + 2 twin', 'sea', '{wifi,minibar,balcony,bathtub,kitchenette}', false, false),
+  ('10000000-0000-0000-0000-000000000302'::uuid, '302', 'suite', 'available', NOW(), NOW(), 2500000,
+   3, 4, '1 king + sofa bed', 'sea', '{wifi,minibar,balcony,bathtub}', false, true),
+  ('10000000-0000-0000-0000-000000000303'::uuid, '303', 'suite', 'occupied', NOW(), NOW(), 2500000,
+   3, 6, '2 king', 'city', '{wifi,minibar,bathtub,kitchenette}', false, false),
   
   -- Floor 4: Mix
-  ('10000000-0000-0000-0000-000000000401'::uuid, '401', 'single', 'available', NOW(), NOW(), 1000000),
-  ('10000000-0000-0000-0000-000000000402'::uuid, '402', 'double', 'available', NOW(), NOW(), 1500000),
-  ('10000000-0000-0000-0000-000000000403'::uuid, '403', 'suite', 'available', NOW(), NOW(), 2500000)
+  ('10000000-0000-0000-0000-000000000401'::uuid, '401', 'single', 'available', NOW(), NOW(), 1000000,
+   4, 2, '1 queen', 'sea', '{wifi,balcony}', false, false),
+  ('10000000-0000-0000-0000-000000000402'::uuid, '402', 'double', 'available', NOW(), NOW(), 1500000,
+   4, 4, '2 double', 'sea', '{wifi,minibar,balcony}', false, false),
+  ('10000000-0000-0000-0000-000000000403'::uuid, '403', 'suite', 'available', NOW(), NOW(), 2500000,
+   4, 6, '1 king + 2 twin', 'sea', '{wifi,minibar,balcony,bathtub}', false, false)
 ON CONFLICT (number) DO UPDATE
   SET room_type = EXCLUDED.room_type,
       status = EXCLUDED.status,
       price = EXCLUDED.price,
+      floor = EXCLUDED.floor,
+      max_occupancy = EXCLUDED.max_occupancy,
+      bed_configuration = EXCLUDED.bed_configuration,
+      view = EXCLUDED.view,
+      amenities = EXCLUDED.amenities,
+      smoking = EXCLUDED.smoking,
+      wheelchair_accessible = EXCLUDED.wheelchair_accessible,
       updated_at = NOW();
 
 -- Ensure existing rows have sensible prices based on room_type (idempotent)
@@ -217,6 +217,18 @@ BEGIN
       NOW() - INTERVAL '28 days'
     );
     
+    -- Every payment starts its history in the payment ledger
+    INSERT INTO payment_ledger_entries (
+      payment_id, booking_id, action, amount_delta, amount, payment_type,
+      payment_method, actor_user_id, created_at
+    )
+    SELECT p.id, p.booking_id, 'created', p.amount, p.amount, p.payment_type,
+           p.payment_method, p.created_by_user_id, p.created_at
+    FROM payments p
+    WHERE NOT EXISTS (
+      SELECT 1 FROM payment_ledger_entries l WHERE l.payment_id = p.id
+    );
+    
     RAISE NOTICE '  ✓ Inserted 13 sample payments';
     RAISE NOTICE '    - 2 payments for booking SEED-001 (checked_in: deposit + partial)';
     RAISE NOTICE '    - 1 full payment for booking SEED-002 (checked_in)';